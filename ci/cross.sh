@@ -0,0 +1,26 @@
+#!/bin/sh
+#
+# Check that the crate builds for some small, bare-metal, targets.
+#
+# Usage: ci/cross.sh [thumb|avr]
+#
+# The AVR build needs a nightly toolchain with the rust-src component.
+
+set -eu
+
+case "${1:-thumb}" in
+    thumb)
+        cargo build --target thumbv6m-none-eabi
+        cargo build --target thumbv6m-none-eabi --features alloc
+        ;;
+    avr)
+        # AVR has a 16-bit usize, which shakes out any bad assumptions. This
+        # target used to be called avr-unknown-gnu-atmega328.
+        RUSTFLAGS="-C target-cpu=atmega328p" \
+            cargo +nightly build --release -Zbuild-std=core --target avr-none
+        ;;
+    *)
+        echo "Unknown target group $1" >&2
+        exit 1
+        ;;
+esac
@@ -1,4 +1,4 @@
-use pc_keyboard::Ps2Decoder;
+use pc_keyboard::prelude::*;
 
 fn main() {
     let mut decoder = Ps2Decoder::new();
@@ -1,13 +1,8 @@
-use pc_keyboard::{
-    layouts::{AnyLayout, Uk105Key},
-    DecodedKey, EventDecoder, KeyCode, KeyEvent, KeyState,
-};
+use pc_keyboard::layouts::{AnyLayout, Uk105Key};
+use pc_keyboard::prelude::*;
 
 fn main() {
-    let mut decoder = EventDecoder::new(
-        AnyLayout::Uk105Key(Uk105Key),
-        pc_keyboard::HandleControl::Ignore,
-    );
+    let mut decoder = EventDecoder::new(AnyLayout::Uk105Key(Uk105Key), HandleControl::Ignore);
 
     // User presses 'A' on their UK keyboard, gets a lower-case 'a'.
     let decoded_key = decoder.process_keyevent(KeyEvent {
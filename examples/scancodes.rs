@@ -1,12 +1,15 @@
-use pc_keyboard::{KeyEvent, ScancodeSet, ScancodeSet1, ScancodeSet2};
+use pc_keyboard::prelude::*;
 
 fn main() {
     let mut s = ScancodeSet1::new();
     // [ 0x01 ] means "Pressed Escape" in Set 1
     match s.advance_state(0x01) {
-        Ok(Some(KeyEvent { code, state })) => {
+        Ok(Some(DecodedItem::Key(KeyEvent { code, state }))) => {
             println!("Scancode Set 1 0x01 is KeyCode '{code:?}' KeyState '{state:?}'");
         }
+        Ok(Some(DecodedItem::Protocol(event))) => {
+            println!("This is wrong, we didn't expect a protocol event: {event:?}");
+        }
         Ok(None) => {
             println!("This is wrong, we didn't think that was a complete sequence");
         }
@@ -16,9 +19,12 @@ fn main() {
     }
     // [ 0x81 ] means "Released Escape" in Set 1
     match s.advance_state(0x81) {
-        Ok(Some(KeyEvent { code, state })) => {
+        Ok(Some(DecodedItem::Key(KeyEvent { code, state }))) => {
             println!("Scancode Set 1 0x81 is KeyCode '{code:?}' KeyState '{state:?}'");
         }
+        Ok(Some(DecodedItem::Protocol(event))) => {
+            println!("This is wrong, we didn't expect a protocol event: {event:?}");
+        }
         Ok(None) => {
             println!("This is wrong, we didn't think that was a complete sequence");
         }
@@ -30,9 +36,12 @@ fn main() {
     let mut s = ScancodeSet2::new();
     // [ 0x01 ] means "Pressed F9" in Set 2
     match s.advance_state(0x01) {
-        Ok(Some(KeyEvent { code, state })) => {
+        Ok(Some(DecodedItem::Key(KeyEvent { code, state }))) => {
             println!("Scancode Set 2 0x01 is KeyCode '{code:?}' KeyState '{state:?}'");
         }
+        Ok(Some(DecodedItem::Protocol(event))) => {
+            println!("This is wrong, we didn't expect a protocol event: {event:?}");
+        }
         Ok(None) => {
             println!("This is wrong, we didn't think that was a complete sequence");
         }
@@ -43,9 +52,12 @@ fn main() {
     // [ 0xF0, 0x01 ] means "Released F9" in Set 2
     assert_eq!(Ok(None), s.advance_state(0xF0));
     match s.advance_state(0x01) {
-        Ok(Some(KeyEvent { code, state })) => {
+        Ok(Some(DecodedItem::Key(KeyEvent { code, state }))) => {
             println!("Scancode Set 2 0xF0 0x01 is KeyCode '{code:?}' KeyState '{state:?}'");
         }
+        Ok(Some(DecodedItem::Protocol(event))) => {
+            println!("This is wrong, we didn't expect a protocol event: {event:?}");
+        }
         Ok(None) => {
             println!("This is wrong, we didn't think that was a complete sequence");
         }
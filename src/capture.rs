@@ -0,0 +1,321 @@
+//! Decoding of PS/2 traffic captured with a logic analyser
+//!
+//! Two formats are supported:
+//!
+//! * The CSV export from Saleae Logic, with a time column followed by the
+//!   clock and data channels (`Time [s], clock, data`). There is one row for
+//!   each time either line changes.
+//! * A plain hex dump, with bytes written as hex digits (optionally with a
+//!   `0x` prefix), separated by whitespace or commas. Anything after a `#`
+//!   on a line is a comment.
+//!
+//! The results come back with the time they were seen (if the format has
+//! times), so you can line them up with the original capture.
+
+use alloc::vec::Vec;
+
+use crate::{DecodedItem, Error, Ps2Decoder, ScancodeSet};
+
+/// A line in a capture file that couldn't be understood
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line number, starting from 1
+    pub line: usize,
+}
+
+/// A bit sampled from the data line, on a falling edge of the clock line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CapturedBit {
+    /// When the clock fell, in seconds
+    pub time: f64,
+    /// The level of the data line
+    pub bit: bool,
+}
+
+/// Something decoded from a capture
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedItem {
+    /// When the last bit of the last byte arrived, in seconds, if known
+    pub time: Option<f64>,
+    /// What was decoded, or what went wrong
+    pub result: Result<DecodedItem, Error>,
+}
+
+/// Parse a Saleae Logic CSV export into the bits sent by the keyboard.
+///
+/// The first line may be a header. Each other line needs a time in seconds,
+/// then the clock level, then the data level (each `0` or `1`). Any extra
+/// columns are ignored.
+pub fn parse_saleae_csv(text: &str) -> Result<Vec<CapturedBit>, ParseError> {
+    let mut bits = Vec::new();
+    let mut last_clock = true;
+    for (idx, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mut columns = line.split(',').map(str::trim);
+        let time = columns.next().and_then(|s| s.parse::<f64>().ok());
+        let clock = columns.next().and_then(parse_level);
+        let data = columns.next().and_then(parse_level);
+        match (time, clock, data) {
+            (Some(time), Some(clock), Some(data)) => {
+                if last_clock && !clock {
+                    bits.push(CapturedBit { time, bit: data });
+                }
+                last_clock = clock;
+            }
+            _ if idx == 0 => {
+                // A header
+            }
+            _ => return Err(ParseError { line: idx + 1 }),
+        }
+    }
+    Ok(bits)
+}
+
+/// Parse a hex dump into bytes.
+pub fn parse_hex_dump(text: &str) -> Result<Vec<u8>, ParseError> {
+    let mut bytes = Vec::new();
+    for (idx, line) in text.lines().enumerate() {
+        let line = match line.find('#') {
+            Some(pos) => &line[..pos],
+            None => line,
+        };
+        for word in line.split(|c: char| c.is_whitespace() || c == ',') {
+            if word.is_empty() {
+                continue;
+            }
+            let digits = word
+                .strip_prefix("0x")
+                .or_else(|| word.strip_prefix("0X"))
+                .unwrap_or(word);
+            match u8::from_str_radix(digits, 16) {
+                Ok(byte) => bytes.push(byte),
+                Err(_) => return Err(ParseError { line: idx + 1 }),
+            }
+        }
+    }
+    Ok(bytes)
+}
+
+/// Decode bits from [`parse_saleae_csv`], using the given scancode set.
+///
+/// Words with bad start, stop or parity bits give an error and are then
+/// skipped, as [`Ps2Decoder`] would.
+pub fn decode_bits<S>(mut scancode_set: S, bits: &[CapturedBit]) -> Vec<CapturedItem>
+where
+    S: ScancodeSet,
+{
+    let mut decoder = Ps2Decoder::new();
+    let mut items = Vec::new();
+    for captured in bits {
+        let result = match decoder.add_bit(captured.bit) {
+            Ok(Some(byte)) => scancode_set.advance_state(byte),
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        };
+        push_result(&mut items, Some(captured.time), result);
+    }
+    items
+}
+
+/// Decode bytes from [`parse_hex_dump`], using the given scancode set.
+pub fn decode_bytes<S>(mut scancode_set: S, bytes: &[u8]) -> Vec<CapturedItem>
+where
+    S: ScancodeSet,
+{
+    let mut items = Vec::new();
+    for byte in bytes {
+        let result = scancode_set.advance_state(*byte);
+        push_result(&mut items, None, result);
+    }
+    items
+}
+
+/// Parse a `0` or `1`
+fn parse_level(s: &str) -> Option<bool> {
+    match s {
+        "0" => Some(false),
+        "1" => Some(true),
+        _ => None,
+    }
+}
+
+/// Keep anything that isn't `Ok(None)`
+fn push_result(
+    items: &mut Vec<CapturedItem>,
+    time: Option<f64>,
+    result: Result<Option<DecodedItem>, Error>,
+) {
+    let result = match result {
+        Ok(Some(item)) => Ok(item),
+        Ok(None) => return,
+        Err(e) => Err(e),
+    };
+    items.push(CapturedItem { time, result });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{KeyCode, KeyEvent, KeyState, ScancodeSet2};
+
+    /// Pressing A, on a clean line
+    const CLEAN: &str = "Time [s], clock, data
+0.000000000, 1, 1
+0.001000000, 1, 0
+0.001020000, 0, 0
+0.001060000, 1, 0
+0.001100000, 0, 0
+0.001140000, 1, 0
+0.001180000, 0, 0
+0.001220000, 1, 0
+0.001240000, 1, 1
+0.001260000, 0, 1
+0.001300000, 1, 1
+0.001340000, 0, 1
+0.001380000, 1, 1
+0.001420000, 0, 1
+0.001460000, 1, 1
+0.001480000, 1, 0
+0.001500000, 0, 0
+0.001540000, 1, 0
+0.001580000, 0, 0
+0.001620000, 1, 0
+0.001660000, 0, 0
+0.001700000, 1, 0
+0.001740000, 0, 0
+0.001780000, 1, 0
+0.001800000, 1, 1
+0.001820000, 0, 1
+0.001860000, 1, 1
+";
+
+    /// Pressing A with a corrupt parity bit, then pressing B
+    const PARITY_ERROR: &str = "Time [s], clock, data
+0.000000000, 1, 1
+0.001000000, 1, 0
+0.001020000, 0, 0
+0.001060000, 1, 0
+0.001100000, 0, 0
+0.001140000, 1, 0
+0.001180000, 0, 0
+0.001220000, 1, 0
+0.001240000, 1, 1
+0.001260000, 0, 1
+0.001300000, 1, 1
+0.001340000, 0, 1
+0.001380000, 1, 1
+0.001420000, 0, 1
+0.001460000, 1, 1
+0.001480000, 1, 0
+0.001500000, 0, 0
+0.001540000, 1, 0
+0.001580000, 0, 0
+0.001620000, 1, 0
+0.001660000, 0, 0
+0.001700000, 1, 0
+0.001720000, 1, 1
+0.001740000, 0, 1
+0.001780000, 1, 1
+0.001820000, 0, 1
+0.001860000, 1, 1
+0.002380000, 1, 0
+0.002400000, 0, 0
+0.002440000, 1, 0
+0.002480000, 0, 0
+0.002520000, 1, 0
+0.002540000, 1, 1
+0.002560000, 0, 1
+0.002600000, 1, 1
+0.002620000, 1, 0
+0.002640000, 0, 0
+0.002680000, 1, 0
+0.002720000, 0, 0
+0.002760000, 1, 0
+0.002780000, 1, 1
+0.002800000, 0, 1
+0.002840000, 1, 1
+0.002880000, 0, 1
+0.002920000, 1, 1
+0.002940000, 1, 0
+0.002960000, 0, 0
+0.003000000, 1, 0
+0.003040000, 0, 0
+0.003080000, 1, 0
+0.003120000, 0, 0
+0.003160000, 1, 0
+0.003180000, 1, 1
+0.003200000, 0, 1
+0.003240000, 1, 1
+";
+
+    #[test]
+    fn test_clean() {
+        let bits = parse_saleae_csv(CLEAN).unwrap();
+        assert_eq!(bits.len(), 11);
+        assert_eq!(
+            bits[0],
+            CapturedBit {
+                time: 0.00102,
+                bit: false
+            }
+        );
+        let items = decode_bits(ScancodeSet2::new(), &bits);
+        assert_eq!(
+            items,
+            [CapturedItem {
+                time: Some(0.00182),
+                result: Ok(DecodedItem::Key(KeyEvent::new(KeyCode::A, KeyState::Down))),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parity_error() {
+        let bits = parse_saleae_csv(PARITY_ERROR).unwrap();
+        assert_eq!(bits.len(), 22);
+        let items = decode_bits(ScancodeSet2::new(), &bits);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].result, Err(Error::ParityError));
+        assert_eq!(
+            items[1].result,
+            Ok(DecodedItem::Key(KeyEvent::new(KeyCode::B, KeyState::Down)))
+        );
+        assert!(items[1].time > items[0].time);
+    }
+
+    #[test]
+    fn test_bad_csv() {
+        assert_eq!(
+            parse_saleae_csv("Time [s], clock, data\n0.0, 1, 1\n0.1, 0\n"),
+            Err(ParseError { line: 3 })
+        );
+        assert_eq!(parse_saleae_csv(""), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_hex_dump() {
+        let dump = "# Press and release A\n1C F0 1c\n0xE0,0x75 # Up arrow\n\n  aa";
+        let bytes = parse_hex_dump(dump).unwrap();
+        assert_eq!(bytes, [0x1C, 0xF0, 0x1C, 0xE0, 0x75, 0xAA]);
+        let items = decode_bytes(ScancodeSet2::new(), &bytes);
+        let results: Vec<_> = items.iter().map(|i| i.result.clone()).collect();
+        assert_eq!(
+            results,
+            [
+                Ok(DecodedItem::Key(KeyEvent::new(KeyCode::A, KeyState::Down))),
+                Ok(DecodedItem::Key(KeyEvent::new(KeyCode::A, KeyState::Up))),
+                Ok(DecodedItem::Key(KeyEvent::new(
+                    KeyCode::ArrowUp,
+                    KeyState::Down
+                ))),
+                Ok(DecodedItem::Protocol(crate::ProtocolEvent::PowerOnTestOk)),
+            ]
+        );
+        assert!(items.iter().all(|i| i.time.is_none()));
+        assert_eq!(parse_hex_dump("1C\nzz"), Err(ParseError { line: 2 }));
+        assert_eq!(parse_hex_dump("123"), Err(ParseError { line: 1 }));
+    }
+}
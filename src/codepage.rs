@@ -0,0 +1,101 @@
+//! Legacy code pages, for Alt + Numpad character entry
+
+/// Code page 437 characters 0x00 to 0x1F, which are symbols rather than
+/// control characters.
+const CP437_LOW: [char; 32] = [
+    '\0', '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘', '○', '◙', '♂', '♀', '♪', '♫', '☼', '►', '◄', '↕',
+    '‼', '¶', '§', '▬', '↨', '↑', '↓', '→', '←', '∟', '↔', '▲', '▼',
+];
+
+/// Code page 437 characters 0x80 to 0xFF.
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', 'É', 'æ', 'Æ',
+    'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', 'á', 'í', 'ó', 'ú', 'ñ', 'Ñ',
+    'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕',
+    '╣', '║', '╗', '╝', '╜', '╛', '┐', '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦',
+    '╠', '═', '╬', '╧', '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐',
+    '▀', 'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', '≡', '±',
+    '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{00A0}',
+];
+
+/// Windows-1252 characters 0x80 to 0x9F. Everything else is the same as
+/// ISO-8859-1 (and hence Unicode).
+const CP1252_HIGH: [Option<char>; 32] = [
+    Some('€'),
+    None,
+    Some('‚'),
+    Some('ƒ'),
+    Some('„'),
+    Some('…'),
+    Some('†'),
+    Some('‡'),
+    Some('ˆ'),
+    Some('‰'),
+    Some('Š'),
+    Some('‹'),
+    Some('Œ'),
+    None,
+    Some('Ž'),
+    None,
+    None,
+    Some('‘'),
+    Some('’'),
+    Some('“'),
+    Some('”'),
+    Some('•'),
+    Some('–'),
+    Some('—'),
+    Some('˜'),
+    Some('™'),
+    Some('š'),
+    Some('›'),
+    Some('œ'),
+    None,
+    Some('ž'),
+    Some('Ÿ'),
+];
+
+/// Convert a byte from code page 437 (the original IBM PC character set) to
+/// Unicode.
+pub(crate) const fn cp437(byte: u8) -> char {
+    match byte {
+        0x00..=0x1F => CP437_LOW[byte as usize],
+        0x7F => '⌂',
+        0x80..=0xFF => CP437_HIGH[(byte - 0x80) as usize],
+        _ => byte as char,
+    }
+}
+
+/// Convert a byte from Windows-1252 to Unicode.
+///
+/// Returns `None` for the five bytes which are not defined.
+pub(crate) const fn cp1252(byte: u8) -> Option<char> {
+    match byte {
+        0x80..=0x9F => CP1252_HIGH[(byte - 0x80) as usize],
+        _ => Some(byte as char),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cp437() {
+        assert_eq!(cp437(0x01), '☺');
+        assert_eq!(cp437(0x41), 'A');
+        assert_eq!(cp437(0x7F), '⌂');
+        assert_eq!(cp437(0x82), 'é');
+        assert_eq!(cp437(0xDB), '█');
+        assert_eq!(cp437(0xFF), '\u{00A0}');
+    }
+
+    #[test]
+    fn test_cp1252() {
+        assert_eq!(cp1252(0x41), Some('A'));
+        assert_eq!(cp1252(0x80), Some('€'));
+        assert_eq!(cp1252(0x81), None);
+        assert_eq!(cp1252(0x9F), Some('Ÿ'));
+        assert_eq!(cp1252(0xE9), Some('é'));
+    }
+}
@@ -0,0 +1,730 @@
+//! Dead key composition
+//!
+//! A dead key is a key which doesn't produce a character by itself, but
+//! instead changes the character produced by the next key. For example, on
+//! many European layouts, pressing `^` and then `e` gives `ê`.
+//!
+//! Layouts report dead keys as [`DecodedKey::DeadKey`], holding the spacing
+//! form of the diacritic (e.g. `^`, `¨`, `´`). The [`EventDecoder`] then uses
+//! the table in this module to combine it with the next character.
+//!
+//! It also holds the default table of Compose key sequences (see
+//! [`EventDecoder::set_compose_key`]).
+//!
+//! [`DecodedKey::DeadKey`]: crate::DecodedKey::DeadKey
+//! [`EventDecoder`]: crate::EventDecoder
+//! [`EventDecoder::set_compose_key`]: crate::EventDecoder::set_compose_key
+
+/// The built-in table of dead key compositions.
+///
+/// Each entry is `((dead_key, base), result)`, where `dead_key` is the
+/// spacing form of the diacritic.
+pub const DEAD_KEY_COMPOSITIONS: &[((char, char), char)] = &[
+    // Grave (`)
+    (('`', 'a'), 'à'),
+    (('`', 'A'), 'À'),
+    (('`', 'e'), 'è'),
+    (('`', 'E'), 'È'),
+    (('`', 'i'), 'ì'),
+    (('`', 'I'), 'Ì'),
+    (('`', 'n'), 'ǹ'),
+    (('`', 'N'), 'Ǹ'),
+    (('`', 'o'), 'ò'),
+    (('`', 'O'), 'Ò'),
+    (('`', 'u'), 'ù'),
+    (('`', 'U'), 'Ù'),
+    (('`', 'w'), 'ẁ'),
+    (('`', 'W'), 'Ẁ'),
+    (('`', 'y'), 'ỳ'),
+    (('`', 'Y'), 'Ỳ'),
+    // Acute (´)
+    (('´', 'a'), 'á'),
+    (('´', 'A'), 'Á'),
+    (('´', 'c'), 'ć'),
+    (('´', 'C'), 'Ć'),
+    (('´', 'e'), 'é'),
+    (('´', 'E'), 'É'),
+    (('´', 'g'), 'ǵ'),
+    (('´', 'G'), 'Ǵ'),
+    (('´', 'i'), 'í'),
+    (('´', 'I'), 'Í'),
+    (('´', 'k'), 'ḱ'),
+    (('´', 'K'), 'Ḱ'),
+    (('´', 'l'), 'ĺ'),
+    (('´', 'L'), 'Ĺ'),
+    (('´', 'm'), 'ḿ'),
+    (('´', 'M'), 'Ḿ'),
+    (('´', 'n'), 'ń'),
+    (('´', 'N'), 'Ń'),
+    (('´', 'o'), 'ó'),
+    (('´', 'O'), 'Ó'),
+    (('´', 'p'), 'ṕ'),
+    (('´', 'P'), 'Ṕ'),
+    (('´', 'r'), 'ŕ'),
+    (('´', 'R'), 'Ŕ'),
+    (('´', 's'), 'ś'),
+    (('´', 'S'), 'Ś'),
+    (('´', 'u'), 'ú'),
+    (('´', 'U'), 'Ú'),
+    (('´', 'w'), 'ẃ'),
+    (('´', 'W'), 'Ẃ'),
+    (('´', 'y'), 'ý'),
+    (('´', 'Y'), 'Ý'),
+    (('´', 'z'), 'ź'),
+    (('´', 'Z'), 'Ź'),
+    // Circumflex (^)
+    (('^', 'a'), 'â'),
+    (('^', 'A'), 'Â'),
+    (('^', 'c'), 'ĉ'),
+    (('^', 'C'), 'Ĉ'),
+    (('^', 'e'), 'ê'),
+    (('^', 'E'), 'Ê'),
+    (('^', 'g'), 'ĝ'),
+    (('^', 'G'), 'Ĝ'),
+    (('^', 'h'), 'ĥ'),
+    (('^', 'H'), 'Ĥ'),
+    (('^', 'i'), 'î'),
+    (('^', 'I'), 'Î'),
+    (('^', 'j'), 'ĵ'),
+    (('^', 'J'), 'Ĵ'),
+    (('^', 'o'), 'ô'),
+    (('^', 'O'), 'Ô'),
+    (('^', 's'), 'ŝ'),
+    (('^', 'S'), 'Ŝ'),
+    (('^', 'u'), 'û'),
+    (('^', 'U'), 'Û'),
+    (('^', 'w'), 'ŵ'),
+    (('^', 'W'), 'Ŵ'),
+    (('^', 'y'), 'ŷ'),
+    (('^', 'Y'), 'Ŷ'),
+    (('^', 'z'), 'ẑ'),
+    (('^', 'Z'), 'Ẑ'),
+    // Tilde (~)
+    (('~', 'a'), 'ã'),
+    (('~', 'A'), 'Ã'),
+    (('~', 'e'), 'ẽ'),
+    (('~', 'E'), 'Ẽ'),
+    (('~', 'i'), 'ĩ'),
+    (('~', 'I'), 'Ĩ'),
+    (('~', 'n'), 'ñ'),
+    (('~', 'N'), 'Ñ'),
+    (('~', 'o'), 'õ'),
+    (('~', 'O'), 'Õ'),
+    (('~', 'u'), 'ũ'),
+    (('~', 'U'), 'Ũ'),
+    (('~', 'v'), 'ṽ'),
+    (('~', 'V'), 'Ṽ'),
+    (('~', 'y'), 'ỹ'),
+    (('~', 'Y'), 'Ỹ'),
+    // Macron (¯)
+    (('¯', 'a'), 'ā'),
+    (('¯', 'A'), 'Ā'),
+    (('¯', 'e'), 'ē'),
+    (('¯', 'E'), 'Ē'),
+    (('¯', 'g'), 'ḡ'),
+    (('¯', 'G'), 'Ḡ'),
+    (('¯', 'i'), 'ī'),
+    (('¯', 'I'), 'Ī'),
+    (('¯', 'o'), 'ō'),
+    (('¯', 'O'), 'Ō'),
+    (('¯', 'u'), 'ū'),
+    (('¯', 'U'), 'Ū'),
+    (('¯', 'y'), 'ȳ'),
+    (('¯', 'Y'), 'Ȳ'),
+    // Breve (˘)
+    (('˘', 'a'), 'ă'),
+    (('˘', 'A'), 'Ă'),
+    (('˘', 'e'), 'ĕ'),
+    (('˘', 'E'), 'Ĕ'),
+    (('˘', 'g'), 'ğ'),
+    (('˘', 'G'), 'Ğ'),
+    (('˘', 'i'), 'ĭ'),
+    (('˘', 'I'), 'Ĭ'),
+    (('˘', 'o'), 'ŏ'),
+    (('˘', 'O'), 'Ŏ'),
+    (('˘', 'u'), 'ŭ'),
+    (('˘', 'U'), 'Ŭ'),
+    // Dot above (˙)
+    (('˙', 'a'), 'ȧ'),
+    (('˙', 'A'), 'Ȧ'),
+    (('˙', 'b'), 'ḃ'),
+    (('˙', 'B'), 'Ḃ'),
+    (('˙', 'c'), 'ċ'),
+    (('˙', 'C'), 'Ċ'),
+    (('˙', 'd'), 'ḋ'),
+    (('˙', 'D'), 'Ḋ'),
+    (('˙', 'e'), 'ė'),
+    (('˙', 'E'), 'Ė'),
+    (('˙', 'f'), 'ḟ'),
+    (('˙', 'F'), 'Ḟ'),
+    (('˙', 'g'), 'ġ'),
+    (('˙', 'G'), 'Ġ'),
+    (('˙', 'h'), 'ḣ'),
+    (('˙', 'H'), 'Ḣ'),
+    (('˙', 'I'), 'İ'),
+    (('˙', 'm'), 'ṁ'),
+    (('˙', 'M'), 'Ṁ'),
+    (('˙', 'n'), 'ṅ'),
+    (('˙', 'N'), 'Ṅ'),
+    (('˙', 'o'), 'ȯ'),
+    (('˙', 'O'), 'Ȯ'),
+    (('˙', 'p'), 'ṗ'),
+    (('˙', 'P'), 'Ṗ'),
+    (('˙', 'r'), 'ṙ'),
+    (('˙', 'R'), 'Ṙ'),
+    (('˙', 's'), 'ṡ'),
+    (('˙', 'S'), 'Ṡ'),
+    (('˙', 't'), 'ṫ'),
+    (('˙', 'T'), 'Ṫ'),
+    (('˙', 'w'), 'ẇ'),
+    (('˙', 'W'), 'Ẇ'),
+    (('˙', 'x'), 'ẋ'),
+    (('˙', 'X'), 'Ẋ'),
+    (('˙', 'y'), 'ẏ'),
+    (('˙', 'Y'), 'Ẏ'),
+    (('˙', 'z'), 'ż'),
+    (('˙', 'Z'), 'Ż'),
+    // Diaeresis (¨)
+    (('¨', 'a'), 'ä'),
+    (('¨', 'A'), 'Ä'),
+    (('¨', 'e'), 'ë'),
+    (('¨', 'E'), 'Ë'),
+    (('¨', 'h'), 'ḧ'),
+    (('¨', 'H'), 'Ḧ'),
+    (('¨', 'i'), 'ï'),
+    (('¨', 'I'), 'Ï'),
+    (('¨', 'o'), 'ö'),
+    (('¨', 'O'), 'Ö'),
+    (('¨', 't'), 'ẗ'),
+    (('¨', 'u'), 'ü'),
+    (('¨', 'U'), 'Ü'),
+    (('¨', 'w'), 'ẅ'),
+    (('¨', 'W'), 'Ẅ'),
+    (('¨', 'x'), 'ẍ'),
+    (('¨', 'X'), 'Ẍ'),
+    (('¨', 'y'), 'ÿ'),
+    (('¨', 'Y'), 'Ÿ'),
+    // Ring above (˚)
+    (('˚', 'a'), 'å'),
+    (('˚', 'A'), 'Å'),
+    (('˚', 'u'), 'ů'),
+    (('˚', 'U'), 'Ů'),
+    (('˚', 'w'), 'ẘ'),
+    (('˚', 'y'), 'ẙ'),
+    // Double acute (˝)
+    (('˝', 'o'), 'ő'),
+    (('˝', 'O'), 'Ő'),
+    (('˝', 'u'), 'ű'),
+    (('˝', 'U'), 'Ű'),
+    // Caron (ˇ)
+    (('ˇ', 'a'), 'ǎ'),
+    (('ˇ', 'A'), 'Ǎ'),
+    (('ˇ', 'c'), 'č'),
+    (('ˇ', 'C'), 'Č'),
+    (('ˇ', 'd'), 'ď'),
+    (('ˇ', 'D'), 'Ď'),
+    (('ˇ', 'e'), 'ě'),
+    (('ˇ', 'E'), 'Ě'),
+    (('ˇ', 'g'), 'ǧ'),
+    (('ˇ', 'G'), 'Ǧ'),
+    (('ˇ', 'h'), 'ȟ'),
+    (('ˇ', 'H'), 'Ȟ'),
+    (('ˇ', 'i'), 'ǐ'),
+    (('ˇ', 'I'), 'Ǐ'),
+    (('ˇ', 'j'), 'ǰ'),
+    (('ˇ', 'k'), 'ǩ'),
+    (('ˇ', 'K'), 'Ǩ'),
+    (('ˇ', 'l'), 'ľ'),
+    (('ˇ', 'L'), 'Ľ'),
+    (('ˇ', 'n'), 'ň'),
+    (('ˇ', 'N'), 'Ň'),
+    (('ˇ', 'o'), 'ǒ'),
+    (('ˇ', 'O'), 'Ǒ'),
+    (('ˇ', 'r'), 'ř'),
+    (('ˇ', 'R'), 'Ř'),
+    (('ˇ', 's'), 'š'),
+    (('ˇ', 'S'), 'Š'),
+    (('ˇ', 't'), 'ť'),
+    (('ˇ', 'T'), 'Ť'),
+    (('ˇ', 'u'), 'ǔ'),
+    (('ˇ', 'U'), 'Ǔ'),
+    (('ˇ', 'z'), 'ž'),
+    (('ˇ', 'Z'), 'Ž'),
+    // Cedilla (¸)
+    (('¸', 'c'), 'ç'),
+    (('¸', 'C'), 'Ç'),
+    (('¸', 'd'), 'ḑ'),
+    (('¸', 'D'), 'Ḑ'),
+    (('¸', 'e'), 'ȩ'),
+    (('¸', 'E'), 'Ȩ'),
+    (('¸', 'g'), 'ģ'),
+    (('¸', 'G'), 'Ģ'),
+    (('¸', 'h'), 'ḩ'),
+    (('¸', 'H'), 'Ḩ'),
+    (('¸', 'k'), 'ķ'),
+    (('¸', 'K'), 'Ķ'),
+    (('¸', 'l'), 'ļ'),
+    (('¸', 'L'), 'Ļ'),
+    (('¸', 'n'), 'ņ'),
+    (('¸', 'N'), 'Ņ'),
+    (('¸', 'r'), 'ŗ'),
+    (('¸', 'R'), 'Ŗ'),
+    (('¸', 's'), 'ş'),
+    (('¸', 'S'), 'Ş'),
+    (('¸', 't'), 'ţ'),
+    (('¸', 'T'), 'Ţ'),
+    // Ogonek (˛)
+    (('˛', 'a'), 'ą'),
+    (('˛', 'A'), 'Ą'),
+    (('˛', 'e'), 'ę'),
+    (('˛', 'E'), 'Ę'),
+    (('˛', 'i'), 'į'),
+    (('˛', 'I'), 'Į'),
+    (('˛', 'o'), 'ǫ'),
+    (('˛', 'O'), 'Ǫ'),
+    (('˛', 'u'), 'ų'),
+    (('˛', 'U'), 'Ų'),
+    // Greek tonos (΄, written with the acute), varia (`) and breathings (᾿ psili, ῾ dasia)
+    (('´', 'α'), 'ά'),
+    (('`', 'α'), 'ὰ'),
+    (('᾿', 'α'), 'ἀ'),
+    (('῾', 'α'), 'ἁ'),
+    (('´', 'ε'), 'έ'),
+    (('`', 'ε'), 'ὲ'),
+    (('᾿', 'ε'), 'ἐ'),
+    (('῾', 'ε'), 'ἑ'),
+    (('´', 'η'), 'ή'),
+    (('`', 'η'), 'ὴ'),
+    (('᾿', 'η'), 'ἠ'),
+    (('῾', 'η'), 'ἡ'),
+    (('´', 'ι'), 'ί'),
+    (('`', 'ι'), 'ὶ'),
+    (('᾿', 'ι'), 'ἰ'),
+    (('῾', 'ι'), 'ἱ'),
+    (('´', 'ο'), 'ό'),
+    (('`', 'ο'), 'ὸ'),
+    (('᾿', 'ο'), 'ὀ'),
+    (('῾', 'ο'), 'ὁ'),
+    (('´', 'υ'), 'ύ'),
+    (('`', 'υ'), 'ὺ'),
+    (('᾿', 'υ'), 'ὐ'),
+    (('῾', 'υ'), 'ὑ'),
+    (('´', 'ω'), 'ώ'),
+    (('`', 'ω'), 'ὼ'),
+    (('᾿', 'ω'), 'ὠ'),
+    (('῾', 'ω'), 'ὡ'),
+    (('´', 'Α'), 'Ά'),
+    (('`', 'Α'), 'Ὰ'),
+    (('᾿', 'Α'), 'Ἀ'),
+    (('῾', 'Α'), 'Ἁ'),
+    (('´', 'Ε'), 'Έ'),
+    (('`', 'Ε'), 'Ὲ'),
+    (('᾿', 'Ε'), 'Ἐ'),
+    (('῾', 'Ε'), 'Ἑ'),
+    (('´', 'Η'), 'Ή'),
+    (('`', 'Η'), 'Ὴ'),
+    (('᾿', 'Η'), 'Ἠ'),
+    (('῾', 'Η'), 'Ἡ'),
+    (('´', 'Ι'), 'Ί'),
+    (('`', 'Ι'), 'Ὶ'),
+    (('᾿', 'Ι'), 'Ἰ'),
+    (('῾', 'Ι'), 'Ἱ'),
+    (('´', 'Ο'), 'Ό'),
+    (('`', 'Ο'), 'Ὸ'),
+    (('᾿', 'Ο'), 'Ὀ'),
+    (('῾', 'Ο'), 'Ὁ'),
+    (('´', 'Υ'), 'Ύ'),
+    (('`', 'Υ'), 'Ὺ'),
+    (('῾', 'Υ'), 'Ὑ'),
+    (('´', 'Ω'), 'Ώ'),
+    (('`', 'Ω'), 'Ὼ'),
+    (('᾿', 'Ω'), 'Ὠ'),
+    (('῾', 'Ω'), 'Ὡ'),
+    // Greek perispomeni (῀)
+    (('῀', 'α'), 'ᾶ'),
+    (('῀', 'η'), 'ῆ'),
+    (('῀', 'ι'), 'ῖ'),
+    (('῀', 'υ'), 'ῦ'),
+    (('῀', 'ω'), 'ῶ'),
+    // Greek dialytika (¨)
+    (('¨', 'ι'), 'ϊ'),
+    (('¨', 'υ'), 'ϋ'),
+    (('¨', 'Ι'), 'Ϊ'),
+    (('¨', 'Υ'), 'Ϋ'),
+];
+
+/// The built-in table of compositions for a second dead key.
+///
+/// These are like [`DEAD_KEY_COMPOSITIONS`], except the base is a character
+/// which already has one diacritic, so two dead keys pressed one after the
+/// other (e.g. a Greek breathing and an accent) can be combined with a
+/// letter.
+pub const MULTI_ACCENT_COMPOSITIONS: &[((char, char), char)] = &[
+    // Greek breathing with an accent, in either order
+    (('´', 'ἀ'), 'ἄ'),
+    (('᾿', 'ά'), 'ἄ'),
+    (('`', 'ἀ'), 'ἂ'),
+    (('᾿', 'ὰ'), 'ἂ'),
+    (('´', 'ἁ'), 'ἅ'),
+    (('῾', 'ά'), 'ἅ'),
+    (('`', 'ἁ'), 'ἃ'),
+    (('῾', 'ὰ'), 'ἃ'),
+    (('´', 'ἐ'), 'ἔ'),
+    (('᾿', 'έ'), 'ἔ'),
+    (('`', 'ἐ'), 'ἒ'),
+    (('᾿', 'ὲ'), 'ἒ'),
+    (('´', 'ἑ'), 'ἕ'),
+    (('῾', 'έ'), 'ἕ'),
+    (('`', 'ἑ'), 'ἓ'),
+    (('῾', 'ὲ'), 'ἓ'),
+    (('´', 'ἠ'), 'ἤ'),
+    (('᾿', 'ή'), 'ἤ'),
+    (('`', 'ἠ'), 'ἢ'),
+    (('᾿', 'ὴ'), 'ἢ'),
+    (('´', 'ἡ'), 'ἥ'),
+    (('῾', 'ή'), 'ἥ'),
+    (('`', 'ἡ'), 'ἣ'),
+    (('῾', 'ὴ'), 'ἣ'),
+    (('´', 'ἰ'), 'ἴ'),
+    (('᾿', 'ί'), 'ἴ'),
+    (('`', 'ἰ'), 'ἲ'),
+    (('᾿', 'ὶ'), 'ἲ'),
+    (('´', 'ἱ'), 'ἵ'),
+    (('῾', 'ί'), 'ἵ'),
+    (('`', 'ἱ'), 'ἳ'),
+    (('῾', 'ὶ'), 'ἳ'),
+    (('´', 'ὀ'), 'ὄ'),
+    (('᾿', 'ό'), 'ὄ'),
+    (('`', 'ὀ'), 'ὂ'),
+    (('᾿', 'ὸ'), 'ὂ'),
+    (('´', 'ὁ'), 'ὅ'),
+    (('῾', 'ό'), 'ὅ'),
+    (('`', 'ὁ'), 'ὃ'),
+    (('῾', 'ὸ'), 'ὃ'),
+    (('´', 'ὐ'), 'ὔ'),
+    (('᾿', 'ύ'), 'ὔ'),
+    (('`', 'ὐ'), 'ὒ'),
+    (('᾿', 'ὺ'), 'ὒ'),
+    (('´', 'ὑ'), 'ὕ'),
+    (('῾', 'ύ'), 'ὕ'),
+    (('`', 'ὑ'), 'ὓ'),
+    (('῾', 'ὺ'), 'ὓ'),
+    (('´', 'ὠ'), 'ὤ'),
+    (('᾿', 'ώ'), 'ὤ'),
+    (('`', 'ὠ'), 'ὢ'),
+    (('᾿', 'ὼ'), 'ὢ'),
+    (('´', 'ὡ'), 'ὥ'),
+    (('῾', 'ώ'), 'ὥ'),
+    (('`', 'ὡ'), 'ὣ'),
+    (('῾', 'ὼ'), 'ὣ'),
+    (('´', 'Ἀ'), 'Ἄ'),
+    (('᾿', 'Ά'), 'Ἄ'),
+    (('`', 'Ἀ'), 'Ἂ'),
+    (('᾿', 'Ὰ'), 'Ἂ'),
+    (('´', 'Ἁ'), 'Ἅ'),
+    (('῾', 'Ά'), 'Ἅ'),
+    (('`', 'Ἁ'), 'Ἃ'),
+    (('῾', 'Ὰ'), 'Ἃ'),
+    (('´', 'Ἐ'), 'Ἔ'),
+    (('᾿', 'Έ'), 'Ἔ'),
+    (('`', 'Ἐ'), 'Ἒ'),
+    (('᾿', 'Ὲ'), 'Ἒ'),
+    (('´', 'Ἑ'), 'Ἕ'),
+    (('῾', 'Έ'), 'Ἕ'),
+    (('`', 'Ἑ'), 'Ἓ'),
+    (('῾', 'Ὲ'), 'Ἓ'),
+    (('´', 'Ἠ'), 'Ἤ'),
+    (('᾿', 'Ή'), 'Ἤ'),
+    (('`', 'Ἠ'), 'Ἢ'),
+    (('᾿', 'Ὴ'), 'Ἢ'),
+    (('´', 'Ἡ'), 'Ἥ'),
+    (('῾', 'Ή'), 'Ἥ'),
+    (('`', 'Ἡ'), 'Ἣ'),
+    (('῾', 'Ὴ'), 'Ἣ'),
+    (('´', 'Ἰ'), 'Ἴ'),
+    (('᾿', 'Ί'), 'Ἴ'),
+    (('`', 'Ἰ'), 'Ἲ'),
+    (('᾿', 'Ὶ'), 'Ἲ'),
+    (('´', 'Ἱ'), 'Ἵ'),
+    (('῾', 'Ί'), 'Ἵ'),
+    (('`', 'Ἱ'), 'Ἳ'),
+    (('῾', 'Ὶ'), 'Ἳ'),
+    (('´', 'Ὀ'), 'Ὄ'),
+    (('᾿', 'Ό'), 'Ὄ'),
+    (('`', 'Ὀ'), 'Ὂ'),
+    (('᾿', 'Ὸ'), 'Ὂ'),
+    (('´', 'Ὁ'), 'Ὅ'),
+    (('῾', 'Ό'), 'Ὅ'),
+    (('`', 'Ὁ'), 'Ὃ'),
+    (('῾', 'Ὸ'), 'Ὃ'),
+    (('´', 'Ὑ'), 'Ὕ'),
+    (('῾', 'Ύ'), 'Ὕ'),
+    (('`', 'Ὑ'), 'Ὓ'),
+    (('῾', 'Ὺ'), 'Ὓ'),
+    (('´', 'Ὠ'), 'Ὤ'),
+    (('᾿', 'Ώ'), 'Ὤ'),
+    (('`', 'Ὠ'), 'Ὢ'),
+    (('᾿', 'Ὼ'), 'Ὢ'),
+    (('´', 'Ὡ'), 'Ὥ'),
+    (('῾', 'Ώ'), 'Ὥ'),
+    (('`', 'Ὡ'), 'Ὣ'),
+    (('῾', 'Ὼ'), 'Ὣ'),
+    // Vietnamese circumflex with a tone mark, in either order
+    (('´', 'â'), 'ấ'),
+    (('`', 'â'), 'ầ'),
+    (('^', 'á'), 'ấ'),
+    (('^', 'à'), 'ầ'),
+    (('´', 'ê'), 'ế'),
+    (('`', 'ê'), 'ề'),
+    (('^', 'é'), 'ế'),
+    (('^', 'è'), 'ề'),
+    (('´', 'ô'), 'ố'),
+    (('`', 'ô'), 'ồ'),
+    (('^', 'ó'), 'ố'),
+    (('^', 'ò'), 'ồ'),
+    (('´', 'Â'), 'Ấ'),
+    (('`', 'Â'), 'Ầ'),
+    (('^', 'Á'), 'Ấ'),
+    (('^', 'À'), 'Ầ'),
+    (('´', 'Ê'), 'Ế'),
+    (('`', 'Ê'), 'Ề'),
+    (('^', 'É'), 'Ế'),
+    (('^', 'È'), 'Ề'),
+    (('´', 'Ô'), 'Ố'),
+    (('`', 'Ô'), 'Ồ'),
+    (('^', 'Ó'), 'Ố'),
+    (('^', 'Ò'), 'Ồ'),
+    (('´', 'ă'), 'ắ'),
+    (('`', 'ă'), 'ằ'),
+    (('˘', 'á'), 'ắ'),
+    (('˘', 'à'), 'ằ'),
+    (('´', 'Ă'), 'Ắ'),
+    (('`', 'Ă'), 'Ằ'),
+    (('˘', 'Á'), 'Ắ'),
+    (('˘', 'À'), 'Ằ'),
+    // Greek breathing with a perispomeni, in either order
+    (('῀', 'ἀ'), 'ἆ'),
+    (('᾿', 'ᾶ'), 'ἆ'),
+    (('῀', 'ἁ'), 'ἇ'),
+    (('῾', 'ᾶ'), 'ἇ'),
+    (('῀', 'ἠ'), 'ἦ'),
+    (('᾿', 'ῆ'), 'ἦ'),
+    (('῀', 'ἡ'), 'ἧ'),
+    (('῾', 'ῆ'), 'ἧ'),
+    (('῀', 'ἰ'), 'ἶ'),
+    (('᾿', 'ῖ'), 'ἶ'),
+    (('῀', 'ἱ'), 'ἷ'),
+    (('῾', 'ῖ'), 'ἷ'),
+    (('῀', 'ὐ'), 'ὖ'),
+    (('᾿', 'ῦ'), 'ὖ'),
+    (('῀', 'ὑ'), 'ὗ'),
+    (('῾', 'ῦ'), 'ὗ'),
+    (('῀', 'ὠ'), 'ὦ'),
+    (('᾿', 'ῶ'), 'ὦ'),
+    (('῀', 'ὡ'), 'ὧ'),
+    (('῾', 'ῶ'), 'ὧ'),
+];
+
+/// The built-in table of Compose key sequences.
+///
+/// Each entry is `(first, second, result)`: pressing the Compose key, then
+/// `first`, then `second`, gives `result`. See
+/// [`EventDecoder::set_compose_key`](crate::EventDecoder::set_compose_key).
+pub const COMPOSE_SEQUENCES: &[(char, char, char)] = &[
+    // Acute
+    ('\'', 'a', 'á'),
+    ('\'', 'A', 'Á'),
+    ('\'', 'e', 'é'),
+    ('\'', 'E', 'É'),
+    ('\'', 'i', 'í'),
+    ('\'', 'I', 'Í'),
+    ('\'', 'o', 'ó'),
+    ('\'', 'O', 'Ó'),
+    ('\'', 'u', 'ú'),
+    ('\'', 'U', 'Ú'),
+    ('\'', 'y', 'ý'),
+    ('\'', 'Y', 'Ý'),
+    // Grave
+    ('`', 'a', 'à'),
+    ('`', 'A', 'À'),
+    ('`', 'e', 'è'),
+    ('`', 'E', 'È'),
+    ('`', 'i', 'ì'),
+    ('`', 'I', 'Ì'),
+    ('`', 'o', 'ò'),
+    ('`', 'O', 'Ò'),
+    ('`', 'u', 'ù'),
+    ('`', 'U', 'Ù'),
+    // Circumflex
+    ('^', 'a', 'â'),
+    ('^', 'A', 'Â'),
+    ('^', 'e', 'ê'),
+    ('^', 'E', 'Ê'),
+    ('^', 'i', 'î'),
+    ('^', 'I', 'Î'),
+    ('^', 'o', 'ô'),
+    ('^', 'O', 'Ô'),
+    ('^', 'u', 'û'),
+    ('^', 'U', 'Û'),
+    ('^', '2', '²'),
+    ('^', '3', '³'),
+    // Diaeresis
+    ('"', 'a', 'ä'),
+    ('"', 'A', 'Ä'),
+    ('"', 'e', 'ë'),
+    ('"', 'E', 'Ë'),
+    ('"', 'i', 'ï'),
+    ('"', 'I', 'Ï'),
+    ('"', 'o', 'ö'),
+    ('"', 'O', 'Ö'),
+    ('"', 'u', 'ü'),
+    ('"', 'U', 'Ü'),
+    ('"', 'y', 'ÿ'),
+    // Tilde
+    ('~', 'a', 'ã'),
+    ('~', 'A', 'Ã'),
+    ('~', 'n', 'ñ'),
+    ('~', 'N', 'Ñ'),
+    ('~', 'o', 'õ'),
+    ('~', 'O', 'Õ'),
+    // Cedilla
+    (',', 'c', 'ç'),
+    (',', 'C', 'Ç'),
+    // Ligatures and other letters
+    ('s', 's', 'ß'),
+    ('a', 'e', 'æ'),
+    ('A', 'E', 'Æ'),
+    ('o', 'e', 'œ'),
+    ('O', 'E', 'Œ'),
+    ('o', '/', 'ø'),
+    ('O', '/', 'Ø'),
+    ('o', 'a', 'å'),
+    ('O', 'A', 'Å'),
+    // Punctuation and symbols
+    ('<', '<', '«'),
+    ('>', '>', '»'),
+    ('!', '!', '¡'),
+    ('?', '?', '¿'),
+    ('C', '=', '€'),
+    ('L', '-', '£'),
+    ('Y', '=', '¥'),
+    ('o', 'c', '©'),
+    ('o', 'r', '®'),
+    ('+', '-', '±'),
+    ('1', '2', '½'),
+    ('1', '4', '¼'),
+    ('3', '4', '¾'),
+];
+
+/// Look up a Compose key sequence in a table.
+pub(crate) fn lookup_sequence(
+    table: &[(char, char, char)],
+    first: char,
+    second: char,
+) -> Option<char> {
+    table
+        .iter()
+        .find(|(f, s, _)| *f == first && *s == second)
+        .map(|(_, _, result)| *result)
+}
+
+/// Combine a dead key with the character that followed it.
+///
+/// A dead key followed by a space gives the dead key's diacritic on its own.
+/// Returns `None` if there is no precomposed form.
+pub fn combine(dead_key: char, base: char) -> Option<char> {
+    if base == ' ' {
+        return Some(dead_key);
+    }
+    lookup(DEAD_KEY_COMPOSITIONS, dead_key, base)
+        .or_else(|| lookup(MULTI_ACCENT_COMPOSITIONS, dead_key, base))
+}
+
+/// Look up a dead key and a base character in a table of compositions.
+pub(crate) fn lookup(table: &[((char, char), char)], dead_key: char, base: char) -> Option<char> {
+    table
+        .iter()
+        .find(|((d, b), _)| *d == dead_key && *b == base)
+        .map(|(_, result)| *result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_combine() {
+        assert_eq!(combine('^', 'e'), Some('ê'));
+        assert_eq!(combine('¨', 'U'), Some('Ü'));
+        assert_eq!(combine('ˇ', 's'), Some('š'));
+        assert_eq!(combine('^', ' '), Some('^'));
+        assert_eq!(combine('^', 'q'), None);
+        assert_eq!(combine('x', 'e'), None);
+    }
+
+    #[test]
+    fn test_multi_accent() {
+        // Psili then oxia, or the other way around
+        assert_eq!(combine('´', 'ἀ'), Some('ἄ'));
+        assert_eq!(combine('᾿', 'ά'), Some('ἄ'));
+        assert_eq!(combine('῾', 'Ω'), Some('Ὡ'));
+        // No capital upsilon with psili
+        assert_eq!(combine('᾿', 'Υ'), None);
+        assert_eq!(combine('῀', 'ἁ'), Some('ἇ'));
+        assert_eq!(combine('᾿', 'ῶ'), Some('ὦ'));
+        assert_eq!(combine('´', 'ê'), Some('ế'));
+        assert_eq!(combine('^', 'é'), Some('ế'));
+    }
+
+    #[test]
+    fn test_welsh() {
+        // Welsh uses the circumflex (to bach) on all seven vowels
+        for (base, result) in [
+            ('a', 'â'),
+            ('e', 'ê'),
+            ('i', 'î'),
+            ('o', 'ô'),
+            ('u', 'û'),
+            ('w', 'ŵ'),
+            ('y', 'ŷ'),
+            ('W', 'Ŵ'),
+            ('Y', 'Ŷ'),
+        ] {
+            assert_eq!(combine('^', base), Some(result));
+        }
+    }
+
+    #[test]
+    fn test_compose_sequences() {
+        assert_eq!(lookup_sequence(COMPOSE_SEQUENCES, '\'', 'a'), Some('á'));
+        assert_eq!(lookup_sequence(COMPOSE_SEQUENCES, 's', 's'), Some('ß'));
+        // The order matters
+        assert_eq!(lookup_sequence(COMPOSE_SEQUENCES, 'a', '\''), None);
+        // No sequence should be in the table twice
+        for (idx, (first, second, _)) in COMPOSE_SEQUENCES.iter().enumerate() {
+            for (first2, second2, _) in &COMPOSE_SEQUENCES[idx + 1..] {
+                assert!(
+                    !(first == first2 && second == second2),
+                    "{}{} duplicated",
+                    first,
+                    second
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_duplicates() {
+        // No entry should be in the table twice
+        for (idx, ((dead, base), _)) in DEAD_KEY_COMPOSITIONS.iter().enumerate() {
+            for ((dead2, base2), _) in &DEAD_KEY_COMPOSITIONS[idx + 1..] {
+                assert!(
+                    !(dead == dead2 && base == base2),
+                    "{}{} duplicated",
+                    dead,
+                    base
+                );
+            }
+        }
+    }
+}
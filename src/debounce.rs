@@ -0,0 +1,232 @@
+//! Filtering of duplicate key events
+
+use crate::{DecodedItem, Error, KeyEvent, ScancodeSet, ScancodeSetKind};
+
+/// Drops duplicate key events that arrive too close together.
+///
+/// A noisy clock line (e.g. a bit-banged PS/2 port on a long cable) can make
+/// a single key press turn up twice, a few microseconds apart. This sits
+/// between the scancode decoder and the [`EventDecoder`](crate::EventDecoder)
+/// and drops any event that is identical to the last one accepted, if it
+/// arrives within the window. Use [`Debounced`] to do that inside a
+/// [`Keyboard`](crate::Keyboard).
+///
+/// Time is measured in ticks of whatever timer you have, and may wrap
+/// around. Make the window shorter than the typematic repeat interval
+/// (typically 33 ms or more), so that held keys still repeat.
+#[derive(Debug, Clone)]
+pub struct Debouncer {
+    window: u32,
+    last: Option<(KeyEvent, u32)>,
+}
+
+impl Debouncer {
+    /// Make a new debouncer, which drops duplicate events less than `window`
+    /// ticks apart.
+    pub const fn new(window: u32) -> Debouncer {
+        Debouncer { window, last: None }
+    }
+
+    /// Get the debounce window, in ticks.
+    pub const fn window(&self) -> u32 {
+        self.window
+    }
+
+    /// Change the debounce window, in ticks.
+    pub fn set_window(&mut self, window: u32) {
+        self.window = window;
+    }
+
+    /// Forget the last event, so the next event is always accepted.
+    pub fn clear(&mut self) {
+        self.last = None;
+    }
+
+    /// Pass an event through the filter.
+    ///
+    /// `now` is the current time, in ticks. You get the event back, unless it
+    /// is a duplicate.
+    pub fn filter(&mut self, event: KeyEvent, now: u32) -> Option<KeyEvent> {
+        if let Some((last, when)) = &self.last {
+            if *last == event && now.wrapping_sub(*when) < self.window {
+                return None;
+            }
+        }
+        self.last = Some((event.clone(), now));
+        Some(event)
+    }
+}
+
+/// A [`ScancodeSet`] which passes its key events through a [`Debouncer`].
+///
+/// `clock` is called for each key event, and gives the current time in
+/// ticks. Protocol events are never dropped.
+///
+/// ```rust
+/// use pc_keyboard::{layouts, Debounced, HandleControl, Keyboard, ScancodeSet2};
+///
+/// let mut ticks = 0;
+/// let scancodes = Debounced::new(ScancodeSet2::new(), 10, move || {
+///     ticks += 1;
+///     ticks
+/// });
+/// let mut kb = Keyboard::new(scancodes, layouts::Us104Key, HandleControl::Ignore);
+/// assert!(kb.add_byte(0x1C).unwrap().is_some());
+/// // The same press again, one tick later
+/// assert!(kb.add_byte(0x1C).unwrap().is_none());
+/// ```
+pub struct Debounced<S, F> {
+    inner: S,
+    debouncer: Debouncer,
+    clock: F,
+}
+
+impl<S, F> Debounced<S, F>
+where
+    S: ScancodeSet,
+    F: FnMut() -> u32,
+{
+    /// Wrap a scancode set, dropping duplicate key events less than `window`
+    /// ticks apart.
+    pub const fn new(inner: S, window: u32, clock: F) -> Debounced<S, F> {
+        Debounced {
+            inner,
+            debouncer: Debouncer::new(window),
+            clock,
+        }
+    }
+
+    /// Get the [`Debouncer`], e.g. to change the window.
+    pub fn debouncer_mut(&mut self) -> &mut Debouncer {
+        &mut self.debouncer
+    }
+
+    /// Get back the wrapped scancode set.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, F> ScancodeSet for Debounced<S, F>
+where
+    S: ScancodeSet,
+    F: FnMut() -> u32,
+{
+    fn advance_state(&mut self, code: u8) -> Result<Option<DecodedItem>, Error> {
+        match self.inner.advance_state(code)? {
+            Some(DecodedItem::Key(ev)) => {
+                let now = (self.clock)();
+                Ok(self.debouncer.filter(ev, now).map(DecodedItem::Key))
+            }
+            item => Ok(item),
+        }
+    }
+
+    fn kind(&self) -> ScancodeSetKind {
+        self.inner.kind()
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.debouncer.clear();
+    }
+}
+
+impl<S, F> core::fmt::Debug for Debounced<S, F>
+where
+    S: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Debounced")
+            .field("inner", &self.inner)
+            .field("debouncer", &self.debouncer)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{layouts, HandleControl, KeyCode, KeyState, Keyboard, ProtocolEvent, ScancodeSet2};
+    use core::cell::Cell;
+
+    #[test]
+    fn test_duplicates() {
+        let mut d = Debouncer::new(10);
+        let a_down = KeyEvent::new(KeyCode::A, KeyState::Down);
+        let a_up = KeyEvent::new(KeyCode::A, KeyState::Up);
+        assert_eq!(d.filter(a_down.clone(), 100), Some(a_down.clone()));
+        // Bounce
+        assert_eq!(d.filter(a_down.clone(), 101), None);
+        assert_eq!(d.filter(a_down.clone(), 109), None);
+        // Release is a different event
+        assert_eq!(d.filter(a_up.clone(), 110), Some(a_up.clone()));
+        assert_eq!(d.filter(a_up, 111), None);
+        // A quick second press is fine, because the last event was a release
+        assert_eq!(d.filter(a_down.clone(), 112), Some(a_down));
+    }
+
+    #[test]
+    fn test_typematic() {
+        let mut d = Debouncer::new(10);
+        let a_down = KeyEvent::new(KeyCode::A, KeyState::Down);
+        assert_eq!(d.filter(a_down.clone(), 0), Some(a_down.clone()));
+        // A repeat outside the window
+        assert_eq!(d.filter(a_down.clone(), 10), Some(a_down.clone()));
+        // Bounces on the repeat are measured from the repeat
+        assert_eq!(d.filter(a_down.clone(), 19), None);
+        assert_eq!(d.filter(a_down.clone(), 20), Some(a_down));
+    }
+
+    #[test]
+    fn test_other_keys() {
+        let mut d = Debouncer::new(10);
+        let a = KeyEvent::new(KeyCode::A, KeyState::Down);
+        let b = KeyEvent::new(KeyCode::B, KeyState::Down);
+        assert_eq!(d.filter(a.clone(), 0), Some(a.clone()));
+        assert_eq!(d.filter(b.clone(), 1), Some(b));
+        // Only consecutive events are compared
+        assert_eq!(d.filter(a.clone(), 2), Some(a));
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let mut d = Debouncer::new(10);
+        let a = KeyEvent::new(KeyCode::A, KeyState::Down);
+        assert_eq!(d.filter(a.clone(), u32::MAX - 2), Some(a.clone()));
+        assert_eq!(d.filter(a.clone(), 3), None);
+        assert_eq!(d.filter(a.clone(), 8), Some(a.clone()));
+        // Turning it off
+        d.set_window(0);
+        assert_eq!(d.filter(a.clone(), 8), Some(a.clone()));
+        d.set_window(10);
+        d.clear();
+        assert_eq!(d.filter(a.clone(), 9), Some(a));
+    }
+
+    #[test]
+    fn test_keyboard() {
+        let now = Cell::new(0);
+        let scancodes = Debounced::new(ScancodeSet2::new(), 10, || now.get());
+        let mut k = Keyboard::new(scancodes, layouts::Us104Key, HandleControl::Ignore);
+        assert_eq!(k.scancode_set_kind(), ScancodeSetKind::Set2);
+        let a_down = Ok(Some(DecodedItem::Key(KeyEvent::new(
+            KeyCode::A,
+            KeyState::Down,
+        ))));
+        assert_eq!(k.add_byte(0x1C), a_down);
+        // Bounce
+        now.set(1);
+        assert_eq!(k.add_byte(0x1C), Ok(None));
+        // Protocol events always get through
+        let poweron = Ok(Some(DecodedItem::Protocol(ProtocolEvent::PowerOnTestOk)));
+        assert_eq!(k.add_byte(0xAA), poweron);
+        assert_eq!(k.add_byte(0xAA), poweron);
+        // Typematic repeat
+        now.set(10);
+        assert_eq!(k.add_byte(0x1C), a_down);
+        // Clearing the keyboard clears the debouncer too
+        k.clear();
+        assert_eq!(k.add_byte(0x1C), a_down);
+    }
+}
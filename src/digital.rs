@@ -0,0 +1,171 @@
+//! Mapping keys to digital (on/off) inputs, e.g. for games
+
+use crate::{KeyCode, KeyEvent, KeyState};
+
+/// Maps physical keys to your own action IDs, and tracks which are held.
+///
+/// Bindings use [`KeyCode`]s, not characters, so they follow the position of
+/// the key whatever the layout. Several keys can share an action ID - for
+/// example, you might bind both [`KeyCode::wasd`] and [`KeyCode::arrows`] to
+/// the same four movement actions.
+///
+/// Feed every [`KeyEvent`] into [`DigitalInputMap::process_keyevent`] (before
+/// or instead of an [`EventDecoder`](crate::EventDecoder)).
+#[derive(Debug, Clone)]
+pub struct DigitalInputMap<const N: usize> {
+    bindings: [(KeyCode, u16); N],
+    pressed: [bool; N],
+}
+
+impl<const N: usize> DigitalInputMap<N> {
+    /// Make a new map from a list of key bindings.
+    pub const fn new(bindings: [(KeyCode, u16); N]) -> DigitalInputMap<N> {
+        DigitalInputMap {
+            bindings,
+            pressed: [false; N],
+        }
+    }
+
+    /// Process a key event.
+    ///
+    /// If the key is bound to an action, and this changes whether that
+    /// action is active, you get the action ID and either `KeyState::Down` or
+    /// `KeyState::Up`. Typematic repeats of a held key give `None`.
+    pub fn process_keyevent(&mut self, ev: &KeyEvent) -> Option<(u16, KeyState)> {
+        let down = match ev.state {
+            KeyState::Down => true,
+            KeyState::Up => false,
+            KeyState::SingleShot => return None,
+        };
+        let mut result = None;
+        for idx in 0..N {
+            let (code, action) = self.bindings[idx];
+            if code != ev.code || self.pressed[idx] == down {
+                continue;
+            }
+            let was_active = self.is_active(action);
+            self.pressed[idx] = down;
+            if was_active != self.is_active(action) {
+                result = Some((action, ev.state));
+            }
+        }
+        result
+    }
+
+    /// Is any key bound to this action currently held down?
+    pub fn is_active(&self, action: u16) -> bool {
+        self.bindings
+            .iter()
+            .zip(self.pressed.iter())
+            .any(|((_, a), pressed)| *a == action && *pressed)
+    }
+
+    /// Is this key currently held down?
+    ///
+    /// Only keys in the map are tracked.
+    pub fn is_pressed(&self, code: KeyCode) -> bool {
+        self.bindings
+            .iter()
+            .zip(self.pressed.iter())
+            .any(|((c, _), pressed)| *c == code && *pressed)
+    }
+
+    /// Mark every key as released (e.g. if your window loses focus).
+    pub fn clear(&mut self) {
+        self.pressed = [false; N];
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const UP: u16 = 0;
+    const LEFT: u16 = 1;
+    const DOWN: u16 = 2;
+    const RIGHT: u16 = 3;
+    const FIRE: u16 = 4;
+
+    fn make_map() -> DigitalInputMap<9> {
+        let [w, a, s, d] = KeyCode::wasd();
+        let [up, left, down, right] = KeyCode::arrows();
+        DigitalInputMap::new([
+            (w, UP),
+            (a, LEFT),
+            (s, DOWN),
+            (d, RIGHT),
+            (up, UP),
+            (left, LEFT),
+            (down, DOWN),
+            (right, RIGHT),
+            (KeyCode::Spacebar, FIRE),
+        ])
+    }
+
+    #[test]
+    fn test_press_hold_release() {
+        let mut map = make_map();
+        let w_down = KeyEvent::new(KeyCode::W, KeyState::Down);
+        assert_eq!(map.process_keyevent(&w_down), Some((UP, KeyState::Down)));
+        assert!(map.is_active(UP));
+        assert!(map.is_pressed(KeyCode::W));
+        // Typematic repeat
+        assert_eq!(map.process_keyevent(&w_down), None);
+        assert!(map.is_active(UP));
+        assert_eq!(
+            map.process_keyevent(&KeyEvent::new(KeyCode::W, KeyState::Up)),
+            Some((UP, KeyState::Up))
+        );
+        assert!(!map.is_active(UP));
+        assert!(!map.is_pressed(KeyCode::W));
+    }
+
+    #[test]
+    fn test_shared_action() {
+        let mut map = make_map();
+        assert_eq!(
+            map.process_keyevent(&KeyEvent::new(KeyCode::A, KeyState::Down)),
+            Some((LEFT, KeyState::Down))
+        );
+        // Already active, so no change
+        assert_eq!(
+            map.process_keyevent(&KeyEvent::new(KeyCode::ArrowLeft, KeyState::Down)),
+            None
+        );
+        assert_eq!(
+            map.process_keyevent(&KeyEvent::new(KeyCode::A, KeyState::Up)),
+            None
+        );
+        assert!(map.is_active(LEFT));
+        assert_eq!(
+            map.process_keyevent(&KeyEvent::new(KeyCode::ArrowLeft, KeyState::Up)),
+            Some((LEFT, KeyState::Up))
+        );
+        assert!(!map.is_active(LEFT));
+    }
+
+    #[test]
+    fn test_unmapped() {
+        let mut map = make_map();
+        assert_eq!(
+            map.process_keyevent(&KeyEvent::new(KeyCode::Q, KeyState::Down)),
+            None
+        );
+        assert!(!map.is_pressed(KeyCode::Q));
+        // Releasing a key we never saw pressed
+        assert_eq!(
+            map.process_keyevent(&KeyEvent::new(KeyCode::Spacebar, KeyState::Up)),
+            None
+        );
+        assert_eq!(
+            map.process_keyevent(&KeyEvent::new(KeyCode::TooManyKeys, KeyState::SingleShot)),
+            None
+        );
+        assert_eq!(
+            map.process_keyevent(&KeyEvent::new(KeyCode::Spacebar, KeyState::Down)),
+            Some((FIRE, KeyState::Down))
+        );
+        map.clear();
+        assert!(!map.is_active(FIRE));
+    }
+}
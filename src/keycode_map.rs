@@ -0,0 +1,124 @@
+//! A value for every key code, without needing `alloc`
+
+use crate::KeyCode;
+
+/// Holds one `T` for every [`KeyCode`].
+///
+/// This is a thin wrapper around `[T; KeyCode::COUNT]`, indexed by
+/// [`KeyCode::to_index`]. Use it for key bindings, or to track which keys are
+/// held down.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct KeyCodeMap<T> {
+    values: [T; KeyCode::COUNT],
+}
+
+impl<T> KeyCodeMap<T>
+where
+    T: Copy,
+{
+    /// Make a new map, with every key code set to `value`.
+    pub const fn new(value: T) -> KeyCodeMap<T> {
+        KeyCodeMap {
+            values: [value; KeyCode::COUNT],
+        }
+    }
+}
+
+impl<T> KeyCodeMap<T> {
+    /// Make a new map from an array, in [`KeyCode::ALL`] order.
+    pub const fn from_array(values: [T; KeyCode::COUNT]) -> KeyCodeMap<T> {
+        KeyCodeMap { values }
+    }
+
+    /// Get the value for a key code.
+    pub const fn get(&self, code: KeyCode) -> &T {
+        &self.values[code.to_index()]
+    }
+
+    /// Change the value for a key code.
+    pub fn set(&mut self, code: KeyCode, value: T) {
+        self.values[code.to_index()] = value;
+    }
+
+    /// Go through every key code, and its value.
+    pub fn iter(&self) -> impl Iterator<Item = (KeyCode, &T)> {
+        KeyCode::ALL.iter().copied().zip(self.values.iter())
+    }
+
+    /// Go through every key code, and its value, which you can change.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (KeyCode, &mut T)> {
+        KeyCode::ALL.iter().copied().zip(self.values.iter_mut())
+    }
+
+    /// Get all the values, in [`KeyCode::ALL`] order.
+    pub const fn as_array(&self) -> &[T; KeyCode::COUNT] {
+        &self.values
+    }
+}
+
+impl<T> Default for KeyCodeMap<T>
+where
+    T: Default + Copy,
+{
+    fn default() -> Self {
+        KeyCodeMap::new(T::default())
+    }
+}
+
+impl<T> core::ops::Index<KeyCode> for KeyCodeMap<T> {
+    type Output = T;
+
+    fn index(&self, code: KeyCode) -> &T {
+        self.get(code)
+    }
+}
+
+impl<T> core::ops::IndexMut<KeyCode> for KeyCodeMap<T> {
+    fn index_mut(&mut self, code: KeyCode) -> &mut T {
+        &mut self.values[code.to_index()]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_all() {
+        assert_eq!(KeyCode::ALL.len(), KeyCode::COUNT);
+        for (idx, code) in KeyCode::ALL.iter().enumerate() {
+            assert_eq!(code.to_index(), idx);
+            assert_eq!(KeyCode::from_index(idx), Some(*code));
+        }
+        assert_eq!(KeyCode::from_index(KeyCode::COUNT), None);
+        assert_eq!(KeyCode::from_index(0), Some(KeyCode::Escape));
+    }
+
+    #[test]
+    fn test_index() {
+        const BINDINGS: KeyCodeMap<Option<u8>> = KeyCodeMap::new(None);
+        let mut map = BINDINGS;
+        map[KeyCode::A] = Some(1);
+        map.set(KeyCode::RAlt2, Some(2));
+        assert_eq!(map[KeyCode::A], Some(1));
+        assert_eq!(*map.get(KeyCode::RAlt2), Some(2));
+        assert_eq!(map[KeyCode::B], None);
+        assert_eq!(map.as_array()[KeyCode::A.to_index()], Some(1));
+        assert_ne!(map, BINDINGS);
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut map: KeyCodeMap<u32> = KeyCodeMap::default();
+        for (code, value) in map.iter_mut() {
+            *value = code.to_index() as u32 * 2;
+        }
+        assert_eq!(map.iter().count(), KeyCode::COUNT);
+        assert!(map
+            .iter()
+            .all(|(code, value)| *value == code.to_index() as u32 * 2));
+        let (code, value) = map.iter().last().unwrap();
+        assert_eq!(code, KeyCode::RAlt2);
+        assert_eq!(*value, (KeyCode::COUNT as u32 - 1) * 2);
+    }
+}
@@ -0,0 +1,190 @@
+//! Amharic (Ethiopic) keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// An Amharic (Ethiopic) 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// Each letter key types the first-order (base) fidel for its sound, with
+/// Shift giving the alternate consonant where there is one (e.g. `H` is
+/// `ሀ` and Shift + `H` is `ኸ`). The vowel keys give the vowel forms of `አ`.
+/// There is no composition of the other orders. Ethiopic has no upper and
+/// lower case, so Caps Lock does nothing.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct AmEt105Key;
+
+impl KeyboardLayout for AmEt105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        if handle_ctrl == HandleControl::MapLettersToUnicode && modifiers.is_ctrl() {
+            // Ctrl + a letter key still gives the control code on the keycap
+            let key = us.map_keycode(keycode, modifiers, handle_ctrl);
+            if matches!(key, DecodedKey::Unicode(ch) if ch.is_control()) {
+                return key;
+            }
+        }
+        match keycode {
+            KeyCode::Q => DecodedKey::Unicode('ቀ'),
+            KeyCode::W => DecodedKey::Unicode('ወ'),
+            KeyCode::E => DecodedKey::Unicode('ኤ'),
+            KeyCode::R => DecodedKey::Unicode('ረ'),
+            KeyCode::T => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ጠ')
+                } else {
+                    DecodedKey::Unicode('ተ')
+                }
+            }
+            KeyCode::Y => DecodedKey::Unicode('የ'),
+            KeyCode::U => DecodedKey::Unicode('ኡ'),
+            KeyCode::I => DecodedKey::Unicode('ኢ'),
+            KeyCode::O => DecodedKey::Unicode('ኦ'),
+            KeyCode::P => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ጰ')
+                } else {
+                    DecodedKey::Unicode('ፐ')
+                }
+            }
+            KeyCode::A => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ዐ')
+                } else {
+                    DecodedKey::Unicode('አ')
+                }
+            }
+            KeyCode::S => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ሠ')
+                } else {
+                    DecodedKey::Unicode('ሰ')
+                }
+            }
+            KeyCode::D => DecodedKey::Unicode('ደ'),
+            KeyCode::F => DecodedKey::Unicode('ፈ'),
+            KeyCode::G => DecodedKey::Unicode('ገ'),
+            KeyCode::H => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ኸ')
+                } else {
+                    DecodedKey::Unicode('ሀ')
+                }
+            }
+            KeyCode::J => DecodedKey::Unicode('ጀ'),
+            KeyCode::K => DecodedKey::Unicode('ከ'),
+            KeyCode::L => DecodedKey::Unicode('ለ'),
+            KeyCode::Oem1 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('፥')
+                } else {
+                    DecodedKey::Unicode('፤')
+                }
+            }
+            KeyCode::Z => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ዠ')
+                } else {
+                    DecodedKey::Unicode('ዘ')
+                }
+            }
+            KeyCode::X => DecodedKey::Unicode('ሸ'),
+            KeyCode::C => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ጨ')
+                } else {
+                    DecodedKey::Unicode('ቸ')
+                }
+            }
+            KeyCode::V => DecodedKey::Unicode('ቨ'),
+            KeyCode::B => DecodedKey::Unicode('በ'),
+            KeyCode::N => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ኘ')
+                } else {
+                    DecodedKey::Unicode('ነ')
+                }
+            }
+            KeyCode::M => DecodedKey::Unicode('መ'),
+            KeyCode::OemComma => DecodedKey::Unicode('፣'),
+            KeyCode::OemPeriod => DecodedKey::Unicode('።'),
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Phonetic placement, as used by most Ethiopic input methods
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Q, 'ቀ', 'ቀ', None, None),
+            (KeyCode::W, 'ወ', 'ወ', None, None),
+            (KeyCode::E, 'ኤ', 'ኤ', None, None),
+            (KeyCode::R, 'ረ', 'ረ', None, None),
+            (KeyCode::T, 'ተ', 'ጠ', None, None),
+            (KeyCode::Y, 'የ', 'የ', None, None),
+            (KeyCode::U, 'ኡ', 'ኡ', None, None),
+            (KeyCode::I, 'ኢ', 'ኢ', None, None),
+            (KeyCode::O, 'ኦ', 'ኦ', None, None),
+            (KeyCode::P, 'ፐ', 'ጰ', None, None),
+            (KeyCode::A, 'አ', 'ዐ', None, None),
+            (KeyCode::S, 'ሰ', 'ሠ', None, None),
+            (KeyCode::D, 'ደ', 'ደ', None, None),
+            (KeyCode::F, 'ፈ', 'ፈ', None, None),
+            (KeyCode::G, 'ገ', 'ገ', None, None),
+            (KeyCode::H, 'ሀ', 'ኸ', None, None),
+            (KeyCode::J, 'ጀ', 'ጀ', None, None),
+            (KeyCode::K, 'ከ', 'ከ', None, None),
+            (KeyCode::L, 'ለ', 'ለ', None, None),
+            (KeyCode::Oem1, '፤', '፥', None, None),
+            (KeyCode::Z, 'ዘ', 'ዠ', None, None),
+            (KeyCode::X, 'ሸ', 'ሸ', None, None),
+            (KeyCode::C, 'ቸ', 'ጨ', None, None),
+            (KeyCode::V, 'ቨ', 'ቨ', None, None),
+            (KeyCode::B, 'በ', 'በ', None, None),
+            (KeyCode::N, 'ነ', 'ኘ', None, None),
+            (KeyCode::M, 'መ', 'መ', None, None),
+            (KeyCode::OemComma, '፣', '፣', None, None),
+            (KeyCode::OemPeriod, '።', '።', None, None),
+            (KeyCode::Key1, '1', '!', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&AmEt105Key, ROWS);
+    }
+
+    #[test]
+    fn test_levels() {
+        let mut dec = EventDecoder::new(AmEt105Key, HandleControl::Ignore);
+        // Caps Lock does nothing
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        for (code, base, shifted) in [
+            (KeyCode::H, 'ሀ', 'ኸ'),
+            (KeyCode::S, 'ሰ', 'ሠ'),
+            (KeyCode::T, 'ተ', 'ጠ'),
+            (KeyCode::N, 'ነ', 'ኘ'),
+            (KeyCode::L, 'ለ', 'ለ'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(base))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(shifted))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        }
+    }
+}
@@ -0,0 +1,417 @@
+//! Arabic keyboard support
+
+use super::ConfigurableLayout;
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A standard Arabic (101) keyboard.
+///
+/// Arabic has no upper and lower case, so Caps Lock does nothing to the
+/// letter keys, and Shift gives the harakat and other extra characters. The
+/// lam-alef keys type a single ligature character (e.g. `ﻻ`), from the
+/// Arabic Presentation Forms block.
+///
+/// AltGr + `,` and AltGr + `.` give the Arabic thousands (`٬`) and decimal
+/// (`٫`) separators. To get Arabic-Indic digits, use
+/// [`LayoutOptions::digits`](super::LayoutOptions::digits).
+///
+/// Has a 1-row high Enter key, with Oem7 above the Enter key (ANSI format).
+pub struct Ar101Key;
+
+impl KeyboardLayout for Ar101Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        if handle_ctrl == HandleControl::MapLettersToUnicode && modifiers.is_ctrl() {
+            // Ctrl + a letter key still gives the control code on the keycap
+            let key = us.map_keycode(keycode, modifiers, handle_ctrl);
+            if matches!(key, DecodedKey::Unicode(ch) if ch.is_control()) {
+                return key;
+            }
+        }
+        match keycode {
+            KeyCode::Oem8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0651}')
+                } else {
+                    DecodedKey::Unicode('ذ')
+                }
+            }
+            KeyCode::Key9 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(')')
+                } else {
+                    DecodedKey::Unicode('9')
+                }
+            }
+            KeyCode::Key0 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('(')
+                } else {
+                    DecodedKey::Unicode('0')
+                }
+            }
+            KeyCode::Q => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{064E}')
+                } else {
+                    DecodedKey::Unicode('ض')
+                }
+            }
+            KeyCode::W => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{064B}')
+                } else {
+                    DecodedKey::Unicode('ص')
+                }
+            }
+            KeyCode::E => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{064F}')
+                } else {
+                    DecodedKey::Unicode('ث')
+                }
+            }
+            KeyCode::R => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{064C}')
+                } else {
+                    DecodedKey::Unicode('ق')
+                }
+            }
+            KeyCode::T => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{FEF9}')
+                } else {
+                    DecodedKey::Unicode('ف')
+                }
+            }
+            KeyCode::Y => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('إ')
+                } else {
+                    DecodedKey::Unicode('غ')
+                }
+            }
+            KeyCode::U => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{2018}')
+                } else {
+                    DecodedKey::Unicode('ع')
+                }
+            }
+            KeyCode::I => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('÷')
+                } else {
+                    DecodedKey::Unicode('ه')
+                }
+            }
+            KeyCode::O => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('×')
+                } else {
+                    DecodedKey::Unicode('خ')
+                }
+            }
+            KeyCode::P => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('؛')
+                } else {
+                    DecodedKey::Unicode('ح')
+                }
+            }
+            KeyCode::Oem4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('<')
+                } else {
+                    DecodedKey::Unicode('ج')
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('>')
+                } else {
+                    DecodedKey::Unicode('د')
+                }
+            }
+            KeyCode::A => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0650}')
+                } else {
+                    DecodedKey::Unicode('ش')
+                }
+            }
+            KeyCode::S => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{064D}')
+                } else {
+                    DecodedKey::Unicode('س')
+                }
+            }
+            KeyCode::D => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(']')
+                } else {
+                    DecodedKey::Unicode('ي')
+                }
+            }
+            KeyCode::F => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('[')
+                } else {
+                    DecodedKey::Unicode('ب')
+                }
+            }
+            KeyCode::G => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{FEF7}')
+                } else {
+                    DecodedKey::Unicode('ل')
+                }
+            }
+            KeyCode::H => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('أ')
+                } else {
+                    DecodedKey::Unicode('ا')
+                }
+            }
+            KeyCode::J => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0640}')
+                } else {
+                    DecodedKey::Unicode('ت')
+                }
+            }
+            KeyCode::K => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('،')
+                } else {
+                    DecodedKey::Unicode('ن')
+                }
+            }
+            KeyCode::L => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('/')
+                } else {
+                    DecodedKey::Unicode('م')
+                }
+            }
+            KeyCode::Oem1 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode('ك')
+                }
+            }
+            KeyCode::Oem3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('"')
+                } else {
+                    DecodedKey::Unicode('ط')
+                }
+            }
+            KeyCode::Z => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('~')
+                } else {
+                    DecodedKey::Unicode('ئ')
+                }
+            }
+            KeyCode::X => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0652}')
+                } else {
+                    DecodedKey::Unicode('ء')
+                }
+            }
+            KeyCode::C => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('}')
+                } else {
+                    DecodedKey::Unicode('ؤ')
+                }
+            }
+            KeyCode::V => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('{')
+                } else {
+                    DecodedKey::Unicode('ر')
+                }
+            }
+            KeyCode::B => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{FEF5}')
+                } else {
+                    DecodedKey::Unicode('\u{FEFB}')
+                }
+            }
+            KeyCode::N => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('آ')
+                } else {
+                    DecodedKey::Unicode('ى')
+                }
+            }
+            KeyCode::M => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{2019}')
+                } else {
+                    DecodedKey::Unicode('ة')
+                }
+            }
+            KeyCode::OemComma => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('٬')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode(',')
+                } else {
+                    DecodedKey::Unicode('و')
+                }
+            }
+            KeyCode::OemPeriod => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('٫')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('.')
+                } else {
+                    DecodedKey::Unicode('ز')
+                }
+            }
+            KeyCode::Oem2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('؟')
+                } else {
+                    DecodedKey::Unicode('ظ')
+                }
+            }
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+impl ConfigurableLayout for Ar101Key {
+    fn from_variant(variant: Option<&str>) -> Option<Ar101Key> {
+        match variant {
+            None => Some(Ar101Key),
+            Some(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::{fixture, Digits, LayoutOptions};
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbda1, with the lam-alef
+        // ligatures as single characters, and the separators added
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, 'ذ', '\u{0651}', None, None),
+            (KeyCode::Key9, '9', ')', None, None),
+            (KeyCode::Key0, '0', '(', None, None),
+            (KeyCode::Q, 'ض', '\u{064E}', None, None),
+            (KeyCode::W, 'ص', '\u{064B}', None, None),
+            (KeyCode::E, 'ث', '\u{064F}', None, None),
+            (KeyCode::R, 'ق', '\u{064C}', None, None),
+            (KeyCode::T, 'ف', '\u{FEF9}', None, None),
+            (KeyCode::Y, 'غ', 'إ', None, None),
+            (KeyCode::U, 'ع', '\u{2018}', None, None),
+            (KeyCode::I, 'ه', '÷', None, None),
+            (KeyCode::O, 'خ', '×', None, None),
+            (KeyCode::P, 'ح', '؛', None, None),
+            (KeyCode::Oem4, 'ج', '<', None, None),
+            (KeyCode::Oem6, 'د', '>', None, None),
+            (KeyCode::A, 'ش', '\u{0650}', None, None),
+            (KeyCode::S, 'س', '\u{064D}', None, None),
+            (KeyCode::D, 'ي', ']', None, None),
+            (KeyCode::F, 'ب', '[', None, None),
+            (KeyCode::G, 'ل', '\u{FEF7}', None, None),
+            (KeyCode::H, 'ا', 'أ', None, None),
+            (KeyCode::J, 'ت', '\u{0640}', None, None),
+            (KeyCode::K, 'ن', '،', None, None),
+            (KeyCode::L, 'م', '/', None, None),
+            (KeyCode::Oem1, 'ك', ':', None, None),
+            (KeyCode::Oem3, 'ط', '"', None, None),
+            (KeyCode::Z, 'ئ', '~', None, None),
+            (KeyCode::X, 'ء', '\u{0652}', None, None),
+            (KeyCode::C, 'ؤ', '}', None, None),
+            (KeyCode::V, 'ر', '{', None, None),
+            (KeyCode::B, '\u{FEFB}', '\u{FEF5}', None, None),
+            (KeyCode::N, 'ى', 'آ', None, None),
+            (KeyCode::M, 'ة', '\u{2019}', None, None),
+            (KeyCode::OemComma, 'و', ',', Some('٬'), None),
+            (KeyCode::OemPeriod, 'ز', '.', Some('٫'), None),
+            (KeyCode::Oem2, 'ظ', '؟', None, None),
+            (KeyCode::Oem7, '\\', '|', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&Ar101Key, ROWS);
+    }
+
+    #[test]
+    fn test_word() {
+        // سلام, then a fatha on the shift level
+        let mut dec = EventDecoder::new(Ar101Key, HandleControl::Ignore);
+        let mut typed = ['\0'; 4];
+        for (code, ch) in [KeyCode::S, KeyCode::G, KeyCode::H, KeyCode::L]
+            .into_iter()
+            .zip(typed.iter_mut())
+        {
+            if let Some(DecodedKey::Unicode(c)) =
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down))
+            {
+                *ch = c;
+            }
+            dec.process_keyevent(KeyEvent::new(code, KeyState::Up));
+        }
+        assert_eq!(typed, ['س', 'ل', 'ا', 'م']);
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Q, KeyState::Down)),
+            Some(DecodedKey::Unicode('\u{064E}'))
+        );
+    }
+
+    #[test]
+    fn test_caps_lock_ignored() {
+        let modifiers = Modifiers {
+            capslock: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            Ar101Key.map_keycode(KeyCode::H, &modifiers, HandleControl::Ignore),
+            DecodedKey::Unicode('ا')
+        );
+        let modifiers = Modifiers {
+            lctrl: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            Ar101Key.map_keycode(KeyCode::C, &modifiers, HandleControl::MapLettersToUnicode),
+            DecodedKey::Unicode('\u{0003}')
+        );
+    }
+
+    #[test]
+    fn test_arabic_digits() {
+        let layout =
+            Ar101Key::with_options(&LayoutOptions::new().digits(Digits::ArabicIndic)).unwrap();
+        let modifiers = Modifiers::default();
+        assert_eq!(
+            layout.map_keycode(KeyCode::Key7, &modifiers, HandleControl::Ignore),
+            DecodedKey::Unicode('٧')
+        );
+        assert_eq!(
+            layout.map_keycode(KeyCode::F, &modifiers, HandleControl::Ignore),
+            DecodedKey::Unicode('ب')
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! Azerbaijani (Latin) keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A standard Azerbaijani (Latin) 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// Like Turkish, Azerbaijani has a dotless `ı` and a dotted `İ`, so the key
+/// marked `I` types `i`/`İ` and the key to the right of `L` types `ı`/`I`.
+/// `ə` is on the key to the right of that, and `ü ö ğ ç ş` are on the
+/// remaining keys.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct AzLatin105Key;
+
+impl KeyboardLayout for AzLatin105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        match keycode {
+            KeyCode::Key2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('"')
+                } else {
+                    DecodedKey::Unicode('2')
+                }
+            }
+            KeyCode::Key3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('№')
+                } else {
+                    DecodedKey::Unicode('3')
+                }
+            }
+            KeyCode::Key4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(';')
+                } else {
+                    DecodedKey::Unicode('4')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode('6')
+                }
+            }
+            KeyCode::Key7 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('?')
+                } else {
+                    DecodedKey::Unicode('7')
+                }
+            }
+            KeyCode::W => modifiers.handle_letter_auto('ü', 'Ü', handle_ctrl),
+            KeyCode::I => modifiers.handle_letter_auto('i', 'İ', handle_ctrl),
+            KeyCode::Oem4 => modifiers.handle_letter_auto('ö', 'Ö', handle_ctrl),
+            KeyCode::Oem6 => modifiers.handle_letter_auto('ğ', 'Ğ', handle_ctrl),
+            KeyCode::Oem1 => modifiers.handle_letter_auto('ı', 'I', handle_ctrl),
+            KeyCode::Oem3 => modifiers.handle_letter_auto('ə', 'Ə', handle_ctrl),
+            KeyCode::Oem7 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('/')
+                } else {
+                    DecodedKey::Unicode('\\')
+                }
+            }
+            KeyCode::OemComma => modifiers.handle_letter_auto('ç', 'Ç', handle_ctrl),
+            KeyCode::OemPeriod => modifiers.handle_letter_auto('ş', 'Ş', handle_ctrl),
+            KeyCode::Oem2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(',')
+                } else {
+                    DecodedKey::Unicode('.')
+                }
+            }
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdazel
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Key2, '2', '"', None, None),
+            (KeyCode::Key3, '3', '№', None, None),
+            (KeyCode::Key4, '4', ';', None, None),
+            (KeyCode::Key6, '6', ':', None, None),
+            (KeyCode::Key7, '7', '?', None, None),
+            (KeyCode::W, 'ü', 'Ü', None, None),
+            (KeyCode::I, 'i', 'İ', None, None),
+            (KeyCode::Oem4, 'ö', 'Ö', None, None),
+            (KeyCode::Oem6, 'ğ', 'Ğ', None, None),
+            (KeyCode::Oem1, 'ı', 'I', None, None),
+            (KeyCode::Oem3, 'ə', 'Ə', None, None),
+            (KeyCode::Oem7, '\\', '/', None, None),
+            (KeyCode::OemComma, 'ç', 'Ç', None, None),
+            (KeyCode::OemPeriod, 'ş', 'Ş', None, None),
+            (KeyCode::Oem2, '.', ',', None, None),
+            (KeyCode::Q, 'q', 'Q', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&AzLatin105Key, ROWS);
+    }
+
+    #[test]
+    fn test_dotted_and_dotless_i() {
+        let mut dec = EventDecoder::new(AzLatin105Key, HandleControl::Ignore);
+        // (key, on its own, with Shift or Caps Lock)
+        for (code, lower, upper) in [
+            (KeyCode::I, 'i', 'İ'),
+            (KeyCode::Oem1, 'ı', 'I'),
+            (KeyCode::Oem3, 'ə', 'Ə'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(lower))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(upper))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+            dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(upper))
+            );
+            // Shift undoes Caps Lock
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(lower))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+            dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        }
+    }
+}
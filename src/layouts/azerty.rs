@@ -6,11 +6,37 @@ use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
 ///
 /// The top row spells `AZERTY`.
 ///
-/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+/// The `^` and `¨` key (right of `P`) is a dead key.
 ///
-/// NB: no "dead key" support for now
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
 pub struct Azerty;
 
+/// The French letters typed with the dead keys.
+const COMPOSITIONS: &[((char, char), char)] = &[
+    (('^', 'a'), 'â'),
+    (('^', 'A'), 'Â'),
+    (('^', 'e'), 'ê'),
+    (('^', 'E'), 'Ê'),
+    (('^', 'i'), 'î'),
+    (('^', 'I'), 'Î'),
+    (('^', 'o'), 'ô'),
+    (('^', 'O'), 'Ô'),
+    (('^', 'u'), 'û'),
+    (('^', 'U'), 'Û'),
+    (('¨', 'a'), 'ä'),
+    (('¨', 'A'), 'Ä'),
+    (('¨', 'e'), 'ë'),
+    (('¨', 'E'), 'Ë'),
+    (('¨', 'i'), 'ï'),
+    (('¨', 'I'), 'Ï'),
+    (('¨', 'o'), 'ö'),
+    (('¨', 'O'), 'Ö'),
+    (('¨', 'u'), 'ü'),
+    (('¨', 'U'), 'Ü'),
+    (('¨', 'y'), 'ÿ'),
+    (('¨', 'Y'), 'Ÿ'),
+];
+
 impl KeyboardLayout for Azerty {
     fn map_keycode(
         &self,
@@ -229,11 +255,11 @@ impl KeyboardLayout for Azerty {
             }
             KeyCode::Oem4 => {
                 if modifiers.is_shifted() {
-                    DecodedKey::Unicode('¨')
+                    DecodedKey::DeadKey('¨')
                 } else if modifiers.is_altgr() {
                     DecodedKey::Unicode('ˇ')
                 } else {
-                    DecodedKey::Unicode('^')
+                    DecodedKey::DeadKey('^')
                 }
             }
             KeyCode::Oem6 => {
@@ -514,12 +540,17 @@ impl KeyboardLayout for Azerty {
             k => DecodedKey::RawKey(k),
         }
     }
+
+    fn dead_key_combine(&self, dead_key: char, ch: char) -> Option<char> {
+        crate::compose::lookup(COMPOSITIONS, dead_key, ch)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{KeyCode, KeyEvent, KeyState, Keyboard, ScancodeSet2};
+    use crate::layouts::AnyLayout;
+    use crate::{EventDecoder, KeyCode, KeyEvent, KeyState, Keyboard, ScancodeSet2};
 
     #[test]
     fn test_frazert() {
@@ -573,4 +604,60 @@ mod test {
             None
         );
     }
+
+    #[test]
+    fn test_compositions() {
+        assert_eq!(Azerty.dead_key_combine('^', 'e'), Some('ê'));
+        assert_eq!(Azerty.dead_key_combine('¨', 'Y'), Some('Ÿ'));
+        // Left to the built-in tables
+        assert_eq!(Azerty.dead_key_combine('^', 'c'), None);
+        assert_eq!(
+            AnyLayout::Azerty(Azerty).dead_key_combine('^', 'e'),
+            Some('ê')
+        );
+    }
+
+    #[test]
+    fn test_dead_keys() {
+        let mut dec = EventDecoder::new(Azerty, HandleControl::Ignore);
+        let shift = KeyEvent::new(KeyCode::LShift, KeyState::Down);
+        let unshift = KeyEvent::new(KeyCode::LShift, KeyState::Up);
+        // The vowels, by their key codes on an AZERTY keyboard
+        let vowels = [KeyCode::Q, KeyCode::E, KeyCode::I, KeyCode::O, KeyCode::U];
+        for (code, ch) in vowels.into_iter().zip("âêîôû".chars()) {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(KeyCode::Oem4, KeyState::Down)),
+                Some(DecodedKey::DeadKey('^'))
+            );
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(ch))
+            );
+        }
+        for (code, ch) in vowels.into_iter().zip("äëïöü".chars()) {
+            dec.process_keyevent(shift.clone());
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(KeyCode::Oem4, KeyState::Down)),
+                Some(DecodedKey::DeadKey('¨'))
+            );
+            dec.process_keyevent(unshift.clone());
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(ch))
+            );
+        }
+        // Space gives the accent on its own
+        dec.process_keyevent(KeyEvent::new(KeyCode::Oem4, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Spacebar, KeyState::Down)),
+            Some(DecodedKey::Unicode('^'))
+        );
+        // A letter without a circumflex gives both
+        dec.process_keyevent(KeyEvent::new(KeyCode::Oem4, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::B, KeyState::Down)),
+            Some(DecodedKey::Unicode('^'))
+        );
+        assert_eq!(dec.take_queued(), Some(DecodedKey::Unicode('b')));
+    }
 }
@@ -0,0 +1,280 @@
+//! French (AFNOR) keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A French 102-key (or 105-key including Windows keys) keyboard, following
+/// the 2019 AFNOR standard NF Z71-300.
+///
+/// The letters are where they are on [`Azerty`](super::Azerty), but the
+/// symbols are rearranged: `@` and `#` get the key left of 1, the number row
+/// gives `à é è ê ( ) ‘ ’ « »` (with the digits on Shift), and AltGr + Space
+/// is a non-breaking space. `^` and `¨` are dead keys.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct AzertyNf105Key;
+
+impl KeyboardLayout for AzertyNf105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let fallback = super::Azerty;
+        match keycode {
+            KeyCode::Oem8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('#')
+                } else {
+                    DecodedKey::Unicode('@')
+                }
+            }
+            KeyCode::Key1 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('1')
+                } else {
+                    DecodedKey::Unicode('à')
+                }
+            }
+            KeyCode::Key2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('2')
+                } else {
+                    DecodedKey::Unicode('é')
+                }
+            }
+            KeyCode::Key3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('3')
+                } else {
+                    DecodedKey::Unicode('è')
+                }
+            }
+            KeyCode::Key4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('4')
+                } else {
+                    DecodedKey::Unicode('ê')
+                }
+            }
+            KeyCode::Key5 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('5')
+                } else {
+                    DecodedKey::Unicode('(')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('6')
+                } else {
+                    DecodedKey::Unicode(')')
+                }
+            }
+            KeyCode::Key7 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('7')
+                } else {
+                    DecodedKey::Unicode('‘')
+                }
+            }
+            KeyCode::Key8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('8')
+                } else {
+                    DecodedKey::Unicode('’')
+                }
+            }
+            KeyCode::Key9 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('9')
+                } else {
+                    DecodedKey::Unicode('«')
+                }
+            }
+            KeyCode::Key0 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('0')
+                } else {
+                    DecodedKey::Unicode('»')
+                }
+            }
+            KeyCode::OemMinus => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('"')
+                } else {
+                    DecodedKey::Unicode('\'')
+                }
+            }
+            KeyCode::Oem4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('–')
+                } else {
+                    DecodedKey::Unicode('-')
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('±')
+                } else {
+                    DecodedKey::Unicode('+')
+                }
+            }
+            KeyCode::Oem3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\\')
+                } else {
+                    DecodedKey::Unicode('/')
+                }
+            }
+            KeyCode::Oem7 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('½')
+                } else {
+                    DecodedKey::Unicode('*')
+                }
+            }
+            KeyCode::Oem5 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('>')
+                } else {
+                    DecodedKey::Unicode('<')
+                }
+            }
+            KeyCode::M => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('?')
+                } else {
+                    DecodedKey::Unicode(',')
+                }
+            }
+            KeyCode::OemComma => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('!')
+                } else {
+                    DecodedKey::Unicode('.')
+                }
+            }
+            KeyCode::OemPeriod => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('…')
+                } else {
+                    DecodedKey::Unicode(':')
+                }
+            }
+            KeyCode::Oem2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('≠')
+                } else {
+                    DecodedKey::Unicode('=')
+                }
+            }
+            KeyCode::OemPlus => {
+                if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('¨')
+                } else {
+                    DecodedKey::DeadKey('^')
+                }
+            }
+            KeyCode::Q => modifiers.handle_ascii_4(ascii_upper!('A'), 'æ', 'Æ', handle_ctrl),
+            KeyCode::E => modifiers.handle_ascii_4(ascii_upper!('E'), '€', '€', handle_ctrl),
+            KeyCode::U => modifiers.handle_ascii_4(ascii_upper!('U'), 'ù', 'Ù', handle_ctrl),
+            KeyCode::O => modifiers.handle_ascii_4(ascii_upper!('O'), 'œ', 'Œ', handle_ctrl),
+            KeyCode::S => modifiers.handle_ascii_4(ascii_upper!('S'), 'ß', 'ẞ', handle_ctrl),
+            KeyCode::Spacebar if modifiers.is_altgr() => DecodedKey::Unicode('\u{00A0}'),
+            e => fallback.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdfrna
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '@', '#', None, None),
+            (KeyCode::Key1, 'à', '1', None, None),
+            (KeyCode::Key2, 'é', '2', None, None),
+            (KeyCode::Key3, 'è', '3', None, None),
+            (KeyCode::Key4, 'ê', '4', None, None),
+            (KeyCode::Key5, '(', '5', None, None),
+            (KeyCode::Key6, ')', '6', None, None),
+            (KeyCode::Key7, '‘', '7', None, None),
+            (KeyCode::Key8, '’', '8', None, None),
+            (KeyCode::Key9, '«', '9', None, None),
+            (KeyCode::Key0, '»', '0', None, None),
+            (KeyCode::OemMinus, '\'', '"', None, None),
+            (KeyCode::OemPlus, '^', '¨', None, None),
+            (KeyCode::Q, 'a', 'A', Some('æ'), Some('Æ')),
+            (KeyCode::W, 'z', 'Z', None, None),
+            (KeyCode::E, 'e', 'E', Some('€'), None),
+            (KeyCode::U, 'u', 'U', Some('ù'), Some('Ù')),
+            (KeyCode::O, 'o', 'O', Some('œ'), Some('Œ')),
+            (KeyCode::Oem4, '-', '–', None, None),
+            (KeyCode::Oem6, '+', '±', None, None),
+            (KeyCode::A, 'q', 'Q', None, None),
+            (KeyCode::S, 's', 'S', Some('ß'), Some('ẞ')),
+            (KeyCode::Oem1, 'm', 'M', None, None),
+            (KeyCode::Oem3, '/', '\\', None, None),
+            (KeyCode::Oem7, '*', '½', None, None),
+            (KeyCode::Oem5, '<', '>', None, None),
+            (KeyCode::Z, 'w', 'W', None, None),
+            (KeyCode::M, ',', '?', None, None),
+            (KeyCode::OemComma, '.', '!', None, None),
+            (KeyCode::OemPeriod, ':', '…', None, None),
+            (KeyCode::Oem2, '=', '≠', None, None),
+            (KeyCode::Spacebar, ' ', ' ', Some('\u{00A0}'), None),
+        ];
+        fixture::check(&AzertyNf105Key, ROWS);
+    }
+
+    #[test]
+    fn test_number_row() {
+        let mut dec = EventDecoder::new(AzertyNf105Key, HandleControl::Ignore);
+        // Unlike the legacy layout, the number row gives accented letters and
+        // the guillemets, with the digits one Shift away
+        for (code, base, digit) in [
+            (KeyCode::Key1, 'à', '1'),
+            (KeyCode::Key4, 'ê', '4'),
+            (KeyCode::Key9, '«', '9'),
+            (KeyCode::Key0, '»', '0'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(base))
+            );
+            assert_ne!(
+                crate::layouts::Azerty.map_keycode(
+                    code,
+                    &Modifiers::default(),
+                    HandleControl::Ignore
+                ),
+                DecodedKey::Unicode(base)
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(digit))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        }
+    }
+
+    #[test]
+    fn test_dead_circumflex() {
+        let mut dec = EventDecoder::new(AzertyNf105Key, HandleControl::Ignore);
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::OemPlus, KeyState::Down)),
+            Some(DecodedKey::DeadKey('^'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::O, KeyState::Down)),
+            Some(DecodedKey::Unicode('ô'))
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Brazilian ABNT2 keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Brazilian ABNT2 104-key (or 107-key including Windows keys) keyboard.
+///
+/// This has two keys that other ISO keyboards don't: the `/?` key next to the
+/// right shift (ABNT C1, reported as [`KeyCode::Oem12`]) and a `.` key on the
+/// numpad (ABNT C2, reported as [`KeyCode::NumpadComma`]). The numpad decimal
+/// key types `,`.
+///
+/// The accent keys currently type plain characters rather than being dead
+/// keys.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct BrAbnt2Key;
+
+impl KeyboardLayout for BrAbnt2Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        match keycode {
+            KeyCode::Oem8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('"')
+                } else {
+                    DecodedKey::Unicode('\'')
+                }
+            }
+            KeyCode::Key1 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¹')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('!')
+                } else {
+                    DecodedKey::Unicode('1')
+                }
+            }
+            KeyCode::Key2 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('²')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('@')
+                } else {
+                    DecodedKey::Unicode('2')
+                }
+            }
+            KeyCode::Key3 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('³')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('#')
+                } else {
+                    DecodedKey::Unicode('3')
+                }
+            }
+            KeyCode::Key4 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('£')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('$')
+                } else {
+                    DecodedKey::Unicode('4')
+                }
+            }
+            KeyCode::Key5 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¢')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('%')
+                } else {
+                    DecodedKey::Unicode('5')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¬')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('¨')
+                } else {
+                    DecodedKey::Unicode('6')
+                }
+            }
+            KeyCode::OemPlus => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('§')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('+')
+                } else {
+                    DecodedKey::Unicode('=')
+                }
+            }
+            KeyCode::Q => modifiers.handle_ascii_4(ascii_upper!('Q'), '/', '/', handle_ctrl),
+            KeyCode::W => modifiers.handle_ascii_4(ascii_upper!('W'), '?', '?', handle_ctrl),
+            KeyCode::E => modifiers.handle_ascii_4(ascii_upper!('E'), '°', '°', handle_ctrl),
+            KeyCode::C => modifiers.handle_ascii_4(ascii_upper!('C'), '₢', '₢', handle_ctrl),
+            KeyCode::Oem4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('`')
+                } else {
+                    DecodedKey::Unicode('´')
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('ª')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('{')
+                } else {
+                    DecodedKey::Unicode('[')
+                }
+            }
+            KeyCode::Oem1 => modifiers.handle_letter_auto('ç', 'Ç', handle_ctrl),
+            KeyCode::Oem3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('^')
+                } else {
+                    DecodedKey::Unicode('~')
+                }
+            }
+            KeyCode::Oem7 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('º')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('}')
+                } else {
+                    DecodedKey::Unicode(']')
+                }
+            }
+            KeyCode::Oem2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode(';')
+                }
+            }
+            KeyCode::Oem12 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('°')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('?')
+                } else {
+                    DecodedKey::Unicode('/')
+                }
+            }
+            KeyCode::NumpadPeriod => {
+                if modifiers.numlock {
+                    DecodedKey::Unicode(',')
+                } else {
+                    DecodedKey::Unicode(127.into())
+                }
+            }
+            KeyCode::NumpadComma => DecodedKey::Unicode('.'),
+            e => {
+                let us = super::Us104Key;
+                us.map_keycode(e, modifiers, handle_ctrl)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{DecodedItem, Keyboard, ScancodeSet1};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdbr
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '\'', '"', None, None),
+            (KeyCode::Key1, '1', '!', Some('¹'), None),
+            (KeyCode::Key2, '2', '@', Some('²'), None),
+            (KeyCode::Key3, '3', '#', Some('³'), None),
+            (KeyCode::Key4, '4', '$', Some('£'), None),
+            (KeyCode::Key5, '5', '%', Some('¢'), None),
+            (KeyCode::Key6, '6', '¨', Some('¬'), None),
+            (KeyCode::Key7, '7', '&', None, None),
+            (KeyCode::Key8, '8', '*', None, None),
+            (KeyCode::Key9, '9', '(', None, None),
+            (KeyCode::Key0, '0', ')', None, None),
+            (KeyCode::OemMinus, '-', '_', None, None),
+            (KeyCode::OemPlus, '=', '+', Some('§'), None),
+            (KeyCode::Q, 'q', 'Q', Some('/'), None),
+            (KeyCode::W, 'w', 'W', Some('?'), None),
+            (KeyCode::E, 'e', 'E', Some('°'), None),
+            (KeyCode::Oem4, '´', '`', None, None),
+            (KeyCode::Oem6, '[', '{', Some('ª'), None),
+            (KeyCode::Oem1, 'ç', 'Ç', None, None),
+            (KeyCode::Oem3, '~', '^', None, None),
+            (KeyCode::Oem7, ']', '}', Some('º'), None),
+            (KeyCode::Oem5, '\\', '|', None, None),
+            (KeyCode::C, 'c', 'C', Some('₢'), None),
+            (KeyCode::OemComma, ',', '<', None, None),
+            (KeyCode::OemPeriod, '.', '>', None, None),
+            (KeyCode::Oem2, ';', ':', None, None),
+            (KeyCode::Oem12, '/', '?', Some('°'), None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&BrAbnt2Key, ROWS);
+    }
+
+    #[test]
+    fn test_abnt_keys() {
+        // FE is also Resend, unless we ask for it to be a key release
+        let mut k = Keyboard::new(ScancodeSet1::new(), BrAbnt2Key, HandleControl::Ignore);
+        assert!(matches!(
+            k.add_byte(0xFE),
+            Ok(Some(DecodedItem::Protocol(_)))
+        ));
+        let mut k = Keyboard::new(
+            ScancodeSet1::new_decoding_jis_releases(),
+            BrAbnt2Key,
+            HandleControl::Ignore,
+        );
+        for (code, ch) in [(0x73, '/'), (0x7E, '.'), (0x53, ',')] {
+            let item = k.add_byte(code).unwrap().unwrap();
+            assert!(matches!(item, DecodedItem::Key(_)));
+            assert_eq!(k.process_item(item), Some(DecodedKey::Unicode(ch)));
+            // And the break code is a key up
+            let item = k.add_byte(code | 0x80).unwrap().unwrap();
+            assert_eq!(k.process_item(item), None);
+        }
+    }
+}
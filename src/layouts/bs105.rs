@@ -0,0 +1,77 @@
+//! Bosnian keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A standard Bosnian 102-key (or 105-key including Windows keys) keyboard.
+///
+/// This is the Croatian layout, which Serbian (Latin) also uses, so it types
+/// exactly what [`SrLatn105Key`](super::SrLatn105Key) does. It has its own
+/// name so it can be picked by locale.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct Bs105Key;
+
+impl KeyboardLayout for Bs105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let fallback = super::SrLatn105Key;
+        fallback.map_keycode(keycode, modifiers, handle_ctrl)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::{fixture, SrLatn105Key};
+
+    #[test]
+    fn test_fixture() {
+        // Based on https://kbdlayout.info/kbdcr
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '¸', '¨', None, None),
+            (KeyCode::Key2, '2', '"', Some('ˇ'), None),
+            (KeyCode::Q, 'q', 'Q', Some('\\'), None),
+            (KeyCode::E, 'e', 'E', Some('€'), None),
+            (KeyCode::Y, 'z', 'Z', None, None),
+            (KeyCode::Oem4, 'š', 'Š', Some('÷'), None),
+            (KeyCode::Oem6, 'đ', 'Đ', Some('×'), None),
+            (KeyCode::Oem1, 'č', 'Č', None, None),
+            (KeyCode::Oem3, 'ć', 'Ć', Some('ß'), None),
+            (KeyCode::Oem7, 'ž', 'Ž', Some('¤'), None),
+            (KeyCode::Z, 'y', 'Y', None, None),
+            (KeyCode::V, 'v', 'V', Some('@'), None),
+            (KeyCode::Oem2, '-', '_', None, None),
+        ];
+        fixture::check(&Bs105Key, ROWS);
+    }
+
+    #[test]
+    fn test_same_as_croatian() {
+        // Any difference from the Croatian (and Serbian Latin) layout should
+        // be a deliberate change to this test
+        for keycode in KeyCode::ALL {
+            for bits in 0..16u8 {
+                let modifiers = Modifiers {
+                    lshift: bits & 1 != 0,
+                    ralt: bits & 2 != 0,
+                    capslock: bits & 4 != 0,
+                    lctrl: bits & 8 != 0,
+                    ..Default::default()
+                };
+                for handle_ctrl in [HandleControl::Ignore, HandleControl::MapLettersToUnicode] {
+                    assert_eq!(
+                        Bs105Key.map_keycode(keycode, &modifiers, handle_ctrl),
+                        SrLatn105Key.map_keycode(keycode, &modifiers, handle_ctrl),
+                        "{:?} {:?}",
+                        keycode,
+                        modifiers
+                    );
+                }
+            }
+        }
+    }
+}
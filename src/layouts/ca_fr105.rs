@@ -0,0 +1,318 @@
+//! Canadian French keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// The older Canadian French 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// This is not the Canadian Multilingual Standard layout (see
+/// [`CaMultilingual105Key`](super::CaMultilingual105Key)). Only `é` has its
+/// own key - the other accented letters come from the `^`, `¸`, `¨`, `` ` ``
+/// and `´` dead keys.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct CaFrench105Key;
+
+impl KeyboardLayout for CaFrench105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        match keycode {
+            // ========= Row 2 (the numbers) =========
+            KeyCode::Oem8 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('\\')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('|')
+                } else {
+                    DecodedKey::Unicode('#')
+                }
+            }
+            KeyCode::Key1 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('±')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('!')
+                } else {
+                    DecodedKey::Unicode('1')
+                }
+            }
+            KeyCode::Key2 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('@')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('"')
+                } else {
+                    DecodedKey::Unicode('2')
+                }
+            }
+            KeyCode::Key3 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('£')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('/')
+                } else {
+                    DecodedKey::Unicode('3')
+                }
+            }
+            KeyCode::Key4 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¢')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('$')
+                } else {
+                    DecodedKey::Unicode('4')
+                }
+            }
+            KeyCode::Key5 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¤')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('%')
+                } else {
+                    DecodedKey::Unicode('5')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¬')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('?')
+                } else {
+                    DecodedKey::Unicode('6')
+                }
+            }
+            KeyCode::Key7 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¦')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('&')
+                } else {
+                    DecodedKey::Unicode('7')
+                }
+            }
+            KeyCode::Key8 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('²')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('*')
+                } else {
+                    DecodedKey::Unicode('8')
+                }
+            }
+            KeyCode::Key9 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('³')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('(')
+                } else {
+                    DecodedKey::Unicode('9')
+                }
+            }
+            KeyCode::Key0 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¼')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode(')')
+                } else {
+                    DecodedKey::Unicode('0')
+                }
+            }
+            KeyCode::OemMinus => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('½')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('_')
+                } else {
+                    DecodedKey::Unicode('-')
+                }
+            }
+            KeyCode::OemPlus => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¾')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('+')
+                } else {
+                    DecodedKey::Unicode('=')
+                }
+            }
+            // ========= Row 3 (QWERTY) =========
+            KeyCode::O => modifiers.handle_ascii_4(ascii_upper!('O'), '§', '§', handle_ctrl),
+            KeyCode::P => modifiers.handle_ascii_4(ascii_upper!('P'), '¶', '¶', handle_ctrl),
+            KeyCode::Oem4 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('[')
+                } else {
+                    DecodedKey::DeadKey('^')
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode(']')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('¨')
+                } else {
+                    DecodedKey::DeadKey('¸')
+                }
+            }
+            // ========= Row 4 (ASDF) =========
+            KeyCode::Oem1 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('~')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode(';')
+                }
+            }
+            KeyCode::Oem3 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('{')
+                } else {
+                    DecodedKey::DeadKey('`')
+                }
+            }
+            KeyCode::Oem7 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('}')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('>')
+                } else {
+                    DecodedKey::Unicode('<')
+                }
+            }
+            // ========= Row 5 (ZXCV) =========
+            KeyCode::Oem5 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('°')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('»')
+                } else {
+                    DecodedKey::Unicode('«')
+                }
+            }
+            KeyCode::M => modifiers.handle_ascii_4(ascii_upper!('M'), 'µ', 'µ', handle_ctrl),
+            KeyCode::OemComma => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¯')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\'')
+                } else {
+                    DecodedKey::Unicode(',')
+                }
+            }
+            KeyCode::OemPeriod => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('\u{AD}')
+                } else {
+                    DecodedKey::Unicode('.')
+                }
+            }
+            KeyCode::Oem2 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('´')
+                } else {
+                    modifiers.handle_letter_auto('é', 'É', handle_ctrl)
+                }
+            }
+            e => {
+                let us = super::Us104Key;
+                us.map_keycode(e, modifiers, handle_ctrl)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{Keyboard, ScancodeSet1};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdfc
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '#', '|', Some('\\'), None),
+            (KeyCode::Key1, '1', '!', Some('±'), None),
+            (KeyCode::Key2, '2', '"', Some('@'), None),
+            (KeyCode::Key3, '3', '/', Some('£'), None),
+            (KeyCode::Key4, '4', '$', Some('¢'), None),
+            (KeyCode::Key5, '5', '%', Some('¤'), None),
+            (KeyCode::Key6, '6', '?', Some('¬'), None),
+            (KeyCode::Key7, '7', '&', Some('¦'), None),
+            (KeyCode::Key8, '8', '*', Some('²'), None),
+            (KeyCode::Key9, '9', '(', Some('³'), None),
+            (KeyCode::Key0, '0', ')', Some('¼'), None),
+            (KeyCode::OemMinus, '-', '_', Some('½'), None),
+            (KeyCode::OemPlus, '=', '+', Some('¾'), None),
+            (KeyCode::O, 'o', 'O', Some('§'), None),
+            (KeyCode::P, 'p', 'P', Some('¶'), None),
+            (KeyCode::Oem4, '^', '^', Some('['), None),
+            (KeyCode::Oem6, '¸', '¨', Some(']'), None),
+            (KeyCode::Oem1, ';', ':', Some('~'), None),
+            (KeyCode::Oem3, '`', '`', Some('{'), None),
+            (KeyCode::Oem7, '<', '>', Some('}'), None),
+            (KeyCode::Oem5, '«', '»', Some('°'), None),
+            (KeyCode::M, 'm', 'M', Some('µ'), None),
+            (KeyCode::OemComma, ',', '\'', Some('¯'), None),
+            (KeyCode::OemPeriod, '.', '.', Some('\u{AD}'), None),
+            (KeyCode::Oem2, 'é', 'É', Some('´'), None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&CaFrench105Key, ROWS);
+    }
+
+    /// Feed Set 1 bytes to a keyboard, and collect what it types.
+    fn type_bytes(bytes: &[u8]) -> [Option<DecodedKey>; 8] {
+        let mut k = Keyboard::new(ScancodeSet1::new(), CaFrench105Key, HandleControl::Ignore);
+        let mut typed = [None; 8];
+        let mut typed_iter = typed.iter_mut();
+        for byte in bytes {
+            if let Some(item) = k.add_byte(*byte).unwrap() {
+                if let Some(key) = k.process_item(item) {
+                    *typed_iter.next().unwrap() = Some(key);
+                }
+            }
+        }
+        typed
+    }
+
+    #[test]
+    fn test_accents() {
+        // é, then ` e for è, then ¸ c for ç
+        let typed = type_bytes(&[0x35, 0xB5, 0x28, 0xA8, 0x12, 0x92, 0x1B, 0x9B, 0x2E, 0xAE]);
+        assert_eq!(
+            typed[..5],
+            [
+                Some(DecodedKey::Unicode('é')),
+                Some(DecodedKey::DeadKey('`')),
+                Some(DecodedKey::Unicode('è')),
+                Some(DecodedKey::DeadKey('¸')),
+                Some(DecodedKey::Unicode('ç')),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_altgr_brackets() {
+        // Right Alt down, then [ ] { }, then Right Alt up
+        let typed = type_bytes(&[
+            0xE0, 0x38, 0x1A, 0x9A, 0x1B, 0x9B, 0x28, 0xA8, 0x2B, 0xAB, 0xE0, 0xB8,
+        ]);
+        assert_eq!(
+            typed[..6],
+            [
+                Some(DecodedKey::RawKey(KeyCode::RAltGr)),
+                Some(DecodedKey::Unicode('[')),
+                Some(DecodedKey::Unicode(']')),
+                Some(DecodedKey::Unicode('{')),
+                Some(DecodedKey::Unicode('}')),
+                None,
+            ]
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! Canadian Multilingual Standard keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Canadian Multilingual Standard (CSA) 102-key (or 105-key including
+/// Windows keys) keyboard.
+///
+/// The accented French letters have their own keys, and `^`, `¨`, `` ` ``
+/// and `~` are dead keys. Real CSA keyboards also have a fourth level on
+/// Right Control - that isn't supported, so only the AltGr level works.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct CaMultilingual105Key;
+
+impl KeyboardLayout for CaMultilingual105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        match keycode {
+            // ========= Row 2 (the numbers) =========
+            KeyCode::Oem8 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('|')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\\')
+                } else {
+                    DecodedKey::Unicode('/')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('?')
+                } else {
+                    DecodedKey::Unicode('6')
+                }
+            }
+            KeyCode::Key7 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('{')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('&')
+                } else {
+                    DecodedKey::Unicode('7')
+                }
+            }
+            KeyCode::Key8 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('}')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('*')
+                } else {
+                    DecodedKey::Unicode('8')
+                }
+            }
+            KeyCode::Key9 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('[')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('(')
+                } else {
+                    DecodedKey::Unicode('9')
+                }
+            }
+            KeyCode::Key0 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode(']')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode(')')
+                } else {
+                    DecodedKey::Unicode('0')
+                }
+            }
+            KeyCode::OemPlus => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¬')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('+')
+                } else {
+                    DecodedKey::Unicode('=')
+                }
+            }
+            // ========= Row 3 (QWERTY) =========
+            KeyCode::Oem4 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('`')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('¨')
+                } else {
+                    DecodedKey::DeadKey('^')
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('~')
+                } else {
+                    modifiers.handle_letter_auto('ç', 'Ç', handle_ctrl)
+                }
+            }
+            // ========= Row 4 (ASDF) =========
+            KeyCode::Oem1 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('°')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode(';')
+                }
+            }
+            KeyCode::Oem3 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('{')
+                } else {
+                    modifiers.handle_letter_auto('è', 'È', handle_ctrl)
+                }
+            }
+            KeyCode::Oem7 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('}')
+                } else {
+                    modifiers.handle_letter_auto('à', 'À', handle_ctrl)
+                }
+            }
+            // ========= Row 5 (ZXCV) =========
+            KeyCode::Oem5 => modifiers.handle_letter_auto('ù', 'Ù', handle_ctrl),
+            KeyCode::Z => modifiers.handle_ascii_4(ascii_upper!('Z'), '«', '«', handle_ctrl),
+            KeyCode::X => modifiers.handle_ascii_4(ascii_upper!('X'), '»', '»', handle_ctrl),
+            KeyCode::OemComma => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('<')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\'')
+                } else {
+                    DecodedKey::Unicode(',')
+                }
+            }
+            KeyCode::OemPeriod => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('>')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('"')
+                } else {
+                    DecodedKey::Unicode('.')
+                }
+            }
+            KeyCode::Oem2 => modifiers.handle_letter_auto('é', 'É', handle_ctrl),
+            e => {
+                let us = super::Us104Key;
+                us.map_keycode(e, modifiers, handle_ctrl)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdcan, without the Right Control
+        // level
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '/', '\\', Some('|'), None),
+            (KeyCode::Key1, '1', '!', None, None),
+            (KeyCode::Key2, '2', '@', None, None),
+            (KeyCode::Key3, '3', '#', None, None),
+            (KeyCode::Key4, '4', '$', None, None),
+            (KeyCode::Key5, '5', '%', None, None),
+            (KeyCode::Key6, '6', '?', None, None),
+            (KeyCode::Key7, '7', '&', Some('{'), None),
+            (KeyCode::Key8, '8', '*', Some('}'), None),
+            (KeyCode::Key9, '9', '(', Some('['), None),
+            (KeyCode::Key0, '0', ')', Some(']'), None),
+            (KeyCode::OemMinus, '-', '_', None, None),
+            (KeyCode::OemPlus, '=', '+', Some('¬'), None),
+            (KeyCode::Oem4, '^', '¨', Some('`'), None),
+            (KeyCode::Oem6, 'ç', 'Ç', Some('~'), None),
+            (KeyCode::Oem1, ';', ':', Some('°'), None),
+            (KeyCode::Oem3, 'è', 'È', Some('{'), None),
+            (KeyCode::Oem7, 'à', 'À', Some('}'), None),
+            (KeyCode::Oem5, 'ù', 'Ù', None, None),
+            (KeyCode::Z, 'z', 'Z', Some('«'), None),
+            (KeyCode::X, 'x', 'X', Some('»'), None),
+            (KeyCode::OemComma, ',', '\'', Some('<'), None),
+            (KeyCode::OemPeriod, '.', '"', Some('>'), None),
+            (KeyCode::Oem2, 'é', 'É', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&CaMultilingual105Key, ROWS);
+    }
+
+    #[test]
+    fn test_accents() {
+        let mut dec = EventDecoder::new(CaMultilingual105Key, HandleControl::Ignore);
+        for (code, ch) in [
+            (KeyCode::Oem2, 'é'),
+            (KeyCode::Oem3, 'è'),
+            (KeyCode::Oem7, 'à'),
+            (KeyCode::Oem5, 'ù'),
+            (KeyCode::Oem6, 'ç'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(ch))
+            );
+        }
+        // Dead circumflex
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem4, KeyState::Down)),
+            Some(DecodedKey::DeadKey('^'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::O, KeyState::Down)),
+            Some(DecodedKey::Unicode('ô'))
+        );
+        // Guillemets
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Z, KeyState::Down)),
+            Some(DecodedKey::Unicode('«'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::X, KeyState::Down)),
+            Some(DecodedKey::Unicode('»'))
+        );
+    }
+}
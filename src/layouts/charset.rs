@@ -0,0 +1,959 @@
+//! Finding out which characters a layout can produce, and how
+
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeSet;
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyEvent, KeyState, KeyboardLayout, Modifiers};
+
+/// All the keys which might produce a character.
+const TYPING_KEYS: &[KeyCode] = &[
+    KeyCode::Oem8,
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+    KeyCode::Key0,
+    KeyCode::OemMinus,
+    KeyCode::OemPlus,
+    KeyCode::Tab,
+    KeyCode::Q,
+    KeyCode::W,
+    KeyCode::E,
+    KeyCode::R,
+    KeyCode::T,
+    KeyCode::Y,
+    KeyCode::U,
+    KeyCode::I,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::Oem4,
+    KeyCode::Oem6,
+    KeyCode::Oem5,
+    KeyCode::Oem7,
+    KeyCode::A,
+    KeyCode::S,
+    KeyCode::D,
+    KeyCode::F,
+    KeyCode::G,
+    KeyCode::H,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::Oem1,
+    KeyCode::Oem3,
+    KeyCode::Return,
+    KeyCode::Z,
+    KeyCode::X,
+    KeyCode::C,
+    KeyCode::V,
+    KeyCode::B,
+    KeyCode::N,
+    KeyCode::M,
+    KeyCode::OemComma,
+    KeyCode::OemPeriod,
+    KeyCode::Oem2,
+    KeyCode::Spacebar,
+    KeyCode::NumpadDivide,
+    KeyCode::NumpadMultiply,
+    KeyCode::NumpadSubtract,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+    KeyCode::NumpadAdd,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::NumpadEnter,
+    KeyCode::Numpad0,
+    KeyCode::NumpadPeriod,
+    KeyCode::Oem9,
+    KeyCode::Oem10,
+    KeyCode::Oem11,
+    KeyCode::Oem12,
+    KeyCode::Oem13,
+];
+
+/// A combination of modifier keys, used to describe how to type a character.
+///
+/// The variants are in order of how easy they are to type.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord)]
+pub enum ModifierCombo {
+    /// No modifiers
+    None,
+    /// Shift
+    Shift,
+    /// AltGr
+    AltGr,
+    /// Shift and AltGr
+    ShiftAltGr,
+}
+
+impl ModifierCombo {
+    /// All the combinations, simplest first.
+    pub const ALL: [ModifierCombo; 4] = [
+        ModifierCombo::None,
+        ModifierCombo::Shift,
+        ModifierCombo::AltGr,
+        ModifierCombo::ShiftAltGr,
+    ];
+
+    /// Get the modifier state for this combination.
+    ///
+    /// Num Lock is on and Caps Lock is off.
+    pub const fn modifiers(&self) -> Modifiers {
+        let (shift, altgr) = match self {
+            ModifierCombo::None => (false, false),
+            ModifierCombo::Shift => (true, false),
+            ModifierCombo::AltGr => (false, true),
+            ModifierCombo::ShiftAltGr => (true, true),
+        };
+        Modifiers {
+            lshift: shift,
+            rshift: false,
+            lctrl: false,
+            rctrl: false,
+            numlock: true,
+            capslock: false,
+            scrolllock: false,
+            lalt: false,
+            ralt: altgr,
+            rctrl2: false,
+            kana: false,
+        }
+    }
+}
+
+/// Find every key and modifier combination which types the given character.
+///
+/// Only single key presses are considered - characters that can only be
+/// typed using a dead key are not found. The simplest combinations (see
+/// [`ModifierCombo`]) are given first.
+pub fn find_chars(
+    layout: &dyn KeyboardLayout,
+    ch: char,
+) -> impl Iterator<Item = (KeyCode, ModifierCombo)> + '_ {
+    ModifierCombo::ALL
+        .iter()
+        .flat_map(|combo| TYPING_KEYS.iter().map(move |keycode| (*keycode, *combo)))
+        .filter(move |(keycode, combo)| {
+            layout.map_keycode(*keycode, &combo.modifiers(), HandleControl::Ignore)
+                == DecodedKey::Unicode(ch)
+        })
+}
+
+/// Find the simplest key and modifier combination which types the given
+/// character.
+///
+/// See [`find_chars`].
+pub fn find_char(layout: &dyn KeyboardLayout, ch: char) -> Option<(KeyCode, ModifierCombo)> {
+    find_chars(layout, ch).next()
+}
+
+/// The most key events needed to type one character: a dead key and then a
+/// letter, each with Shift and AltGr.
+const MAX_EVENTS: usize = 12;
+
+/// The key events which type one character.
+///
+/// This is a fixed-size list, so it works without `alloc`. It dereferences to
+/// a slice of [`KeyEvent`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyEvents {
+    events: [KeyEvent; MAX_EVENTS],
+    len: usize,
+}
+
+impl KeyEvents {
+    const EMPTY: KeyEvent = KeyEvent::new(KeyCode::Escape, KeyState::Up);
+
+    const fn new() -> KeyEvents {
+        KeyEvents {
+            events: [Self::EMPTY; MAX_EVENTS],
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, code: KeyCode, state: KeyState) {
+        self.events[self.len] = KeyEvent::new(code, state);
+        self.len += 1;
+    }
+
+    /// Add events to press and release a key, with the given modifiers held.
+    fn tap(&mut self, code: KeyCode, combo: ModifierCombo) {
+        let (shift, altgr) = match combo {
+            ModifierCombo::None => (false, false),
+            ModifierCombo::Shift => (true, false),
+            ModifierCombo::AltGr => (false, true),
+            ModifierCombo::ShiftAltGr => (true, true),
+        };
+        if shift {
+            self.push(KeyCode::LShift, KeyState::Down);
+        }
+        if altgr {
+            self.push(KeyCode::RAltGr, KeyState::Down);
+        }
+        self.push(code, KeyState::Down);
+        self.push(code, KeyState::Up);
+        if altgr {
+            self.push(KeyCode::RAltGr, KeyState::Up);
+        }
+        if shift {
+            self.push(KeyCode::LShift, KeyState::Up);
+        }
+    }
+}
+
+impl core::ops::Deref for KeyEvents {
+    type Target = [KeyEvent];
+
+    fn deref(&self) -> &[KeyEvent] {
+        &self.events[..self.len]
+    }
+}
+
+/// Every key and modifier combination, with what the layout makes of it.
+fn all_keys(
+    layout: &dyn KeyboardLayout,
+) -> impl Iterator<Item = (KeyCode, ModifierCombo, DecodedKey)> + '_ {
+    ModifierCombo::ALL
+        .iter()
+        .flat_map(|combo| TYPING_KEYS.iter().map(move |keycode| (*keycode, *combo)))
+        .map(move |(keycode, combo)| {
+            let key = layout.map_keycode(keycode, &combo.modifiers(), HandleControl::Ignore);
+            (keycode, combo, key)
+        })
+}
+
+/// Work out the key events which type the given character, starting with
+/// Num Lock on, Caps Lock off and no keys held.
+///
+/// A single key press (see [`find_char`]) is used if there is one, otherwise
+/// we look for a dead key followed by a single key press, using the
+/// layout's compositions and the built-in tables. Any Shift or AltGr presses needed are included,
+/// and every key is released again at the end.
+pub fn events_for_char(layout: &dyn KeyboardLayout, ch: char) -> Option<KeyEvents> {
+    let mut events = KeyEvents::new();
+    if let Some((keycode, combo)) = find_char(layout, ch) {
+        events.tap(keycode, combo);
+        return Some(events);
+    }
+    for (dead_code, dead_combo, dead_key) in all_keys(layout) {
+        let dead_key = match dead_key {
+            DecodedKey::DeadKey(dead_key) => dead_key,
+            _ => continue,
+        };
+        for (keycode, combo, key) in all_keys(layout) {
+            match key {
+                DecodedKey::Unicode(base) if combine(layout, dead_key, base) == Some(ch) => {
+                    events.tap(dead_code, dead_combo);
+                    events.tap(keycode, combo);
+                    return Some(events);
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// Combine a dead key with a character, as [`EventDecoder`] would with no
+/// extra compositions added.
+///
+/// [`EventDecoder`]: crate::EventDecoder
+fn combine(layout: &dyn KeyboardLayout, dead_key: char, ch: char) -> Option<char> {
+    layout
+        .dead_key_combine(dead_key, ch)
+        .or_else(|| crate::compose::combine(dead_key, ch))
+}
+
+/// Get every character that a layout can produce.
+///
+/// Every key that might produce a character is tried with every combination
+/// of Shift, AltGr, Caps Lock and Num Lock. Any dead keys found are combined
+/// with every character found, using the layout's compositions and the
+/// built-in tables.
+///
+/// Control characters (like Tab, or Enter) are not included. The characters
+/// are given in ascending order, with no duplicates.
+#[cfg(feature = "alloc")]
+pub fn character_set(layout: &dyn KeyboardLayout) -> impl Iterator<Item = char> {
+    let mut chars = BTreeSet::new();
+    let mut dead_keys = BTreeSet::new();
+    for bits in 0..16u8 {
+        let modifiers = Modifiers {
+            lshift: (bits & 1) != 0,
+            ralt: (bits & 2) != 0,
+            capslock: (bits & 4) != 0,
+            numlock: (bits & 8) != 0,
+            ..Default::default()
+        };
+        for keycode in TYPING_KEYS {
+            match layout.map_keycode(*keycode, &modifiers, HandleControl::Ignore) {
+                DecodedKey::Unicode(ch) if !ch.is_control() => {
+                    chars.insert(ch);
+                }
+                DecodedKey::DeadKey(dead_key) => {
+                    dead_keys.insert(dead_key);
+                }
+                _ => {}
+            }
+        }
+    }
+    let mut composed = BTreeSet::new();
+    for dead_key in dead_keys {
+        // Dead key then space gives the dead key on its own
+        composed.insert(dead_key);
+        for ch in &chars {
+            if let Some(combined) = combine(layout, dead_key, *ch) {
+                composed.insert(combined);
+            }
+        }
+    }
+    chars.extend(composed);
+    chars.into_iter()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::{De105Key, Us104Key};
+    #[cfg(feature = "alloc")]
+    use core::ops::RangeInclusive;
+
+    #[test]
+    fn test_find_char() {
+        assert_eq!(
+            find_char(&De105Key, '€'),
+            Some((KeyCode::E, ModifierCombo::AltGr))
+        );
+        assert_eq!(
+            find_char(&De105Key, '@'),
+            Some((KeyCode::Q, ModifierCombo::AltGr))
+        );
+        assert_eq!(
+            find_char(&De105Key, 'ß'),
+            Some((KeyCode::OemMinus, ModifierCombo::None))
+        );
+        assert_eq!(
+            find_char(&De105Key, 'Z'),
+            Some((KeyCode::Y, ModifierCombo::Shift))
+        );
+        assert_eq!(find_char(&De105Key, 'ж'), None);
+    }
+
+    #[test]
+    fn test_find_chars() {
+        let found: Vec<_> = find_chars(&Us104Key, '1').collect();
+        // The main row and the numpad, simplest first
+        assert_eq!(found[0], (KeyCode::Key1, ModifierCombo::None));
+        assert_eq!(found[1], (KeyCode::Numpad1, ModifierCombo::None));
+        // The US layout ignores AltGr
+        assert!(found.contains(&(KeyCode::Key1, ModifierCombo::AltGr)));
+        assert!(!found.contains(&(KeyCode::Key1, ModifierCombo::Shift)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_de105() {
+        let chars: BTreeSet<char> = character_set(&De105Key).collect();
+        assert!(chars.contains(&'ß'));
+        assert!(chars.contains(&'€'));
+        assert!(chars.contains(&'ü'));
+        assert!(chars.contains(&'Ü'));
+        assert!(!chars.contains(&'\t'));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_us104() {
+        let chars: BTreeSet<char> = character_set(&Us104Key).collect();
+        assert!(!chars.contains(&'ß'));
+        assert!(!chars.contains(&'€'));
+        // All the printable ASCII characters, and nothing else
+        let ascii: BTreeSet<char> = (' '..='~').collect();
+        assert_eq!(chars, ascii);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_dead_keys() {
+        let chars: BTreeSet<char> = character_set(&crate::layouts::CyUk105Key).collect();
+        // Only available through the dead circumflex
+        assert!(chars.contains(&'Ê'));
+        assert!(chars.contains(&'^'));
+        // There's no dead caron
+        assert!(!chars.contains(&'ǩ'));
+    }
+
+    #[test]
+    fn test_events_for_char() {
+        assert_eq!(
+            &*events_for_char(&De105Key, '€').unwrap(),
+            &[
+                KeyEvent::new(KeyCode::RAltGr, KeyState::Down),
+                KeyEvent::new(KeyCode::E, KeyState::Down),
+                KeyEvent::new(KeyCode::E, KeyState::Up),
+                KeyEvent::new(KeyCode::RAltGr, KeyState::Up),
+            ]
+        );
+        assert_eq!(
+            &*events_for_char(&Us104Key, 'a').unwrap(),
+            &[
+                KeyEvent::new(KeyCode::A, KeyState::Down),
+                KeyEvent::new(KeyCode::A, KeyState::Up),
+            ]
+        );
+        assert_eq!(events_for_char(&Us104Key, 'é'), None);
+        // Only available with a dead key
+        assert_eq!(
+            &*events_for_char(&crate::layouts::CyUk105Key, 'Ê').unwrap(),
+            &[
+                KeyEvent::new(KeyCode::RAltGr, KeyState::Down),
+                KeyEvent::new(KeyCode::Key6, KeyState::Down),
+                KeyEvent::new(KeyCode::Key6, KeyState::Up),
+                KeyEvent::new(KeyCode::RAltGr, KeyState::Up),
+                KeyEvent::new(KeyCode::LShift, KeyState::Down),
+                KeyEvent::new(KeyCode::E, KeyState::Down),
+                KeyEvent::new(KeyCode::E, KeyState::Up),
+                KeyEvent::new(KeyCode::LShift, KeyState::Up),
+            ]
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    const BASIC_LATIN: RangeInclusive<char> = ' '..='~';
+    #[cfg(feature = "alloc")]
+    const LATIN_1: RangeInclusive<char> = '\u{A0}'..='ÿ';
+    #[cfg(feature = "alloc")]
+    const LATIN_EXT_A: RangeInclusive<char> = 'Ā'..='ſ';
+    #[cfg(feature = "alloc")]
+    const LATIN_EXT_B: RangeInclusive<char> = 'ƀ'..='ɏ';
+    #[cfg(feature = "alloc")]
+    const IPA_EXTENSIONS: RangeInclusive<char> = 'ɐ'..='ʯ';
+    #[cfg(feature = "alloc")]
+    const SPACING_MODIFIERS: RangeInclusive<char> = 'ʰ'..='˿';
+    #[cfg(feature = "alloc")]
+    const LATIN_EXT_ADDITIONAL: RangeInclusive<char> = 'Ḁ'..='ỿ';
+    #[cfg(feature = "alloc")]
+    const CURRENCY: RangeInclusive<char> = '₠'..='⃏';
+    #[cfg(feature = "alloc")]
+    const GENERAL_PUNCTUATION: RangeInclusive<char> = '\u{2000}'..='\u{206F}';
+    #[cfg(feature = "alloc")]
+    const LETTERLIKE: RangeInclusive<char> = '\u{2100}'..='\u{214F}';
+    #[cfg(feature = "alloc")]
+    const MATH_OPERATORS: RangeInclusive<char> = '\u{2200}'..='\u{22FF}';
+    #[cfg(feature = "alloc")]
+    const GREEK: RangeInclusive<char> = '\u{0370}'..='\u{03FF}';
+    #[cfg(feature = "alloc")]
+    const GREEK_EXTENDED: RangeInclusive<char> = '\u{1F00}'..='\u{1FFF}';
+    #[cfg(feature = "alloc")]
+    const CYRILLIC: RangeInclusive<char> = '\u{0400}'..='\u{04FF}';
+    #[cfg(feature = "alloc")]
+    const ARMENIAN: RangeInclusive<char> = '\u{0530}'..='\u{058F}';
+    #[cfg(feature = "alloc")]
+    const KHMER: RangeInclusive<char> = '\u{1780}'..='\u{17FF}';
+    #[cfg(feature = "alloc")]
+    const LAO: RangeInclusive<char> = '\u{0E80}'..='\u{0EFF}';
+    #[cfg(feature = "alloc")]
+    const SINHALA: RangeInclusive<char> = '\u{0D80}'..='\u{0DFF}';
+    #[cfg(feature = "alloc")]
+    const TAMIL: RangeInclusive<char> = '\u{0B80}'..='\u{0BFF}';
+    #[cfg(feature = "alloc")]
+    const ETHIOPIC: RangeInclusive<char> = '\u{1200}'..='\u{137F}';
+    #[cfg(feature = "alloc")]
+    const TIFINAGH: RangeInclusive<char> = '\u{2D30}'..='\u{2D7F}';
+    #[cfg(feature = "alloc")]
+    const ARABIC: RangeInclusive<char> = '\u{0600}'..='\u{06FF}';
+    #[cfg(feature = "alloc")]
+    const DEVANAGARI: RangeInclusive<char> = '\u{0900}'..='\u{097F}';
+    #[cfg(feature = "alloc")]
+    const ARABIC_PRESENTATION_A: RangeInclusive<char> = '\u{FB50}'..='\u{FDFF}';
+    #[cfg(feature = "alloc")]
+    const ARABIC_PRESENTATION_B: RangeInclusive<char> = '\u{FE70}'..='\u{FEFF}';
+
+    /// The Unicode blocks that each layout should produce characters from.
+    ///
+    /// Every layout must be listed here, so that a new layout gets a look at
+    /// what it produces.
+    #[cfg(feature = "alloc")]
+    static EXPECTED_BLOCKS: &[(&str, &[RangeInclusive<char>])] = &[
+        ("DVP104Key", &[BASIC_LATIN]),
+        ("Dvorak104Key", &[BASIC_LATIN]),
+        ("Us104Key", &[BASIC_LATIN]),
+        ("Uk105Key", &[BASIC_LATIN, LATIN_1, CURRENCY]),
+        ("Jis109Key", &[BASIC_LATIN, LATIN_1]),
+        (
+            "Azerty",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                SPACING_MODIFIERS,
+                LATIN_EXT_ADDITIONAL,
+            ],
+        ),
+        ("Colemak", &[BASIC_LATIN]),
+        (
+            "De105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        ("De105KeyLegacy", &[BASIC_LATIN, LATIN_1, CURRENCY]),
+        (
+            "No105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        (
+            "FiSe105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        ("Haw104Key", &[BASIC_LATIN, LATIN_EXT_A, SPACING_MODIFIERS]),
+        ("MiNz104Key", &[BASIC_LATIN, LATIN_EXT_A]),
+        (
+            "CyUk105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        (
+            "SeSami105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        (
+            "La105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+            ],
+        ),
+        (
+            "Pt105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        ("BrAbnt2Key", &[BASIC_LATIN, LATIN_1, CURRENCY]),
+        (
+            "SwissGerman105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        (
+            "Dk105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        (
+            "TrQ105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        (
+            "SrLatn105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                SPACING_MODIFIERS,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        (
+            "Lv105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        ("Lt105Key", &[BASIC_LATIN, LATIN_EXT_A, CURRENCY]),
+        ("Kr104Key", &[BASIC_LATIN]),
+        (
+            "Neo2",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                SPACING_MODIFIERS,
+                LATIN_EXT_ADDITIONAL,
+                GENERAL_PUNCTUATION,
+                CURRENCY,
+                LETTERLIKE,
+            ],
+        ),
+        (
+            "UsInternational104Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+                GENERAL_PUNCTUATION,
+                CURRENCY,
+            ],
+        ),
+        (
+            "CaFrench105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+            ],
+        ),
+        (
+            "CaMultilingual105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+            ],
+        ),
+        (
+            "Ar101Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                GENERAL_PUNCTUATION,
+                ARABIC,
+                ARABIC_PRESENTATION_B,
+            ],
+        ),
+        (
+            "AzertyNf105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_ADDITIONAL,
+                GENERAL_PUNCTUATION,
+                CURRENCY,
+                MATH_OPERATORS,
+            ],
+        ),
+        (
+            "Svorak105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        (
+            "SvSami105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                IPA_EXTENSIONS,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        (
+            "NoSami105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                IPA_EXTENSIONS,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        (
+            "FaIr105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                GENERAL_PUNCTUATION,
+                ARABIC,
+                ARABIC_PRESENTATION_A,
+            ],
+        ),
+        ("InScript105Key", &[BASIC_LATIN, DEVANAGARI]),
+        (
+            "GrPolytonic105Key",
+            &[BASIC_LATIN, LATIN_1, GREEK, GREEK_EXTENDED],
+        ),
+        ("Mk105Key", &[BASIC_LATIN, CYRILLIC, GENERAL_PUNCTUATION]),
+        (
+            "AzLatin105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                IPA_EXTENSIONS,
+                LETTERLIKE,
+            ],
+        ),
+        ("HyPhonetic105Key", &[BASIC_LATIN, ARMENIAN]),
+        (
+            "Km105Key",
+            &[BASIC_LATIN, LATIN_1, GENERAL_PUNCTUATION, KHMER],
+        ),
+        ("Lo105Key", &[BASIC_LATIN, LAO]),
+        ("SiWijesekara105Key", &[BASIC_LATIN, SINHALA]),
+        ("Ta99Key", &[BASIC_LATIN, TAMIL]),
+        ("AmEt105Key", &[BASIC_LATIN, ETHIOPIC]),
+        ("TifinaghKey", &[BASIC_LATIN, TIFINAGH]),
+        (
+            "Bs105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                SPACING_MODIFIERS,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        (
+            "Lb105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        (
+            "SwissFrench105Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                LATIN_EXT_B,
+                LATIN_EXT_ADDITIONAL,
+                CURRENCY,
+            ],
+        ),
+        ("Ky105Key", &[BASIC_LATIN, CYRILLIC, LETTERLIKE]),
+        ("UzLatin105Key", &[BASIC_LATIN, SPACING_MODIFIERS]),
+        (
+            "Ipa104Key",
+            &[
+                BASIC_LATIN,
+                LATIN_1,
+                LATIN_EXT_A,
+                IPA_EXTENSIONS,
+                SPACING_MODIFIERS,
+                GREEK,
+            ],
+        ),
+    ];
+
+    /// Characters which Unicode Normalization Form C replaces with something
+    /// else, so a layout should produce the replacement instead.
+    ///
+    /// This only covers the Latin, Greek and symbol blocks. Add any others
+    /// when a layout for that script comes along.
+    static NOT_NFC: &[(char, char)] = &[
+        // Combining grave and acute tone marks => combining grave and acute
+        ('\u{340}', '\u{300}'),
+        ('\u{341}', '\u{301}'),
+        // Combining Greek koronis => combining comma above
+        ('\u{343}', '\u{313}'),
+        // Combining Greek dialytika tonos => diaeresis + acute
+        ('\u{344}', '\u{308}'),
+        // Greek numeral sign => modifier letter prime
+        ('\u{374}', 'ʹ'),
+        // Greek question mark => semicolon
+        ('\u{37E}', ';'),
+        // Greek ano teleia => middle dot
+        ('\u{387}', '·'),
+        // Greek with oxia => Greek with tonos
+        ('\u{1F71}', 'ά'),
+        ('\u{1F73}', 'έ'),
+        ('\u{1F75}', 'ή'),
+        ('\u{1F77}', 'ί'),
+        ('\u{1F79}', 'ό'),
+        ('\u{1F7B}', 'ύ'),
+        ('\u{1F7D}', 'ώ'),
+        ('\u{1FBB}', 'Ά'),
+        ('\u{1FC9}', 'Έ'),
+        ('\u{1FCB}', 'Ή'),
+        ('\u{1FD3}', 'ΐ'),
+        ('\u{1FDB}', 'Ί'),
+        ('\u{1FE3}', 'ΰ'),
+        ('\u{1FEB}', 'Ύ'),
+        ('\u{1FF9}', 'Ό'),
+        ('\u{1FFB}', 'Ώ'),
+        // Greek prosgegrammeni => iota
+        ('\u{1FBE}', 'ι'),
+        // Greek dialytika and oxia => dialytika tonos
+        ('\u{1FEE}', '΅'),
+        // Greek varia => grave accent
+        ('\u{1FEF}', '`'),
+        // Greek oxia => acute accent
+        ('\u{1FFD}', '´'),
+        // En quad and em quad => en space and em space
+        ('\u{2000}', '\u{2002}'),
+        ('\u{2001}', '\u{2003}'),
+        // Ohm, Kelvin and Angstrom signs => the letters
+        ('\u{2126}', 'Ω'),
+        ('\u{212A}', 'K'),
+        ('\u{212B}', 'Å'),
+        // Angle brackets => CJK angle brackets
+        ('\u{2329}', '\u{3008}'),
+        ('\u{232A}', '\u{3009}'),
+    ];
+
+    fn check_nfc(what: &str, ch: char) {
+        if let Some((_, nfc)) = NOT_NFC.iter().find(|(c, _)| *c == ch) {
+            panic!(
+                "{} produces {:?} (U+{:04X}), which should be {:?} (U+{:04X})",
+                what, ch, ch as u32, nfc, *nfc as u32
+            );
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_layout_blocks() {
+        for (names, make_layout) in super::super::LAYOUT_NAMES {
+            let (_, blocks) = EXPECTED_BLOCKS
+                .iter()
+                .find(|(name, _)| *name == names[0])
+                .unwrap_or_else(|| panic!("{} is missing from EXPECTED_BLOCKS", names[0]));
+            for ch in character_set(&make_layout()) {
+                check_nfc(names[0], ch);
+                assert!(
+                    blocks.iter().any(|block| block.contains(&ch)),
+                    "{} produces {:?} (U+{:04X}), which is in an unexpected block",
+                    names[0],
+                    ch,
+                    ch as u32
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_compositions_nfc() {
+        let tables = [
+            crate::compose::DEAD_KEY_COMPOSITIONS,
+            crate::compose::MULTI_ACCENT_COMPOSITIONS,
+        ];
+        for ((dead_key, base), ch) in tables.iter().flat_map(|t| t.iter()) {
+            check_nfc("the composition table", *dead_key);
+            check_nfc("the composition table", *base);
+            check_nfc("the composition table", *ch);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_events_for_char_round_trip() {
+        use crate::EventDecoder;
+        for (names, make_layout) in super::super::LAYOUT_NAMES {
+            let layout = make_layout();
+            for ch in character_set(&layout) {
+                let events = events_for_char(&layout, ch)
+                    .unwrap_or_else(|| panic!("{} can't type {:?}", names[0], ch));
+                let mut dec = EventDecoder::new(make_layout(), HandleControl::Ignore);
+                let mut typed = Vec::new();
+                for ev in events.iter() {
+                    if let Some(DecodedKey::Unicode(c)) = dec.process_keyevent(ev.clone()) {
+                        typed.push(c);
+                    }
+                    if let Some(DecodedKey::Unicode(c)) = dec.take_queued() {
+                        typed.push(c);
+                    }
+                }
+                assert_eq!(
+                    typed,
+                    [ch],
+                    "{} typing {:?} with {:?}",
+                    names[0],
+                    ch,
+                    events
+                );
+            }
+        }
+    }
+}
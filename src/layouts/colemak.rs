@@ -4,7 +4,9 @@ use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
 
 /// A Colemak 101-key (or 104-key including Windows keys) keyboard.
 ///
-/// Has a 1-row high Enter key, with Oem5 above (ANSI layout).
+/// Has a 1-row high Enter key, with Oem7 above (ANSI layout). Some ANSI
+/// keyboards also have an Oem5 key next to the left shift, and it types the
+/// same thing as Oem7.
 pub struct Colemak;
 
 impl KeyboardLayout for Colemak {
@@ -212,7 +214,7 @@ impl KeyboardLayout for Colemak {
                     DecodedKey::Unicode(']')
                 }
             }
-            KeyCode::Oem7 => {
+            KeyCode::Oem5 | KeyCode::Oem7 => {
                 if modifiers.is_shifted() {
                     DecodedKey::Unicode('|')
                 } else {
@@ -0,0 +1,123 @@
+//! Welsh keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Welsh 102-key (or 105-key including Windows keys) keyboard.
+///
+/// This is a UK layout, with some extras from the UK Extended layout:
+///
+/// * AltGr + W and AltGr + Y give ŵ and ŷ
+/// * AltGr + A, E, I, O or U give the vowel with an acute accent (as on the
+///   UK layout)
+/// * AltGr + 6 is a dead circumflex (to bach), which works on all seven
+///   Welsh vowels
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct CyUk105Key;
+
+impl KeyboardLayout for CyUk105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let fallback = super::Uk105Key;
+        match keycode {
+            KeyCode::Key6 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('^')
+                } else {
+                    fallback.map_keycode(keycode, modifiers, handle_ctrl)
+                }
+            }
+            KeyCode::W => modifiers.handle_ascii_4(ascii_upper!('W'), 'ŵ', 'Ŵ', handle_ctrl),
+            KeyCode::Y => modifiers.handle_ascii_4(ascii_upper!('Y'), 'ŷ', 'Ŷ', handle_ctrl),
+            KeyCode::A => modifiers.handle_ascii_4(ascii_upper!('A'), 'á', 'Á', handle_ctrl),
+            KeyCode::E => modifiers.handle_ascii_4(ascii_upper!('E'), 'é', 'É', handle_ctrl),
+            KeyCode::I => modifiers.handle_ascii_4(ascii_upper!('I'), 'í', 'Í', handle_ctrl),
+            KeyCode::O => modifiers.handle_ascii_4(ascii_upper!('O'), 'ó', 'Ó', handle_ctrl),
+            KeyCode::U => modifiers.handle_ascii_4(ascii_upper!('U'), 'ú', 'Ú', handle_ctrl),
+            e => fallback.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn direct() {
+        let mut dec = EventDecoder::new(CyUk105Key, HandleControl::Ignore);
+        let w = KeyEvent::new(KeyCode::W, KeyState::Down);
+        let y = KeyEvent::new(KeyCode::Y, KeyState::Down);
+        assert_eq!(
+            dec.process_keyevent(w.clone()),
+            Some(DecodedKey::Unicode('w'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(w.clone()),
+            Some(DecodedKey::Unicode('ŵ'))
+        );
+        assert_eq!(
+            dec.process_keyevent(y.clone()),
+            Some(DecodedKey::Unicode('ŷ'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(dec.process_keyevent(w), Some(DecodedKey::Unicode('Ŵ')));
+        assert_eq!(dec.process_keyevent(y), Some(DecodedKey::Unicode('Ŷ')));
+    }
+
+    #[test]
+    fn dead_circumflex() {
+        let mut dec = EventDecoder::new(CyUk105Key, HandleControl::Ignore);
+        let altgr_down = KeyEvent::new(KeyCode::RAltGr, KeyState::Down);
+        let altgr_up = KeyEvent::new(KeyCode::RAltGr, KeyState::Up);
+        let circumflex = KeyEvent::new(KeyCode::Key6, KeyState::Down);
+        for (code, shifted, result) in [
+            (KeyCode::A, false, 'â'),
+            (KeyCode::E, false, 'ê'),
+            (KeyCode::I, false, 'î'),
+            (KeyCode::O, false, 'ô'),
+            (KeyCode::U, false, 'û'),
+            (KeyCode::W, false, 'ŵ'),
+            (KeyCode::Y, false, 'ŷ'),
+            (KeyCode::W, true, 'Ŵ'),
+            (KeyCode::Y, true, 'Ŷ'),
+        ] {
+            dec.process_keyevent(altgr_down.clone());
+            assert_eq!(
+                dec.process_keyevent(circumflex.clone()),
+                Some(DecodedKey::DeadKey('^'))
+            );
+            dec.process_keyevent(altgr_up.clone());
+            if shifted {
+                dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            }
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(result))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+            assert_eq!(dec.take_queued(), None);
+        }
+    }
+
+    #[test]
+    fn dead_circumflex_no_match() {
+        let mut dec = EventDecoder::new(CyUk105Key, HandleControl::Ignore);
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        dec.process_keyevent(KeyEvent::new(KeyCode::Key6, KeyState::Down));
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Up));
+        // There is no 'q' with a circumflex
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Q, KeyState::Down)),
+            Some(DecodedKey::Unicode('^'))
+        );
+        assert_eq!(dec.take_queued(), Some(DecodedKey::Unicode('q')));
+        assert_eq!(dec.take_queued(), None);
+    }
+}
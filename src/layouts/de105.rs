@@ -1,14 +1,24 @@
 //! German keyboard support
 
+use super::ConfigurableLayout;
 use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
 
 /// A standard German 102-key (or 105-key including Windows keys) keyboard.
 ///
-/// The top row spells `QWERTZ`.
+/// The top row spells `QWERTZ`. The `´` key (left of Backspace) is a dead
+/// key, giving `´` and, with Shift, `` ` ``. Use [`De105KeyLegacy`] if you
+/// want that key to type those characters directly.
 ///
 /// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
 pub struct De105Key;
 
+/// A German 102-key (or 105-key including Windows keys) keyboard, where the
+/// `´` key is not a dead key.
+///
+/// This is [`De105Key`], except that the `´` key types `´` and `` ` ``
+/// straight away, as `De105Key` used to.
+pub struct De105KeyLegacy;
+
 impl KeyboardLayout for De105Key {
     fn map_keycode(
         &self,
@@ -105,9 +115,9 @@ impl KeyboardLayout for De105Key {
             }
             KeyCode::OemPlus => {
                 if modifiers.is_shifted() {
-                    DecodedKey::Unicode('`')
+                    DecodedKey::DeadKey('`')
                 } else {
-                    DecodedKey::Unicode('´')
+                    DecodedKey::DeadKey('´')
                 }
             }
             KeyCode::Backspace => DecodedKey::Unicode(0x08.into()),
@@ -143,13 +153,7 @@ impl KeyboardLayout for De105Key {
                     DecodedKey::Unicode('z')
                 }
             }
-            KeyCode::Oem4 => {
-                if modifiers.is_caps() {
-                    DecodedKey::Unicode('Ü')
-                } else {
-                    DecodedKey::Unicode('ü')
-                }
-            }
+            KeyCode::Oem4 => modifiers.handle_letter_auto('ü', 'Ü', handle_ctrl),
             KeyCode::Oem6 => {
                 if modifiers.is_altgr() {
                     DecodedKey::Unicode('~')
@@ -227,3 +231,95 @@ impl KeyboardLayout for De105Key {
         }
     }
 }
+
+impl KeyboardLayout for De105KeyLegacy {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        match De105Key.map_keycode(keycode, modifiers, handle_ctrl) {
+            DecodedKey::DeadKey(ch) => DecodedKey::Unicode(ch),
+            key => key,
+        }
+    }
+}
+
+impl ConfigurableLayout for De105Key {
+    fn from_variant(variant: Option<&str>) -> Option<De105Key> {
+        match variant {
+            None => Some(De105Key),
+            Some(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    /// Press each key in turn, collecting the characters
+    fn type_keys<L: KeyboardLayout>(layout: L, keys: &[(KeyCode, bool)]) -> String {
+        let mut dec = EventDecoder::new(layout, HandleControl::Ignore);
+        let mut output = String::new();
+        for (code, shifted) in keys {
+            if *shifted {
+                dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            }
+            if let Some(DecodedKey::Unicode(ch)) =
+                dec.process_keyevent(KeyEvent::new(*code, KeyState::Down))
+            {
+                output.push(ch);
+            }
+            while let Some(DecodedKey::Unicode(ch)) = dec.take_queued() {
+                output.push(ch);
+            }
+            if *shifted {
+                dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn test_dead_keys() {
+        let acute = (KeyCode::OemPlus, false);
+        let grave = (KeyCode::OemPlus, true);
+        assert_eq!(type_keys(De105Key, &[acute, (KeyCode::E, false)]), "é");
+        assert_eq!(type_keys(De105Key, &[grave, (KeyCode::A, false)]), "à");
+        assert_eq!(type_keys(De105Key, &[acute, (KeyCode::E, true)]), "É");
+        // QWERTZ, so this is y
+        assert_eq!(type_keys(De105Key, &[acute, (KeyCode::Z, false)]), "ý");
+        assert_eq!(
+            type_keys(De105Key, &[acute, (KeyCode::Spacebar, false)]),
+            "´"
+        );
+        assert_eq!(
+            type_keys(De105Key, &[grave, (KeyCode::Spacebar, false)]),
+            "`"
+        );
+        // A consonant without an accented form gives both
+        assert_eq!(type_keys(De105Key, &[acute, (KeyCode::T, false)]), "´t");
+    }
+
+    #[test]
+    fn test_legacy() {
+        let acute = (KeyCode::OemPlus, false);
+        let grave = (KeyCode::OemPlus, true);
+        assert_eq!(
+            type_keys(De105KeyLegacy, &[acute, (KeyCode::E, false)]),
+            "´e"
+        );
+        assert_eq!(
+            type_keys(De105KeyLegacy, &[grave, (KeyCode::A, false)]),
+            "`a"
+        );
+        // Everything else is the same
+        assert_eq!(
+            De105KeyLegacy.map_keycode(KeyCode::Q, &Modifiers::default(), HandleControl::Ignore),
+            DecodedKey::Unicode('q')
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! Compile-time selection of a default layout
+//!
+//! Enable one of the `default-layout-*` features (e.g. `default-layout-de`)
+//! to choose the layout that [`DefaultLayout`] refers to. If you don't enable
+//! any of them, you get [`Us104Key`](super::Us104Key).
+
+// Otherwise you'd only get told that `DefaultLayout` is defined twice
+const _: () = assert!(
+    (cfg!(feature = "default-layout-us") as usize
+        + cfg!(feature = "default-layout-gb") as usize
+        + cfg!(feature = "default-layout-jp") as usize
+        + cfg!(feature = "default-layout-fr") as usize
+        + cfg!(feature = "default-layout-de") as usize
+        + cfg!(feature = "default-layout-no") as usize
+        + cfg!(feature = "default-layout-fi") as usize
+        + cfg!(feature = "default-layout-haw") as usize
+        + cfg!(feature = "default-layout-mi") as usize
+        + cfg!(feature = "default-layout-cy") as usize
+        + cfg!(feature = "default-layout-dvp") as usize
+        + cfg!(feature = "default-layout-dvorak") as usize
+        + cfg!(feature = "default-layout-colemak") as usize)
+        <= 1,
+    "Only one of the `default-layout-*` features can be enabled"
+);
+
+/// The layout chosen with the `default-layout-*` features.
+#[cfg(not(any(
+    feature = "default-layout-us",
+    feature = "default-layout-gb",
+    feature = "default-layout-jp",
+    feature = "default-layout-fr",
+    feature = "default-layout-de",
+    feature = "default-layout-no",
+    feature = "default-layout-fi",
+    feature = "default-layout-haw",
+    feature = "default-layout-mi",
+    feature = "default-layout-cy",
+    feature = "default-layout-dvp",
+    feature = "default-layout-dvorak",
+    feature = "default-layout-colemak"
+)))]
+pub type DefaultLayout = super::Us104Key;
+
+#[cfg(feature = "default-layout-us")]
+pub type DefaultLayout = super::Us104Key;
+
+#[cfg(feature = "default-layout-gb")]
+pub type DefaultLayout = super::Uk105Key;
+
+#[cfg(feature = "default-layout-jp")]
+pub type DefaultLayout = super::Jis109Key;
+
+#[cfg(feature = "default-layout-fr")]
+pub type DefaultLayout = super::Azerty;
+
+#[cfg(feature = "default-layout-de")]
+pub type DefaultLayout = super::De105Key;
+
+#[cfg(feature = "default-layout-no")]
+pub type DefaultLayout = super::No105Key;
+
+#[cfg(feature = "default-layout-fi")]
+pub type DefaultLayout = super::FiSe105Key;
+
+#[cfg(feature = "default-layout-haw")]
+pub type DefaultLayout = super::Haw104Key;
+
+#[cfg(feature = "default-layout-mi")]
+pub type DefaultLayout = super::MiNz104Key;
+
+#[cfg(feature = "default-layout-cy")]
+pub type DefaultLayout = super::CyUk105Key;
+
+#[cfg(feature = "default-layout-dvp")]
+pub type DefaultLayout = super::DVP104Key;
+
+#[cfg(feature = "default-layout-dvorak")]
+pub type DefaultLayout = super::Dvorak104Key;
+
+#[cfg(feature = "default-layout-colemak")]
+pub type DefaultLayout = super::Colemak;
+
+/// Make the layout chosen with the `default-layout-*` features.
+pub const fn default_layout() -> DefaultLayout {
+    DefaultLayout {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{DecodedKey, EventDecoder, HandleControl, KeyCode, KeyEvent, KeyState};
+
+    fn decode(code: KeyCode) -> Option<DecodedKey> {
+        let mut decoder = EventDecoder::new(default_layout(), HandleControl::Ignore);
+        decoder.process_keyevent(KeyEvent::new(code, KeyState::Down))
+    }
+
+    #[cfg(not(any(
+        feature = "default-layout-us",
+        feature = "default-layout-gb",
+        feature = "default-layout-jp",
+        feature = "default-layout-fr",
+        feature = "default-layout-de",
+        feature = "default-layout-no",
+        feature = "default-layout-fi",
+        feature = "default-layout-haw",
+        feature = "default-layout-mi",
+        feature = "default-layout-cy",
+        feature = "default-layout-dvp",
+        feature = "default-layout-dvorak",
+        feature = "default-layout-colemak"
+    )))]
+    #[test]
+    fn test_default() {
+        assert_eq!(decode(KeyCode::Y), Some(DecodedKey::Unicode('y')));
+        assert_eq!(decode(KeyCode::Oem7), Some(DecodedKey::Unicode('\\')));
+    }
+
+    #[cfg(feature = "default-layout-de")]
+    #[test]
+    fn test_de() {
+        assert_eq!(decode(KeyCode::Y), Some(DecodedKey::Unicode('z')));
+        assert_eq!(decode(KeyCode::OemMinus), Some(DecodedKey::Unicode('ß')));
+    }
+
+    #[cfg(feature = "default-layout-fr")]
+    #[test]
+    fn test_fr() {
+        assert_eq!(decode(KeyCode::Q), Some(DecodedKey::Unicode('a')));
+        assert_eq!(decode(KeyCode::Key2), Some(DecodedKey::Unicode('é')));
+    }
+}
@@ -0,0 +1,132 @@
+//! Danish keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A standard Danish 102-key (or 105-key including Windows keys) keyboard.
+///
+/// This is the Norwegian layout with `æ` and `ø` swapped, and a few
+/// differences around the edges. The `´`, `` ` ``, `¨`, `^` and `~` keys are
+/// dead keys.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct Dk105Key;
+
+impl KeyboardLayout for Dk105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        match keycode {
+            KeyCode::Oem8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('§')
+                } else {
+                    DecodedKey::Unicode('½')
+                }
+            }
+            KeyCode::OemPlus => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('|')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('`')
+                } else {
+                    DecodedKey::DeadKey('´')
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('~')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('^')
+                } else {
+                    DecodedKey::DeadKey('¨')
+                }
+            }
+            KeyCode::Oem1 => modifiers.handle_letter_auto('æ', 'Æ', handle_ctrl),
+            KeyCode::Oem3 => modifiers.handle_letter_auto('ø', 'Ø', handle_ctrl),
+            e => {
+                let no = super::No105Key;
+                no.map_keycode(e, modifiers, handle_ctrl)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::{fixture, No105Key};
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdda
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '½', '§', None, None),
+            (KeyCode::Key1, '1', '!', None, None),
+            (KeyCode::Key2, '2', '"', Some('@'), None),
+            (KeyCode::Key3, '3', '#', Some('£'), None),
+            (KeyCode::Key4, '4', '¤', Some('$'), None),
+            (KeyCode::Key5, '5', '%', Some('€'), None),
+            (KeyCode::Key6, '6', '&', None, None),
+            (KeyCode::Key7, '7', '/', Some('{'), None),
+            (KeyCode::Key8, '8', '(', Some('['), None),
+            (KeyCode::Key9, '9', ')', Some(']'), None),
+            (KeyCode::Key0, '0', '=', Some('}'), None),
+            (KeyCode::OemMinus, '+', '?', None, None),
+            (KeyCode::OemPlus, '´', '`', Some('|'), None),
+            (KeyCode::E, 'e', 'E', Some('€'), None),
+            (KeyCode::Oem4, 'å', 'Å', None, None),
+            (KeyCode::Oem6, '¨', '^', Some('~'), None),
+            (KeyCode::Oem1, 'æ', 'Æ', None, None),
+            (KeyCode::Oem3, 'ø', 'Ø', None, None),
+            (KeyCode::Oem7, '\'', '*', None, None),
+            (KeyCode::Oem5, '<', '>', None, None),
+            (KeyCode::M, 'm', 'M', Some('µ'), None),
+            (KeyCode::OemComma, ',', ';', None, None),
+            (KeyCode::OemPeriod, '.', ':', None, None),
+            (KeyCode::Oem2, '-', '_', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&Dk105Key, ROWS);
+    }
+
+    #[test]
+    fn test_swapped_from_norwegian() {
+        let modifiers = Modifiers::default();
+        for (code, danish, norwegian) in [(KeyCode::Oem1, 'æ', 'ø'), (KeyCode::Oem3, 'ø', 'æ')]
+        {
+            assert_eq!(
+                Dk105Key.map_keycode(code, &modifiers, HandleControl::Ignore),
+                DecodedKey::Unicode(danish)
+            );
+            assert_eq!(
+                No105Key.map_keycode(code, &modifiers, HandleControl::Ignore),
+                DecodedKey::Unicode(norwegian)
+            );
+        }
+    }
+
+    #[test]
+    fn test_dead_keys() {
+        let mut dec = EventDecoder::new(Dk105Key, HandleControl::Ignore);
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::OemPlus, KeyState::Down)),
+            Some(DecodedKey::DeadKey('´'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::E, KeyState::Down)),
+            Some(DecodedKey::Unicode('é'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem6, KeyState::Down)),
+            Some(DecodedKey::DeadKey('¨'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::U, KeyState::Down)),
+            Some(DecodedKey::Unicode('ü'))
+        );
+    }
+}
@@ -4,7 +4,9 @@ use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
 
 /// A Dvorak 101-key (or 104-key including Windows keys) keyboard.
 ///
-/// Has a 1-row high Enter key, with Oem5 above (ANSI layout).
+/// Has a 1-row high Enter key, with Oem7 above (ANSI layout). Some ANSI
+/// keyboards also have an Oem5 key next to the left shift, and it types the
+/// same thing as Oem7.
 pub struct Dvorak104Key;
 
 impl KeyboardLayout for Dvorak104Key {
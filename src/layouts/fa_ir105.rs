@@ -0,0 +1,481 @@
+//! Persian keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Persian (ISIRI 9147) 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// Persian has no upper and lower case, so Caps Lock does nothing to the
+/// letter keys, and Shift gives the diacritics and other extra characters.
+/// The number row types the Extended Arabic-Indic digits (`۰۱۲۳…`), and
+/// Shift + Space types a zero-width non-joiner (U+200C).
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct FaIr105Key;
+
+impl KeyboardLayout for FaIr105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        if handle_ctrl == HandleControl::MapLettersToUnicode && modifiers.is_ctrl() {
+            // Ctrl + a letter key still gives the control code on the keycap
+            let key = us.map_keycode(keycode, modifiers, handle_ctrl);
+            if matches!(key, DecodedKey::Unicode(ch) if ch.is_control()) {
+                return key;
+            }
+        }
+        match keycode {
+            KeyCode::Oem8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('÷')
+                } else {
+                    DecodedKey::Unicode('\u{200D}')
+                }
+            }
+            KeyCode::Key1 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('!')
+                } else {
+                    DecodedKey::Unicode('۱')
+                }
+            }
+            KeyCode::Key2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('٬')
+                } else {
+                    DecodedKey::Unicode('۲')
+                }
+            }
+            KeyCode::Key3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('٫')
+                } else {
+                    DecodedKey::Unicode('۳')
+                }
+            }
+            KeyCode::Key4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('﷼')
+                } else {
+                    DecodedKey::Unicode('۴')
+                }
+            }
+            KeyCode::Key5 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('٪')
+                } else {
+                    DecodedKey::Unicode('۵')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('×')
+                } else {
+                    DecodedKey::Unicode('۶')
+                }
+            }
+            KeyCode::Key7 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('،')
+                } else {
+                    DecodedKey::Unicode('۷')
+                }
+            }
+            KeyCode::Key8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('*')
+                } else {
+                    DecodedKey::Unicode('۸')
+                }
+            }
+            KeyCode::Key9 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(')')
+                } else {
+                    DecodedKey::Unicode('۹')
+                }
+            }
+            KeyCode::Key0 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('(')
+                } else {
+                    DecodedKey::Unicode('۰')
+                }
+            }
+            KeyCode::OemMinus => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0640}')
+                } else {
+                    DecodedKey::Unicode('-')
+                }
+            }
+            KeyCode::OemPlus => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('+')
+                } else {
+                    DecodedKey::Unicode('=')
+                }
+            }
+            KeyCode::Q => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0652}')
+                } else {
+                    DecodedKey::Unicode('ض')
+                }
+            }
+            KeyCode::W => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{064C}')
+                } else {
+                    DecodedKey::Unicode('ص')
+                }
+            }
+            KeyCode::E => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{064D}')
+                } else {
+                    DecodedKey::Unicode('ث')
+                }
+            }
+            KeyCode::R => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{064B}')
+                } else {
+                    DecodedKey::Unicode('ق')
+                }
+            }
+            KeyCode::T => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{064F}')
+                } else {
+                    DecodedKey::Unicode('ف')
+                }
+            }
+            KeyCode::Y => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0650}')
+                } else {
+                    DecodedKey::Unicode('غ')
+                }
+            }
+            KeyCode::U => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{064E}')
+                } else {
+                    DecodedKey::Unicode('ع')
+                }
+            }
+            KeyCode::I => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0651}')
+                } else {
+                    DecodedKey::Unicode('ه')
+                }
+            }
+            KeyCode::O => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(']')
+                } else {
+                    DecodedKey::Unicode('خ')
+                }
+            }
+            KeyCode::P => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('[')
+                } else {
+                    DecodedKey::Unicode('ح')
+                }
+            }
+            KeyCode::Oem4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('}')
+                } else {
+                    DecodedKey::Unicode('ج')
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('{')
+                } else {
+                    DecodedKey::Unicode('چ')
+                }
+            }
+            KeyCode::A => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ؤ')
+                } else {
+                    DecodedKey::Unicode('ش')
+                }
+            }
+            KeyCode::S => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ئ')
+                } else {
+                    DecodedKey::Unicode('س')
+                }
+            }
+            KeyCode::D => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ي')
+                } else {
+                    DecodedKey::Unicode('ی')
+                }
+            }
+            KeyCode::F => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('إ')
+                } else {
+                    DecodedKey::Unicode('ب')
+                }
+            }
+            KeyCode::G => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('أ')
+                } else {
+                    DecodedKey::Unicode('ل')
+                }
+            }
+            KeyCode::H => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('آ')
+                } else {
+                    DecodedKey::Unicode('ا')
+                }
+            }
+            KeyCode::J => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ة')
+                } else {
+                    DecodedKey::Unicode('ت')
+                }
+            }
+            KeyCode::K => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('»')
+                } else {
+                    DecodedKey::Unicode('ن')
+                }
+            }
+            KeyCode::L => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('«')
+                } else {
+                    DecodedKey::Unicode('م')
+                }
+            }
+            KeyCode::Oem1 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode('ک')
+                }
+            }
+            KeyCode::Oem3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('؛')
+                } else {
+                    DecodedKey::Unicode('گ')
+                }
+            }
+            KeyCode::Z => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ك')
+                } else {
+                    DecodedKey::Unicode('ظ')
+                }
+            }
+            KeyCode::X => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0653}')
+                } else {
+                    DecodedKey::Unicode('ط')
+                }
+            }
+            KeyCode::C => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ژ')
+                } else {
+                    DecodedKey::Unicode('ز')
+                }
+            }
+            KeyCode::V => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0670}')
+                } else {
+                    DecodedKey::Unicode('ر')
+                }
+            }
+            KeyCode::B => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{200C}')
+                } else {
+                    DecodedKey::Unicode('ذ')
+                }
+            }
+            KeyCode::N => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0654}')
+                } else {
+                    DecodedKey::Unicode('د')
+                }
+            }
+            KeyCode::M => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ء')
+                } else {
+                    DecodedKey::Unicode('پ')
+                }
+            }
+            KeyCode::OemComma => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('>')
+                } else {
+                    DecodedKey::Unicode('و')
+                }
+            }
+            KeyCode::OemPeriod => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('<')
+                } else {
+                    DecodedKey::Unicode('.')
+                }
+            }
+            KeyCode::Oem2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('؟')
+                } else {
+                    DecodedKey::Unicode('/')
+                }
+            }
+            KeyCode::Spacebar => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{200C}')
+                } else {
+                    DecodedKey::Unicode(' ')
+                }
+            }
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdfa
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '\u{200D}', '÷', None, None),
+            (KeyCode::Key1, '۱', '!', None, None),
+            (KeyCode::Key2, '۲', '٬', None, None),
+            (KeyCode::Key3, '۳', '٫', None, None),
+            (KeyCode::Key4, '۴', '﷼', None, None),
+            (KeyCode::Key5, '۵', '٪', None, None),
+            (KeyCode::Key6, '۶', '×', None, None),
+            (KeyCode::Key7, '۷', '،', None, None),
+            (KeyCode::Key8, '۸', '*', None, None),
+            (KeyCode::Key9, '۹', ')', None, None),
+            (KeyCode::Key0, '۰', '(', None, None),
+            (KeyCode::OemMinus, '-', '\u{0640}', None, None),
+            (KeyCode::OemPlus, '=', '+', None, None),
+            (KeyCode::Q, 'ض', '\u{0652}', None, None),
+            (KeyCode::W, 'ص', '\u{064C}', None, None),
+            (KeyCode::E, 'ث', '\u{064D}', None, None),
+            (KeyCode::R, 'ق', '\u{064B}', None, None),
+            (KeyCode::T, 'ف', '\u{064F}', None, None),
+            (KeyCode::Y, 'غ', '\u{0650}', None, None),
+            (KeyCode::U, 'ع', '\u{064E}', None, None),
+            (KeyCode::I, 'ه', '\u{0651}', None, None),
+            (KeyCode::O, 'خ', ']', None, None),
+            (KeyCode::P, 'ح', '[', None, None),
+            (KeyCode::Oem4, 'ج', '}', None, None),
+            (KeyCode::Oem6, 'چ', '{', None, None),
+            (KeyCode::A, 'ش', 'ؤ', None, None),
+            (KeyCode::S, 'س', 'ئ', None, None),
+            (KeyCode::D, 'ی', 'ي', None, None),
+            (KeyCode::F, 'ب', 'إ', None, None),
+            (KeyCode::G, 'ل', 'أ', None, None),
+            (KeyCode::H, 'ا', 'آ', None, None),
+            (KeyCode::J, 'ت', 'ة', None, None),
+            (KeyCode::K, 'ن', '»', None, None),
+            (KeyCode::L, 'م', '«', None, None),
+            (KeyCode::Oem1, 'ک', ':', None, None),
+            (KeyCode::Oem3, 'گ', '؛', None, None),
+            (KeyCode::Z, 'ظ', 'ك', None, None),
+            (KeyCode::X, 'ط', '\u{0653}', None, None),
+            (KeyCode::C, 'ز', 'ژ', None, None),
+            (KeyCode::V, 'ر', '\u{0670}', None, None),
+            (KeyCode::B, 'ذ', '\u{200C}', None, None),
+            (KeyCode::N, 'د', '\u{0654}', None, None),
+            (KeyCode::M, 'پ', 'ء', None, None),
+            (KeyCode::OemComma, 'و', '>', None, None),
+            (KeyCode::OemPeriod, '.', '<', None, None),
+            (KeyCode::Oem2, '/', '؟', None, None),
+            (KeyCode::Oem5, '\\', '|', None, None),
+            (KeyCode::Spacebar, ' ', '\u{200C}', None, None),
+        ];
+        fixture::check(&FaIr105Key, ROWS);
+    }
+
+    #[test]
+    fn test_digits() {
+        let mut dec = EventDecoder::new(FaIr105Key, HandleControl::Ignore);
+        let mut typed = ['\0'; 10];
+        for (code, ch) in [
+            KeyCode::Key0,
+            KeyCode::Key1,
+            KeyCode::Key2,
+            KeyCode::Key3,
+            KeyCode::Key4,
+            KeyCode::Key5,
+            KeyCode::Key6,
+            KeyCode::Key7,
+            KeyCode::Key8,
+            KeyCode::Key9,
+        ]
+        .into_iter()
+        .zip(typed.iter_mut())
+        {
+            if let Some(DecodedKey::Unicode(c)) =
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down))
+            {
+                *ch = c;
+            }
+            dec.process_keyevent(KeyEvent::new(code, KeyState::Up));
+        }
+        assert_eq!(typed, ['۰', '۱', '۲', '۳', '۴', '۵', '۶', '۷', '۸', '۹']);
+    }
+
+    #[test]
+    fn test_shift_space() {
+        let mut dec = EventDecoder::new(FaIr105Key, HandleControl::Ignore);
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Spacebar, KeyState::Down)),
+            Some(DecodedKey::Unicode(' '))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::RShift, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Spacebar, KeyState::Down)),
+            Some(DecodedKey::Unicode('\u{200C}'))
+        );
+        // Caps Lock is not Shift
+        dec.process_keyevent(KeyEvent::new(KeyCode::RShift, KeyState::Up));
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Spacebar, KeyState::Down)),
+            Some(DecodedKey::Unicode(' '))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::A, KeyState::Down)),
+            Some(DecodedKey::Unicode('ش'))
+        );
+    }
+}
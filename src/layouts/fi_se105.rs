@@ -1,9 +1,12 @@
 //! Finnish/Swedish keyboard support
 
+use super::ConfigurableLayout;
 use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
 
 /// A standard Finnish/Swedish 102-key (or 105-key including Windows keys) keyboard.
 ///
+/// The `´`/`` ` `` key and the `¨`/`^`/`~` key are dead keys.
+///
 /// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
 pub struct FiSe105Key;
 
@@ -115,9 +118,9 @@ impl KeyboardLayout for FiSe105Key {
             }
             KeyCode::OemPlus => {
                 if modifiers.is_shifted() {
-                    DecodedKey::Unicode('`')
+                    DecodedKey::DeadKey('`')
                 } else {
-                    DecodedKey::Unicode('´')
+                    DecodedKey::DeadKey('´')
                 }
             }
             // ========= Row 3 (QWERTY) =========
@@ -132,37 +135,19 @@ impl KeyboardLayout for FiSe105Key {
                     DecodedKey::Unicode('e')
                 }
             }
-            KeyCode::Oem4 => {
-                if modifiers.is_caps() {
-                    DecodedKey::Unicode('Å')
-                } else {
-                    DecodedKey::Unicode('å')
-                }
-            }
+            KeyCode::Oem4 => modifiers.handle_letter_auto('å', 'Å', handle_ctrl),
             KeyCode::Oem6 => {
                 if modifiers.is_shifted() {
-                    DecodedKey::Unicode('^')
+                    DecodedKey::DeadKey('^')
                 } else if modifiers.is_altgr() {
-                    DecodedKey::Unicode('~')
+                    DecodedKey::DeadKey('~')
                 } else {
-                    DecodedKey::Unicode('¨')
+                    DecodedKey::DeadKey('¨')
                 }
             }
             // ========= Row 4 (ASDF) =========
-            KeyCode::Oem1 => {
-                if modifiers.is_caps() {
-                    DecodedKey::Unicode('Ö')
-                } else {
-                    DecodedKey::Unicode('ö')
-                }
-            }
-            KeyCode::Oem3 => {
-                if modifiers.is_caps() {
-                    DecodedKey::Unicode('Ä')
-                } else {
-                    DecodedKey::Unicode('ä')
-                }
-            }
+            KeyCode::Oem1 => modifiers.handle_letter_auto('ö', 'Ö', handle_ctrl),
+            KeyCode::Oem3 => modifiers.handle_letter_auto('ä', 'Ä', handle_ctrl),
             KeyCode::Oem7 => {
                 if modifiers.is_shifted() {
                     DecodedKey::Unicode('*')
@@ -224,3 +209,61 @@ impl KeyboardLayout for FiSe105Key {
         }
     }
 }
+
+impl ConfigurableLayout for FiSe105Key {
+    fn from_variant(variant: Option<&str>) -> Option<FiSe105Key> {
+        // Finnish and Swedish share the same layout
+        match variant {
+            None | Some("fi") | Some("se") => Some(FiSe105Key),
+            Some(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Keyboard, ScancodeSet2};
+
+    /// Feed Set 2 scancodes through a keyboard, collecting the characters
+    fn type_bytes(bytes: &[u8]) -> String {
+        let mut k = Keyboard::new(ScancodeSet2::new(), FiSe105Key, HandleControl::Ignore);
+        let mut output = String::new();
+        for byte in bytes {
+            if let Ok(Some(item)) = k.add_byte(*byte) {
+                if let Some(DecodedKey::Unicode(ch)) = k.process_item(item) {
+                    output.push(ch);
+                }
+                while let Some(DecodedKey::Unicode(ch)) = k.take_queued() {
+                    output.push(ch);
+                }
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn test_dead_keys() {
+        // ´ then e
+        assert_eq!(type_bytes(&[0x55, 0xF0, 0x55, 0x24, 0xF0, 0x24]), "é");
+        // ¨ then a
+        assert_eq!(type_bytes(&[0x5B, 0xF0, 0x5B, 0x1C, 0xF0, 0x1C]), "ä");
+        // Shift + ´ is a dead `, then u
+        assert_eq!(
+            type_bytes(&[0x12, 0x55, 0xF0, 0x55, 0xF0, 0x12, 0x3C, 0xF0, 0x3C]),
+            "ù"
+        );
+        // Shift + ¨ is a dead ^, then o
+        assert_eq!(
+            type_bytes(&[0x12, 0x5B, 0xF0, 0x5B, 0xF0, 0x12, 0x44, 0xF0, 0x44]),
+            "ô"
+        );
+        // AltGr + ¨ is a dead ~, then n
+        assert_eq!(
+            type_bytes(&[0xE0, 0x11, 0x5B, 0xF0, 0x5B, 0xE0, 0xF0, 0x11, 0x31, 0xF0, 0x31]),
+            "ñ"
+        );
+        // Space gives the accent on its own
+        assert_eq!(type_bytes(&[0x55, 0xF0, 0x55, 0x29, 0xF0, 0x29]), "´");
+    }
+}
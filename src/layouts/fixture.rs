@@ -0,0 +1,49 @@
+//! Checks a layout against a table taken from <https://kbdlayout.info>
+//!
+//! Each row gives a key, and what it types on its own, with Shift, with AltGr
+//! and with Shift + AltGr. Use `None` where kbdlayout.info shows nothing, and
+//! that state isn't checked.
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A key, then what it gives in the base, Shift, AltGr and Shift + AltGr
+/// states.
+pub(crate) type Row = (KeyCode, char, char, Option<char>, Option<char>);
+
+/// Check every row of `rows` against `layout`.
+///
+/// Dead keys are written as their spacing character, like any other.
+pub(crate) fn check(layout: &dyn KeyboardLayout, rows: &[Row]) {
+    for &(code, base, shift, altgr, shift_altgr) in rows {
+        let states = [
+            (false, false, Some(base)),
+            (true, false, Some(shift)),
+            (false, true, altgr),
+            (true, true, shift_altgr),
+        ];
+        for (shifted, ralt, expected) in states {
+            let expected = match expected {
+                Some(ch) => ch,
+                None => continue,
+            };
+            let modifiers = Modifiers {
+                lshift: shifted,
+                ralt,
+                ..Default::default()
+            };
+            let decoded = layout.map_keycode(code, &modifiers, HandleControl::Ignore);
+            let ch = match decoded {
+                DecodedKey::Unicode(ch) | DecodedKey::DeadKey(ch) => Some(ch),
+                DecodedKey::RawKey(_) => None,
+            };
+            assert_eq!(
+                ch,
+                Some(expected),
+                "{:?} with shift={} altgr={}",
+                code,
+                shifted,
+                ralt
+            );
+        }
+    }
+}
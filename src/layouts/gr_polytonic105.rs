@@ -0,0 +1,248 @@
+//! Greek Polytonic keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Greek Polytonic 102-key (or 105-key including Windows keys) keyboard.
+///
+/// The letters are where they are on a standard Greek keyboard. AltGr on a
+/// vowel gives it with a smooth breathing (e.g. `ἀ`), and Shift + AltGr with
+/// a rough breathing (e.g. `ἁ`). For a capital with a breathing, use the
+/// breathing dead keys.
+///
+/// The accents are dead keys:
+///
+/// * `;` is the acute (tonos) and, with Shift, the dialytika
+/// * `[` is the grave (varia) and, with Shift, the circumflex (perispomeni)
+/// * `]` is the smooth breathing (psili) and, with Shift, the rough
+///   breathing (dasia)
+///
+/// Set [`EventDecoder::set_max_dead_keys`](crate::EventDecoder::set_max_dead_keys)
+/// to 2 to combine a breathing with an accent (e.g. `ἄ`, `ἇ`).
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct GrPolytonic105Key;
+
+/// The Greek letters, in lower and upper case.
+const LETTERS: &[(KeyCode, char, char)] = &[
+    (KeyCode::W, 'ς', 'Σ'),
+    (KeyCode::E, 'ε', 'Ε'),
+    (KeyCode::R, 'ρ', 'Ρ'),
+    (KeyCode::T, 'τ', 'Τ'),
+    (KeyCode::Y, 'υ', 'Υ'),
+    (KeyCode::U, 'θ', 'Θ'),
+    (KeyCode::I, 'ι', 'Ι'),
+    (KeyCode::O, 'ο', 'Ο'),
+    (KeyCode::P, 'π', 'Π'),
+    (KeyCode::A, 'α', 'Α'),
+    (KeyCode::S, 'σ', 'Σ'),
+    (KeyCode::D, 'δ', 'Δ'),
+    (KeyCode::F, 'φ', 'Φ'),
+    (KeyCode::G, 'γ', 'Γ'),
+    (KeyCode::H, 'η', 'Η'),
+    (KeyCode::J, 'ξ', 'Ξ'),
+    (KeyCode::K, 'κ', 'Κ'),
+    (KeyCode::L, 'λ', 'Λ'),
+    (KeyCode::Z, 'ζ', 'Ζ'),
+    (KeyCode::X, 'χ', 'Χ'),
+    (KeyCode::C, 'ψ', 'Ψ'),
+    (KeyCode::V, 'ω', 'Ω'),
+    (KeyCode::B, 'β', 'Β'),
+    (KeyCode::N, 'ν', 'Ν'),
+    (KeyCode::M, 'μ', 'Μ'),
+];
+
+/// The vowels with a breathing: psili, then dasia.
+const BREATHINGS: &[(KeyCode, char, char)] = &[
+    (KeyCode::A, 'ἀ', 'ἁ'),
+    (KeyCode::E, 'ἐ', 'ἑ'),
+    (KeyCode::H, 'ἠ', 'ἡ'),
+    (KeyCode::I, 'ἰ', 'ἱ'),
+    (KeyCode::O, 'ὀ', 'ὁ'),
+    (KeyCode::Y, 'ὐ', 'ὑ'),
+    (KeyCode::V, 'ὠ', 'ὡ'),
+];
+
+impl KeyboardLayout for GrPolytonic105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        if handle_ctrl == HandleControl::MapLettersToUnicode && modifiers.is_ctrl() {
+            // Ctrl + a letter key still gives the control code on the keycap
+            let key = us.map_keycode(keycode, modifiers, handle_ctrl);
+            if matches!(key, DecodedKey::Unicode(ch) if ch.is_control()) {
+                return key;
+            }
+        }
+        if modifiers.is_altgr() {
+            if let Some((_, psili, dasia)) = BREATHINGS.iter().find(|(code, _, _)| *code == keycode)
+            {
+                // Shift picks the breathing, not the case
+                if modifiers.is_shifted() {
+                    return DecodedKey::Unicode(*dasia);
+                } else {
+                    return DecodedKey::Unicode(*psili);
+                }
+            }
+        }
+        if let Some((_, lower, upper)) = LETTERS.iter().find(|(code, _, _)| *code == keycode) {
+            return modifiers.handle_letter_auto(*lower, *upper, HandleControl::Ignore);
+        }
+        match keycode {
+            KeyCode::Q => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode(';')
+                }
+            }
+            KeyCode::Oem1 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('¨')
+                } else {
+                    DecodedKey::DeadKey('´')
+                }
+            }
+            KeyCode::Oem4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('῀')
+                } else {
+                    DecodedKey::DeadKey('`')
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('῾')
+                } else {
+                    DecodedKey::DeadKey('᾿')
+                }
+            }
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Based on https://kbdlayout.info/kbdhela2
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Q, ';', ':', None, None),
+            (KeyCode::W, 'ς', 'Σ', None, None),
+            (KeyCode::E, 'ε', 'Ε', Some('ἐ'), Some('ἑ')),
+            (KeyCode::Y, 'υ', 'Υ', Some('ὐ'), Some('ὑ')),
+            (KeyCode::U, 'θ', 'Θ', None, None),
+            (KeyCode::Oem4, '`', '῀', None, None),
+            (KeyCode::Oem6, '᾿', '῾', None, None),
+            (KeyCode::A, 'α', 'Α', Some('ἀ'), Some('ἁ')),
+            (KeyCode::H, 'η', 'Η', Some('ἠ'), Some('ἡ')),
+            (KeyCode::Oem1, '´', '¨', None, None),
+            (KeyCode::C, 'ψ', 'Ψ', None, None),
+            (KeyCode::V, 'ω', 'Ω', Some('ὠ'), Some('ὡ')),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&GrPolytonic105Key, ROWS);
+    }
+
+    #[test]
+    fn test_alpha() {
+        // Every combination of Shift, AltGr and Caps Lock on the alpha key
+        let expected = [
+            // shift, altgr, capslock, result
+            (false, false, false, 'α'),
+            (true, false, false, 'Α'),
+            (false, false, true, 'Α'),
+            (true, false, true, 'α'),
+            (false, true, false, 'ἀ'),
+            (false, true, true, 'ἀ'),
+            (true, true, false, 'ἁ'),
+            (true, true, true, 'ἁ'),
+        ];
+        for (shift, altgr, capslock, result) in expected {
+            let modifiers = Modifiers {
+                lshift: shift,
+                ralt: altgr,
+                capslock,
+                ..Default::default()
+            };
+            assert_eq!(
+                GrPolytonic105Key.map_keycode(KeyCode::A, &modifiers, HandleControl::Ignore),
+                DecodedKey::Unicode(result),
+                "shift={} altgr={} capslock={}",
+                shift,
+                altgr,
+                capslock
+            );
+        }
+        // Ctrl + A is still Ctrl + A
+        let modifiers = Modifiers {
+            lctrl: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            GrPolytonic105Key.map_keycode(
+                KeyCode::A,
+                &modifiers,
+                HandleControl::MapLettersToUnicode
+            ),
+            DecodedKey::Unicode('\u{0001}')
+        );
+    }
+
+    #[test]
+    fn test_dead_keys() {
+        let mut dec = EventDecoder::new(GrPolytonic105Key, HandleControl::Ignore);
+        dec.set_max_dead_keys(2);
+        // Perispomeni
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem4, KeyState::Down)),
+            Some(DecodedKey::DeadKey('῀'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::H, KeyState::Down)),
+            Some(DecodedKey::Unicode('ῆ'))
+        );
+        // Psili then tonos
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem6, KeyState::Down)),
+            Some(DecodedKey::DeadKey('᾿'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem1, KeyState::Down)),
+            Some(DecodedKey::DeadKey('´'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::A, KeyState::Down)),
+            Some(DecodedKey::Unicode('ἄ'))
+        );
+        // A capital with a breathing
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem6, KeyState::Down)),
+            Some(DecodedKey::DeadKey('῾'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::V, KeyState::Down)),
+            Some(DecodedKey::Unicode('Ὡ'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        // Dialytika
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        dec.process_keyevent(KeyEvent::new(KeyCode::Oem1, KeyState::Down));
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::I, KeyState::Down)),
+            Some(DecodedKey::Unicode('ϊ'))
+        );
+    }
+}
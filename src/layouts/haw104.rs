@@ -0,0 +1,102 @@
+//! Hawaiian keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Hawaiian 101-key (or 104-key including Windows keys) keyboard.
+///
+/// This is a US layout, except that the quote key gives the ʻokina (U+02BB)
+/// and AltGr on the vowels gives the vowels with a kahakō (macron).
+///
+/// Has a 1-row high Enter key, with Oem7 above (ANSI layout).
+pub struct Haw104Key;
+
+impl KeyboardLayout for Haw104Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let fallback = super::Us104Key;
+        match keycode {
+            KeyCode::Oem3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('"')
+                } else if modifiers.is_altgr() {
+                    DecodedKey::Unicode('\'')
+                } else {
+                    DecodedKey::Unicode('ʻ')
+                }
+            }
+            KeyCode::A => modifiers.handle_ascii_4(ascii_upper!('A'), 'ā', 'Ā', handle_ctrl),
+            KeyCode::E => modifiers.handle_ascii_4(ascii_upper!('E'), 'ē', 'Ē', handle_ctrl),
+            KeyCode::I => modifiers.handle_ascii_4(ascii_upper!('I'), 'ī', 'Ī', handle_ctrl),
+            KeyCode::O => modifiers.handle_ascii_4(ascii_upper!('O'), 'ō', 'Ō', handle_ctrl),
+            KeyCode::U => modifiers.handle_ascii_4(ascii_upper!('U'), 'ū', 'Ū', handle_ctrl),
+            e => fallback.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn okina() {
+        let mut dec = EventDecoder::new(Haw104Key, HandleControl::Ignore);
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem3, KeyState::Down)),
+            Some(DecodedKey::Unicode('\u{02BB}'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem3, KeyState::Down)),
+            Some(DecodedKey::Unicode('\''))
+        );
+    }
+
+    #[test]
+    fn kahako() {
+        let mut dec = EventDecoder::new(Haw104Key, HandleControl::MapLettersToUnicode);
+        let a = KeyEvent::new(KeyCode::A, KeyState::Down);
+        assert_eq!(
+            dec.process_keyevent(a.clone()),
+            Some(DecodedKey::Unicode('a'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(a.clone()),
+            Some(DecodedKey::Unicode('ā'))
+        );
+        // Shift gives upper case
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(a.clone()),
+            Some(DecodedKey::Unicode('Ā'))
+        );
+        // Caps Lock and Shift cancel out
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(a.clone()),
+            Some(DecodedKey::Unicode('ā'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::U, KeyState::Down)),
+            Some(DecodedKey::Unicode('Ū'))
+        );
+        // Without AltGr, we get plain letters, and control codes work
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Up));
+        assert_eq!(
+            dec.process_keyevent(a.clone()),
+            Some(DecodedKey::Unicode('A'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::LControl, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(a),
+            Some(DecodedKey::Unicode('\u{0001}'))
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! Armenian phonetic keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// An Armenian phonetic 102-key (or 105-key including Windows keys) keyboard.
+///
+/// Each letter key types the Armenian letter that sounds like it (`A` is
+/// `ա`, `B` is `բ`, and so on). The letters with no Latin equivalent are on
+/// the number row and the bracket keys, so AltGr on the number row gives
+/// the digits. The Armenian comma (`՝`), exclamation mark (`՜`), full stop
+/// (`։`) and question mark (`՞`) are on the punctuation keys.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct HyPhonetic105Key;
+
+impl KeyboardLayout for HyPhonetic105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        if handle_ctrl == HandleControl::MapLettersToUnicode && modifiers.is_ctrl() {
+            // Ctrl + a letter key still gives the control code on the keycap
+            let key = us.map_keycode(keycode, modifiers, handle_ctrl);
+            if matches!(key, DecodedKey::Unicode(ch) if ch.is_control()) {
+                return key;
+            }
+        }
+        match keycode {
+            KeyCode::Key1
+            | KeyCode::Key2
+            | KeyCode::Key3
+            | KeyCode::Key4
+            | KeyCode::Key5
+            | KeyCode::Key7
+            | KeyCode::Key8
+            | KeyCode::Key9
+            | KeyCode::Key0
+            | KeyCode::OemPlus
+                if modifiers.is_altgr() =>
+            {
+                // What's printed on a US keycap, for the digits
+                us.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            KeyCode::Oem8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('՜')
+                } else {
+                    DecodedKey::Unicode('՝')
+                }
+            }
+            KeyCode::Key1 => modifiers.handle_letter_auto('է', 'Է', handle_ctrl),
+            KeyCode::Key2 => modifiers.handle_letter_auto('թ', 'Թ', handle_ctrl),
+            KeyCode::Key3 => modifiers.handle_letter_auto('փ', 'Փ', handle_ctrl),
+            KeyCode::Key4 => modifiers.handle_letter_auto('ձ', 'Ձ', handle_ctrl),
+            KeyCode::Key5 => modifiers.handle_letter_auto('ջ', 'Ջ', handle_ctrl),
+            // This ligature has no capital
+            KeyCode::Key7 => DecodedKey::Unicode('և'),
+            KeyCode::Key8 => modifiers.handle_letter_auto('ռ', 'Ռ', handle_ctrl),
+            KeyCode::Key9 => modifiers.handle_letter_auto('չ', 'Չ', handle_ctrl),
+            KeyCode::Key0 => modifiers.handle_letter_auto('ճ', 'Ճ', handle_ctrl),
+            KeyCode::OemPlus => modifiers.handle_letter_auto('ժ', 'Ժ', handle_ctrl),
+            KeyCode::Q => modifiers.handle_letter_auto('ք', 'Ք', handle_ctrl),
+            KeyCode::W => modifiers.handle_letter_auto('ո', 'Ո', handle_ctrl),
+            KeyCode::E => modifiers.handle_letter_auto('ե', 'Ե', handle_ctrl),
+            KeyCode::R => modifiers.handle_letter_auto('ր', 'Ր', handle_ctrl),
+            KeyCode::T => modifiers.handle_letter_auto('տ', 'Տ', handle_ctrl),
+            KeyCode::Y => modifiers.handle_letter_auto('ը', 'Ը', handle_ctrl),
+            KeyCode::U => modifiers.handle_letter_auto('ւ', 'Ւ', handle_ctrl),
+            KeyCode::I => modifiers.handle_letter_auto('ի', 'Ի', handle_ctrl),
+            KeyCode::O => modifiers.handle_letter_auto('օ', 'Օ', handle_ctrl),
+            KeyCode::P => modifiers.handle_letter_auto('պ', 'Պ', handle_ctrl),
+            KeyCode::Oem4 => modifiers.handle_letter_auto('խ', 'Խ', handle_ctrl),
+            KeyCode::Oem6 => modifiers.handle_letter_auto('ծ', 'Ծ', handle_ctrl),
+            KeyCode::A => modifiers.handle_letter_auto('ա', 'Ա', handle_ctrl),
+            KeyCode::S => modifiers.handle_letter_auto('ս', 'Ս', handle_ctrl),
+            KeyCode::D => modifiers.handle_letter_auto('դ', 'Դ', handle_ctrl),
+            KeyCode::F => modifiers.handle_letter_auto('ֆ', 'Ֆ', handle_ctrl),
+            KeyCode::G => modifiers.handle_letter_auto('գ', 'Գ', handle_ctrl),
+            KeyCode::H => modifiers.handle_letter_auto('հ', 'Հ', handle_ctrl),
+            KeyCode::J => modifiers.handle_letter_auto('յ', 'Յ', handle_ctrl),
+            KeyCode::K => modifiers.handle_letter_auto('կ', 'Կ', handle_ctrl),
+            KeyCode::L => modifiers.handle_letter_auto('լ', 'Լ', handle_ctrl),
+            KeyCode::Oem1 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode('։')
+                }
+            }
+            KeyCode::Oem7 => modifiers.handle_letter_auto('շ', 'Շ', handle_ctrl),
+            KeyCode::Z => modifiers.handle_letter_auto('զ', 'Զ', handle_ctrl),
+            KeyCode::X => modifiers.handle_letter_auto('ղ', 'Ղ', handle_ctrl),
+            KeyCode::C => modifiers.handle_letter_auto('ց', 'Ց', handle_ctrl),
+            KeyCode::V => modifiers.handle_letter_auto('վ', 'Վ', handle_ctrl),
+            KeyCode::B => modifiers.handle_letter_auto('բ', 'Բ', handle_ctrl),
+            KeyCode::N => modifiers.handle_letter_auto('ն', 'Ն', handle_ctrl),
+            KeyCode::M => modifiers.handle_letter_auto('մ', 'Մ', handle_ctrl),
+            KeyCode::Oem2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('՞')
+                } else {
+                    DecodedKey::Unicode('/')
+                }
+            }
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Armenian phonetic, as on https://kbdlayout.info/kbdarmph
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '՝', '՜', None, None),
+            (KeyCode::Key1, 'է', 'Է', None, None),
+            (KeyCode::Key2, 'թ', 'Թ', None, None),
+            (KeyCode::Key3, 'փ', 'Փ', None, None),
+            (KeyCode::Key4, 'ձ', 'Ձ', None, None),
+            (KeyCode::Key5, 'ջ', 'Ջ', None, None),
+            (KeyCode::Key7, 'և', 'և', None, None),
+            (KeyCode::Key8, 'ռ', 'Ռ', None, None),
+            (KeyCode::Key9, 'չ', 'Չ', None, None),
+            (KeyCode::Key0, 'ճ', 'Ճ', None, None),
+            (KeyCode::OemPlus, 'ժ', 'Ժ', None, None),
+            (KeyCode::Q, 'ք', 'Ք', None, None),
+            (KeyCode::W, 'ո', 'Ո', None, None),
+            (KeyCode::E, 'ե', 'Ե', None, None),
+            (KeyCode::R, 'ր', 'Ր', None, None),
+            (KeyCode::T, 'տ', 'Տ', None, None),
+            (KeyCode::Y, 'ը', 'Ը', None, None),
+            (KeyCode::U, 'ւ', 'Ւ', None, None),
+            (KeyCode::I, 'ի', 'Ի', None, None),
+            (KeyCode::O, 'օ', 'Օ', None, None),
+            (KeyCode::P, 'պ', 'Պ', None, None),
+            (KeyCode::Oem4, 'խ', 'Խ', None, None),
+            (KeyCode::Oem6, 'ծ', 'Ծ', None, None),
+            (KeyCode::A, 'ա', 'Ա', None, None),
+            (KeyCode::S, 'ս', 'Ս', None, None),
+            (KeyCode::D, 'դ', 'Դ', None, None),
+            (KeyCode::F, 'ֆ', 'Ֆ', None, None),
+            (KeyCode::G, 'գ', 'Գ', None, None),
+            (KeyCode::H, 'հ', 'Հ', None, None),
+            (KeyCode::J, 'յ', 'Յ', None, None),
+            (KeyCode::K, 'կ', 'Կ', None, None),
+            (KeyCode::L, 'լ', 'Լ', None, None),
+            (KeyCode::Oem1, '։', ':', None, None),
+            (KeyCode::Oem7, 'շ', 'Շ', None, None),
+            (KeyCode::Z, 'զ', 'Զ', None, None),
+            (KeyCode::X, 'ղ', 'Ղ', None, None),
+            (KeyCode::C, 'ց', 'Ց', None, None),
+            (KeyCode::V, 'վ', 'Վ', None, None),
+            (KeyCode::B, 'բ', 'Բ', None, None),
+            (KeyCode::N, 'ն', 'Ն', None, None),
+            (KeyCode::M, 'մ', 'Մ', None, None),
+            (KeyCode::Oem2, '/', '՞', None, None),
+            (KeyCode::Key6, '6', '^', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&HyPhonetic105Key, ROWS);
+    }
+
+    #[test]
+    fn test_case_pairs() {
+        let mut dec = EventDecoder::new(HyPhonetic105Key, HandleControl::Ignore);
+        for (code, lower, upper) in [
+            (KeyCode::A, 'ա', 'Ա'),
+            (KeyCode::B, 'բ', 'Բ'),
+            (KeyCode::Key1, 'է', 'Է'),
+            (KeyCode::Oem4, 'խ', 'Խ'),
+            (KeyCode::W, 'ո', 'Ո'),
+            (KeyCode::O, 'օ', 'Օ'),
+            (KeyCode::F, 'ֆ', 'Ֆ'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(lower))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(upper))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+            dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(upper))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        }
+    }
+
+    #[test]
+    fn test_digits_on_altgr() {
+        let mut dec = EventDecoder::new(HyPhonetic105Key, HandleControl::Ignore);
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Key1, KeyState::Down)),
+            Some(DecodedKey::Unicode('1'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Key0, KeyState::Down)),
+            Some(DecodedKey::Unicode('0'))
+        );
+    }
+}
@@ -0,0 +1,398 @@
+//! Devanagari InScript keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Devanagari InScript 102-key (or 105-key including Windows keys)
+/// keyboard, as used for Hindi, Marathi and Nepali.
+///
+/// The vowels are under the left hand and the consonants under the right.
+/// A vowel key types the dependent vowel sign (matra), and with Shift the
+/// independent vowel. Devanagari has no upper and lower case, so Caps Lock
+/// does nothing to these keys.
+///
+/// The InScript conjuncts on Shift + 3 to Shift + 8 (like `क्ष`) take more
+/// than one character, so those keys type what's printed on a US keycap. To
+/// get Devanagari digits, use
+/// [`LayoutOptions::digits`](super::LayoutOptions::digits).
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct InScript105Key;
+
+impl KeyboardLayout for InScript105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        if handle_ctrl == HandleControl::MapLettersToUnicode && modifiers.is_ctrl() {
+            // Ctrl + a letter key still gives the control code on the keycap
+            let key = us.map_keycode(keycode, modifiers, handle_ctrl);
+            if matches!(key, DecodedKey::Unicode(ch) if ch.is_control()) {
+                return key;
+            }
+        }
+        match keycode {
+            KeyCode::Oem8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ऒ')
+                } else {
+                    DecodedKey::Unicode('\u{094A}')
+                }
+            }
+            KeyCode::Key1 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ऍ')
+                } else {
+                    DecodedKey::Unicode('1')
+                }
+            }
+            KeyCode::Key2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0945}')
+                } else {
+                    DecodedKey::Unicode('2')
+                }
+            }
+            KeyCode::OemMinus => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0903}')
+                } else {
+                    DecodedKey::Unicode('-')
+                }
+            }
+            KeyCode::OemPlus => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ऋ')
+                } else {
+                    DecodedKey::Unicode('\u{0943}')
+                }
+            }
+            KeyCode::Q => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('औ')
+                } else {
+                    DecodedKey::Unicode('\u{094C}')
+                }
+            }
+            KeyCode::W => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ऐ')
+                } else {
+                    DecodedKey::Unicode('\u{0948}')
+                }
+            }
+            KeyCode::E => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('आ')
+                } else {
+                    DecodedKey::Unicode('\u{093E}')
+                }
+            }
+            KeyCode::R => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ई')
+                } else {
+                    DecodedKey::Unicode('\u{0940}')
+                }
+            }
+            KeyCode::T => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ऊ')
+                } else {
+                    DecodedKey::Unicode('\u{0942}')
+                }
+            }
+            KeyCode::Y => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('भ')
+                } else {
+                    DecodedKey::Unicode('ब')
+                }
+            }
+            KeyCode::U => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ङ')
+                } else {
+                    DecodedKey::Unicode('ह')
+                }
+            }
+            KeyCode::I => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('घ')
+                } else {
+                    DecodedKey::Unicode('ग')
+                }
+            }
+            KeyCode::O => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ध')
+                } else {
+                    DecodedKey::Unicode('द')
+                }
+            }
+            KeyCode::P => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('झ')
+                } else {
+                    DecodedKey::Unicode('ज')
+                }
+            }
+            KeyCode::Oem4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ढ')
+                } else {
+                    DecodedKey::Unicode('ड')
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ञ')
+                } else {
+                    DecodedKey::Unicode('\u{093C}')
+                }
+            }
+            KeyCode::A => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ओ')
+                } else {
+                    DecodedKey::Unicode('\u{094B}')
+                }
+            }
+            KeyCode::S => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ए')
+                } else {
+                    DecodedKey::Unicode('\u{0947}')
+                }
+            }
+            KeyCode::D => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('अ')
+                } else {
+                    DecodedKey::Unicode('\u{094D}')
+                }
+            }
+            KeyCode::F => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('इ')
+                } else {
+                    DecodedKey::Unicode('\u{093F}')
+                }
+            }
+            KeyCode::G => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('उ')
+                } else {
+                    DecodedKey::Unicode('\u{0941}')
+                }
+            }
+            KeyCode::H => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('फ')
+                } else {
+                    DecodedKey::Unicode('प')
+                }
+            }
+            KeyCode::J => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ऱ')
+                } else {
+                    DecodedKey::Unicode('र')
+                }
+            }
+            KeyCode::K => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ख')
+                } else {
+                    DecodedKey::Unicode('क')
+                }
+            }
+            KeyCode::L => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('थ')
+                } else {
+                    DecodedKey::Unicode('त')
+                }
+            }
+            KeyCode::Oem1 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('छ')
+                } else {
+                    DecodedKey::Unicode('च')
+                }
+            }
+            KeyCode::Oem3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ठ')
+                } else {
+                    DecodedKey::Unicode('ट')
+                }
+            }
+            KeyCode::Oem7 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ऑ')
+                } else {
+                    DecodedKey::Unicode('\u{0949}')
+                }
+            }
+            KeyCode::Z => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ऎ')
+                } else {
+                    DecodedKey::Unicode('\u{0946}')
+                }
+            }
+            KeyCode::X => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0901}')
+                } else {
+                    DecodedKey::Unicode('\u{0902}')
+                }
+            }
+            KeyCode::C => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ण')
+                } else {
+                    DecodedKey::Unicode('म')
+                }
+            }
+            KeyCode::V => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ऩ')
+                } else {
+                    DecodedKey::Unicode('न')
+                }
+            }
+            KeyCode::B => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ऴ')
+                } else {
+                    DecodedKey::Unicode('व')
+                }
+            }
+            KeyCode::N => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ळ')
+                } else {
+                    DecodedKey::Unicode('ल')
+                }
+            }
+            KeyCode::M => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('श')
+                } else {
+                    DecodedKey::Unicode('स')
+                }
+            }
+            KeyCode::OemComma => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ष')
+                } else {
+                    DecodedKey::Unicode(',')
+                }
+            }
+            KeyCode::OemPeriod => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('।')
+                } else {
+                    DecodedKey::Unicode('.')
+                }
+            }
+            KeyCode::Oem2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('य़')
+                } else {
+                    DecodedKey::Unicode('य')
+                }
+            }
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdinhin
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '\u{094A}', 'ऒ', None, None),
+            (KeyCode::Key1, '1', 'ऍ', None, None),
+            (KeyCode::Key2, '2', '\u{0945}', None, None),
+            (KeyCode::OemMinus, '-', '\u{0903}', None, None),
+            (KeyCode::OemPlus, '\u{0943}', 'ऋ', None, None),
+            (KeyCode::Q, '\u{094C}', 'औ', None, None),
+            (KeyCode::W, '\u{0948}', 'ऐ', None, None),
+            (KeyCode::E, '\u{093E}', 'आ', None, None),
+            (KeyCode::R, '\u{0940}', 'ई', None, None),
+            (KeyCode::T, '\u{0942}', 'ऊ', None, None),
+            (KeyCode::Y, 'ब', 'भ', None, None),
+            (KeyCode::U, 'ह', 'ङ', None, None),
+            (KeyCode::I, 'ग', 'घ', None, None),
+            (KeyCode::O, 'द', 'ध', None, None),
+            (KeyCode::P, 'ज', 'झ', None, None),
+            (KeyCode::Oem4, 'ड', 'ढ', None, None),
+            (KeyCode::Oem6, '\u{093C}', 'ञ', None, None),
+            (KeyCode::A, '\u{094B}', 'ओ', None, None),
+            (KeyCode::S, '\u{0947}', 'ए', None, None),
+            (KeyCode::D, '\u{094D}', 'अ', None, None),
+            (KeyCode::F, '\u{093F}', 'इ', None, None),
+            (KeyCode::G, '\u{0941}', 'उ', None, None),
+            (KeyCode::H, 'प', 'फ', None, None),
+            (KeyCode::J, 'र', 'ऱ', None, None),
+            (KeyCode::K, 'क', 'ख', None, None),
+            (KeyCode::L, 'त', 'थ', None, None),
+            (KeyCode::Oem1, 'च', 'छ', None, None),
+            (KeyCode::Oem3, 'ट', 'ठ', None, None),
+            (KeyCode::Oem7, '\u{0949}', 'ऑ', None, None),
+            (KeyCode::Z, '\u{0946}', 'ऎ', None, None),
+            (KeyCode::X, '\u{0902}', '\u{0901}', None, None),
+            (KeyCode::C, 'म', 'ण', None, None),
+            (KeyCode::V, 'न', 'ऩ', None, None),
+            (KeyCode::B, 'व', 'ऴ', None, None),
+            (KeyCode::N, 'ल', 'ळ', None, None),
+            (KeyCode::M, 'स', 'श', None, None),
+            (KeyCode::OemComma, ',', 'ष', None, None),
+            (KeyCode::OemPeriod, '.', '।', None, None),
+            (KeyCode::Oem2, 'य', 'य़', None, None),
+            (KeyCode::Key5, '5', '%', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&InScript105Key, ROWS);
+    }
+
+    #[test]
+    fn test_matras_and_vowels() {
+        let mut dec = EventDecoder::new(InScript105Key, HandleControl::Ignore);
+        // Caps Lock changes nothing
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        for (code, matra, vowel) in [
+            (KeyCode::E, '\u{093E}', 'आ'),
+            (KeyCode::R, '\u{0940}', 'ई'),
+            (KeyCode::F, '\u{093F}', 'इ'),
+            (KeyCode::G, '\u{0941}', 'उ'),
+            (KeyCode::T, '\u{0942}', 'ऊ'),
+            (KeyCode::S, '\u{0947}', 'ए'),
+            (KeyCode::W, '\u{0948}', 'ऐ'),
+            (KeyCode::A, '\u{094B}', 'ओ'),
+            (KeyCode::Q, '\u{094C}', 'औ'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(matra))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(vowel))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! International Phonetic Alphabet keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A US 101-key (or 104-key including Windows keys) keyboard, with the
+/// symbols of the International Phonetic Alphabet on AltGr.
+///
+/// The letters are where they are on a US keyboard. AltGr gives the IPA
+/// symbol that sounds most like the key (e.g. AltGr + `E` is `ə`), and
+/// Shift + AltGr a related one (e.g. `ɛ`). Caps Lock has no effect on the
+/// IPA symbols. The length mark `ː` is on AltGr + `;`, and the stress marks
+/// `ˈ` and `ˌ` are on AltGr + `'`.
+///
+/// Has a 1-row high Enter key, with Oem7 above (ANSI layout).
+pub struct Ipa104Key;
+
+/// The IPA symbols: AltGr, then Shift + AltGr.
+const SYMBOLS: &[(KeyCode, char, char)] = &[
+    (KeyCode::Q, 'ʔ', 'ʕ'),
+    (KeyCode::E, 'ə', 'ɛ'),
+    (KeyCode::R, 'ɾ', 'ʁ'),
+    (KeyCode::T, 'θ', 'ʈ'),
+    (KeyCode::Y, 'ʏ', 'ʎ'),
+    (KeyCode::U, 'ʊ', 'ʉ'),
+    (KeyCode::I, 'ɪ', 'ɨ'),
+    (KeyCode::O, 'ɔ', 'ɒ'),
+    (KeyCode::A, 'æ', 'ɑ'),
+    (KeyCode::S, 'ʃ', 'ʂ'),
+    (KeyCode::D, 'ð', 'ɖ'),
+    (KeyCode::G, 'ɣ', 'ɡ'),
+    (KeyCode::H, 'ɦ', 'ħ'),
+    (KeyCode::L, 'ɬ', 'ɮ'),
+    (KeyCode::Oem1, 'ː', 'ˑ'),
+    (KeyCode::Oem3, 'ˈ', 'ˌ'),
+    (KeyCode::Z, 'ʒ', 'ʐ'),
+    (KeyCode::X, 'χ', 'ɸ'),
+    (KeyCode::C, 'ç', 'ɕ'),
+    (KeyCode::V, 'ʌ', 'ʋ'),
+    (KeyCode::N, 'ŋ', 'ɲ'),
+];
+
+impl KeyboardLayout for Ipa104Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        if modifiers.is_altgr() {
+            if let Some((_, symbol, shifted)) = SYMBOLS.iter().find(|(code, _, _)| *code == keycode)
+            {
+                if modifiers.is_shifted() {
+                    return DecodedKey::Unicode(*shifted);
+                } else {
+                    return DecodedKey::Unicode(*symbol);
+                }
+            }
+        }
+        us.map_keycode(keycode, modifiers, handle_ctrl)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+
+    #[test]
+    fn test_fixture() {
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Key1, '1', '!', None, None),
+            (KeyCode::Q, 'q', 'Q', Some('ʔ'), Some('ʕ')),
+            (KeyCode::E, 'e', 'E', Some('ə'), Some('ɛ')),
+            (KeyCode::R, 'r', 'R', Some('ɾ'), Some('ʁ')),
+            (KeyCode::T, 't', 'T', Some('θ'), Some('ʈ')),
+            (KeyCode::O, 'o', 'O', Some('ɔ'), Some('ɒ')),
+            (KeyCode::S, 's', 'S', Some('ʃ'), Some('ʂ')),
+            (KeyCode::D, 'd', 'D', Some('ð'), Some('ɖ')),
+            (KeyCode::Oem1, ';', ':', Some('ː'), Some('ˑ')),
+            (KeyCode::Oem3, '\'', '"', Some('ˈ'), Some('ˌ')),
+            (KeyCode::Z, 'z', 'Z', Some('ʒ'), Some('ʐ')),
+            (KeyCode::N, 'n', 'N', Some('ŋ'), Some('ɲ')),
+            (KeyCode::M, 'm', 'M', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&Ipa104Key, ROWS);
+    }
+
+    #[test]
+    fn test_altgr_symbols() {
+        let expected = [
+            (KeyCode::E, 'ə'),
+            (KeyCode::E, 'ɛ'),
+            (KeyCode::O, 'ɔ'),
+            (KeyCode::S, 'ʃ'),
+            (KeyCode::Z, 'ʒ'),
+            (KeyCode::N, 'ŋ'),
+            (KeyCode::T, 'θ'),
+            (KeyCode::D, 'ð'),
+            (KeyCode::R, 'ɾ'),
+            (KeyCode::Q, 'ʔ'),
+            (KeyCode::Oem1, 'ː'),
+            (KeyCode::Oem3, 'ˈ'),
+            (KeyCode::Oem3, 'ˌ'),
+        ];
+        for (code, symbol) in expected {
+            let shifted = SYMBOLS
+                .iter()
+                .any(|(c, _, shifted)| *c == code && *shifted == symbol);
+            // Caps Lock makes no difference
+            for capslock in [false, true] {
+                let modifiers = Modifiers {
+                    lshift: shifted,
+                    ralt: true,
+                    capslock,
+                    ..Default::default()
+                };
+                assert_eq!(
+                    Ipa104Key.map_keycode(code, &modifiers, HandleControl::Ignore),
+                    DecodedKey::Unicode(symbol),
+                    "{:?} capslock={}",
+                    code,
+                    capslock
+                );
+            }
+        }
+    }
+}
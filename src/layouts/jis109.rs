@@ -8,8 +8,70 @@ use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
 ///
 /// We used <https://www.win.tue.nl/~aeb/linux/kbd/scancodes-8.html> as a
 /// reference.
+///
+/// When [`Modifiers::kana`] is set (the `EventDecoder` toggles it with the
+/// Hiragana/Katakana key), the keys type hiragana following the JIS X 6002
+/// kana layout. Shift gives the small kana and the bracket punctuation.
 pub struct Jis109Key;
 
+impl Jis109Key {
+    /// Look up the kana engraved on a key, if it has one.
+    fn map_kana(keycode: KeyCode, shifted: bool) -> Option<char> {
+        let (base, shift) = match keycode {
+            KeyCode::Key1 => ('ぬ', 'ぬ'),
+            KeyCode::Key2 => ('ふ', 'ふ'),
+            KeyCode::Key3 => ('あ', 'ぁ'),
+            KeyCode::Key4 => ('う', 'ぅ'),
+            KeyCode::Key5 => ('え', 'ぇ'),
+            KeyCode::Key6 => ('お', 'ぉ'),
+            KeyCode::Key7 => ('や', 'ゃ'),
+            KeyCode::Key8 => ('ゆ', 'ゅ'),
+            KeyCode::Key9 => ('よ', 'ょ'),
+            KeyCode::Key0 => ('わ', 'を'),
+            KeyCode::OemMinus => ('ほ', 'ほ'),
+            KeyCode::OemPlus => ('へ', 'へ'),
+            KeyCode::Oem13 => ('ー', 'ー'),
+            KeyCode::Q => ('た', 'た'),
+            KeyCode::W => ('て', 'て'),
+            KeyCode::E => ('い', 'ぃ'),
+            KeyCode::R => ('す', 'す'),
+            KeyCode::T => ('か', 'か'),
+            KeyCode::Y => ('ん', 'ん'),
+            KeyCode::U => ('な', 'な'),
+            KeyCode::I => ('に', 'に'),
+            KeyCode::O => ('ら', 'ら'),
+            KeyCode::P => ('せ', 'せ'),
+            KeyCode::Oem4 => ('゛', '゛'),
+            KeyCode::Oem6 => ('゜', '「'),
+            KeyCode::A => ('ち', 'ち'),
+            KeyCode::S => ('と', 'と'),
+            KeyCode::D => ('し', 'し'),
+            KeyCode::F => ('は', 'は'),
+            KeyCode::G => ('き', 'き'),
+            KeyCode::H => ('く', 'く'),
+            KeyCode::J => ('ま', 'ま'),
+            KeyCode::K => ('の', 'の'),
+            KeyCode::L => ('り', 'り'),
+            KeyCode::Oem1 => ('れ', 'れ'),
+            KeyCode::Oem3 => ('け', 'け'),
+            KeyCode::Oem7 => ('む', '」'),
+            KeyCode::Z => ('つ', 'っ'),
+            KeyCode::X => ('さ', 'さ'),
+            KeyCode::C => ('そ', 'そ'),
+            KeyCode::V => ('ひ', 'ひ'),
+            KeyCode::B => ('こ', 'こ'),
+            KeyCode::N => ('み', 'み'),
+            KeyCode::M => ('も', 'も'),
+            KeyCode::OemComma => ('ね', '、'),
+            KeyCode::OemPeriod => ('る', '。'),
+            KeyCode::Oem2 => ('め', '・'),
+            KeyCode::Oem12 => ('ろ', 'ろ'),
+            _ => return None,
+        };
+        Some(if shifted { shift } else { base })
+    }
+}
+
 impl KeyboardLayout for Jis109Key {
     fn map_keycode(
         &self,
@@ -17,6 +79,11 @@ impl KeyboardLayout for Jis109Key {
         modifiers: &Modifiers,
         handle_ctrl: HandleControl,
     ) -> DecodedKey {
+        if modifiers.kana && !modifiers.is_ctrl() && !modifiers.is_altgr() {
+            if let Some(c) = Self::map_kana(keycode, modifiers.is_shifted()) {
+                return DecodedKey::Unicode(c);
+            }
+        }
         match keycode {
             KeyCode::Oem8 => {
                 // hankaku/zenkaku/kanji
@@ -0,0 +1,451 @@
+//! Khmer keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Khmer (NiDA) 102-key (or 105-key including Windows keys) keyboard.
+///
+/// Khmer has no upper and lower case, so Caps Lock does nothing, and Shift
+/// gives the second consonant series, the subscript and the other vowel
+/// signs. Shift + Space types a zero-width space (U+200B), used to mark
+/// word breaks.
+///
+/// A few NiDA keys type two characters (like `ាំ` on Shift + A). A layout
+/// can only give one, so those keys type the same with or without Shift, and
+/// the `,` key types what's printed on a US keycap.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct Km105Key;
+
+impl KeyboardLayout for Km105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        if handle_ctrl == HandleControl::MapLettersToUnicode && modifiers.is_ctrl() {
+            // Ctrl + a letter key still gives the control code on the keycap
+            let key = us.map_keycode(keycode, modifiers, handle_ctrl);
+            if matches!(key, DecodedKey::Unicode(ch) if ch.is_control()) {
+                return key;
+            }
+        }
+        match keycode {
+            KeyCode::Oem8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('»')
+                } else {
+                    DecodedKey::Unicode('«')
+                }
+            }
+            KeyCode::Key1 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('!')
+                } else {
+                    DecodedKey::Unicode('១')
+                }
+            }
+            KeyCode::Key2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17D7}')
+                } else {
+                    DecodedKey::Unicode('២')
+                }
+            }
+            KeyCode::Key3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('"')
+                } else {
+                    DecodedKey::Unicode('៣')
+                }
+            }
+            KeyCode::Key4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('៛')
+                } else {
+                    DecodedKey::Unicode('៤')
+                }
+            }
+            KeyCode::Key5 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('%')
+                } else {
+                    DecodedKey::Unicode('៥')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17CD}')
+                } else {
+                    DecodedKey::Unicode('៦')
+                }
+            }
+            KeyCode::Key7 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17D0}')
+                } else {
+                    DecodedKey::Unicode('៧')
+                }
+            }
+            KeyCode::Key8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17CF}')
+                } else {
+                    DecodedKey::Unicode('៨')
+                }
+            }
+            KeyCode::Key9 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('(')
+                } else {
+                    DecodedKey::Unicode('៩')
+                }
+            }
+            KeyCode::Key0 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(')')
+                } else {
+                    DecodedKey::Unicode('០')
+                }
+            }
+            KeyCode::OemMinus => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17CC}')
+                } else {
+                    DecodedKey::Unicode('ឥ')
+                }
+            }
+            KeyCode::OemPlus => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('=')
+                } else {
+                    DecodedKey::Unicode('ឲ')
+                }
+            }
+            KeyCode::Q => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ឈ')
+                } else {
+                    DecodedKey::Unicode('ឆ')
+                }
+            }
+            KeyCode::W => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17BA}')
+                } else {
+                    DecodedKey::Unicode('\u{17B9}')
+                }
+            }
+            KeyCode::E => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17C2}')
+                } else {
+                    DecodedKey::Unicode('\u{17C1}')
+                }
+            }
+            KeyCode::R => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ឬ')
+                } else {
+                    DecodedKey::Unicode('រ')
+                }
+            }
+            KeyCode::T => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ទ')
+                } else {
+                    DecodedKey::Unicode('ត')
+                }
+            }
+            KeyCode::Y => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17BD}')
+                } else {
+                    DecodedKey::Unicode('យ')
+                }
+            }
+            KeyCode::U => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17BC}')
+                } else {
+                    DecodedKey::Unicode('\u{17BB}')
+                }
+            }
+            KeyCode::I => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17B8}')
+                } else {
+                    DecodedKey::Unicode('\u{17B7}')
+                }
+            }
+            KeyCode::O => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17C5}')
+                } else {
+                    DecodedKey::Unicode('\u{17C4}')
+                }
+            }
+            KeyCode::P => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ភ')
+                } else {
+                    DecodedKey::Unicode('ផ')
+                }
+            }
+            KeyCode::Oem4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17BF}')
+                } else {
+                    DecodedKey::Unicode('\u{17C0}')
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ឧ')
+                } else {
+                    DecodedKey::Unicode('ឪ')
+                }
+            }
+            KeyCode::Oem7 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ឭ')
+                } else {
+                    DecodedKey::Unicode('ឮ')
+                }
+            }
+            KeyCode::A => DecodedKey::Unicode('\u{17B6}'),
+            KeyCode::S => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17C3}')
+                } else {
+                    DecodedKey::Unicode('ស')
+                }
+            }
+            KeyCode::D => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ឌ')
+                } else {
+                    DecodedKey::Unicode('ដ')
+                }
+            }
+            KeyCode::F => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ធ')
+                } else {
+                    DecodedKey::Unicode('ថ')
+                }
+            }
+            KeyCode::G => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('អ')
+                } else {
+                    DecodedKey::Unicode('ង')
+                }
+            }
+            KeyCode::H => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17C7}')
+                } else {
+                    DecodedKey::Unicode('ហ')
+                }
+            }
+            KeyCode::J => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ញ')
+                } else {
+                    DecodedKey::Unicode('\u{17D2}')
+                }
+            }
+            KeyCode::K => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('គ')
+                } else {
+                    DecodedKey::Unicode('ក')
+                }
+            }
+            KeyCode::L => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ឡ')
+                } else {
+                    DecodedKey::Unicode('ល')
+                }
+            }
+            KeyCode::Oem1 => DecodedKey::Unicode('\u{17BE}'),
+            KeyCode::Oem3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17C9}')
+                } else {
+                    DecodedKey::Unicode('\u{17CB}')
+                }
+            }
+            KeyCode::Z => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ឍ')
+                } else {
+                    DecodedKey::Unicode('ឋ')
+                }
+            }
+            KeyCode::X => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ឃ')
+                } else {
+                    DecodedKey::Unicode('ខ')
+                }
+            }
+            KeyCode::C => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ជ')
+                } else {
+                    DecodedKey::Unicode('ច')
+                }
+            }
+            KeyCode::V => DecodedKey::Unicode('វ'),
+            KeyCode::B => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ព')
+                } else {
+                    DecodedKey::Unicode('ប')
+                }
+            }
+            KeyCode::N => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ណ')
+                } else {
+                    DecodedKey::Unicode('ន')
+                }
+            }
+            KeyCode::M => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{17C6}')
+                } else {
+                    DecodedKey::Unicode('ម')
+                }
+            }
+            KeyCode::OemPeriod => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('៕')
+                } else {
+                    DecodedKey::Unicode('។')
+                }
+            }
+            KeyCode::Oem2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('?')
+                } else {
+                    DecodedKey::Unicode('\u{17CA}')
+                }
+            }
+            KeyCode::Spacebar => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{200B}')
+                } else {
+                    DecodedKey::Unicode(' ')
+                }
+            }
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdkhmr
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '«', '»', None, None),
+            (KeyCode::Key1, '១', '!', None, None),
+            (KeyCode::Key2, '២', '\u{17D7}', None, None),
+            (KeyCode::Key3, '៣', '"', None, None),
+            (KeyCode::Key4, '៤', '៛', None, None),
+            (KeyCode::Key5, '៥', '%', None, None),
+            (KeyCode::Key6, '៦', '\u{17CD}', None, None),
+            (KeyCode::Key7, '៧', '\u{17D0}', None, None),
+            (KeyCode::Key8, '៨', '\u{17CF}', None, None),
+            (KeyCode::Key9, '៩', '(', None, None),
+            (KeyCode::Key0, '០', ')', None, None),
+            (KeyCode::OemMinus, 'ឥ', '\u{17CC}', None, None),
+            (KeyCode::OemPlus, 'ឲ', '=', None, None),
+            (KeyCode::Q, 'ឆ', 'ឈ', None, None),
+            (KeyCode::W, '\u{17B9}', '\u{17BA}', None, None),
+            (KeyCode::E, '\u{17C1}', '\u{17C2}', None, None),
+            (KeyCode::R, 'រ', 'ឬ', None, None),
+            (KeyCode::T, 'ត', 'ទ', None, None),
+            (KeyCode::Y, 'យ', '\u{17BD}', None, None),
+            (KeyCode::U, '\u{17BB}', '\u{17BC}', None, None),
+            (KeyCode::I, '\u{17B7}', '\u{17B8}', None, None),
+            (KeyCode::O, '\u{17C4}', '\u{17C5}', None, None),
+            (KeyCode::P, 'ផ', 'ភ', None, None),
+            (KeyCode::Oem4, '\u{17C0}', '\u{17BF}', None, None),
+            (KeyCode::Oem6, 'ឪ', 'ឧ', None, None),
+            (KeyCode::Oem7, 'ឮ', 'ឭ', None, None),
+            (KeyCode::A, '\u{17B6}', '\u{17B6}', None, None),
+            (KeyCode::S, 'ស', '\u{17C3}', None, None),
+            (KeyCode::D, 'ដ', 'ឌ', None, None),
+            (KeyCode::F, 'ថ', 'ធ', None, None),
+            (KeyCode::G, 'ង', 'អ', None, None),
+            (KeyCode::H, 'ហ', '\u{17C7}', None, None),
+            (KeyCode::J, '\u{17D2}', 'ញ', None, None),
+            (KeyCode::K, 'ក', 'គ', None, None),
+            (KeyCode::L, 'ល', 'ឡ', None, None),
+            (KeyCode::Oem1, '\u{17BE}', '\u{17BE}', None, None),
+            (KeyCode::Oem3, '\u{17CB}', '\u{17C9}', None, None),
+            (KeyCode::Z, 'ឋ', 'ឍ', None, None),
+            (KeyCode::X, 'ខ', 'ឃ', None, None),
+            (KeyCode::C, 'ច', 'ជ', None, None),
+            (KeyCode::V, 'វ', 'វ', None, None),
+            (KeyCode::B, 'ប', 'ព', None, None),
+            (KeyCode::N, 'ន', 'ណ', None, None),
+            (KeyCode::M, 'ម', '\u{17C6}', None, None),
+            (KeyCode::OemPeriod, '។', '៕', None, None),
+            (KeyCode::Oem2, '\u{17CA}', '?', None, None),
+            (KeyCode::Spacebar, ' ', '\u{200B}', None, None),
+            (KeyCode::OemComma, ',', '<', None, None),
+        ];
+        fixture::check(&Km105Key, ROWS);
+    }
+
+    #[test]
+    fn test_two_levels() {
+        let mut dec = EventDecoder::new(Km105Key, HandleControl::Ignore);
+        // Caps Lock does nothing
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        for (code, base, shifted) in [
+            (KeyCode::K, 'ក', 'គ'),
+            (KeyCode::T, 'ត', 'ទ'),
+            (KeyCode::E, '\u{17C1}', '\u{17C2}'),
+            (KeyCode::I, '\u{17B7}', '\u{17B8}'),
+            (KeyCode::Key1, '១', '!'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(base))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(shifted))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        }
+    }
+
+    #[test]
+    fn test_shift_space() {
+        let mut dec = EventDecoder::new(Km105Key, HandleControl::Ignore);
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Spacebar, KeyState::Down)),
+            Some(DecodedKey::Unicode(' '))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Spacebar, KeyState::Down)),
+            Some(DecodedKey::Unicode('\u{200B}'))
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Korean keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Korean 103-key (or 106-key including Windows keys) keyboard.
+///
+/// This is a US layout, with the extra 한/영 ([`KeyCode::Hangul`]) and 한자
+/// ([`KeyCode::Hanja`]) keys. This crate doesn't do Hangul input, so those
+/// two keys come out as [`DecodedKey::RawKey`] for an input method to deal
+/// with.
+///
+/// Has a 1-row high Enter key, with Oem7 above the Enter key (ANSI format).
+pub struct Kr104Key;
+
+impl KeyboardLayout for Kr104Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        match keycode {
+            KeyCode::Hangul => DecodedKey::RawKey(KeyCode::Hangul),
+            KeyCode::Hanja => DecodedKey::RawKey(KeyCode::Hanja),
+            e => {
+                let us = super::Us104Key;
+                us.map_keycode(e, modifiers, handle_ctrl)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{DecodedItem, KeyEvent, KeyState, Keyboard, ScancodeSet1, ScancodeSet2};
+
+    #[test]
+    fn test_set1() {
+        let mut k = Keyboard::new(ScancodeSet1::new(), Kr104Key, HandleControl::Ignore);
+        for (byte, code) in [(0xF2, KeyCode::Hangul), (0xF1, KeyCode::Hanja)] {
+            let item = k.add_byte(byte).unwrap().unwrap();
+            assert_eq!(
+                item,
+                DecodedItem::Key(KeyEvent::new(code, KeyState::SingleShot))
+            );
+            assert_eq!(k.process_item(item), Some(DecodedKey::RawKey(code)));
+        }
+        // Everything else is US
+        let item = k.add_byte(0x1E).unwrap().unwrap();
+        assert_eq!(k.process_item(item), Some(DecodedKey::Unicode('a')));
+    }
+
+    #[test]
+    fn test_set2() {
+        let mut k = Keyboard::new(ScancodeSet2::new(), Kr104Key, HandleControl::Ignore);
+        let item = k.add_byte(0xF2).unwrap().unwrap();
+        assert_eq!(
+            k.process_item(item),
+            Some(DecodedKey::RawKey(KeyCode::Hangul))
+        );
+        // There's no break code, so a following key isn't affected
+        let item = k.add_byte(0x1C).unwrap().unwrap();
+        assert_eq!(k.process_item(item), Some(DecodedKey::Unicode('a')));
+    }
+}
@@ -0,0 +1,231 @@
+//! Kyrgyz keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A standard Kyrgyz 102-key (or 105-key including Windows keys) keyboard.
+///
+/// The letter keys type Russian Cyrillic in the `ЙЦУКЕН` arrangement. AltGr
+/// on the `Н`, `О` and `У` keys gives the Kyrgyz letters `ң`, `ө` and `ү`.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct Ky105Key;
+
+impl KeyboardLayout for Ky105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        if handle_ctrl == HandleControl::MapLettersToUnicode && modifiers.is_ctrl() {
+            // Ctrl + a letter key still gives the control code on the keycap
+            let key = us.map_keycode(keycode, modifiers, handle_ctrl);
+            if matches!(key, DecodedKey::Unicode(ch) if ch.is_control()) {
+                return key;
+            }
+        }
+        match keycode {
+            KeyCode::E => {
+                if modifiers.is_altgr() {
+                    modifiers.handle_letter_auto('ү', 'Ү', HandleControl::Ignore)
+                } else {
+                    modifiers.handle_letter_auto('у', 'У', handle_ctrl)
+                }
+            }
+            KeyCode::Y => {
+                if modifiers.is_altgr() {
+                    modifiers.handle_letter_auto('ң', 'Ң', HandleControl::Ignore)
+                } else {
+                    modifiers.handle_letter_auto('н', 'Н', handle_ctrl)
+                }
+            }
+            KeyCode::J => {
+                if modifiers.is_altgr() {
+                    modifiers.handle_letter_auto('ө', 'Ө', HandleControl::Ignore)
+                } else {
+                    modifiers.handle_letter_auto('о', 'О', handle_ctrl)
+                }
+            }
+            KeyCode::Key2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('"')
+                } else {
+                    DecodedKey::Unicode('2')
+                }
+            }
+            KeyCode::Key3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('№')
+                } else {
+                    DecodedKey::Unicode('3')
+                }
+            }
+            KeyCode::Key4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(';')
+                } else {
+                    DecodedKey::Unicode('4')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode('6')
+                }
+            }
+            KeyCode::Key7 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('?')
+                } else {
+                    DecodedKey::Unicode('7')
+                }
+            }
+            KeyCode::Oem8 => modifiers.handle_letter_auto('ё', 'Ё', handle_ctrl),
+            KeyCode::Q => modifiers.handle_letter_auto('й', 'Й', handle_ctrl),
+            KeyCode::W => modifiers.handle_letter_auto('ц', 'Ц', handle_ctrl),
+            KeyCode::R => modifiers.handle_letter_auto('к', 'К', handle_ctrl),
+            KeyCode::T => modifiers.handle_letter_auto('е', 'Е', handle_ctrl),
+            KeyCode::U => modifiers.handle_letter_auto('г', 'Г', handle_ctrl),
+            KeyCode::I => modifiers.handle_letter_auto('ш', 'Ш', handle_ctrl),
+            KeyCode::O => modifiers.handle_letter_auto('щ', 'Щ', handle_ctrl),
+            KeyCode::P => modifiers.handle_letter_auto('з', 'З', handle_ctrl),
+            KeyCode::Oem4 => modifiers.handle_letter_auto('х', 'Х', handle_ctrl),
+            KeyCode::Oem6 => modifiers.handle_letter_auto('ъ', 'Ъ', handle_ctrl),
+            KeyCode::A => modifiers.handle_letter_auto('ф', 'Ф', handle_ctrl),
+            KeyCode::S => modifiers.handle_letter_auto('ы', 'Ы', handle_ctrl),
+            KeyCode::D => modifiers.handle_letter_auto('в', 'В', handle_ctrl),
+            KeyCode::F => modifiers.handle_letter_auto('а', 'А', handle_ctrl),
+            KeyCode::G => modifiers.handle_letter_auto('п', 'П', handle_ctrl),
+            KeyCode::H => modifiers.handle_letter_auto('р', 'Р', handle_ctrl),
+            KeyCode::K => modifiers.handle_letter_auto('л', 'Л', handle_ctrl),
+            KeyCode::L => modifiers.handle_letter_auto('д', 'Д', handle_ctrl),
+            KeyCode::Oem1 => modifiers.handle_letter_auto('ж', 'Ж', handle_ctrl),
+            KeyCode::Oem3 => modifiers.handle_letter_auto('э', 'Э', handle_ctrl),
+            KeyCode::Z => modifiers.handle_letter_auto('я', 'Я', handle_ctrl),
+            KeyCode::X => modifiers.handle_letter_auto('ч', 'Ч', handle_ctrl),
+            KeyCode::C => modifiers.handle_letter_auto('с', 'С', handle_ctrl),
+            KeyCode::V => modifiers.handle_letter_auto('м', 'М', handle_ctrl),
+            KeyCode::B => modifiers.handle_letter_auto('и', 'И', handle_ctrl),
+            KeyCode::N => modifiers.handle_letter_auto('т', 'Т', handle_ctrl),
+            KeyCode::M => modifiers.handle_letter_auto('ь', 'Ь', handle_ctrl),
+            KeyCode::OemComma => modifiers.handle_letter_auto('б', 'Б', handle_ctrl),
+            KeyCode::OemPeriod => modifiers.handle_letter_auto('ю', 'Ю', handle_ctrl),
+            KeyCode::Oem2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(',')
+                } else {
+                    DecodedKey::Unicode('.')
+                }
+            }
+            KeyCode::Oem7 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('/')
+                } else {
+                    DecodedKey::Unicode('\\')
+                }
+            }
+            KeyCode::Oem5 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('/')
+                } else {
+                    DecodedKey::Unicode('\\')
+                }
+            }
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Based on https://kbdlayout.info/kbdkyr
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Key2, '2', '"', None, None),
+            (KeyCode::Key3, '3', '№', None, None),
+            (KeyCode::Key4, '4', ';', None, None),
+            (KeyCode::Key6, '6', ':', None, None),
+            (KeyCode::Key7, '7', '?', None, None),
+            (KeyCode::Oem8, 'ё', 'Ё', None, None),
+            (KeyCode::Q, 'й', 'Й', None, None),
+            (KeyCode::W, 'ц', 'Ц', None, None),
+            (KeyCode::R, 'к', 'К', None, None),
+            (KeyCode::T, 'е', 'Е', None, None),
+            (KeyCode::U, 'г', 'Г', None, None),
+            (KeyCode::I, 'ш', 'Ш', None, None),
+            (KeyCode::O, 'щ', 'Щ', None, None),
+            (KeyCode::P, 'з', 'З', None, None),
+            (KeyCode::Oem4, 'х', 'Х', None, None),
+            (KeyCode::Oem6, 'ъ', 'Ъ', None, None),
+            (KeyCode::A, 'ф', 'Ф', None, None),
+            (KeyCode::S, 'ы', 'Ы', None, None),
+            (KeyCode::D, 'в', 'В', None, None),
+            (KeyCode::F, 'а', 'А', None, None),
+            (KeyCode::G, 'п', 'П', None, None),
+            (KeyCode::H, 'р', 'Р', None, None),
+            (KeyCode::K, 'л', 'Л', None, None),
+            (KeyCode::L, 'д', 'Д', None, None),
+            (KeyCode::Oem1, 'ж', 'Ж', None, None),
+            (KeyCode::Oem3, 'э', 'Э', None, None),
+            (KeyCode::Z, 'я', 'Я', None, None),
+            (KeyCode::X, 'ч', 'Ч', None, None),
+            (KeyCode::C, 'с', 'С', None, None),
+            (KeyCode::V, 'м', 'М', None, None),
+            (KeyCode::B, 'и', 'И', None, None),
+            (KeyCode::N, 'т', 'Т', None, None),
+            (KeyCode::M, 'ь', 'Ь', None, None),
+            (KeyCode::OemComma, 'б', 'Б', None, None),
+            (KeyCode::OemPeriod, 'ю', 'Ю', None, None),
+            (KeyCode::Oem2, '.', ',', None, None),
+            (KeyCode::Oem7, '\\', '/', None, None),
+            (KeyCode::Oem5, '\\', '/', None, None),
+            (KeyCode::E, 'у', 'У', Some('ү'), Some('Ү')),
+            (KeyCode::Y, 'н', 'Н', Some('ң'), Some('Ң')),
+            (KeyCode::J, 'о', 'О', Some('ө'), Some('Ө')),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&Ky105Key, ROWS);
+    }
+
+    #[test]
+    fn test_kyrgyz_letters() {
+        let mut dec = EventDecoder::new(Ky105Key, HandleControl::MapLettersToUnicode);
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        for (code, lower, upper) in [
+            (KeyCode::Y, 'ң', 'Ң'),
+            (KeyCode::J, 'ө', 'Ө'),
+            (KeyCode::E, 'ү', 'Ү'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(lower))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(upper))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        }
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Up));
+        // Without AltGr, the same keys are the Russian letters
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Y, KeyState::Down)),
+            Some(DecodedKey::Unicode('н'))
+        );
+        // Caps Lock gives the capitals
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::J, KeyState::Down)),
+            Some(DecodedKey::Unicode('Ө'))
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! Latin American Spanish keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Latin American Spanish 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// This is not the same as the layout used in Spain. The `´`, `¨`, `^` and
+/// `` ` `` keys are dead keys.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct La105Key;
+
+impl KeyboardLayout for La105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        match keycode {
+            KeyCode::Oem8 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¬')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('°')
+                } else {
+                    DecodedKey::Unicode('|')
+                }
+            }
+            KeyCode::Key2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('"')
+                } else {
+                    DecodedKey::Unicode('2')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('&')
+                } else {
+                    DecodedKey::Unicode('6')
+                }
+            }
+            KeyCode::Key7 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('/')
+                } else {
+                    DecodedKey::Unicode('7')
+                }
+            }
+            KeyCode::Key8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('(')
+                } else {
+                    DecodedKey::Unicode('8')
+                }
+            }
+            KeyCode::Key9 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(')')
+                } else {
+                    DecodedKey::Unicode('9')
+                }
+            }
+            KeyCode::Key0 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('=')
+                } else {
+                    DecodedKey::Unicode('0')
+                }
+            }
+            KeyCode::OemMinus => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('\\')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('?')
+                } else {
+                    DecodedKey::Unicode('\'')
+                }
+            }
+            KeyCode::OemPlus => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('¡')
+                } else {
+                    DecodedKey::Unicode('¿')
+                }
+            }
+            KeyCode::Q => modifiers.handle_ascii_4(ascii_upper!('Q'), '@', '@', handle_ctrl),
+            KeyCode::Oem4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('¨')
+                } else {
+                    DecodedKey::DeadKey('´')
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('~')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('*')
+                } else {
+                    DecodedKey::Unicode('+')
+                }
+            }
+            KeyCode::Oem1 => modifiers.handle_letter_auto('ñ', 'Ñ', handle_ctrl),
+            KeyCode::Oem3 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('^')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('[')
+                } else {
+                    DecodedKey::Unicode('{')
+                }
+            }
+            KeyCode::Oem7 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('`')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode(']')
+                } else {
+                    DecodedKey::Unicode('}')
+                }
+            }
+            KeyCode::Oem5 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('>')
+                } else {
+                    DecodedKey::Unicode('<')
+                }
+            }
+            KeyCode::OemComma => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(';')
+                } else {
+                    DecodedKey::Unicode(',')
+                }
+            }
+            KeyCode::OemPeriod => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode('.')
+                }
+            }
+            KeyCode::Oem2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('_')
+                } else {
+                    DecodedKey::Unicode('-')
+                }
+            }
+            e => {
+                let us = super::Us104Key;
+                us.map_keycode(e, modifiers, handle_ctrl)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdla
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '|', '°', Some('¬'), None),
+            (KeyCode::Key1, '1', '!', None, None),
+            (KeyCode::Key2, '2', '"', None, None),
+            (KeyCode::Key3, '3', '#', None, None),
+            (KeyCode::Key4, '4', '$', None, None),
+            (KeyCode::Key5, '5', '%', None, None),
+            (KeyCode::Key6, '6', '&', None, None),
+            (KeyCode::Key7, '7', '/', None, None),
+            (KeyCode::Key8, '8', '(', None, None),
+            (KeyCode::Key9, '9', ')', None, None),
+            (KeyCode::Key0, '0', '=', None, None),
+            (KeyCode::OemMinus, '\'', '?', Some('\\'), None),
+            (KeyCode::OemPlus, '¿', '¡', None, None),
+            (KeyCode::Q, 'q', 'Q', Some('@'), None),
+            (KeyCode::Oem4, '´', '¨', None, None),
+            (KeyCode::Oem6, '+', '*', Some('~'), None),
+            (KeyCode::Oem1, 'ñ', 'Ñ', None, None),
+            (KeyCode::Oem3, '{', '[', Some('^'), None),
+            (KeyCode::Oem7, '}', ']', Some('`'), None),
+            (KeyCode::Oem5, '<', '>', None, None),
+            (KeyCode::OemComma, ',', ';', None, None),
+            (KeyCode::OemPeriod, '.', ':', None, None),
+            (KeyCode::Oem2, '-', '_', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&La105Key, ROWS);
+    }
+
+    #[test]
+    fn test_dead_keys() {
+        let mut dec = EventDecoder::new(La105Key, HandleControl::Ignore);
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem4, KeyState::Down)),
+            Some(DecodedKey::DeadKey('´'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::A, KeyState::Down)),
+            Some(DecodedKey::Unicode('á'))
+        );
+        // AltGr + { gives a dead ^
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem3, KeyState::Down)),
+            Some(DecodedKey::DeadKey('^'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Up));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::E, KeyState::Down)),
+            Some(DecodedKey::Unicode('ê'))
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! Luxembourgish keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A standard Luxembourgish 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// Luxembourg uses the Swiss French layout, so this types exactly what
+/// [`SwissFrench105Key`](super::SwissFrench105Key) does. It has its own name
+/// so it can be picked by locale.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct Lb105Key;
+
+impl KeyboardLayout for Lb105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let fallback = super::SwissFrench105Key;
+        fallback.map_keycode(keycode, modifiers, handle_ctrl)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::SwissFrench105Key;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_accents_and_brackets() {
+        let mut dec = EventDecoder::new(Lb105Key, HandleControl::Ignore);
+        for (code, ch) in [
+            (KeyCode::Oem1, 'é'),
+            (KeyCode::Oem4, 'è'),
+            (KeyCode::Oem3, 'à'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(ch))
+            );
+        }
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        for (code, ch) in [
+            (KeyCode::Oem4, '['),
+            (KeyCode::Oem6, ']'),
+            (KeyCode::Oem3, '{'),
+            (KeyCode::Oem7, '}'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(ch))
+            );
+        }
+    }
+
+    #[test]
+    fn test_same_as_swiss_french() {
+        // Any difference from the Swiss French layout should be a deliberate
+        // change to this test
+        for keycode in KeyCode::ALL {
+            for bits in 0..16u8 {
+                let modifiers = Modifiers {
+                    lshift: bits & 1 != 0,
+                    ralt: bits & 2 != 0,
+                    capslock: bits & 4 != 0,
+                    lctrl: bits & 8 != 0,
+                    ..Default::default()
+                };
+                for handle_ctrl in [HandleControl::Ignore, HandleControl::MapLettersToUnicode] {
+                    assert_eq!(
+                        Lb105Key.map_keycode(keycode, &modifiers, handle_ctrl),
+                        SwissFrench105Key.map_keycode(keycode, &modifiers, handle_ctrl),
+                        "{:?} {:?}",
+                        keycode,
+                        modifiers
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,313 @@
+//! Lao keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Lao 102-key (or 105-key including Windows keys) keyboard.
+///
+/// The number row types Lao letters, with the Lao digits on Shift. Lao has
+/// no upper and lower case, so Caps Lock does nothing. Shift on the top row
+/// of letters gives the rarer letters and marks (like `ໜ`, `ຣ` and `ໆ`),
+/// and on the other letter keys types the same as without it.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct Lo105Key;
+
+impl KeyboardLayout for Lo105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        if handle_ctrl == HandleControl::MapLettersToUnicode && modifiers.is_ctrl() {
+            // Ctrl + a letter key still gives the control code on the keycap
+            let key = us.map_keycode(keycode, modifiers, handle_ctrl);
+            if matches!(key, DecodedKey::Unicode(ch) if ch.is_control()) {
+                return key;
+            }
+        }
+        match keycode {
+            KeyCode::Key1 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('໑')
+                } else {
+                    DecodedKey::Unicode('ຢ')
+                }
+            }
+            KeyCode::Key2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('໒')
+                } else {
+                    DecodedKey::Unicode('ຟ')
+                }
+            }
+            KeyCode::Key3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('໓')
+                } else {
+                    DecodedKey::Unicode('ໂ')
+                }
+            }
+            KeyCode::Key4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('໔')
+                } else {
+                    DecodedKey::Unicode('ຖ')
+                }
+            }
+            KeyCode::Key5 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('໕')
+                } else {
+                    DecodedKey::Unicode('\u{0EB8}')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('໖')
+                } else {
+                    DecodedKey::Unicode('\u{0EB9}')
+                }
+            }
+            KeyCode::Key7 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('໗')
+                } else {
+                    DecodedKey::Unicode('ຄ')
+                }
+            }
+            KeyCode::Key8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('໘')
+                } else {
+                    DecodedKey::Unicode('ຕ')
+                }
+            }
+            KeyCode::Key9 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('໙')
+                } else {
+                    DecodedKey::Unicode('ຈ')
+                }
+            }
+            KeyCode::Key0 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('໐')
+                } else {
+                    DecodedKey::Unicode('ຂ')
+                }
+            }
+            KeyCode::OemMinus => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('-')
+                } else {
+                    DecodedKey::Unicode('ຊ')
+                }
+            }
+            KeyCode::OemPlus => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('=')
+                } else {
+                    DecodedKey::Unicode('\u{0ECD}')
+                }
+            }
+            KeyCode::Q => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ໜ')
+                } else {
+                    DecodedKey::Unicode('\u{0EBB}')
+                }
+            }
+            KeyCode::W => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ໝ')
+                } else {
+                    DecodedKey::Unicode('ໄ')
+                }
+            }
+            KeyCode::E => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ຯ')
+                } else {
+                    DecodedKey::Unicode('ຳ')
+                }
+            }
+            KeyCode::R => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ຣ')
+                } else {
+                    DecodedKey::Unicode('ພ')
+                }
+            }
+            KeyCode::T => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ຽ')
+                } else {
+                    DecodedKey::Unicode('ະ')
+                }
+            }
+            KeyCode::Y => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0ECC}')
+                } else {
+                    DecodedKey::Unicode('\u{0EB4}')
+                }
+            }
+            KeyCode::U => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0ECD}')
+                } else {
+                    DecodedKey::Unicode('\u{0EB5}')
+                }
+            }
+            KeyCode::I => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0EC6}')
+                } else {
+                    DecodedKey::Unicode('ຮ')
+                }
+            }
+            KeyCode::O => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0EBC}')
+                } else {
+                    DecodedKey::Unicode('ນ')
+                }
+            }
+            KeyCode::P => DecodedKey::Unicode('ຍ'),
+            KeyCode::Oem4 => DecodedKey::Unicode('ບ'),
+            KeyCode::Oem6 => DecodedKey::Unicode('ລ'),
+            KeyCode::A => DecodedKey::Unicode('\u{0EB1}'),
+            KeyCode::S => DecodedKey::Unicode('ຫ'),
+            KeyCode::D => DecodedKey::Unicode('ກ'),
+            KeyCode::F => DecodedKey::Unicode('ດ'),
+            KeyCode::G => DecodedKey::Unicode('ເ'),
+            KeyCode::H => DecodedKey::Unicode('\u{0EC9}'),
+            KeyCode::J => DecodedKey::Unicode('\u{0EC8}'),
+            KeyCode::K => DecodedKey::Unicode('າ'),
+            KeyCode::L => DecodedKey::Unicode('ສ'),
+            KeyCode::Oem1 => DecodedKey::Unicode('ວ'),
+            KeyCode::Oem3 => DecodedKey::Unicode('ງ'),
+            KeyCode::Z => DecodedKey::Unicode('ຜ'),
+            KeyCode::X => DecodedKey::Unicode('ປ'),
+            KeyCode::C => DecodedKey::Unicode('ແ'),
+            KeyCode::V => DecodedKey::Unicode('ອ'),
+            KeyCode::B => DecodedKey::Unicode('\u{0EB6}'),
+            KeyCode::N => DecodedKey::Unicode('\u{0EB7}'),
+            KeyCode::M => DecodedKey::Unicode('ທ'),
+            KeyCode::OemComma => DecodedKey::Unicode('ມ'),
+            KeyCode::OemPeriod => DecodedKey::Unicode('ໃ'),
+            KeyCode::Oem2 => DecodedKey::Unicode('ຝ'),
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Based on https://kbdlayout.info/kbdlao
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Key1, 'ຢ', '໑', None, None),
+            (KeyCode::Key2, 'ຟ', '໒', None, None),
+            (KeyCode::Key3, 'ໂ', '໓', None, None),
+            (KeyCode::Key4, 'ຖ', '໔', None, None),
+            (KeyCode::Key5, '\u{0EB8}', '໕', None, None),
+            (KeyCode::Key6, '\u{0EB9}', '໖', None, None),
+            (KeyCode::Key7, 'ຄ', '໗', None, None),
+            (KeyCode::Key8, 'ຕ', '໘', None, None),
+            (KeyCode::Key9, 'ຈ', '໙', None, None),
+            (KeyCode::Key0, 'ຂ', '໐', None, None),
+            (KeyCode::OemMinus, 'ຊ', '-', None, None),
+            (KeyCode::OemPlus, '\u{0ECD}', '=', None, None),
+            (KeyCode::Q, '\u{0EBB}', 'ໜ', None, None),
+            (KeyCode::W, 'ໄ', 'ໝ', None, None),
+            (KeyCode::E, 'ຳ', 'ຯ', None, None),
+            (KeyCode::R, 'ພ', 'ຣ', None, None),
+            (KeyCode::T, 'ະ', 'ຽ', None, None),
+            (KeyCode::Y, '\u{0EB4}', '\u{0ECC}', None, None),
+            (KeyCode::U, '\u{0EB5}', '\u{0ECD}', None, None),
+            (KeyCode::I, 'ຮ', '\u{0EC6}', None, None),
+            (KeyCode::O, 'ນ', '\u{0EBC}', None, None),
+            (KeyCode::P, 'ຍ', 'ຍ', None, None),
+            (KeyCode::Oem4, 'ບ', 'ບ', None, None),
+            (KeyCode::Oem6, 'ລ', 'ລ', None, None),
+            (KeyCode::A, '\u{0EB1}', '\u{0EB1}', None, None),
+            (KeyCode::S, 'ຫ', 'ຫ', None, None),
+            (KeyCode::D, 'ກ', 'ກ', None, None),
+            (KeyCode::F, 'ດ', 'ດ', None, None),
+            (KeyCode::G, 'ເ', 'ເ', None, None),
+            (KeyCode::H, '\u{0EC9}', '\u{0EC9}', None, None),
+            (KeyCode::J, '\u{0EC8}', '\u{0EC8}', None, None),
+            (KeyCode::K, 'າ', 'າ', None, None),
+            (KeyCode::L, 'ສ', 'ສ', None, None),
+            (KeyCode::Oem1, 'ວ', 'ວ', None, None),
+            (KeyCode::Oem3, 'ງ', 'ງ', None, None),
+            (KeyCode::Z, 'ຜ', 'ຜ', None, None),
+            (KeyCode::X, 'ປ', 'ປ', None, None),
+            (KeyCode::C, 'ແ', 'ແ', None, None),
+            (KeyCode::V, 'ອ', 'ອ', None, None),
+            (KeyCode::B, '\u{0EB6}', '\u{0EB6}', None, None),
+            (KeyCode::N, '\u{0EB7}', '\u{0EB7}', None, None),
+            (KeyCode::M, 'ທ', 'ທ', None, None),
+            (KeyCode::OemComma, 'ມ', 'ມ', None, None),
+            (KeyCode::OemPeriod, 'ໃ', 'ໃ', None, None),
+            (KeyCode::Oem2, 'ຝ', 'ຝ', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&Lo105Key, ROWS);
+    }
+
+    #[test]
+    fn test_number_row() {
+        let mut dec = EventDecoder::new(Lo105Key, HandleControl::Ignore);
+        for (code, base, digit) in [
+            (KeyCode::Key1, 'ຢ', '໑'),
+            (KeyCode::Key4, 'ຖ', '໔'),
+            (KeyCode::Key7, 'ຄ', '໗'),
+            (KeyCode::Key0, 'ຂ', '໐'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(base))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(digit))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        }
+    }
+
+    #[test]
+    fn test_letters() {
+        let mut dec = EventDecoder::new(Lo105Key, HandleControl::Ignore);
+        // Caps Lock does nothing
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        for (code, base, shifted) in [
+            (KeyCode::D, 'ກ', 'ກ'),
+            (KeyCode::R, 'ພ', 'ຣ'),
+            (KeyCode::Q, '\u{0EBB}', 'ໜ'),
+            (KeyCode::I, 'ຮ', 'ໆ'),
+            (KeyCode::K, 'າ', 'າ'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(base))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(shifted))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        }
+    }
+}
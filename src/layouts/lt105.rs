@@ -0,0 +1,111 @@
+//! Lithuanian keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A standard Lithuanian 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// The number row types the Lithuanian letters `ą č ę ė į š ų ū`, with `ž`
+/// on the `=` key. To get the digits (and the US symbols on Shift) hold
+/// AltGr.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct Lt105Key;
+
+impl KeyboardLayout for Lt105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let fallback = super::Us104Key;
+        match keycode {
+            KeyCode::Key1
+            | KeyCode::Key2
+            | KeyCode::Key3
+            | KeyCode::Key4
+            | KeyCode::Key5
+            | KeyCode::Key6
+            | KeyCode::Key7
+            | KeyCode::Key8
+            | KeyCode::OemPlus
+                if modifiers.is_altgr() =>
+            {
+                // What's printed on a US keycap
+                fallback.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            KeyCode::Key1 => modifiers.handle_letter_auto('ą', 'Ą', handle_ctrl),
+            KeyCode::Key2 => modifiers.handle_letter_auto('č', 'Č', handle_ctrl),
+            KeyCode::Key3 => modifiers.handle_letter_auto('ę', 'Ę', handle_ctrl),
+            KeyCode::Key4 => modifiers.handle_letter_auto('ė', 'Ė', handle_ctrl),
+            KeyCode::Key5 => modifiers.handle_letter_auto('į', 'Į', handle_ctrl),
+            KeyCode::Key6 => modifiers.handle_letter_auto('š', 'Š', handle_ctrl),
+            KeyCode::Key7 => modifiers.handle_letter_auto('ų', 'Ų', handle_ctrl),
+            KeyCode::Key8 => modifiers.handle_letter_auto('ū', 'Ū', handle_ctrl),
+            KeyCode::OemPlus => modifiers.handle_letter_auto('ž', 'Ž', handle_ctrl),
+            KeyCode::E => modifiers.handle_ascii_4(ascii_upper!('E'), '€', '€', handle_ctrl),
+            e => fallback.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdlt1
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '`', '~', None, None),
+            (KeyCode::Key1, 'ą', 'Ą', Some('1'), Some('!')),
+            (KeyCode::Key2, 'č', 'Č', Some('2'), Some('@')),
+            (KeyCode::Key3, 'ę', 'Ę', Some('3'), Some('#')),
+            (KeyCode::Key4, 'ė', 'Ė', Some('4'), Some('$')),
+            (KeyCode::Key5, 'į', 'Į', Some('5'), Some('%')),
+            (KeyCode::Key6, 'š', 'Š', Some('6'), Some('^')),
+            (KeyCode::Key7, 'ų', 'Ų', Some('7'), Some('&')),
+            (KeyCode::Key8, 'ū', 'Ū', Some('8'), Some('*')),
+            (KeyCode::Key9, '9', '(', None, None),
+            (KeyCode::Key0, '0', ')', None, None),
+            (KeyCode::OemMinus, '-', '_', None, None),
+            (KeyCode::OemPlus, 'ž', 'Ž', Some('='), Some('+')),
+            (KeyCode::E, 'e', 'E', Some('€'), None),
+            (KeyCode::Oem5, '\\', '|', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&Lt105Key, ROWS);
+    }
+
+    #[test]
+    fn test_number_row() {
+        let plain = Modifiers::default();
+        let altgr = Modifiers {
+            ralt: true,
+            ..Default::default()
+        };
+        let caps = Modifiers {
+            capslock: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            Lt105Key.map_keycode(KeyCode::Key1, &plain, HandleControl::Ignore),
+            DecodedKey::Unicode('ą')
+        );
+        assert_eq!(
+            Lt105Key.map_keycode(KeyCode::Key1, &altgr, HandleControl::Ignore),
+            DecodedKey::Unicode('1')
+        );
+        // They're letters, so Caps Lock works on them
+        assert_eq!(
+            Lt105Key.map_keycode(KeyCode::Key1, &caps, HandleControl::Ignore),
+            DecodedKey::Unicode('Ą')
+        );
+        assert_eq!(
+            Lt105Key.map_keycode(KeyCode::Key9, &plain, HandleControl::Ignore),
+            DecodedKey::Unicode('9')
+        );
+    }
+}
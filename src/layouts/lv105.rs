@@ -0,0 +1,121 @@
+//! Latvian keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Latvian (QWERTY) 102-key (or 105-key including Windows keys) keyboard.
+///
+/// This is a US layout, except that AltGr on the letters gives the Latvian
+/// letters with a macron, cedilla or caron. AltGr + `'` is a dead `´`, and
+/// Shift + AltGr + `'` is a dead `¨`.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct Lv105Key;
+
+impl KeyboardLayout for Lv105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let fallback = super::Us104Key;
+        match keycode {
+            KeyCode::Key4 if modifiers.is_altgr() => DecodedKey::Unicode('€'),
+            KeyCode::E => modifiers.handle_ascii_4(ascii_upper!('E'), 'ē', 'Ē', handle_ctrl),
+            KeyCode::R => modifiers.handle_ascii_4(ascii_upper!('R'), 'ŗ', 'Ŗ', handle_ctrl),
+            KeyCode::U => modifiers.handle_ascii_4(ascii_upper!('U'), 'ū', 'Ū', handle_ctrl),
+            KeyCode::I => modifiers.handle_ascii_4(ascii_upper!('I'), 'ī', 'Ī', handle_ctrl),
+            KeyCode::O => modifiers.handle_ascii_4(ascii_upper!('O'), 'õ', 'Õ', handle_ctrl),
+            KeyCode::A => modifiers.handle_ascii_4(ascii_upper!('A'), 'ā', 'Ā', handle_ctrl),
+            KeyCode::S => modifiers.handle_ascii_4(ascii_upper!('S'), 'š', 'Š', handle_ctrl),
+            KeyCode::G => modifiers.handle_ascii_4(ascii_upper!('G'), 'ģ', 'Ģ', handle_ctrl),
+            KeyCode::K => modifiers.handle_ascii_4(ascii_upper!('K'), 'ķ', 'Ķ', handle_ctrl),
+            KeyCode::L => modifiers.handle_ascii_4(ascii_upper!('L'), 'ļ', 'Ļ', handle_ctrl),
+            KeyCode::Z => modifiers.handle_ascii_4(ascii_upper!('Z'), 'ž', 'Ž', handle_ctrl),
+            KeyCode::C => modifiers.handle_ascii_4(ascii_upper!('C'), 'č', 'Č', handle_ctrl),
+            KeyCode::N => modifiers.handle_ascii_4(ascii_upper!('N'), 'ņ', 'Ņ', handle_ctrl),
+            KeyCode::Oem3 if modifiers.is_altgr() => {
+                if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('¨')
+                } else {
+                    DecodedKey::DeadKey('´')
+                }
+            }
+            e => fallback.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdlv1
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '`', '~', None, None),
+            (KeyCode::Key4, '4', '$', Some('€'), None),
+            (KeyCode::E, 'e', 'E', Some('ē'), Some('Ē')),
+            (KeyCode::R, 'r', 'R', Some('ŗ'), Some('Ŗ')),
+            (KeyCode::U, 'u', 'U', Some('ū'), Some('Ū')),
+            (KeyCode::I, 'i', 'I', Some('ī'), Some('Ī')),
+            (KeyCode::O, 'o', 'O', Some('õ'), Some('Õ')),
+            (KeyCode::A, 'a', 'A', Some('ā'), Some('Ā')),
+            (KeyCode::S, 's', 'S', Some('š'), Some('Š')),
+            (KeyCode::G, 'g', 'G', Some('ģ'), Some('Ģ')),
+            (KeyCode::K, 'k', 'K', Some('ķ'), Some('Ķ')),
+            (KeyCode::L, 'l', 'L', Some('ļ'), Some('Ļ')),
+            (KeyCode::Oem3, '\'', '"', Some('´'), Some('¨')),
+            (KeyCode::Z, 'z', 'Z', Some('ž'), Some('Ž')),
+            (KeyCode::C, 'c', 'C', Some('č'), Some('Č')),
+            (KeyCode::N, 'n', 'N', Some('ņ'), Some('Ņ')),
+            (KeyCode::Oem5, '\\', '|', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&Lv105Key, ROWS);
+    }
+
+    #[test]
+    fn test_altgr_letters() {
+        let mut dec = EventDecoder::new(Lv105Key, HandleControl::MapLettersToUnicode);
+        let letters = [
+            (KeyCode::A, 'ā', 'Ā'),
+            (KeyCode::E, 'ē', 'Ē'),
+            (KeyCode::I, 'ī', 'Ī'),
+            (KeyCode::U, 'ū', 'Ū'),
+            (KeyCode::K, 'ķ', 'Ķ'),
+            (KeyCode::L, 'ļ', 'Ļ'),
+            (KeyCode::N, 'ņ', 'Ņ'),
+            (KeyCode::G, 'ģ', 'Ģ'),
+            (KeyCode::C, 'č', 'Č'),
+            (KeyCode::S, 'š', 'Š'),
+            (KeyCode::Z, 'ž', 'Ž'),
+        ];
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        for (code, lower, upper) in letters {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(lower))
+            );
+            dec.process_keyevent(KeyEvent::new(code, KeyState::Up));
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(upper))
+            );
+            dec.process_keyevent(KeyEvent::new(code, KeyState::Up));
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        }
+        // AltGr wins over the Ctrl in Ctrl + Left Alt
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Up));
+        dec.process_keyevent(KeyEvent::new(KeyCode::LControl, KeyState::Down));
+        dec.process_keyevent(KeyEvent::new(KeyCode::LAlt, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::S, KeyState::Down)),
+            Some(DecodedKey::Unicode('š'))
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Māori (New Zealand) keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Māori 101-key (or 104-key including Windows keys) keyboard.
+///
+/// This is a US layout, except that AltGr on the vowels gives the vowels
+/// with a macron, as per the NZ Māori keyboard.
+///
+/// Has a 1-row high Enter key, with Oem7 above (ANSI layout).
+pub struct MiNz104Key;
+
+impl KeyboardLayout for MiNz104Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let fallback = super::Us104Key;
+        match keycode {
+            KeyCode::A => modifiers.handle_ascii_4(ascii_upper!('A'), 'ā', 'Ā', handle_ctrl),
+            KeyCode::E => modifiers.handle_ascii_4(ascii_upper!('E'), 'ē', 'Ē', handle_ctrl),
+            KeyCode::I => modifiers.handle_ascii_4(ascii_upper!('I'), 'ī', 'Ī', handle_ctrl),
+            KeyCode::O => modifiers.handle_ascii_4(ascii_upper!('O'), 'ō', 'Ō', handle_ctrl),
+            KeyCode::U => modifiers.handle_ascii_4(ascii_upper!('U'), 'ū', 'Ū', handle_ctrl),
+            e => fallback.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn macrons() {
+        let mut dec = EventDecoder::new(MiNz104Key, HandleControl::MapLettersToUnicode);
+        let o = KeyEvent::new(KeyCode::O, KeyState::Down);
+        assert_eq!(
+            dec.process_keyevent(o.clone()),
+            Some(DecodedKey::Unicode('o'))
+        );
+        // Ctrl + Left Alt works as AltGr
+        dec.process_keyevent(KeyEvent::new(KeyCode::LControl, KeyState::Down));
+        dec.process_keyevent(KeyEvent::new(KeyCode::LAlt, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(o.clone()),
+            Some(DecodedKey::Unicode('ō'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::LAlt, KeyState::Up));
+        dec.process_keyevent(KeyEvent::new(KeyCode::LControl, KeyState::Up));
+        // Caps Lock gives upper case
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(o.clone()),
+            Some(DecodedKey::Unicode('Ō'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::E, KeyState::Down)),
+            Some(DecodedKey::Unicode('Ē'))
+        );
+        // Consonants are unchanged
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::K, KeyState::Down)),
+            Some(DecodedKey::Unicode('K'))
+        );
+        // The quote key is still a quote
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem3, KeyState::Down)),
+            Some(DecodedKey::Unicode('\''))
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Macedonian keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A standard Macedonian 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// The letter keys type Macedonian Cyrillic, including `ѓ ќ ѕ џ љ њ ј` on
+/// their own keys. Shift + 2, 3, 4 and 6 give the Macedonian quotation marks
+/// (`„ “ ’ ‘`).
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct Mk105Key;
+
+impl KeyboardLayout for Mk105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        if handle_ctrl == HandleControl::MapLettersToUnicode && modifiers.is_ctrl() {
+            // Ctrl + a letter key still gives the control code on the keycap
+            let key = us.map_keycode(keycode, modifiers, handle_ctrl);
+            if matches!(key, DecodedKey::Unicode(ch) if ch.is_control()) {
+                return key;
+            }
+        }
+        match keycode {
+            KeyCode::Key2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('„')
+                } else {
+                    DecodedKey::Unicode('2')
+                }
+            }
+            KeyCode::Key3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('“')
+                } else {
+                    DecodedKey::Unicode('3')
+                }
+            }
+            KeyCode::Key4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('’')
+                } else {
+                    DecodedKey::Unicode('4')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('‘')
+                } else {
+                    DecodedKey::Unicode('6')
+                }
+            }
+            KeyCode::Q => modifiers.handle_letter_auto('љ', 'Љ', handle_ctrl),
+            KeyCode::W => modifiers.handle_letter_auto('њ', 'Њ', handle_ctrl),
+            KeyCode::E => modifiers.handle_letter_auto('е', 'Е', handle_ctrl),
+            KeyCode::R => modifiers.handle_letter_auto('р', 'Р', handle_ctrl),
+            KeyCode::T => modifiers.handle_letter_auto('т', 'Т', handle_ctrl),
+            KeyCode::Y => modifiers.handle_letter_auto('ѕ', 'Ѕ', handle_ctrl),
+            KeyCode::U => modifiers.handle_letter_auto('у', 'У', handle_ctrl),
+            KeyCode::I => modifiers.handle_letter_auto('и', 'И', handle_ctrl),
+            KeyCode::O => modifiers.handle_letter_auto('о', 'О', handle_ctrl),
+            KeyCode::P => modifiers.handle_letter_auto('п', 'П', handle_ctrl),
+            KeyCode::Oem4 => modifiers.handle_letter_auto('ш', 'Ш', handle_ctrl),
+            KeyCode::Oem6 => modifiers.handle_letter_auto('ѓ', 'Ѓ', handle_ctrl),
+            KeyCode::A => modifiers.handle_letter_auto('а', 'А', handle_ctrl),
+            KeyCode::S => modifiers.handle_letter_auto('с', 'С', handle_ctrl),
+            KeyCode::D => modifiers.handle_letter_auto('д', 'Д', handle_ctrl),
+            KeyCode::F => modifiers.handle_letter_auto('ф', 'Ф', handle_ctrl),
+            KeyCode::G => modifiers.handle_letter_auto('г', 'Г', handle_ctrl),
+            KeyCode::H => modifiers.handle_letter_auto('х', 'Х', handle_ctrl),
+            KeyCode::J => modifiers.handle_letter_auto('ј', 'Ј', handle_ctrl),
+            KeyCode::K => modifiers.handle_letter_auto('к', 'К', handle_ctrl),
+            KeyCode::L => modifiers.handle_letter_auto('л', 'Л', handle_ctrl),
+            KeyCode::Oem1 => modifiers.handle_letter_auto('ч', 'Ч', handle_ctrl),
+            KeyCode::Oem3 => modifiers.handle_letter_auto('ќ', 'Ќ', handle_ctrl),
+            KeyCode::Oem7 => modifiers.handle_letter_auto('ж', 'Ж', handle_ctrl),
+            KeyCode::Z => modifiers.handle_letter_auto('з', 'З', handle_ctrl),
+            KeyCode::X => modifiers.handle_letter_auto('џ', 'Џ', handle_ctrl),
+            KeyCode::C => modifiers.handle_letter_auto('ц', 'Ц', handle_ctrl),
+            KeyCode::V => modifiers.handle_letter_auto('в', 'В', handle_ctrl),
+            KeyCode::B => modifiers.handle_letter_auto('б', 'Б', handle_ctrl),
+            KeyCode::N => modifiers.handle_letter_auto('н', 'Н', handle_ctrl),
+            KeyCode::M => modifiers.handle_letter_auto('м', 'М', handle_ctrl),
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdmac
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Key2, '2', '„', None, None),
+            (KeyCode::Key3, '3', '“', None, None),
+            (KeyCode::Key4, '4', '’', None, None),
+            (KeyCode::Key6, '6', '‘', None, None),
+            (KeyCode::Q, 'љ', 'Љ', None, None),
+            (KeyCode::W, 'њ', 'Њ', None, None),
+            (KeyCode::E, 'е', 'Е', None, None),
+            (KeyCode::R, 'р', 'Р', None, None),
+            (KeyCode::T, 'т', 'Т', None, None),
+            (KeyCode::Y, 'ѕ', 'Ѕ', None, None),
+            (KeyCode::U, 'у', 'У', None, None),
+            (KeyCode::I, 'и', 'И', None, None),
+            (KeyCode::O, 'о', 'О', None, None),
+            (KeyCode::P, 'п', 'П', None, None),
+            (KeyCode::Oem4, 'ш', 'Ш', None, None),
+            (KeyCode::Oem6, 'ѓ', 'Ѓ', None, None),
+            (KeyCode::A, 'а', 'А', None, None),
+            (KeyCode::S, 'с', 'С', None, None),
+            (KeyCode::D, 'д', 'Д', None, None),
+            (KeyCode::F, 'ф', 'Ф', None, None),
+            (KeyCode::G, 'г', 'Г', None, None),
+            (KeyCode::H, 'х', 'Х', None, None),
+            (KeyCode::J, 'ј', 'Ј', None, None),
+            (KeyCode::K, 'к', 'К', None, None),
+            (KeyCode::L, 'л', 'Л', None, None),
+            (KeyCode::Oem1, 'ч', 'Ч', None, None),
+            (KeyCode::Oem3, 'ќ', 'Ќ', None, None),
+            (KeyCode::Oem7, 'ж', 'Ж', None, None),
+            (KeyCode::Z, 'з', 'З', None, None),
+            (KeyCode::X, 'џ', 'Џ', None, None),
+            (KeyCode::C, 'ц', 'Ц', None, None),
+            (KeyCode::V, 'в', 'В', None, None),
+            (KeyCode::B, 'б', 'Б', None, None),
+            (KeyCode::N, 'н', 'Н', None, None),
+            (KeyCode::M, 'м', 'М', None, None),
+            (KeyCode::Key1, '1', '!', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&Mk105Key, ROWS);
+    }
+
+    #[test]
+    fn test_macedonian_letters() {
+        let mut dec = EventDecoder::new(Mk105Key, HandleControl::MapLettersToUnicode);
+        let letters = [
+            (KeyCode::Oem6, 'ѓ', 'Ѓ'),
+            (KeyCode::Oem3, 'ќ', 'Ќ'),
+            (KeyCode::Y, 'ѕ', 'Ѕ'),
+            (KeyCode::X, 'џ', 'Џ'),
+            (KeyCode::Q, 'љ', 'Љ'),
+            (KeyCode::W, 'њ', 'Њ'),
+        ];
+        for (code, lower, upper) in letters {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(lower))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(upper))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        }
+        // Ctrl + the key marked C is still Ctrl + C
+        dec.process_keyevent(KeyEvent::new(KeyCode::LControl, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::C, KeyState::Down)),
+            Some(DecodedKey::Unicode('\u{0003}'))
+        );
+    }
+}
@@ -4,6 +4,27 @@
 //! handle all the 'different' keys first, and then jump to another handler -
 //! see [`Uk105Key`] and [`Us104Key`] as an example of that.
 
+/// Make an `AsciiUpper` letter, checking it at compile time.
+macro_rules! ascii_upper {
+    ($letter:literal) => {{
+        const LETTER: $crate::AsciiUpper = $crate::AsciiUpper::new($letter);
+        LETTER
+    }};
+}
+
+mod default;
+pub use self::default::{default_layout, DefaultLayout};
+
+mod charset;
+#[cfg(test)]
+mod fixture;
+#[cfg(feature = "alloc")]
+pub use self::charset::character_set;
+pub use self::charset::{events_for_char, find_char, find_chars, KeyEvents, ModifierCombo};
+
+mod options;
+pub use self::options::{ConfigurableLayout, Configured, Digits, LayoutError, LayoutOptions};
+
 mod dvorak_programmer104;
 pub use self::dvorak_programmer104::DVP104Key;
 
@@ -26,7 +47,7 @@ mod colemak;
 pub use self::colemak::Colemak;
 
 mod de105;
-pub use self::de105::De105Key;
+pub use self::de105::{De105Key, De105KeyLegacy};
 
 mod no105;
 pub use self::no105::No105Key;
@@ -34,6 +55,126 @@ pub use self::no105::No105Key;
 mod fi_se105;
 pub use self::fi_se105::FiSe105Key;
 
+mod haw104;
+pub use self::haw104::Haw104Key;
+
+mod mi_nz104;
+pub use self::mi_nz104::MiNz104Key;
+
+mod cy_uk105;
+pub use self::cy_uk105::CyUk105Key;
+
+mod se_sami105;
+pub use self::se_sami105::SeSami105Key;
+
+mod la105;
+pub use self::la105::La105Key;
+
+mod pt105;
+pub use self::pt105::Pt105Key;
+
+mod br_abnt2;
+pub use self::br_abnt2::BrAbnt2Key;
+
+mod sg105;
+pub use self::sg105::{Swiss105Key, SwissFrench105Key, SwissGerman105Key};
+
+mod dk105;
+pub use self::dk105::Dk105Key;
+
+mod tr_q105;
+pub use self::tr_q105::TrQ105Key;
+
+mod ar101;
+pub use self::ar101::Ar101Key;
+
+mod sr_latn105;
+pub use self::sr_latn105::SrLatn105Key;
+
+mod lv105;
+pub use self::lv105::Lv105Key;
+
+mod lt105;
+pub use self::lt105::Lt105Key;
+
+mod ca_multilingual105;
+pub use self::ca_multilingual105::CaMultilingual105Key;
+
+mod ca_fr105;
+pub use self::ca_fr105::CaFrench105Key;
+
+mod us_intl104;
+pub use self::us_intl104::UsInternational104Key;
+
+mod neo2;
+pub use self::neo2::Neo2;
+
+mod kr104;
+pub use self::kr104::Kr104Key;
+
+mod no_sami105;
+pub use self::no_sami105::NoSami105Key;
+
+mod sv_sami105;
+pub use self::sv_sami105::SvSami105Key;
+
+mod azerty_nf105;
+pub use self::azerty_nf105::AzertyNf105Key;
+
+mod svorak105;
+pub use self::svorak105::Svorak105Key;
+
+mod fa_ir105;
+pub use self::fa_ir105::FaIr105Key;
+
+mod inscript105;
+pub use self::inscript105::InScript105Key;
+
+mod gr_polytonic105;
+pub use self::gr_polytonic105::GrPolytonic105Key;
+
+mod mk105;
+pub use self::mk105::Mk105Key;
+
+mod az_latn105;
+pub use self::az_latn105::AzLatin105Key;
+
+mod hy_phonetic105;
+pub use self::hy_phonetic105::HyPhonetic105Key;
+
+mod km105;
+pub use self::km105::Km105Key;
+
+mod lo105;
+pub use self::lo105::Lo105Key;
+
+mod si_wijesekara105;
+pub use self::si_wijesekara105::SiWijesekara105Key;
+
+mod ta99;
+pub use self::ta99::Ta99Key;
+
+mod am_et105;
+pub use self::am_et105::AmEt105Key;
+
+mod tifinagh;
+pub use self::tifinagh::TifinaghKey;
+
+mod bs105;
+pub use self::bs105::Bs105Key;
+
+mod lb105;
+pub use self::lb105::Lb105Key;
+
+mod ky105;
+pub use self::ky105::Ky105Key;
+
+mod uz_latn105;
+pub use self::uz_latn105::UzLatin105Key;
+
+mod ipa104;
+pub use self::ipa104::Ipa104Key;
+
 /// A enum of all the supported keyboard layouts.
 pub enum AnyLayout {
     DVP104Key(DVP104Key),
@@ -44,8 +185,285 @@ pub enum AnyLayout {
     Azerty(Azerty),
     Colemak(Colemak),
     De105Key(De105Key),
+    De105KeyLegacy(De105KeyLegacy),
     No105Key(No105Key),
     FiSe105Key(FiSe105Key),
+    Haw104Key(Haw104Key),
+    MiNz104Key(MiNz104Key),
+    CyUk105Key(CyUk105Key),
+    SeSami105Key(SeSami105Key),
+    La105Key(La105Key),
+    Pt105Key(Pt105Key),
+    BrAbnt2Key(BrAbnt2Key),
+    SwissGerman105Key(SwissGerman105Key),
+    Dk105Key(Dk105Key),
+    TrQ105Key(TrQ105Key),
+    Ar101Key(Ar101Key),
+    SrLatn105Key(SrLatn105Key),
+    Lv105Key(Lv105Key),
+    Lt105Key(Lt105Key),
+    CaMultilingual105Key(CaMultilingual105Key),
+    CaFrench105Key(CaFrench105Key),
+    UsInternational104Key(UsInternational104Key),
+    Neo2(Neo2),
+    Kr104Key(Kr104Key),
+    NoSami105Key(NoSami105Key),
+    SvSami105Key(SvSami105Key),
+    AzertyNf105Key(AzertyNf105Key),
+    Svorak105Key(Svorak105Key),
+    FaIr105Key(FaIr105Key),
+    InScript105Key(InScript105Key),
+    GrPolytonic105Key(GrPolytonic105Key),
+    Mk105Key(Mk105Key),
+    AzLatin105Key(AzLatin105Key),
+    HyPhonetic105Key(HyPhonetic105Key),
+    Km105Key(Km105Key),
+    Lo105Key(Lo105Key),
+    SiWijesekara105Key(SiWijesekara105Key),
+    Ta99Key(Ta99Key),
+    AmEt105Key(AmEt105Key),
+    TifinaghKey(TifinaghKey),
+    Bs105Key(Bs105Key),
+    Lb105Key(Lb105Key),
+    Ky105Key(Ky105Key),
+    UzLatin105Key(UzLatin105Key),
+    Ipa104Key(Ipa104Key),
+    SwissFrench105Key(SwissFrench105Key),
+}
+
+/// Some names for a layout, and a function to make that layout.
+type LayoutName = (&'static [&'static str], fn() -> AnyLayout);
+
+/// The names of each layout, for [`AnyLayout::from_name`].
+///
+/// Each layout can be found by its type name, and by any other names listed.
+static LAYOUT_NAMES: &[LayoutName] = &[
+    (&["DVP104Key", "dvp"], || AnyLayout::DVP104Key(DVP104Key)),
+    (&["Dvorak104Key", "dvorak"], || {
+        AnyLayout::Dvorak104Key(Dvorak104Key)
+    }),
+    (&["Us104Key", "us", "en-US"], || {
+        AnyLayout::Us104Key(Us104Key)
+    }),
+    (&["Uk105Key", "gb", "en-GB"], || {
+        AnyLayout::Uk105Key(Uk105Key)
+    }),
+    (&["Jis109Key", "jp", "ja-JP"], || {
+        AnyLayout::Jis109Key(Jis109Key)
+    }),
+    (&["Azerty", "fr", "fr-FR"], || AnyLayout::Azerty(Azerty)),
+    (&["Colemak"], || AnyLayout::Colemak(Colemak)),
+    (&["De105Key", "de", "de-DE"], || {
+        AnyLayout::De105Key(De105Key)
+    }),
+    (&["De105KeyLegacy"], || {
+        AnyLayout::De105KeyLegacy(De105KeyLegacy)
+    }),
+    (&["No105Key", "no", "nb-NO"], || {
+        AnyLayout::No105Key(No105Key)
+    }),
+    (&["FiSe105Key", "fi", "fi-FI", "sv-SE", "sv-FI"], || {
+        AnyLayout::FiSe105Key(FiSe105Key)
+    }),
+    (&["Haw104Key", "haw", "haw-US"], || {
+        AnyLayout::Haw104Key(Haw104Key)
+    }),
+    (&["MiNz104Key", "mi", "mi-NZ"], || {
+        AnyLayout::MiNz104Key(MiNz104Key)
+    }),
+    (&["CyUk105Key", "cy", "cy-GB"], || {
+        AnyLayout::CyUk105Key(CyUk105Key)
+    }),
+    (&["SeSami105Key", "se-SE", "se-FI"], || {
+        AnyLayout::SeSami105Key(SeSami105Key)
+    }),
+    (&["La105Key", "la", "es-419"], || {
+        AnyLayout::La105Key(La105Key)
+    }),
+    (&["Pt105Key", "pt", "pt-PT"], || {
+        AnyLayout::Pt105Key(Pt105Key)
+    }),
+    (&["BrAbnt2Key", "br", "pt-BR"], || {
+        AnyLayout::BrAbnt2Key(BrAbnt2Key)
+    }),
+    (
+        &[
+            "SwissGerman105Key",
+            "sg",
+            "ch",
+            "swiss",
+            "de-CH",
+            "li",
+            "de-LI",
+        ],
+        || AnyLayout::SwissGerman105Key(SwissGerman105Key),
+    ),
+    (&["Dk105Key", "dk", "da", "da-DK"], || {
+        AnyLayout::Dk105Key(Dk105Key)
+    }),
+    (&["TrQ105Key", "tr", "tr-TR"], || {
+        AnyLayout::TrQ105Key(TrQ105Key)
+    }),
+    (&["Ar101Key", "ar", "ar-SA"], || {
+        AnyLayout::Ar101Key(Ar101Key)
+    }),
+    (&["SrLatn105Key", "sr-Latn", "sr-Latn-RS"], || {
+        AnyLayout::SrLatn105Key(SrLatn105Key)
+    }),
+    (&["Lv105Key", "lv", "lv-LV"], || {
+        AnyLayout::Lv105Key(Lv105Key)
+    }),
+    (&["Lt105Key", "lt", "lt-LT"], || {
+        AnyLayout::Lt105Key(Lt105Key)
+    }),
+    (&["CaMultilingual105Key", "csa", "ca-multi"], || {
+        AnyLayout::CaMultilingual105Key(CaMultilingual105Key)
+    }),
+    (&["CaFrench105Key", "ca", "fr-CA"], || {
+        AnyLayout::CaFrench105Key(CaFrench105Key)
+    }),
+    (&["UsInternational104Key", "us-intl", "intl"], || {
+        AnyLayout::UsInternational104Key(UsInternational104Key)
+    }),
+    (&["Neo2", "neo"], || AnyLayout::Neo2(Neo2)),
+    (&["Kr104Key", "ko", "ko-KR"], || {
+        AnyLayout::Kr104Key(Kr104Key)
+    }),
+    (&["NoSami105Key", "se-NO", "smj-NO", "sma-NO"], || {
+        AnyLayout::NoSami105Key(NoSami105Key)
+    }),
+    (&["SvSami105Key", "smj-SE", "sma-SE"], || {
+        AnyLayout::SvSami105Key(SvSami105Key)
+    }),
+    (&["AzertyNf105Key", "fr-afnor"], || {
+        AnyLayout::AzertyNf105Key(AzertyNf105Key)
+    }),
+    (&["Svorak105Key", "svorak", "sv-dvorak"], || {
+        AnyLayout::Svorak105Key(Svorak105Key)
+    }),
+    (&["FaIr105Key", "fa", "fa-IR"], || {
+        AnyLayout::FaIr105Key(FaIr105Key)
+    }),
+    (&["InScript105Key", "hi", "hi-IN", "inscript"], || {
+        AnyLayout::InScript105Key(InScript105Key)
+    }),
+    (&["GrPolytonic105Key", "grc", "el-polyton"], || {
+        AnyLayout::GrPolytonic105Key(GrPolytonic105Key)
+    }),
+    (&["Mk105Key", "mk", "mk-MK"], || {
+        AnyLayout::Mk105Key(Mk105Key)
+    }),
+    (&["AzLatin105Key", "az", "az-Latn-AZ"], || {
+        AnyLayout::AzLatin105Key(AzLatin105Key)
+    }),
+    (&["HyPhonetic105Key", "hy", "hy-AM"], || {
+        AnyLayout::HyPhonetic105Key(HyPhonetic105Key)
+    }),
+    (&["Km105Key", "km", "km-KH"], || {
+        AnyLayout::Km105Key(Km105Key)
+    }),
+    (&["Lo105Key", "lo", "lo-LA"], || {
+        AnyLayout::Lo105Key(Lo105Key)
+    }),
+    (&["SiWijesekara105Key", "si", "si-LK"], || {
+        AnyLayout::SiWijesekara105Key(SiWijesekara105Key)
+    }),
+    (&["Ta99Key", "ta", "ta-IN", "tamil99"], || {
+        AnyLayout::Ta99Key(Ta99Key)
+    }),
+    (&["AmEt105Key", "am", "am-ET"], || {
+        AnyLayout::AmEt105Key(AmEt105Key)
+    }),
+    (&["TifinaghKey", "zgh", "zgh-MA", "ber"], || {
+        AnyLayout::TifinaghKey(TifinaghKey)
+    }),
+    (&["Bs105Key", "bs", "bs-BA", "bs-Latn-BA"], || {
+        AnyLayout::Bs105Key(Bs105Key)
+    }),
+    (&["Lb105Key", "lb", "lb-LU", "lu", "fr-LU", "de-LU"], || {
+        AnyLayout::Lb105Key(Lb105Key)
+    }),
+    (&["Ky105Key", "ky", "ky-KG"], || {
+        AnyLayout::Ky105Key(Ky105Key)
+    }),
+    (&["UzLatin105Key", "uz", "uz-Latn-UZ"], || {
+        AnyLayout::UzLatin105Key(UzLatin105Key)
+    }),
+    (&["Ipa104Key", "ipa", "und-fonipa"], || {
+        AnyLayout::Ipa104Key(Ipa104Key)
+    }),
+    (&["SwissFrench105Key", "fr-CH"], || {
+        AnyLayout::SwissFrench105Key(SwissFrench105Key)
+    }),
+];
+
+impl AnyLayout {
+    /// Find a layout by name.
+    ///
+    /// You can use the name of the layout type (e.g. `"Uk105Key"`), or a
+    /// language tag (e.g. `"en-GB"`). Some layouts also have a short name
+    /// (e.g. `"gb"` or `"dvorak"`). The match ignores ASCII case.
+    ///
+    /// You can pick a variant of the layout by adding it after a colon (e.g.
+    /// `"fi:se"`). Any other [`LayoutOptions`] need
+    /// [`AnyLayout::from_name_with_options`].
+    pub fn from_name(name: &str) -> Option<AnyLayout> {
+        let (name, options) = LayoutOptions::parse(name).ok()?;
+        if !options.is_variant_only() {
+            return None;
+        }
+        let layout = Self::from_plain_name(name)?;
+        match options.get_variant() {
+            Some(variant) => layout.into_variant(variant),
+            None => Some(layout),
+        }
+    }
+
+    /// Find a layout by name, with any options added after colons (e.g.
+    /// `"de:numpad-period"`).
+    ///
+    /// See [`AnyLayout::from_name`] for the names, and
+    /// [`LayoutOptions::parse`] for the options.
+    pub fn from_name_with_options(name: &str) -> Result<Configured<AnyLayout>, LayoutError> {
+        let (name, options) = LayoutOptions::parse(name)?;
+        let mut layout = Self::from_plain_name(name).ok_or(LayoutError::UnknownLayout)?;
+        if let Some(variant) = options.get_variant() {
+            layout = layout
+                .into_variant(variant)
+                .ok_or(LayoutError::UnknownVariant)?;
+        }
+        Ok(Configured::new(layout, &options))
+    }
+
+    /// Find a layout by name, without any options.
+    fn from_plain_name(name: &str) -> Option<AnyLayout> {
+        LAYOUT_NAMES
+            .iter()
+            .find(|(names, _)| names.iter().any(|n| n.eq_ignore_ascii_case(name)))
+            .map(|(_, make)| make())
+    }
+
+    /// Switch to a variant of this layout, if it has one with that name.
+    fn into_variant(self, variant: &str) -> Option<AnyLayout> {
+        match self {
+            AnyLayout::Us104Key(_) => {
+                Us104Key::from_variant(Some(variant)).map(AnyLayout::Us104Key)
+            }
+            AnyLayout::De105Key(_) => {
+                De105Key::from_variant(Some(variant)).map(AnyLayout::De105Key)
+            }
+            AnyLayout::FiSe105Key(_) => {
+                FiSe105Key::from_variant(Some(variant)).map(AnyLayout::FiSe105Key)
+            }
+            AnyLayout::SwissGerman105Key(_) | AnyLayout::SwissFrench105Key(_) => {
+                Swiss105Key::from_variant(Some(variant)).map(|layout| match layout {
+                    Swiss105Key::German => AnyLayout::SwissGerman105Key(SwissGerman105Key),
+                    Swiss105Key::French => AnyLayout::SwissFrench105Key(SwissFrench105Key),
+                })
+            }
+            _ => None,
+        }
+    }
 }
 
 impl super::KeyboardLayout for AnyLayout {
@@ -64,8 +482,121 @@ impl super::KeyboardLayout for AnyLayout {
             AnyLayout::Azerty(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
             AnyLayout::Colemak(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
             AnyLayout::De105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::De105KeyLegacy(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
             AnyLayout::No105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
             AnyLayout::FiSe105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Haw104Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::MiNz104Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::CyUk105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::SeSami105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::La105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Pt105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::BrAbnt2Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::SwissGerman105Key(inner) => {
+                inner.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            AnyLayout::Dk105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::TrQ105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Ar101Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::SrLatn105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Lv105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Lt105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::CaMultilingual105Key(inner) => {
+                inner.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            AnyLayout::CaFrench105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::UsInternational104Key(inner) => {
+                inner.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            AnyLayout::Neo2(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Kr104Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::NoSami105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::SvSami105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::AzertyNf105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Svorak105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::FaIr105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::InScript105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::GrPolytonic105Key(inner) => {
+                inner.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            AnyLayout::Mk105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::AzLatin105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::HyPhonetic105Key(inner) => {
+                inner.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            AnyLayout::Km105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Lo105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::SiWijesekara105Key(inner) => {
+                inner.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            AnyLayout::Ta99Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::AmEt105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::TifinaghKey(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Bs105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Lb105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Ky105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::UzLatin105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Ipa104Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::SwissFrench105Key(inner) => {
+                inner.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+        }
+    }
+
+    fn dead_key_combine(&self, dead_key: char, ch: char) -> Option<char> {
+        match self {
+            AnyLayout::DVP104Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Dvorak104Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Us104Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Uk105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Jis109Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Azerty(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Colemak(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::De105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::De105KeyLegacy(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::No105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::FiSe105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Haw104Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::MiNz104Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::CyUk105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::SeSami105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::La105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Pt105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::BrAbnt2Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::SwissGerman105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Dk105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::TrQ105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Ar101Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::SrLatn105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Lv105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Lt105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::CaMultilingual105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::CaFrench105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::UsInternational104Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Neo2(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Kr104Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::NoSami105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::SvSami105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::AzertyNf105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Svorak105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::FaIr105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::InScript105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::GrPolytonic105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Mk105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::AzLatin105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::HyPhonetic105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Km105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Lo105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::SiWijesekara105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Ta99Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::AmEt105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::TifinaghKey(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Bs105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Lb105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Ky105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::UzLatin105Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::Ipa104Key(inner) => inner.dead_key_combine(dead_key, ch),
+            AnyLayout::SwissFrench105Key(inner) => inner.dead_key_combine(dead_key, ch),
         }
     }
 }
@@ -86,10 +617,70 @@ impl super::KeyboardLayout for &AnyLayout {
             AnyLayout::Azerty(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
             AnyLayout::Colemak(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
             AnyLayout::De105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::De105KeyLegacy(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
             AnyLayout::No105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
             AnyLayout::FiSe105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Haw104Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::MiNz104Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::CyUk105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::SeSami105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::La105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Pt105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::BrAbnt2Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::SwissGerman105Key(inner) => {
+                inner.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            AnyLayout::Dk105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::TrQ105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Ar101Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::SrLatn105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Lv105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Lt105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::CaMultilingual105Key(inner) => {
+                inner.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            AnyLayout::CaFrench105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::UsInternational104Key(inner) => {
+                inner.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            AnyLayout::Neo2(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Kr104Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::NoSami105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::SvSami105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::AzertyNf105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Svorak105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::FaIr105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::InScript105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::GrPolytonic105Key(inner) => {
+                inner.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            AnyLayout::Mk105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::AzLatin105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::HyPhonetic105Key(inner) => {
+                inner.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            AnyLayout::Km105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Lo105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::SiWijesekara105Key(inner) => {
+                inner.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            AnyLayout::Ta99Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::AmEt105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::TifinaghKey(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Bs105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Lb105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Ky105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::UzLatin105Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::Ipa104Key(inner) => inner.map_keycode(keycode, modifiers, handle_ctrl),
+            AnyLayout::SwissFrench105Key(inner) => {
+                inner.map_keycode(keycode, modifiers, handle_ctrl)
+            }
         }
     }
+
+    fn dead_key_combine(&self, dead_key: char, ch: char) -> Option<char> {
+        (**self).dead_key_combine(dead_key, ch)
+    }
 }
 
 #[cfg(test)]
@@ -115,4 +706,106 @@ mod test {
         });
         assert_eq!(decoded, Some(DecodedKey::Unicode('a')));
     }
+
+    #[test]
+    fn test_from_name() {
+        let hash = KeyEvent::new(KeyCode::Oem7, KeyState::Down);
+        for name in ["Uk105Key", "uk105key", "gb", "en-GB", "EN-gb"] {
+            let layout = AnyLayout::from_name(name).unwrap();
+            assert!(matches!(layout, AnyLayout::Uk105Key(_)));
+            let mut decoder = EventDecoder::new(layout, HandleControl::Ignore);
+            assert_eq!(
+                decoder.process_keyevent(hash.clone()),
+                Some(DecodedKey::Unicode('#'))
+            );
+        }
+        assert!(matches!(
+            AnyLayout::from_name("de-DE"),
+            Some(AnyLayout::De105Key(_))
+        ));
+        assert!(matches!(
+            AnyLayout::from_name("Colemak"),
+            Some(AnyLayout::Colemak(_))
+        ));
+        assert!(AnyLayout::from_name("xx-XX").is_none());
+        assert!(AnyLayout::from_name("").is_none());
+    }
+
+    #[test]
+    fn test_swiss_names() {
+        // Liechtenstein uses the Swiss German layout
+        let ue = KeyEvent::new(KeyCode::Oem4, KeyState::Down);
+        for name in ["sg", "ch:de", "de-CH", "li", "de-LI"] {
+            let layout = AnyLayout::from_name(name).unwrap();
+            let mut decoder = EventDecoder::new(layout, HandleControl::Ignore);
+            assert_eq!(
+                decoder.process_keyevent(ue.clone()),
+                Some(DecodedKey::Unicode('ü')),
+                "{}",
+                name
+            );
+        }
+        // Luxembourg uses the Swiss French layout
+        let e_acute = KeyEvent::new(KeyCode::Oem1, KeyState::Down);
+        for name in [
+            "SwissFrench105Key",
+            "fr-CH",
+            "lb",
+            "lb-LU",
+            "lu",
+            "fr-LU",
+            "de-LU",
+        ] {
+            let layout = AnyLayout::from_name(name).unwrap();
+            let mut decoder = EventDecoder::new(layout, HandleControl::Ignore);
+            assert_eq!(
+                decoder.process_keyevent(e_acute.clone()),
+                Some(DecodedKey::Unicode('é')),
+                "{}",
+                name
+            );
+        }
+        assert!(AnyLayout::from_name("ch:xx").is_none());
+    }
+
+    #[test]
+    fn test_all_names() {
+        // Every layout can be found by its own type name
+        for (names, make) in LAYOUT_NAMES {
+            assert_eq!(
+                core::mem::discriminant(&AnyLayout::from_name(names[0]).unwrap()),
+                core::mem::discriminant(&make())
+            );
+        }
+    }
+
+    #[test]
+    fn test_extra_keys() {
+        // Every layout passes these straight through
+        let modifiers = Modifiers::default();
+        let codes = [
+            KeyCode::WWWSearch,
+            KeyCode::WWWFavourites,
+            KeyCode::WWWRefresh,
+            KeyCode::WWWStop,
+            KeyCode::WWWForward,
+            KeyCode::WWWBack,
+            KeyCode::MyComputer,
+            KeyCode::Email,
+            KeyCode::MediaSelect,
+            KeyCode::Power,
+            KeyCode::Sleep,
+            KeyCode::Wake,
+        ];
+        for (names, make) in LAYOUT_NAMES {
+            for code in codes {
+                assert_eq!(
+                    make().map_keycode(code, &modifiers, HandleControl::Ignore),
+                    DecodedKey::RawKey(code),
+                    "{}",
+                    names[0]
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,205 @@
+//! Neo 2 keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// The German Neo 2 layout, on a 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// Only layers 1 to 3 are supported. On real Neo 2 layouts, layer 3 is
+/// reached with Caps Lock or `#` (Mod3), but those are not modifiers here, so
+/// layer 3 is on AltGr instead. The keys which are usually Mod3 and Mod4
+/// type what they would on a German layout.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct Neo2;
+
+/// A character on a layer
+const fn ch(c: char) -> DecodedKey {
+    DecodedKey::Unicode(c)
+}
+
+/// A dead key on a layer
+const fn dead(c: char) -> DecodedKey {
+    DecodedKey::DeadKey(c)
+}
+
+impl Neo2 {
+    /// Which layer the modifiers pick, counting from 0.
+    const fn layer(modifiers: &Modifiers) -> usize {
+        if modifiers.is_altgr() {
+            2
+        } else if modifiers.is_shifted() {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+impl KeyboardLayout for Neo2 {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let layers = match keycode {
+            // ========= Row 2 (the numbers) =========
+            KeyCode::Oem8 => [dead('^'), dead('ˇ'), DecodedKey::RawKey(keycode)],
+            KeyCode::Key1 => [ch('1'), ch('°'), ch('¹')],
+            KeyCode::Key2 => [ch('2'), ch('§'), ch('²')],
+            KeyCode::Key3 => [ch('3'), ch('ℓ'), ch('³')],
+            KeyCode::Key4 => [ch('4'), ch('»'), ch('›')],
+            KeyCode::Key5 => [ch('5'), ch('«'), ch('‹')],
+            KeyCode::Key6 => [ch('6'), ch('$'), ch('¢')],
+            KeyCode::Key7 => [ch('7'), ch('€'), ch('¥')],
+            KeyCode::Key8 => [ch('8'), ch('„'), ch('‚')],
+            KeyCode::Key9 => [ch('9'), ch('“'), ch('‘')],
+            KeyCode::Key0 => [ch('0'), ch('”'), ch('’')],
+            KeyCode::OemMinus => [ch('-'), ch('—'), DecodedKey::RawKey(keycode)],
+            KeyCode::OemPlus => [dead('`'), dead('¸'), dead('˚')],
+            // ========= Row 3 =========
+            KeyCode::Q => [ch('x'), ch('X'), ch('…')],
+            KeyCode::W => [ch('v'), ch('V'), ch('_')],
+            KeyCode::E => [ch('l'), ch('L'), ch('[')],
+            KeyCode::R => [ch('c'), ch('C'), ch(']')],
+            KeyCode::T => [ch('w'), ch('W'), ch('^')],
+            KeyCode::Y => [ch('k'), ch('K'), ch('!')],
+            KeyCode::U => [ch('h'), ch('H'), ch('<')],
+            KeyCode::I => [ch('g'), ch('G'), ch('>')],
+            KeyCode::O => [ch('f'), ch('F'), ch('=')],
+            KeyCode::P => [ch('q'), ch('Q'), ch('&')],
+            KeyCode::Oem4 => [ch('ß'), ch('ẞ'), ch('ſ')],
+            KeyCode::Oem6 => [dead('´'), dead('~'), DecodedKey::RawKey(keycode)],
+            // ========= Row 4 (the home row) =========
+            KeyCode::A => [ch('u'), ch('U'), ch('\\')],
+            KeyCode::S => [ch('i'), ch('I'), ch('/')],
+            KeyCode::D => [ch('a'), ch('A'), ch('{')],
+            KeyCode::F => [ch('e'), ch('E'), ch('}')],
+            KeyCode::G => [ch('o'), ch('O'), ch('*')],
+            KeyCode::H => [ch('s'), ch('S'), ch('?')],
+            KeyCode::J => [ch('n'), ch('N'), ch('(')],
+            KeyCode::K => [ch('r'), ch('R'), ch(')')],
+            KeyCode::L => [ch('t'), ch('T'), ch('-')],
+            KeyCode::Oem1 => [ch('d'), ch('D'), ch(':')],
+            KeyCode::Oem3 => [ch('y'), ch('Y'), ch('@')],
+            // ========= Row 5 =========
+            KeyCode::Z => [ch('ü'), ch('Ü'), ch('#')],
+            KeyCode::X => [ch('ö'), ch('Ö'), ch('$')],
+            KeyCode::C => [ch('ä'), ch('Ä'), ch('|')],
+            KeyCode::V => [ch('p'), ch('P'), ch('~')],
+            KeyCode::B => [ch('z'), ch('Z'), ch('`')],
+            KeyCode::N => [ch('b'), ch('B'), ch('+')],
+            KeyCode::M => [ch('m'), ch('M'), ch('%')],
+            KeyCode::OemComma => [ch(','), ch('–'), ch('"')],
+            KeyCode::OemPeriod => [ch('.'), ch('•'), ch('\'')],
+            KeyCode::Oem2 => [ch('j'), ch('J'), ch(';')],
+            e => {
+                let de = super::De105Key;
+                return de.map_keycode(e, modifiers, handle_ctrl);
+            }
+        };
+        let layer = Self::layer(modifiers);
+        match (layers[0], layers[1]) {
+            (DecodedKey::Unicode(lower), DecodedKey::Unicode(upper))
+                if layer < 2 && lower.is_alphabetic() =>
+            {
+                // Letters follow Caps Lock, and give control codes with Ctrl
+                modifiers.handle_letter_auto(lower, upper, handle_ctrl)
+            }
+            _ => layers[layer],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://neo-layout.org, layers 1 to 3
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '^', 'ˇ', None, None),
+            (KeyCode::Key1, '1', '°', Some('¹'), None),
+            (KeyCode::Key2, '2', '§', Some('²'), None),
+            (KeyCode::Key3, '3', 'ℓ', Some('³'), None),
+            (KeyCode::Key4, '4', '»', Some('›'), None),
+            (KeyCode::Key5, '5', '«', Some('‹'), None),
+            (KeyCode::Key6, '6', '$', Some('¢'), None),
+            (KeyCode::Key7, '7', '€', Some('¥'), None),
+            (KeyCode::Key8, '8', '„', Some('‚'), None),
+            (KeyCode::Key9, '9', '“', Some('‘'), None),
+            (KeyCode::Key0, '0', '”', Some('’'), None),
+            (KeyCode::OemMinus, '-', '—', None, None),
+            (KeyCode::OemPlus, '`', '¸', Some('˚'), None),
+            (KeyCode::Q, 'x', 'X', Some('…'), None),
+            (KeyCode::W, 'v', 'V', Some('_'), None),
+            (KeyCode::E, 'l', 'L', Some('['), None),
+            (KeyCode::R, 'c', 'C', Some(']'), None),
+            (KeyCode::T, 'w', 'W', Some('^'), None),
+            (KeyCode::Y, 'k', 'K', Some('!'), None),
+            (KeyCode::U, 'h', 'H', Some('<'), None),
+            (KeyCode::I, 'g', 'G', Some('>'), None),
+            (KeyCode::O, 'f', 'F', Some('='), None),
+            (KeyCode::P, 'q', 'Q', Some('&'), None),
+            (KeyCode::Oem4, 'ß', 'ẞ', Some('ſ'), None),
+            (KeyCode::Oem6, '´', '~', None, None),
+            (KeyCode::A, 'u', 'U', Some('\\'), None),
+            (KeyCode::S, 'i', 'I', Some('/'), None),
+            (KeyCode::D, 'a', 'A', Some('{'), None),
+            (KeyCode::F, 'e', 'E', Some('}'), None),
+            (KeyCode::G, 'o', 'O', Some('*'), None),
+            (KeyCode::H, 's', 'S', Some('?'), None),
+            (KeyCode::J, 'n', 'N', Some('('), None),
+            (KeyCode::K, 'r', 'R', Some(')'), None),
+            (KeyCode::L, 't', 'T', Some('-'), None),
+            (KeyCode::Oem1, 'd', 'D', Some(':'), None),
+            (KeyCode::Oem3, 'y', 'Y', Some('@'), None),
+            (KeyCode::Z, 'ü', 'Ü', Some('#'), None),
+            (KeyCode::X, 'ö', 'Ö', Some('$'), None),
+            (KeyCode::C, 'ä', 'Ä', Some('|'), None),
+            (KeyCode::V, 'p', 'P', Some('~'), None),
+            (KeyCode::B, 'z', 'Z', Some('`'), None),
+            (KeyCode::N, 'b', 'B', Some('+'), None),
+            (KeyCode::M, 'm', 'M', Some('%'), None),
+            (KeyCode::OemComma, ',', '–', Some('"'), None),
+            (KeyCode::OemPeriod, '.', '•', Some('\''), None),
+            (KeyCode::Oem2, 'j', 'J', Some(';'), None),
+            (KeyCode::Spacebar, ' ', ' ', Some(' '), None),
+        ];
+        fixture::check(&Neo2, ROWS);
+    }
+
+    #[test]
+    fn test_layers() {
+        let mut dec = EventDecoder::new(Neo2, HandleControl::MapLettersToUnicode);
+        let tap = |dec: &mut EventDecoder<Neo2>, code| {
+            let key = dec.process_keyevent(KeyEvent::new(code, KeyState::Down));
+            dec.process_keyevent(KeyEvent::new(code, KeyState::Up));
+            key
+        };
+        // Layer 1
+        assert_eq!(tap(&mut dec, KeyCode::D), Some(DecodedKey::Unicode('a')));
+        assert_eq!(tap(&mut dec, KeyCode::Oem4), Some(DecodedKey::Unicode('ß')));
+        // Layer 2
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(tap(&mut dec, KeyCode::D), Some(DecodedKey::Unicode('A')));
+        assert_eq!(tap(&mut dec, KeyCode::Key7), Some(DecodedKey::Unicode('€')));
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        // Layer 3
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        assert_eq!(tap(&mut dec, KeyCode::Q), Some(DecodedKey::Unicode('…')));
+        assert_eq!(tap(&mut dec, KeyCode::E), Some(DecodedKey::Unicode('[')));
+        assert_eq!(tap(&mut dec, KeyCode::T), Some(DecodedKey::Unicode('^')));
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Up));
+        // Ctrl goes by the Neo letter, so the key marked Q is Ctrl+X
+        dec.process_keyevent(KeyEvent::new(KeyCode::LControl, KeyState::Down));
+        assert_eq!(
+            tap(&mut dec, KeyCode::Q),
+            Some(DecodedKey::Unicode('\u{0018}'))
+        );
+    }
+}
@@ -4,6 +4,9 @@ use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
 
 /// A standard Norwegian 102-key (or 105-key including Windows keys) keyboard.
 ///
+/// The `¨`/`^`/`~` key is a dead key, as are Shift and AltGr on the `\`
+/// key (`` ` `` and `´`).
+///
 /// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
 pub struct No105Key;
 
@@ -119,9 +122,9 @@ impl KeyboardLayout for No105Key {
             }
             KeyCode::OemPlus => {
                 if modifiers.is_shifted() {
-                    DecodedKey::Unicode('`')
+                    DecodedKey::DeadKey('`')
                 } else if modifiers.is_altgr() {
-                    DecodedKey::Unicode('´')
+                    DecodedKey::DeadKey('´')
                 } else {
                     DecodedKey::Unicode('\\')
                 }
@@ -139,20 +142,14 @@ impl KeyboardLayout for No105Key {
                     DecodedKey::Unicode('e')
                 }
             }
-            KeyCode::Oem4 => {
-                if modifiers.is_caps() {
-                    DecodedKey::Unicode('Å')
-                } else {
-                    DecodedKey::Unicode('å')
-                }
-            }
+            KeyCode::Oem4 => modifiers.handle_letter_auto('å', 'Å', handle_ctrl),
             KeyCode::Oem6 => {
                 if modifiers.is_altgr() {
-                    DecodedKey::Unicode('~')
+                    DecodedKey::DeadKey('~')
                 } else if modifiers.is_shifted() {
-                    DecodedKey::Unicode('^')
+                    DecodedKey::DeadKey('^')
                 } else {
-                    DecodedKey::Unicode('¨')
+                    DecodedKey::DeadKey('¨')
                 }
             }
             KeyCode::Return => DecodedKey::Unicode(10.into()),
@@ -163,20 +160,8 @@ impl KeyboardLayout for No105Key {
                     DecodedKey::Unicode('\'')
                 }
             }
-            KeyCode::Oem1 => {
-                if modifiers.is_caps() {
-                    DecodedKey::Unicode('Ø')
-                } else {
-                    DecodedKey::Unicode('ø')
-                }
-            }
-            KeyCode::Oem3 => {
-                if modifiers.is_caps() {
-                    DecodedKey::Unicode('Æ')
-                } else {
-                    DecodedKey::Unicode('æ')
-                }
-            }
+            KeyCode::Oem1 => modifiers.handle_letter_auto('ø', 'Ø', handle_ctrl),
+            KeyCode::Oem3 => modifiers.handle_letter_auto('æ', 'Æ', handle_ctrl),
             KeyCode::M => {
                 if map_to_unicode && modifiers.is_ctrl() {
                     DecodedKey::Unicode('\u{000D}')
@@ -230,3 +215,53 @@ impl KeyboardLayout for No105Key {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Keyboard, ScancodeSet2};
+
+    /// Feed Set 2 scancodes through a keyboard, collecting the characters
+    fn type_bytes(bytes: &[u8]) -> String {
+        let mut k = Keyboard::new(ScancodeSet2::new(), No105Key, HandleControl::Ignore);
+        let mut output = String::new();
+        for byte in bytes {
+            if let Ok(Some(item)) = k.add_byte(*byte) {
+                if let Some(DecodedKey::Unicode(ch)) = k.process_item(item) {
+                    output.push(ch);
+                }
+                while let Some(DecodedKey::Unicode(ch)) = k.take_queued() {
+                    output.push(ch);
+                }
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn test_dead_keys() {
+        // AltGr + \\ is a dead ´, then e
+        assert_eq!(
+            type_bytes(&[0xE0, 0x11, 0x55, 0xF0, 0x55, 0xE0, 0xF0, 0x11, 0x24, 0xF0, 0x24]),
+            "é"
+        );
+        // ¨ then a
+        assert_eq!(type_bytes(&[0x5B, 0xF0, 0x5B, 0x1C, 0xF0, 0x1C]), "ä");
+        // ¨ then u
+        assert_eq!(type_bytes(&[0x5B, 0xF0, 0x5B, 0x3C, 0xF0, 0x3C]), "ü");
+        // Shift + ¨ is a dead ^, then o
+        assert_eq!(
+            type_bytes(&[0x12, 0x5B, 0xF0, 0x5B, 0xF0, 0x12, 0x44, 0xF0, 0x44]),
+            "ô"
+        );
+        // AltGr + ¨ is a dead ~, then n
+        assert_eq!(
+            type_bytes(&[0xE0, 0x11, 0x5B, 0xF0, 0x5B, 0xE0, 0xF0, 0x11, 0x31, 0xF0, 0x31]),
+            "ñ"
+        );
+        // Space gives the accent on its own
+        assert_eq!(type_bytes(&[0x5B, 0xF0, 0x5B, 0x29, 0xF0, 0x29]), "¨");
+        // Without AltGr, the key is still a plain backslash
+        assert_eq!(type_bytes(&[0x55, 0xF0, 0x55]), "\\");
+    }
+}
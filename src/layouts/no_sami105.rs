@@ -0,0 +1,102 @@
+//! Norwegian with Sámi keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Norwegian with Sámi 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// This is the Norwegian layout, with the Sámi letters on AltGr. The Latin
+/// letters stay where they are.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct NoSami105Key;
+
+impl KeyboardLayout for NoSami105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let fallback = super::No105Key;
+        match keycode {
+            KeyCode::T => modifiers.handle_ascii_4(ascii_upper!('T'), 'ŧ', 'Ŧ', handle_ctrl),
+            KeyCode::A => modifiers.handle_ascii_4(ascii_upper!('A'), 'á', 'Á', handle_ctrl),
+            KeyCode::S => modifiers.handle_ascii_4(ascii_upper!('S'), 'š', 'Š', handle_ctrl),
+            KeyCode::D => modifiers.handle_ascii_4(ascii_upper!('D'), 'đ', 'Đ', handle_ctrl),
+            KeyCode::G => modifiers.handle_ascii_4(ascii_upper!('G'), 'ǧ', 'Ǧ', handle_ctrl),
+            KeyCode::H => modifiers.handle_ascii_4(ascii_upper!('H'), 'ǥ', 'Ǥ', handle_ctrl),
+            KeyCode::K => modifiers.handle_ascii_4(ascii_upper!('K'), 'ǩ', 'Ǩ', handle_ctrl),
+            KeyCode::Z => modifiers.handle_ascii_4(ascii_upper!('Z'), 'ž', 'Ž', handle_ctrl),
+            KeyCode::C => modifiers.handle_ascii_4(ascii_upper!('C'), 'č', 'Č', handle_ctrl),
+            KeyCode::V => modifiers.handle_ascii_4(ascii_upper!('V'), 'ʒ', 'Ʒ', handle_ctrl),
+            KeyCode::N => modifiers.handle_ascii_4(ascii_upper!('N'), 'ŋ', 'Ŋ', handle_ctrl),
+            e => fallback.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Based on https://kbdlayout.info/kbdnno
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Key2, '2', '"', Some('@'), None),
+            (KeyCode::E, 'e', 'E', Some('€'), None),
+            (KeyCode::T, 't', 'T', Some('ŧ'), Some('Ŧ')),
+            (KeyCode::Oem4, 'å', 'Å', None, None),
+            (KeyCode::A, 'a', 'A', Some('á'), Some('Á')),
+            (KeyCode::S, 's', 'S', Some('š'), Some('Š')),
+            (KeyCode::D, 'd', 'D', Some('đ'), Some('Đ')),
+            (KeyCode::G, 'g', 'G', Some('ǧ'), Some('Ǧ')),
+            (KeyCode::H, 'h', 'H', Some('ǥ'), Some('Ǥ')),
+            (KeyCode::K, 'k', 'K', Some('ǩ'), Some('Ǩ')),
+            (KeyCode::Oem1, 'ø', 'Ø', None, None),
+            (KeyCode::Oem3, 'æ', 'Æ', None, None),
+            (KeyCode::Z, 'z', 'Z', Some('ž'), Some('Ž')),
+            (KeyCode::C, 'c', 'C', Some('č'), Some('Č')),
+            (KeyCode::V, 'v', 'V', Some('ʒ'), Some('Ʒ')),
+            (KeyCode::N, 'n', 'N', Some('ŋ'), Some('Ŋ')),
+            (KeyCode::M, 'm', 'M', Some('µ'), None),
+        ];
+        fixture::check(&NoSami105Key, ROWS);
+    }
+
+    #[test]
+    fn test_shift_altgr_capitals() {
+        let mut dec = EventDecoder::new(NoSami105Key, HandleControl::MapLettersToUnicode);
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::S, KeyState::Down)),
+            Some(DecodedKey::Unicode('š'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::S, KeyState::Down)),
+            Some(DecodedKey::Unicode('Š'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::C, KeyState::Down)),
+            Some(DecodedKey::Unicode('Č'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        // Caps Lock also gives the capitals
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::C, KeyState::Down)),
+            Some(DecodedKey::Unicode('Č'))
+        );
+        // Without AltGr, Ctrl still gives control codes
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Up));
+        dec.process_keyevent(KeyEvent::new(KeyCode::LControl, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::C, KeyState::Down)),
+            Some(DecodedKey::Unicode('\u{0003}'))
+        );
+    }
+}
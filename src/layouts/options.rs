@@ -0,0 +1,423 @@
+//! Options that change how a layout behaves
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// Which digits the number keys (and the numpad) type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Digits {
+    /// `0123456789`
+    Latin,
+    /// `٠١٢٣٤٥٦٧٨٩`, as used with Arabic
+    ArabicIndic,
+    /// `۰۱۲۳۴۵۶۷۸۹`, as used with Persian and Urdu
+    Persian,
+    /// `०१२३४५६७८९`
+    Devanagari,
+}
+
+/// Something went wrong making a layout.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LayoutError {
+    /// There's no layout with that name
+    UnknownLayout,
+    /// The layout doesn't have a variant with that name
+    UnknownVariant,
+}
+
+/// Options for a layout, made with a builder.
+///
+/// ```
+/// use pc_keyboard::layouts::{Digits, LayoutOptions};
+/// let options = LayoutOptions::new()
+///     .digits(Digits::ArabicIndic)
+///     .numpad_decimal('٫');
+/// ```
+///
+/// Apply them with [`ConfigurableLayout::with_options`], or write them after
+/// the layout name in [`AnyLayout::from_name_with_options`](super::AnyLayout::from_name_with_options).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LayoutOptions<'a> {
+    numpad_decimal: Option<char>,
+    digits: Digits,
+    variant: Option<&'a str>,
+    caps_affects_symbols: bool,
+}
+
+/// A layout, with some [`LayoutOptions`] applied.
+#[derive(Debug, Clone)]
+pub struct Configured<L> {
+    layout: L,
+    numpad_decimal: Option<char>,
+    digits: Digits,
+    caps_affects_symbols: bool,
+}
+
+/// A layout which can be made with [`LayoutOptions`].
+pub trait ConfigurableLayout: KeyboardLayout + Sized {
+    /// Make the layout, in the given variant, or the default variant if
+    /// `variant` is `None`.
+    ///
+    /// Gives `None` if there is no such variant.
+    fn from_variant(variant: Option<&str>) -> Option<Self>;
+
+    /// Make the layout, with the given options.
+    fn with_options(options: &LayoutOptions) -> Result<Configured<Self>, LayoutError> {
+        let layout = Self::from_variant(options.variant).ok_or(LayoutError::UnknownVariant)?;
+        Ok(Configured::new(layout, options))
+    }
+}
+
+impl Digits {
+    /// Get the zero digit in this script.
+    ///
+    /// The other digits follow it in order.
+    const fn zero(self) -> char {
+        match self {
+            Digits::Latin => '0',
+            Digits::ArabicIndic => '\u{0660}',
+            Digits::Persian => '\u{06F0}',
+            Digits::Devanagari => '\u{0966}',
+        }
+    }
+
+    /// Convert an ASCII digit into this script.
+    ///
+    /// Anything else is left alone.
+    pub fn convert(self, ch: char) -> char {
+        match ch.to_digit(10) {
+            Some(value) if ch.is_ascii_digit() => {
+                char::from_u32(self.zero() as u32 + value).unwrap_or(ch)
+            }
+            _ => ch,
+        }
+    }
+}
+
+impl<'a> LayoutOptions<'a> {
+    /// The default options: the layout's default variant, with nothing
+    /// changed.
+    pub const fn new() -> LayoutOptions<'a> {
+        LayoutOptions {
+            numpad_decimal: None,
+            digits: Digits::Latin,
+            variant: None,
+            caps_affects_symbols: false,
+        }
+    }
+
+    /// Make the numpad decimal key type `ch`.
+    pub const fn numpad_decimal(self, ch: char) -> LayoutOptions<'a> {
+        LayoutOptions {
+            numpad_decimal: Some(ch),
+            ..self
+        }
+    }
+
+    /// Make the number keys type digits in another script.
+    pub const fn digits(self, digits: Digits) -> LayoutOptions<'a> {
+        LayoutOptions { digits, ..self }
+    }
+
+    /// Pick a variant of the layout.
+    ///
+    /// Which variants there are depends on the layout.
+    pub const fn variant(self, variant: &'a str) -> LayoutOptions<'a> {
+        LayoutOptions {
+            variant: Some(variant),
+            ..self
+        }
+    }
+
+    /// Make Caps Lock work like Shift on the symbol keys, not just the
+    /// letters (like Shift Lock on a typewriter).
+    pub const fn caps_affects_symbols(self, enabled: bool) -> LayoutOptions<'a> {
+        LayoutOptions {
+            caps_affects_symbols: enabled,
+            ..self
+        }
+    }
+
+    /// Get the variant, if one was picked.
+    pub const fn get_variant(&self) -> Option<&'a str> {
+        self.variant
+    }
+
+    /// Do these options do nothing more than pick a variant?
+    pub(crate) fn is_variant_only(&self) -> bool {
+        *self
+            == LayoutOptions {
+                variant: self.variant,
+                ..LayoutOptions::new()
+            }
+    }
+
+    /// Split a name like `"de:caps-symbols:numpad-comma"` into the layout
+    /// name and its options.
+    ///
+    /// Each option after a `:` is one of:
+    ///
+    /// * `latin-digits`, `arabic-digits`, `persian-digits` or
+    ///   `devanagari-digits`
+    /// * `numpad-period` or `numpad-comma`
+    /// * `caps-symbols`
+    ///
+    /// Anything else is taken as the variant, and only one variant is
+    /// allowed.
+    pub fn parse(name: &'a str) -> Result<(&'a str, LayoutOptions<'a>), LayoutError> {
+        let mut parts = name.split(':');
+        let layout_name = parts.next().unwrap_or("");
+        let mut options = LayoutOptions::new();
+        for part in parts {
+            options = match part {
+                "latin-digits" => options.digits(Digits::Latin),
+                "arabic-digits" => options.digits(Digits::ArabicIndic),
+                "persian-digits" => options.digits(Digits::Persian),
+                "devanagari-digits" => options.digits(Digits::Devanagari),
+                "numpad-period" => options.numpad_decimal('.'),
+                "numpad-comma" => options.numpad_decimal(','),
+                "caps-symbols" => options.caps_affects_symbols(true),
+                _ if options.variant.is_some() => return Err(LayoutError::UnknownVariant),
+                variant => options.variant(variant),
+            };
+        }
+        Ok((layout_name, options))
+    }
+}
+
+impl<'a> Default for LayoutOptions<'a> {
+    fn default() -> Self {
+        LayoutOptions::new()
+    }
+}
+
+impl<L> Configured<L> {
+    /// Apply some options to a layout.
+    ///
+    /// The variant in `options` is ignored - the layout is used as it is.
+    pub const fn new(layout: L, options: &LayoutOptions) -> Configured<L> {
+        Configured {
+            layout,
+            numpad_decimal: options.numpad_decimal,
+            digits: options.digits,
+            caps_affects_symbols: options.caps_affects_symbols,
+        }
+    }
+
+    /// Get the layout underneath.
+    pub const fn inner(&self) -> &L {
+        &self.layout
+    }
+
+    /// Get the layout underneath, dropping the options.
+    pub fn into_inner(self) -> L {
+        self.layout
+    }
+}
+
+/// Is this a key on the numeric keypad?
+const fn is_numpad(keycode: KeyCode) -> bool {
+    matches!(
+        keycode,
+        KeyCode::Numpad0
+            | KeyCode::Numpad1
+            | KeyCode::Numpad2
+            | KeyCode::Numpad3
+            | KeyCode::Numpad4
+            | KeyCode::Numpad5
+            | KeyCode::Numpad6
+            | KeyCode::Numpad7
+            | KeyCode::Numpad8
+            | KeyCode::Numpad9
+            | KeyCode::NumpadPeriod
+            | KeyCode::NumpadAdd
+            | KeyCode::NumpadSubtract
+            | KeyCode::NumpadMultiply
+            | KeyCode::NumpadDivide
+            | KeyCode::NumpadEnter
+    )
+}
+
+impl<L> KeyboardLayout for Configured<L>
+where
+    L: KeyboardLayout,
+{
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let mut key = self.layout.map_keycode(keycode, modifiers, handle_ctrl);
+        if self.caps_affects_symbols && modifiers.capslock && !is_numpad(keycode) {
+            if let DecodedKey::Unicode(ch) = key {
+                if !ch.is_alphabetic() && !ch.is_control() {
+                    // Caps Lock flips Shift, like it does for the letters
+                    let shift_lock = Modifiers {
+                        lshift: !modifiers.is_shifted(),
+                        rshift: false,
+                        capslock: false,
+                        ..modifiers.clone()
+                    };
+                    key = self.layout.map_keycode(keycode, &shift_lock, handle_ctrl);
+                }
+            }
+        }
+        match key {
+            DecodedKey::Unicode(ch @ ('.' | ',')) if keycode == KeyCode::NumpadPeriod => {
+                DecodedKey::Unicode(self.numpad_decimal.unwrap_or(ch))
+            }
+            DecodedKey::Unicode(ch) => DecodedKey::Unicode(self.digits.convert(ch)),
+            key => key,
+        }
+    }
+
+    fn map_raw(&self, keycode: KeyCode, modifiers: &Modifiers) -> KeyCode {
+        self.layout.map_raw(keycode, modifiers)
+    }
+
+    fn dead_key_combine(&self, dead_key: char, ch: char) -> Option<char> {
+        self.layout.dead_key_combine(dead_key, ch)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::{AnyLayout, De105Key, FiSe105Key, Us104Key};
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    fn tap<L: KeyboardLayout>(dec: &mut EventDecoder<L>, code: KeyCode) -> Option<DecodedKey> {
+        let key = dec.process_keyevent(KeyEvent::new(code, KeyState::Down));
+        dec.process_keyevent(KeyEvent::new(code, KeyState::Up));
+        key
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(LayoutOptions::parse("us"), Ok(("us", LayoutOptions::new())));
+        assert_eq!(
+            LayoutOptions::parse("fi:se:arabic-digits:caps-symbols"),
+            Ok((
+                "fi",
+                LayoutOptions::new()
+                    .variant("se")
+                    .digits(Digits::ArabicIndic)
+                    .caps_affects_symbols(true)
+            ))
+        );
+        assert_eq!(
+            LayoutOptions::parse("de:numpad-period"),
+            Ok(("de", LayoutOptions::new().numpad_decimal('.')))
+        );
+        assert_eq!(
+            LayoutOptions::parse("fi:se:fi"),
+            Err(LayoutError::UnknownVariant)
+        );
+    }
+
+    #[test]
+    fn test_variants() {
+        assert!(FiSe105Key::with_options(&LayoutOptions::new().variant("se")).is_ok());
+        assert!(FiSe105Key::with_options(&LayoutOptions::new()).is_ok());
+        assert_eq!(
+            Us104Key::with_options(&LayoutOptions::new().variant("se")).err(),
+            Some(LayoutError::UnknownVariant)
+        );
+        assert!(AnyLayout::from_name("fi:se").is_some());
+        assert!(AnyLayout::from_name("fi:xx").is_none());
+        // Needs from_name_with_options
+        assert!(AnyLayout::from_name("fi:arabic-digits").is_none());
+        assert_eq!(
+            AnyLayout::from_name_with_options("us:se").err(),
+            Some(LayoutError::UnknownVariant)
+        );
+        assert_eq!(
+            AnyLayout::from_name_with_options("xx:se").err(),
+            Some(LayoutError::UnknownLayout)
+        );
+    }
+
+    #[test]
+    fn test_swiss_variants() {
+        for name in ["swiss:fr", "ch:fr", "fr-CH:fr", "sg:fr"] {
+            let layout = AnyLayout::from_name(name).unwrap();
+            assert!(
+                matches!(layout, AnyLayout::SwissFrench105Key(_)),
+                "{}",
+                name
+            );
+            let mut dec = EventDecoder::new(layout, HandleControl::Ignore);
+            assert_eq!(tap(&mut dec, KeyCode::Oem1), Some(DecodedKey::Unicode('é')));
+        }
+        let layout = AnyLayout::from_name_with_options("swiss:fr:numpad-comma").unwrap();
+        let mut dec = EventDecoder::new(layout, HandleControl::Ignore);
+        assert_eq!(tap(&mut dec, KeyCode::Oem1), Some(DecodedKey::Unicode('é')));
+        // And back again
+        assert!(matches!(
+            AnyLayout::from_name("fr-CH:de"),
+            Some(AnyLayout::SwissGerman105Key(_))
+        ));
+    }
+
+    #[test]
+    fn test_digits() {
+        let layout = AnyLayout::from_name_with_options("us:arabic-digits").unwrap();
+        let mut dec = EventDecoder::new(layout, HandleControl::Ignore);
+        assert_eq!(tap(&mut dec, KeyCode::Key1), Some(DecodedKey::Unicode('١')));
+        assert_eq!(
+            tap(&mut dec, KeyCode::Numpad9),
+            Some(DecodedKey::Unicode('٩'))
+        );
+        assert_eq!(tap(&mut dec, KeyCode::A), Some(DecodedKey::Unicode('a')));
+        assert_eq!(Digits::Devanagari.convert('7'), '७');
+        assert_eq!(Digits::Persian.convert('x'), 'x');
+    }
+
+    #[test]
+    fn test_numpad_decimal() {
+        let mut dec = EventDecoder::new(De105Key, HandleControl::Ignore);
+        assert_eq!(
+            tap(&mut dec, KeyCode::NumpadPeriod),
+            Some(DecodedKey::Unicode('.'))
+        );
+        let layout = De105Key::with_options(&LayoutOptions::new().numpad_decimal(',')).unwrap();
+        let mut dec = EventDecoder::new(layout, HandleControl::Ignore);
+        assert_eq!(
+            tap(&mut dec, KeyCode::NumpadPeriod),
+            Some(DecodedKey::Unicode(','))
+        );
+        // The same, by name
+        let layout = AnyLayout::from_name_with_options("de:numpad-comma").unwrap();
+        let mut dec = EventDecoder::new(layout, HandleControl::Ignore);
+        assert_eq!(
+            tap(&mut dec, KeyCode::NumpadPeriod),
+            Some(DecodedKey::Unicode(','))
+        );
+        // Without Num Lock, it's still Delete
+        tap(&mut dec, KeyCode::NumpadLock);
+        assert_eq!(
+            tap(&mut dec, KeyCode::NumpadPeriod),
+            Some(DecodedKey::Unicode('\u{7f}'))
+        );
+    }
+
+    #[test]
+    fn test_caps_affects_symbols() {
+        let layout =
+            Us104Key::with_options(&LayoutOptions::new().caps_affects_symbols(true)).unwrap();
+        let mut dec = EventDecoder::new(layout, HandleControl::Ignore);
+        assert_eq!(tap(&mut dec, KeyCode::Key1), Some(DecodedKey::Unicode('1')));
+        tap(&mut dec, KeyCode::CapsLock);
+        assert_eq!(tap(&mut dec, KeyCode::Key1), Some(DecodedKey::Unicode('!')));
+        assert_eq!(tap(&mut dec, KeyCode::A), Some(DecodedKey::Unicode('A')));
+        assert_eq!(
+            tap(&mut dec, KeyCode::Numpad1),
+            Some(DecodedKey::Unicode('1'))
+        );
+        // Shift undoes it
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(tap(&mut dec, KeyCode::Key1), Some(DecodedKey::Unicode('1')));
+    }
+}
@@ -0,0 +1,301 @@
+//! Portuguese keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A standard Portuguese (Portugal) 102-key (or 105-key including Windows
+/// keys) keyboard.
+///
+/// The `´`, `` ` ``, `~`, `^` and `¨` keys are dead keys.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct Pt105Key;
+
+impl KeyboardLayout for Pt105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        match keycode {
+            KeyCode::Oem8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('|')
+                } else {
+                    DecodedKey::Unicode('\\')
+                }
+            }
+            KeyCode::Key2 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('@')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('"')
+                } else {
+                    DecodedKey::Unicode('2')
+                }
+            }
+            KeyCode::Key3 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('£')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('#')
+                } else {
+                    DecodedKey::Unicode('3')
+                }
+            }
+            KeyCode::Key4 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('§')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('$')
+                } else {
+                    DecodedKey::Unicode('4')
+                }
+            }
+            KeyCode::Key5 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('€')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('%')
+                } else {
+                    DecodedKey::Unicode('5')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('&')
+                } else {
+                    DecodedKey::Unicode('6')
+                }
+            }
+            KeyCode::Key7 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('{')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('/')
+                } else {
+                    DecodedKey::Unicode('7')
+                }
+            }
+            KeyCode::Key8 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('[')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('(')
+                } else {
+                    DecodedKey::Unicode('8')
+                }
+            }
+            KeyCode::Key9 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode(']')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode(')')
+                } else {
+                    DecodedKey::Unicode('9')
+                }
+            }
+            KeyCode::Key0 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('}')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('=')
+                } else {
+                    DecodedKey::Unicode('0')
+                }
+            }
+            KeyCode::OemMinus => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('?')
+                } else {
+                    DecodedKey::Unicode('\'')
+                }
+            }
+            KeyCode::OemPlus => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('»')
+                } else {
+                    DecodedKey::Unicode('«')
+                }
+            }
+            KeyCode::E => modifiers.handle_ascii_4(ascii_upper!('E'), '€', '€', handle_ctrl),
+            KeyCode::Oem4 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('¨')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('*')
+                } else {
+                    DecodedKey::Unicode('+')
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('`')
+                } else {
+                    DecodedKey::DeadKey('´')
+                }
+            }
+            KeyCode::Oem1 => modifiers.handle_letter_auto('ç', 'Ç', handle_ctrl),
+            KeyCode::Oem3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ª')
+                } else {
+                    DecodedKey::Unicode('º')
+                }
+            }
+            KeyCode::Oem7 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('^')
+                } else {
+                    DecodedKey::DeadKey('~')
+                }
+            }
+            KeyCode::Oem5 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('>')
+                } else {
+                    DecodedKey::Unicode('<')
+                }
+            }
+            KeyCode::OemComma => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(';')
+                } else {
+                    DecodedKey::Unicode(',')
+                }
+            }
+            KeyCode::OemPeriod => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode('.')
+                }
+            }
+            KeyCode::Oem2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('_')
+                } else {
+                    DecodedKey::Unicode('-')
+                }
+            }
+            e => {
+                let us = super::Us104Key;
+                us.map_keycode(e, modifiers, handle_ctrl)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdpo
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '\\', '|', None, None),
+            (KeyCode::Key1, '1', '!', None, None),
+            (KeyCode::Key2, '2', '"', Some('@'), None),
+            (KeyCode::Key3, '3', '#', Some('£'), None),
+            (KeyCode::Key4, '4', '$', Some('§'), None),
+            (KeyCode::Key5, '5', '%', Some('€'), None),
+            (KeyCode::Key6, '6', '&', None, None),
+            (KeyCode::Key7, '7', '/', Some('{'), None),
+            (KeyCode::Key8, '8', '(', Some('['), None),
+            (KeyCode::Key9, '9', ')', Some(']'), None),
+            (KeyCode::Key0, '0', '=', Some('}'), None),
+            (KeyCode::OemMinus, '\'', '?', None, None),
+            (KeyCode::OemPlus, '«', '»', None, None),
+            (KeyCode::E, 'e', 'E', Some('€'), None),
+            (KeyCode::Oem4, '+', '*', Some('¨'), None),
+            (KeyCode::Oem6, '´', '`', None, None),
+            (KeyCode::Oem1, 'ç', 'Ç', None, None),
+            (KeyCode::Oem3, 'º', 'ª', None, None),
+            (KeyCode::Oem7, '~', '^', None, None),
+            (KeyCode::Oem5, '<', '>', None, None),
+            (KeyCode::OemComma, ',', ';', None, None),
+            (KeyCode::OemPeriod, '.', ':', None, None),
+            (KeyCode::Oem2, '-', '_', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&Pt105Key, ROWS);
+    }
+
+    #[test]
+    fn test_altgr_brackets() {
+        // Right Alt, and Ctrl + Left Alt, both work as AltGr. Shift doesn't
+        // change anything.
+        let altgrs = [
+            Modifiers {
+                ralt: true,
+                ..Default::default()
+            },
+            Modifiers {
+                lctrl: true,
+                lalt: true,
+                ..Default::default()
+            },
+            Modifiers {
+                ralt: true,
+                lshift: true,
+                ..Default::default()
+            },
+        ];
+        for modifiers in altgrs.iter() {
+            for (code, ch) in [
+                (KeyCode::Key7, '{'),
+                (KeyCode::Key8, '['),
+                (KeyCode::Key9, ']'),
+                (KeyCode::Key0, '}'),
+                (KeyCode::Key2, '@'),
+            ] {
+                for handle_ctrl in [HandleControl::Ignore, HandleControl::MapLettersToUnicode] {
+                    assert_eq!(
+                        Pt105Key.map_keycode(code, modifiers, handle_ctrl),
+                        DecodedKey::Unicode(ch),
+                        "{:?} with {:?}",
+                        code,
+                        modifiers
+                    );
+                }
+            }
+        }
+        // Left Alt on its own isn't AltGr
+        let modifiers = Modifiers {
+            lalt: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            Pt105Key.map_keycode(KeyCode::Key8, &modifiers, HandleControl::Ignore),
+            DecodedKey::Unicode('8')
+        );
+    }
+
+    #[test]
+    fn test_dead_keys() {
+        let mut dec = EventDecoder::new(Pt105Key, HandleControl::Ignore);
+        for (dead, shifted, letter, result) in [
+            (KeyCode::Oem7, false, KeyCode::A, 'ã'),
+            (KeyCode::Oem7, false, KeyCode::O, 'õ'),
+            (KeyCode::Oem7, true, KeyCode::E, 'ê'),
+            (KeyCode::Oem6, false, KeyCode::A, 'á'),
+            (KeyCode::Oem6, true, KeyCode::A, 'à'),
+        ] {
+            if shifted {
+                dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            }
+            assert!(matches!(
+                dec.process_keyevent(KeyEvent::new(dead, KeyState::Down)),
+                Some(DecodedKey::DeadKey(_))
+            ));
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(letter, KeyState::Down)),
+                Some(DecodedKey::Unicode(result))
+            );
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! Northern Sámi keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Northern Sámi 102-key (or 105-key including Windows keys) keyboard, as
+/// used in Sweden and Finland.
+///
+/// This is the Finnish/Swedish layout, with á š ŧ ŋ đ ž č on the letter keys
+/// and the Latin letters they replace (q w y z x) moved to AltGr. The ¨ and '
+/// symbols are also moved to AltGr.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct SeSami105Key;
+
+impl KeyboardLayout for SeSami105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let map_to_unicode = handle_ctrl == HandleControl::MapLettersToUnicode;
+        let fallback = super::FiSe105Key;
+        match keycode {
+            // ========= Row 3 (QWERTY) =========
+            KeyCode::Q => {
+                if map_to_unicode && modifiers.is_ctrl() {
+                    DecodedKey::Unicode('\u{0011}')
+                } else if modifiers.is_altgr() {
+                    if modifiers.is_caps() {
+                        DecodedKey::Unicode('Q')
+                    } else {
+                        DecodedKey::Unicode('q')
+                    }
+                } else if modifiers.is_caps() {
+                    DecodedKey::Unicode('Á')
+                } else {
+                    DecodedKey::Unicode('á')
+                }
+            }
+            KeyCode::W => {
+                if map_to_unicode && modifiers.is_ctrl() {
+                    DecodedKey::Unicode('\u{0017}')
+                } else if modifiers.is_altgr() {
+                    if modifiers.is_caps() {
+                        DecodedKey::Unicode('W')
+                    } else {
+                        DecodedKey::Unicode('w')
+                    }
+                } else if modifiers.is_caps() {
+                    DecodedKey::Unicode('Š')
+                } else {
+                    DecodedKey::Unicode('š')
+                }
+            }
+            KeyCode::Y => {
+                if map_to_unicode && modifiers.is_ctrl() {
+                    DecodedKey::Unicode('\u{0019}')
+                } else if modifiers.is_altgr() {
+                    if modifiers.is_caps() {
+                        DecodedKey::Unicode('Y')
+                    } else {
+                        DecodedKey::Unicode('y')
+                    }
+                } else if modifiers.is_caps() {
+                    DecodedKey::Unicode('Ŧ')
+                } else {
+                    DecodedKey::Unicode('ŧ')
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_altgr() {
+                    if modifiers.is_shifted() {
+                        DecodedKey::Unicode('^')
+                    } else {
+                        DecodedKey::Unicode('¨')
+                    }
+                } else if modifiers.is_caps() {
+                    DecodedKey::Unicode('Ŋ')
+                } else {
+                    DecodedKey::Unicode('ŋ')
+                }
+            }
+            // ========= Row 4 (ASDF) =========
+            KeyCode::Oem7 => {
+                if modifiers.is_altgr() {
+                    if modifiers.is_shifted() {
+                        DecodedKey::Unicode('*')
+                    } else {
+                        DecodedKey::Unicode('\'')
+                    }
+                } else if modifiers.is_caps() {
+                    DecodedKey::Unicode('Đ')
+                } else {
+                    DecodedKey::Unicode('đ')
+                }
+            }
+            // ========= Row 5 (ZXCV) =========
+            KeyCode::Z => {
+                if map_to_unicode && modifiers.is_ctrl() {
+                    DecodedKey::Unicode('\u{001A}')
+                } else if modifiers.is_altgr() {
+                    if modifiers.is_caps() {
+                        DecodedKey::Unicode('Z')
+                    } else {
+                        DecodedKey::Unicode('z')
+                    }
+                } else if modifiers.is_caps() {
+                    DecodedKey::Unicode('Ž')
+                } else {
+                    DecodedKey::Unicode('ž')
+                }
+            }
+            KeyCode::X => {
+                if map_to_unicode && modifiers.is_ctrl() {
+                    DecodedKey::Unicode('\u{0018}')
+                } else if modifiers.is_altgr() {
+                    if modifiers.is_caps() {
+                        DecodedKey::Unicode('X')
+                    } else {
+                        DecodedKey::Unicode('x')
+                    }
+                } else if modifiers.is_caps() {
+                    DecodedKey::Unicode('Č')
+                } else {
+                    DecodedKey::Unicode('č')
+                }
+            }
+            e => fallback.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{EventDecoder, KeyEvent, KeyState, ScancodeSet, ScancodeSet1};
+
+    #[test]
+    fn letters() {
+        // Codes taken from https://kbdlayout.info/kbdsmsfi/overview+scancodes
+        let mut s = ScancodeSet1::new();
+        let mut dec = EventDecoder::new(SeSami105Key, HandleControl::Ignore);
+        let data = [
+            (0x10, 'á'),
+            (0x11, 'š'),
+            (0x12, 'e'),
+            (0x15, 'ŧ'),
+            (0x1a, 'å'),
+            (0x1b, 'ŋ'),
+            (0x27, 'ö'),
+            (0x28, 'ä'),
+            (0x2b, 'đ'),
+            (0x2c, 'ž'),
+            (0x2d, 'č'),
+            (0x2e, 'c'),
+        ];
+        for (code, unicode) in data {
+            let ev = s.advance_state(code).unwrap().unwrap();
+            assert_eq!(Some(DecodedKey::Unicode(unicode)), dec.process_item(ev));
+        }
+    }
+
+    #[test]
+    fn caps_lock() {
+        let mut dec = EventDecoder::new(SeSami105Key, HandleControl::Ignore);
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        for (code, upper, lower) in [
+            (KeyCode::Q, 'Á', 'á'),
+            (KeyCode::W, 'Š', 'š'),
+            (KeyCode::Y, 'Ŧ', 'ŧ'),
+            (KeyCode::Oem6, 'Ŋ', 'ŋ'),
+            (KeyCode::Oem7, 'Đ', 'đ'),
+            (KeyCode::Z, 'Ž', 'ž'),
+            (KeyCode::X, 'Č', 'č'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(upper))
+            );
+            // Shift cancels out Caps Lock
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(lower))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        }
+    }
+
+    #[test]
+    fn altgr() {
+        let mut dec = EventDecoder::new(SeSami105Key, HandleControl::MapLettersToUnicode);
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        for (code, unicode) in [
+            (KeyCode::Q, 'q'),
+            (KeyCode::W, 'w'),
+            (KeyCode::Y, 'y'),
+            (KeyCode::Z, 'z'),
+            (KeyCode::X, 'x'),
+            (KeyCode::Oem6, '¨'),
+            (KeyCode::Oem7, '\''),
+            (KeyCode::E, '€'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(unicode))
+            );
+        }
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Q, KeyState::Down)),
+            Some(DecodedKey::Unicode('Q'))
+        );
+        // Control codes still work
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Up));
+        dec.process_keyevent(KeyEvent::new(KeyCode::LControl, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Z, KeyState::Down)),
+            Some(DecodedKey::Unicode('\u{001A}'))
+        );
+    }
+}
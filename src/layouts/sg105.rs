@@ -0,0 +1,473 @@
+//! Swiss German keyboard support
+
+use super::ConfigurableLayout;
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A standard Swiss German 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// The top row spells `QWERTZ`. Swiss French keyboards have the same keys,
+/// but with the French accented letters on the unshifted level (see
+/// [`SwissFrench105Key`]). The `´`, `^`, `` ` ``, `~` and `¨` keys are dead
+/// keys.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct SwissGerman105Key;
+
+/// A standard Swiss French 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// This is [`SwissGerman105Key`] with the French accented letters on the
+/// unshifted level and the German umlauts on Shift.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct SwissFrench105Key;
+
+/// The Swiss layouts, as one layout with a variant for each language.
+///
+/// The variants are `de` ([`SwissGerman105Key`], the default) and `fr`
+/// ([`SwissFrench105Key`]). Use [`ConfigurableLayout::from_variant`] to pick
+/// one by name.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Swiss105Key {
+    /// Swiss German, also used in Liechtenstein
+    German,
+    /// Swiss French, also used in Luxembourg
+    French,
+}
+
+impl KeyboardLayout for SwissGerman105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        match keycode {
+            // ========= Row 2 (the numbers) =========
+            KeyCode::Oem8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('°')
+                } else {
+                    DecodedKey::Unicode('§')
+                }
+            }
+            KeyCode::Key1 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¦')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('+')
+                } else {
+                    DecodedKey::Unicode('1')
+                }
+            }
+            KeyCode::Key2 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('@')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('"')
+                } else {
+                    DecodedKey::Unicode('2')
+                }
+            }
+            KeyCode::Key3 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('#')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('*')
+                } else {
+                    DecodedKey::Unicode('3')
+                }
+            }
+            KeyCode::Key4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ç')
+                } else {
+                    DecodedKey::Unicode('4')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¬')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('&')
+                } else {
+                    DecodedKey::Unicode('6')
+                }
+            }
+            KeyCode::Key7 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('|')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('/')
+                } else {
+                    DecodedKey::Unicode('7')
+                }
+            }
+            KeyCode::Key8 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¢')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('(')
+                } else {
+                    DecodedKey::Unicode('8')
+                }
+            }
+            KeyCode::Key9 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(')')
+                } else {
+                    DecodedKey::Unicode('9')
+                }
+            }
+            KeyCode::Key0 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('=')
+                } else {
+                    DecodedKey::Unicode('0')
+                }
+            }
+            KeyCode::OemMinus => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('´')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('?')
+                } else {
+                    DecodedKey::Unicode('\'')
+                }
+            }
+            KeyCode::OemPlus => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('~')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('`')
+                } else {
+                    DecodedKey::DeadKey('^')
+                }
+            }
+            // ========= Row 3 (QWERTZ) =========
+            KeyCode::E => modifiers.handle_ascii_4(ascii_upper!('E'), '€', '€', handle_ctrl),
+            KeyCode::Y => modifiers.handle_letter_auto('z', 'Z', handle_ctrl),
+            KeyCode::Oem4 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('[')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('è')
+                } else {
+                    DecodedKey::Unicode('ü')
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode(']')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('!')
+                } else {
+                    DecodedKey::DeadKey('¨')
+                }
+            }
+            // ========= Row 4 (ASDF) =========
+            KeyCode::Oem1 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('é')
+                } else {
+                    DecodedKey::Unicode('ö')
+                }
+            }
+            KeyCode::Oem3 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('{')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('à')
+                } else {
+                    DecodedKey::Unicode('ä')
+                }
+            }
+            KeyCode::Oem7 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('}')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('£')
+                } else {
+                    DecodedKey::Unicode('$')
+                }
+            }
+            // ========= Row 5 (ZXCV) =========
+            KeyCode::Oem5 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('\\')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('>')
+                } else {
+                    DecodedKey::Unicode('<')
+                }
+            }
+            KeyCode::Z => modifiers.handle_letter_auto('y', 'Y', handle_ctrl),
+            KeyCode::OemComma => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(';')
+                } else {
+                    DecodedKey::Unicode(',')
+                }
+            }
+            KeyCode::OemPeriod => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode('.')
+                }
+            }
+            KeyCode::Oem2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('_')
+                } else {
+                    DecodedKey::Unicode('-')
+                }
+            }
+            e => {
+                let us = super::Us104Key;
+                us.map_keycode(e, modifiers, handle_ctrl)
+            }
+        }
+    }
+}
+
+impl KeyboardLayout for SwissFrench105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        match keycode {
+            KeyCode::Oem4 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('[')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ü')
+                } else {
+                    DecodedKey::Unicode('è')
+                }
+            }
+            KeyCode::Oem1 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ö')
+                } else {
+                    DecodedKey::Unicode('é')
+                }
+            }
+            KeyCode::Oem3 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('{')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ä')
+                } else {
+                    DecodedKey::Unicode('à')
+                }
+            }
+            e => SwissGerman105Key.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+impl KeyboardLayout for Swiss105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        match self {
+            Swiss105Key::German => SwissGerman105Key.map_keycode(keycode, modifiers, handle_ctrl),
+            Swiss105Key::French => SwissFrench105Key.map_keycode(keycode, modifiers, handle_ctrl),
+        }
+    }
+}
+
+impl ConfigurableLayout for SwissGerman105Key {
+    fn from_variant(variant: Option<&str>) -> Option<SwissGerman105Key> {
+        match variant {
+            None | Some("de") => Some(SwissGerman105Key),
+            Some(_) => None,
+        }
+    }
+}
+
+impl ConfigurableLayout for Swiss105Key {
+    fn from_variant(variant: Option<&str>) -> Option<Swiss105Key> {
+        match variant {
+            None | Some("de") => Some(Swiss105Key::German),
+            Some("fr") => Some(Swiss105Key::French),
+            Some(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdsg
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '§', '°', None, None),
+            (KeyCode::Key1, '1', '+', Some('¦'), None),
+            (KeyCode::Key2, '2', '"', Some('@'), None),
+            (KeyCode::Key3, '3', '*', Some('#'), None),
+            (KeyCode::Key4, '4', 'ç', None, None),
+            (KeyCode::Key5, '5', '%', None, None),
+            (KeyCode::Key6, '6', '&', Some('¬'), None),
+            (KeyCode::Key7, '7', '/', Some('|'), None),
+            (KeyCode::Key8, '8', '(', Some('¢'), None),
+            (KeyCode::Key9, '9', ')', None, None),
+            (KeyCode::Key0, '0', '=', None, None),
+            (KeyCode::OemMinus, '\'', '?', Some('´'), None),
+            (KeyCode::OemPlus, '^', '`', Some('~'), None),
+            (KeyCode::E, 'e', 'E', Some('€'), None),
+            (KeyCode::Y, 'z', 'Z', None, None),
+            (KeyCode::Oem4, 'ü', 'è', Some('['), None),
+            (KeyCode::Oem6, '¨', '!', Some(']'), None),
+            (KeyCode::Oem1, 'ö', 'é', None, None),
+            (KeyCode::Oem3, 'ä', 'à', Some('{'), None),
+            (KeyCode::Oem7, '$', '£', Some('}'), None),
+            (KeyCode::Oem5, '<', '>', Some('\\'), None),
+            (KeyCode::Z, 'y', 'Y', None, None),
+            (KeyCode::OemComma, ',', ';', None, None),
+            (KeyCode::OemPeriod, '.', ':', None, None),
+            (KeyCode::Oem2, '-', '_', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&SwissGerman105Key, ROWS);
+    }
+
+    #[test]
+    fn test_french_fixture() {
+        // Taken from https://kbdlayout.info/kbdsf
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '§', '°', None, None),
+            (KeyCode::Key2, '2', '"', Some('@'), None),
+            (KeyCode::Key4, '4', 'ç', None, None),
+            (KeyCode::E, 'e', 'E', Some('€'), None),
+            (KeyCode::Y, 'z', 'Z', None, None),
+            (KeyCode::Oem4, 'è', 'ü', Some('['), None),
+            (KeyCode::Oem6, '¨', '!', Some(']'), None),
+            (KeyCode::Oem1, 'é', 'ö', None, None),
+            (KeyCode::Oem3, 'à', 'ä', Some('{'), None),
+            (KeyCode::Oem7, '$', '£', Some('}'), None),
+            (KeyCode::Oem5, '<', '>', Some('\\'), None),
+            (KeyCode::Z, 'y', 'Y', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&SwissFrench105Key, ROWS);
+    }
+
+    #[test]
+    fn test_variants() {
+        let mut modifiers = Modifiers::default();
+        for (variant, unshifted, shifted) in [
+            (None, 'ö', 'é'),
+            (Some("de"), 'ö', 'é'),
+            (Some("fr"), 'é', 'ö'),
+        ] {
+            let layout = Swiss105Key::from_variant(variant).unwrap();
+            modifiers.lshift = false;
+            assert_eq!(
+                layout.map_keycode(KeyCode::Oem1, &modifiers, HandleControl::Ignore),
+                DecodedKey::Unicode(unshifted)
+            );
+            modifiers.lshift = true;
+            assert_eq!(
+                layout.map_keycode(KeyCode::Oem1, &modifiers, HandleControl::Ignore),
+                DecodedKey::Unicode(shifted)
+            );
+        }
+        assert!(Swiss105Key::from_variant(Some("it")).is_none());
+    }
+
+    #[test]
+    fn test_three_levels() {
+        // Shift doesn't change what AltGr gives, and Ctrl + Left Alt works as
+        // AltGr too
+        let altgrs = [
+            Modifiers {
+                ralt: true,
+                ..Default::default()
+            },
+            Modifiers {
+                ralt: true,
+                lshift: true,
+                ..Default::default()
+            },
+            Modifiers {
+                lctrl: true,
+                lalt: true,
+                ..Default::default()
+            },
+        ];
+        for modifiers in altgrs.iter() {
+            for (code, ch) in [
+                (KeyCode::Key2, '@'),
+                (KeyCode::Key3, '#'),
+                (KeyCode::Key1, '¦'),
+                (KeyCode::Key8, '¢'),
+                (KeyCode::Oem4, '['),
+                (KeyCode::Oem6, ']'),
+                (KeyCode::Oem3, '{'),
+                (KeyCode::Oem7, '}'),
+                (KeyCode::Oem5, '\\'),
+            ] {
+                assert_eq!(
+                    SwissGerman105Key.map_keycode(code, modifiers, HandleControl::Ignore),
+                    DecodedKey::Unicode(ch),
+                    "{:?}",
+                    code
+                );
+            }
+            assert_eq!(
+                SwissGerman105Key.map_keycode(KeyCode::OemPlus, modifiers, HandleControl::Ignore),
+                DecodedKey::DeadKey('~')
+            );
+        }
+    }
+
+    #[test]
+    fn test_ctrl_z() {
+        // Ctrl + the key marked Z is Ctrl+Z, wherever it is
+        let modifiers = Modifiers {
+            lctrl: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            SwissGerman105Key.map_keycode(
+                KeyCode::Y,
+                &modifiers,
+                HandleControl::MapLettersToUnicode
+            ),
+            DecodedKey::Unicode('\u{001A}')
+        );
+    }
+
+    #[test]
+    fn test_dead_keys() {
+        let mut dec = EventDecoder::new(SwissGerman105Key, HandleControl::Ignore);
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::OemPlus, KeyState::Down)),
+            Some(DecodedKey::DeadKey('^'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::E, KeyState::Down)),
+            Some(DecodedKey::Unicode('ê'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem6, KeyState::Down)),
+            Some(DecodedKey::DeadKey('¨'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::I, KeyState::Down)),
+            Some(DecodedKey::Unicode('ï'))
+        );
+    }
+}
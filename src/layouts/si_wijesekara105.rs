@@ -0,0 +1,321 @@
+//! Sinhala (Wijesekara) keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Sinhala Wijesekara 102-key (or 105-key including Windows keys) keyboard.
+///
+/// The consonants and vowel signs are on the letter keys, with Shift giving
+/// the aspirated and retroflex forms. The al-lakuna (`්`) is on `A`. Sinhala
+/// has no upper and lower case, so Caps Lock does nothing.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct SiWijesekara105Key;
+
+impl KeyboardLayout for SiWijesekara105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        if handle_ctrl == HandleControl::MapLettersToUnicode && modifiers.is_ctrl() {
+            // Ctrl + a letter key still gives the control code on the keycap
+            let key = us.map_keycode(keycode, modifiers, handle_ctrl);
+            if matches!(key, DecodedKey::Unicode(ch) if ch.is_control()) {
+                return key;
+            }
+        }
+        match keycode {
+            KeyCode::Q => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0DD6}')
+                } else {
+                    DecodedKey::Unicode('\u{0DD4}')
+                }
+            }
+            KeyCode::W => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('උ')
+                } else {
+                    DecodedKey::Unicode('අ')
+                }
+            }
+            KeyCode::E => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0DD1}')
+                } else {
+                    DecodedKey::Unicode('\u{0DD0}')
+                }
+            }
+            KeyCode::R => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ඍ')
+                } else {
+                    DecodedKey::Unicode('ර')
+                }
+            }
+            KeyCode::T => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ඔ')
+                } else {
+                    DecodedKey::Unicode('එ')
+                }
+            }
+            KeyCode::Y => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ශ')
+                } else {
+                    DecodedKey::Unicode('හ')
+                }
+            }
+            KeyCode::U => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ඹ')
+                } else {
+                    DecodedKey::Unicode('ම')
+                }
+            }
+            KeyCode::I => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ෂ')
+                } else {
+                    DecodedKey::Unicode('ස')
+                }
+            }
+            KeyCode::O => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ධ')
+                } else {
+                    DecodedKey::Unicode('ද')
+                }
+            }
+            KeyCode::P => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ඡ')
+                } else {
+                    DecodedKey::Unicode('ච')
+                }
+            }
+            KeyCode::Oem4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ඥ')
+                } else {
+                    DecodedKey::Unicode('ඤ')
+                }
+            }
+            KeyCode::A => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0DDF}')
+                } else {
+                    DecodedKey::Unicode('\u{0DCA}')
+                }
+            }
+            KeyCode::S => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0DD3}')
+                } else {
+                    DecodedKey::Unicode('\u{0DD2}')
+                }
+            }
+            KeyCode::D => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{0DD8}')
+                } else {
+                    DecodedKey::Unicode('\u{0DCF}')
+                }
+            }
+            KeyCode::F => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ෆ')
+                } else {
+                    DecodedKey::Unicode('\u{0DD9}')
+                }
+            }
+            KeyCode::G => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ඨ')
+                } else {
+                    DecodedKey::Unicode('ට')
+                }
+            }
+            KeyCode::H => DecodedKey::Unicode('ය'),
+            KeyCode::J => DecodedKey::Unicode('ව'),
+            KeyCode::K => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ණ')
+                } else {
+                    DecodedKey::Unicode('න')
+                }
+            }
+            KeyCode::L => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ඛ')
+                } else {
+                    DecodedKey::Unicode('ක')
+                }
+            }
+            KeyCode::Oem1 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ථ')
+                } else {
+                    DecodedKey::Unicode('ත')
+                }
+            }
+            KeyCode::Z => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('"')
+                } else {
+                    DecodedKey::Unicode('\'')
+                }
+            }
+            KeyCode::X => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ඞ')
+                } else {
+                    DecodedKey::Unicode('\u{0D82}')
+                }
+            }
+            KeyCode::C => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ඣ')
+                } else {
+                    DecodedKey::Unicode('ජ')
+                }
+            }
+            KeyCode::V => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ඪ')
+                } else {
+                    DecodedKey::Unicode('ඩ')
+                }
+            }
+            KeyCode::B => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ඊ')
+                } else {
+                    DecodedKey::Unicode('ඉ')
+                }
+            }
+            KeyCode::N => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('භ')
+                } else {
+                    DecodedKey::Unicode('බ')
+                }
+            }
+            KeyCode::M => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ඵ')
+                } else {
+                    DecodedKey::Unicode('ප')
+                }
+            }
+            KeyCode::OemComma => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ළ')
+                } else {
+                    DecodedKey::Unicode('ල')
+                }
+            }
+            KeyCode::OemPeriod => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ඝ')
+                } else {
+                    DecodedKey::Unicode('ග')
+                }
+            }
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Based on https://kbdlayout.info/kbdsn1
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Q, '\u{0DD4}', '\u{0DD6}', None, None),
+            (KeyCode::W, 'අ', 'උ', None, None),
+            (KeyCode::E, '\u{0DD0}', '\u{0DD1}', None, None),
+            (KeyCode::R, 'ර', 'ඍ', None, None),
+            (KeyCode::T, 'එ', 'ඔ', None, None),
+            (KeyCode::Y, 'හ', 'ශ', None, None),
+            (KeyCode::U, 'ම', 'ඹ', None, None),
+            (KeyCode::I, 'ස', 'ෂ', None, None),
+            (KeyCode::O, 'ද', 'ධ', None, None),
+            (KeyCode::P, 'ච', 'ඡ', None, None),
+            (KeyCode::Oem4, 'ඤ', 'ඥ', None, None),
+            (KeyCode::A, '\u{0DCA}', '\u{0DDF}', None, None),
+            (KeyCode::S, '\u{0DD2}', '\u{0DD3}', None, None),
+            (KeyCode::D, '\u{0DCF}', '\u{0DD8}', None, None),
+            (KeyCode::F, '\u{0DD9}', 'ෆ', None, None),
+            (KeyCode::G, 'ට', 'ඨ', None, None),
+            (KeyCode::H, 'ය', 'ය', None, None),
+            (KeyCode::J, 'ව', 'ව', None, None),
+            (KeyCode::K, 'න', 'ණ', None, None),
+            (KeyCode::L, 'ක', 'ඛ', None, None),
+            (KeyCode::Oem1, 'ත', 'ථ', None, None),
+            (KeyCode::Z, '\'', '"', None, None),
+            (KeyCode::X, '\u{0D82}', 'ඞ', None, None),
+            (KeyCode::C, 'ජ', 'ඣ', None, None),
+            (KeyCode::V, 'ඩ', 'ඪ', None, None),
+            (KeyCode::B, 'ඉ', 'ඊ', None, None),
+            (KeyCode::N, 'බ', 'භ', None, None),
+            (KeyCode::M, 'ප', 'ඵ', None, None),
+            (KeyCode::OemComma, 'ල', 'ළ', None, None),
+            (KeyCode::OemPeriod, 'ග', 'ඝ', None, None),
+            (KeyCode::Key1, '1', '!', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&SiWijesekara105Key, ROWS);
+    }
+
+    #[test]
+    fn test_consonant_pairs() {
+        let mut dec = EventDecoder::new(SiWijesekara105Key, HandleControl::Ignore);
+        for (code, base, shifted) in [
+            (KeyCode::L, 'ක', 'ඛ'),
+            (KeyCode::G, 'ට', 'ඨ'),
+            (KeyCode::O, 'ද', 'ධ'),
+            (KeyCode::N, 'බ', 'භ'),
+            (KeyCode::K, 'න', 'ණ'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(base))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(shifted))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        }
+    }
+
+    #[test]
+    fn test_caps_lock_ignored() {
+        let mut dec = EventDecoder::new(SiWijesekara105Key, HandleControl::Ignore);
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        // The al-lakuna
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::A, KeyState::Down)),
+            Some(DecodedKey::Unicode('\u{0DCA}'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::L, KeyState::Down)),
+            Some(DecodedKey::Unicode('ක'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::L, KeyState::Down)),
+            Some(DecodedKey::Unicode('ඛ'))
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! Serbian (Latin) keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A standard Serbian (Latin) 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// The top row spells `QWERTZ`. AltGr on the number row gives dead keys for
+/// most of the accents used in Central Europe, and Shift on the key left of
+/// `1` gives a dead `¨`.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct SrLatn105Key;
+
+impl KeyboardLayout for SrLatn105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        match keycode {
+            KeyCode::Oem8 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('¨')
+                } else {
+                    DecodedKey::DeadKey('¸')
+                }
+            }
+            KeyCode::Key1 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('~')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('!')
+                } else {
+                    DecodedKey::Unicode('1')
+                }
+            }
+            KeyCode::Key2 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('ˇ')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('"')
+                } else {
+                    DecodedKey::Unicode('2')
+                }
+            }
+            KeyCode::Key3 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('^')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('#')
+                } else {
+                    DecodedKey::Unicode('3')
+                }
+            }
+            KeyCode::Key4 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('˘')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('$')
+                } else {
+                    DecodedKey::Unicode('4')
+                }
+            }
+            KeyCode::Key5 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('˚')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('%')
+                } else {
+                    DecodedKey::Unicode('5')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('˛')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('&')
+                } else {
+                    DecodedKey::Unicode('6')
+                }
+            }
+            KeyCode::Key7 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('`')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('/')
+                } else {
+                    DecodedKey::Unicode('7')
+                }
+            }
+            KeyCode::Key8 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('˙')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('(')
+                } else {
+                    DecodedKey::Unicode('8')
+                }
+            }
+            KeyCode::Key9 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('´')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode(')')
+                } else {
+                    DecodedKey::Unicode('9')
+                }
+            }
+            KeyCode::Key0 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('˝')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('=')
+                } else {
+                    DecodedKey::Unicode('0')
+                }
+            }
+            KeyCode::OemMinus => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('¨')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('?')
+                } else {
+                    DecodedKey::Unicode('\'')
+                }
+            }
+            KeyCode::OemPlus => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('¸')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('*')
+                } else {
+                    DecodedKey::Unicode('+')
+                }
+            }
+            KeyCode::Q => modifiers.handle_ascii_4(ascii_upper!('Q'), '\\', '\\', handle_ctrl),
+            KeyCode::W => modifiers.handle_ascii_4(ascii_upper!('W'), '|', '|', handle_ctrl),
+            KeyCode::E => modifiers.handle_ascii_4(ascii_upper!('E'), '€', '€', handle_ctrl),
+            KeyCode::Y => modifiers.handle_letter_auto('z', 'Z', handle_ctrl),
+            KeyCode::F => modifiers.handle_ascii_4(ascii_upper!('F'), '[', '[', handle_ctrl),
+            KeyCode::G => modifiers.handle_ascii_4(ascii_upper!('G'), ']', ']', handle_ctrl),
+            KeyCode::K => modifiers.handle_ascii_4(ascii_upper!('K'), 'ł', 'ł', handle_ctrl),
+            KeyCode::L => modifiers.handle_ascii_4(ascii_upper!('L'), 'Ł', 'Ł', handle_ctrl),
+            KeyCode::Z => modifiers.handle_letter_auto('y', 'Y', handle_ctrl),
+            KeyCode::V => modifiers.handle_ascii_4(ascii_upper!('V'), '@', '@', handle_ctrl),
+            KeyCode::B => modifiers.handle_ascii_4(ascii_upper!('B'), '{', '{', handle_ctrl),
+            KeyCode::N => modifiers.handle_ascii_4(ascii_upper!('N'), '}', '}', handle_ctrl),
+            KeyCode::M => modifiers.handle_ascii_4(ascii_upper!('M'), '§', '§', handle_ctrl),
+            KeyCode::Oem4 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('÷')
+                } else {
+                    modifiers.handle_letter_auto('š', 'Š', handle_ctrl)
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('×')
+                } else {
+                    modifiers.handle_letter_auto('đ', 'Đ', handle_ctrl)
+                }
+            }
+            KeyCode::Oem1 => modifiers.handle_letter_auto('č', 'Č', handle_ctrl),
+            KeyCode::Oem3 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('ß')
+                } else {
+                    modifiers.handle_letter_auto('ć', 'Ć', handle_ctrl)
+                }
+            }
+            KeyCode::Oem7 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('¤')
+                } else {
+                    modifiers.handle_letter_auto('ž', 'Ž', handle_ctrl)
+                }
+            }
+            KeyCode::Oem5 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('>')
+                } else {
+                    DecodedKey::Unicode('<')
+                }
+            }
+            KeyCode::OemComma => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('<')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode(';')
+                } else {
+                    DecodedKey::Unicode(',')
+                }
+            }
+            KeyCode::OemPeriod => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('>')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode('.')
+                }
+            }
+            KeyCode::Oem2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('_')
+                } else {
+                    DecodedKey::Unicode('-')
+                }
+            }
+            KeyCode::NumpadPeriod => {
+                if modifiers.numlock {
+                    DecodedKey::Unicode(',')
+                } else {
+                    DecodedKey::Unicode(127.into())
+                }
+            }
+            e => {
+                let us = super::Us104Key;
+                us.map_keycode(e, modifiers, handle_ctrl)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState, Keyboard, ScancodeSet1};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdycl, with the ring above as
+        // U+02DA rather than a degree sign
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '¸', '¨', None, None),
+            (KeyCode::Key1, '1', '!', Some('~'), None),
+            (KeyCode::Key2, '2', '"', Some('ˇ'), None),
+            (KeyCode::Key3, '3', '#', Some('^'), None),
+            (KeyCode::Key4, '4', '$', Some('˘'), None),
+            (KeyCode::Key5, '5', '%', Some('˚'), None),
+            (KeyCode::Key6, '6', '&', Some('˛'), None),
+            (KeyCode::Key7, '7', '/', Some('`'), None),
+            (KeyCode::Key8, '8', '(', Some('˙'), None),
+            (KeyCode::Key9, '9', ')', Some('´'), None),
+            (KeyCode::Key0, '0', '=', Some('˝'), None),
+            (KeyCode::OemMinus, '\'', '?', Some('¨'), None),
+            (KeyCode::OemPlus, '+', '*', Some('¸'), None),
+            (KeyCode::Q, 'q', 'Q', Some('\\'), None),
+            (KeyCode::W, 'w', 'W', Some('|'), None),
+            (KeyCode::E, 'e', 'E', Some('€'), None),
+            (KeyCode::Y, 'z', 'Z', None, None),
+            (KeyCode::F, 'f', 'F', Some('['), None),
+            (KeyCode::G, 'g', 'G', Some(']'), None),
+            (KeyCode::K, 'k', 'K', Some('ł'), None),
+            (KeyCode::L, 'l', 'L', Some('Ł'), None),
+            (KeyCode::Z, 'y', 'Y', None, None),
+            (KeyCode::V, 'v', 'V', Some('@'), None),
+            (KeyCode::B, 'b', 'B', Some('{'), None),
+            (KeyCode::N, 'n', 'N', Some('}'), None),
+            (KeyCode::M, 'm', 'M', Some('§'), None),
+            (KeyCode::Oem4, 'š', 'Š', Some('÷'), None),
+            (KeyCode::Oem6, 'đ', 'Đ', Some('×'), None),
+            (KeyCode::Oem1, 'č', 'Č', None, None),
+            (KeyCode::Oem3, 'ć', 'Ć', Some('ß'), None),
+            (KeyCode::Oem7, 'ž', 'Ž', Some('¤'), None),
+            (KeyCode::Oem5, '<', '>', None, None),
+            (KeyCode::OemComma, ',', ';', Some('<'), None),
+            (KeyCode::OemPeriod, '.', ':', Some('>'), None),
+            (KeyCode::Oem2, '-', '_', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&SrLatn105Key, ROWS);
+    }
+
+    #[test]
+    fn test_oem_scancodes() {
+        let mut k = Keyboard::new(ScancodeSet1::new(), SrLatn105Key, HandleControl::Ignore);
+        for (code, ch) in [
+            (0x1A, 'š'),
+            (0x1B, 'đ'),
+            (0x27, 'č'),
+            (0x28, 'ć'),
+            (0x2B, 'ž'),
+        ] {
+            let item = k.add_byte(code).unwrap().unwrap();
+            assert_eq!(k.process_item(item), Some(DecodedKey::Unicode(ch)));
+            let item = k.add_byte(code | 0x80).unwrap().unwrap();
+            assert_eq!(k.process_item(item), None);
+        }
+        // And in upper case
+        let shift = k.add_byte(0x2A).unwrap().unwrap();
+        k.process_item(shift);
+        let item = k.add_byte(0x1B).unwrap().unwrap();
+        assert_eq!(k.process_item(item), Some(DecodedKey::Unicode('Đ')));
+    }
+
+    #[test]
+    fn test_dead_keys() {
+        let mut dec = EventDecoder::new(SrLatn105Key, HandleControl::Ignore);
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Key2, KeyState::Down)),
+            Some(DecodedKey::DeadKey('ˇ'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Up));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::R, KeyState::Down)),
+            Some(DecodedKey::Unicode('ř'))
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Swedish Dvorak (Svorak) keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Swedish Dvorak (Svorak A5) 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// The letters are rearranged Dvorak-style, with å, ä and ö on the Q, `<` and
+/// Z keys. The number row, and everything on AltGr, is the same as on
+/// [`FiSe105Key`](super::FiSe105Key).
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct Svorak105Key;
+
+impl KeyboardLayout for Svorak105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let fallback = super::FiSe105Key;
+        match keycode {
+            KeyCode::Q
+            | KeyCode::W
+            | KeyCode::E
+            | KeyCode::R
+            | KeyCode::T
+            | KeyCode::Y
+            | KeyCode::U
+            | KeyCode::I
+            | KeyCode::O
+            | KeyCode::P
+            | KeyCode::Oem4
+            | KeyCode::A
+            | KeyCode::S
+            | KeyCode::D
+            | KeyCode::F
+            | KeyCode::G
+            | KeyCode::H
+            | KeyCode::J
+            | KeyCode::K
+            | KeyCode::L
+            | KeyCode::Oem1
+            | KeyCode::Oem3
+            | KeyCode::Oem7
+            | KeyCode::Oem5
+            | KeyCode::Z
+            | KeyCode::X
+            | KeyCode::C
+            | KeyCode::V
+            | KeyCode::B
+            | KeyCode::N
+            | KeyCode::M
+            | KeyCode::OemComma
+            | KeyCode::OemPeriod
+            | KeyCode::Oem2
+                if modifiers.is_altgr() =>
+            {
+                // What's printed on a Swedish keycap
+                fallback.map_keycode(keycode, modifiers, handle_ctrl)
+            }
+            KeyCode::Q => modifiers.handle_letter_auto('å', 'Å', handle_ctrl),
+            KeyCode::W => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(';')
+                } else {
+                    DecodedKey::Unicode(',')
+                }
+            }
+            KeyCode::E => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode('.')
+                }
+            }
+            KeyCode::R => modifiers.handle_letter_auto('p', 'P', handle_ctrl),
+            KeyCode::T => modifiers.handle_letter_auto('y', 'Y', handle_ctrl),
+            KeyCode::Y => modifiers.handle_letter_auto('f', 'F', handle_ctrl),
+            KeyCode::U => modifiers.handle_letter_auto('g', 'G', handle_ctrl),
+            KeyCode::I => modifiers.handle_letter_auto('c', 'C', handle_ctrl),
+            KeyCode::O => modifiers.handle_letter_auto('r', 'R', handle_ctrl),
+            KeyCode::P => modifiers.handle_letter_auto('l', 'L', handle_ctrl),
+            KeyCode::Oem4 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('*')
+                } else {
+                    DecodedKey::Unicode('\'')
+                }
+            }
+            KeyCode::A => modifiers.handle_letter_auto('a', 'A', handle_ctrl),
+            KeyCode::S => modifiers.handle_letter_auto('o', 'O', handle_ctrl),
+            KeyCode::D => modifiers.handle_letter_auto('e', 'E', handle_ctrl),
+            KeyCode::F => modifiers.handle_letter_auto('u', 'U', handle_ctrl),
+            KeyCode::G => modifiers.handle_letter_auto('i', 'I', handle_ctrl),
+            KeyCode::H => modifiers.handle_letter_auto('d', 'D', handle_ctrl),
+            KeyCode::J => modifiers.handle_letter_auto('h', 'H', handle_ctrl),
+            KeyCode::K => modifiers.handle_letter_auto('t', 'T', handle_ctrl),
+            KeyCode::L => modifiers.handle_letter_auto('n', 'N', handle_ctrl),
+            KeyCode::Oem1 => modifiers.handle_letter_auto('s', 'S', handle_ctrl),
+            KeyCode::Oem3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('_')
+                } else {
+                    DecodedKey::Unicode('-')
+                }
+            }
+            KeyCode::Oem7 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('>')
+                } else {
+                    DecodedKey::Unicode('<')
+                }
+            }
+            KeyCode::Oem5 => modifiers.handle_letter_auto('ä', 'Ä', handle_ctrl),
+            KeyCode::Z => modifiers.handle_letter_auto('ö', 'Ö', handle_ctrl),
+            KeyCode::X => modifiers.handle_letter_auto('q', 'Q', handle_ctrl),
+            KeyCode::C => modifiers.handle_letter_auto('j', 'J', handle_ctrl),
+            KeyCode::V => modifiers.handle_letter_auto('k', 'K', handle_ctrl),
+            KeyCode::B => modifiers.handle_letter_auto('x', 'X', handle_ctrl),
+            KeyCode::N => modifiers.handle_letter_auto('b', 'B', handle_ctrl),
+            KeyCode::M => modifiers.handle_letter_auto('m', 'M', handle_ctrl),
+            KeyCode::OemComma => modifiers.handle_letter_auto('w', 'W', handle_ctrl),
+            KeyCode::OemPeriod => modifiers.handle_letter_auto('v', 'V', handle_ctrl),
+            KeyCode::Oem2 => modifiers.handle_letter_auto('z', 'Z', handle_ctrl),
+            e => fallback.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Svorak A5, from https://sv.wikipedia.org/wiki/Svorak
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Key2, '2', '"', Some('@'), None),
+            (KeyCode::Q, 'å', 'Å', None, None),
+            (KeyCode::W, ',', ';', None, None),
+            (KeyCode::E, '.', ':', None, None),
+            (KeyCode::R, 'p', 'P', None, None),
+            (KeyCode::T, 'y', 'Y', None, None),
+            (KeyCode::Y, 'f', 'F', None, None),
+            (KeyCode::U, 'g', 'G', None, None),
+            (KeyCode::I, 'c', 'C', None, None),
+            (KeyCode::O, 'r', 'R', None, None),
+            (KeyCode::P, 'l', 'L', None, None),
+            (KeyCode::Oem4, '\'', '*', None, None),
+            (KeyCode::A, 'a', 'A', None, None),
+            (KeyCode::S, 'o', 'O', None, None),
+            (KeyCode::D, 'e', 'E', None, None),
+            (KeyCode::F, 'u', 'U', None, None),
+            (KeyCode::G, 'i', 'I', None, None),
+            (KeyCode::H, 'd', 'D', None, None),
+            (KeyCode::J, 'h', 'H', None, None),
+            (KeyCode::K, 't', 'T', None, None),
+            (KeyCode::L, 'n', 'N', None, None),
+            (KeyCode::Oem1, 's', 'S', None, None),
+            (KeyCode::Oem3, '-', '_', None, None),
+            (KeyCode::Oem7, '<', '>', None, None),
+            (KeyCode::Oem5, 'ä', 'Ä', None, None),
+            (KeyCode::Z, 'ö', 'Ö', None, None),
+            (KeyCode::X, 'q', 'Q', None, None),
+            (KeyCode::C, 'j', 'J', None, None),
+            (KeyCode::V, 'k', 'K', None, None),
+            (KeyCode::B, 'x', 'X', None, None),
+            (KeyCode::N, 'b', 'B', None, None),
+            (KeyCode::M, 'm', 'M', None, None),
+            (KeyCode::OemComma, 'w', 'W', None, None),
+            (KeyCode::OemPeriod, 'v', 'V', None, None),
+            (KeyCode::Oem2, 'z', 'Z', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&Svorak105Key, ROWS);
+    }
+
+    #[test]
+    fn test_alphabet() {
+        let mut dec = EventDecoder::new(Svorak105Key, HandleControl::Ignore);
+        // Find the key for each letter, then check it types that letter
+        let modifiers = Modifiers::default();
+        for letter in "abcdefghijklmnopqrstuvwxyzåäö".chars() {
+            let code = KeyCode::ALL
+                .iter()
+                .copied()
+                .find(|&code| {
+                    Svorak105Key.map_keycode(code, &modifiers, HandleControl::Ignore)
+                        == DecodedKey::Unicode(letter)
+                })
+                .unwrap_or_else(|| panic!("no key types {:?}", letter));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(letter))
+            );
+            dec.process_keyevent(KeyEvent::new(code, KeyState::Up));
+        }
+    }
+
+    #[test]
+    fn test_swedish_letters() {
+        let mut dec = EventDecoder::new(Svorak105Key, HandleControl::Ignore);
+        for (code, letter) in [(KeyCode::Q, 'å'), (KeyCode::Oem5, 'ä'), (KeyCode::Z, 'ö')] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(letter))
+            );
+        }
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Z, KeyState::Down)),
+            Some(DecodedKey::Unicode('Ö'))
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Tamil 99 keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Tamil 99 keyboard.
+///
+/// The vowels are under the left hand and the consonants under the right,
+/// with the pulli (`்`) on `F`. Shift on the top row gives the Grantha
+/// letters (`ஸ`, `ஷ`, `ஜ`, `ஹ`, `ஶ`), and Shift + `F` the aytham (`ஃ`). The
+/// other letter keys type the same with or without Shift. Tamil has no
+/// upper and lower case, so Caps Lock does nothing.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct Ta99Key;
+
+impl KeyboardLayout for Ta99Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        if handle_ctrl == HandleControl::MapLettersToUnicode && modifiers.is_ctrl() {
+            // Ctrl + a letter key still gives the control code on the keycap
+            let key = us.map_keycode(keycode, modifiers, handle_ctrl);
+            if matches!(key, DecodedKey::Unicode(ch) if ch.is_control()) {
+                return key;
+            }
+        }
+        match keycode {
+            KeyCode::Q => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ஸ')
+                } else {
+                    DecodedKey::Unicode('ஆ')
+                }
+            }
+            KeyCode::W => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ஷ')
+                } else {
+                    DecodedKey::Unicode('ஈ')
+                }
+            }
+            KeyCode::E => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ஜ')
+                } else {
+                    DecodedKey::Unicode('ஊ')
+                }
+            }
+            KeyCode::R => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ஹ')
+                } else {
+                    DecodedKey::Unicode('ஐ')
+                }
+            }
+            KeyCode::T => DecodedKey::Unicode('ஏ'),
+            KeyCode::Y => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ஶ')
+                } else {
+                    DecodedKey::Unicode('ள')
+                }
+            }
+            KeyCode::U => DecodedKey::Unicode('ற'),
+            KeyCode::I => DecodedKey::Unicode('ன'),
+            KeyCode::O => DecodedKey::Unicode('ட'),
+            KeyCode::P => DecodedKey::Unicode('ண'),
+            KeyCode::Oem4 => DecodedKey::Unicode('ச'),
+            KeyCode::Oem6 => DecodedKey::Unicode('ஞ'),
+            KeyCode::A => DecodedKey::Unicode('அ'),
+            KeyCode::S => DecodedKey::Unicode('இ'),
+            KeyCode::D => DecodedKey::Unicode('உ'),
+            KeyCode::F => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ஃ')
+                } else {
+                    DecodedKey::Unicode('\u{0BCD}')
+                }
+            }
+            KeyCode::G => DecodedKey::Unicode('எ'),
+            KeyCode::H => DecodedKey::Unicode('க'),
+            KeyCode::J => DecodedKey::Unicode('ப'),
+            KeyCode::K => DecodedKey::Unicode('ம'),
+            KeyCode::L => DecodedKey::Unicode('த'),
+            KeyCode::Oem1 => DecodedKey::Unicode('ந'),
+            KeyCode::Oem3 => DecodedKey::Unicode('ய'),
+            KeyCode::Z => DecodedKey::Unicode('ஔ'),
+            KeyCode::X => DecodedKey::Unicode('ஓ'),
+            KeyCode::C => DecodedKey::Unicode('ஒ'),
+            KeyCode::V => DecodedKey::Unicode('வ'),
+            KeyCode::B => DecodedKey::Unicode('ங'),
+            KeyCode::N => DecodedKey::Unicode('ல'),
+            KeyCode::M => DecodedKey::Unicode('ர'),
+            KeyCode::Oem2 => DecodedKey::Unicode('ழ'),
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Based on https://kbdlayout.info/kbdtam99
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Q, 'ஆ', 'ஸ', None, None),
+            (KeyCode::W, 'ஈ', 'ஷ', None, None),
+            (KeyCode::E, 'ஊ', 'ஜ', None, None),
+            (KeyCode::R, 'ஐ', 'ஹ', None, None),
+            (KeyCode::T, 'ஏ', 'ஏ', None, None),
+            (KeyCode::Y, 'ள', 'ஶ', None, None),
+            (KeyCode::U, 'ற', 'ற', None, None),
+            (KeyCode::I, 'ன', 'ன', None, None),
+            (KeyCode::O, 'ட', 'ட', None, None),
+            (KeyCode::P, 'ண', 'ண', None, None),
+            (KeyCode::Oem4, 'ச', 'ச', None, None),
+            (KeyCode::Oem6, 'ஞ', 'ஞ', None, None),
+            (KeyCode::A, 'அ', 'அ', None, None),
+            (KeyCode::S, 'இ', 'இ', None, None),
+            (KeyCode::D, 'உ', 'உ', None, None),
+            (KeyCode::F, '\u{0BCD}', 'ஃ', None, None),
+            (KeyCode::G, 'எ', 'எ', None, None),
+            (KeyCode::H, 'க', 'க', None, None),
+            (KeyCode::J, 'ப', 'ப', None, None),
+            (KeyCode::K, 'ம', 'ம', None, None),
+            (KeyCode::L, 'த', 'த', None, None),
+            (KeyCode::Oem1, 'ந', 'ந', None, None),
+            (KeyCode::Oem3, 'ய', 'ய', None, None),
+            (KeyCode::Z, 'ஔ', 'ஔ', None, None),
+            (KeyCode::X, 'ஓ', 'ஓ', None, None),
+            (KeyCode::C, 'ஒ', 'ஒ', None, None),
+            (KeyCode::V, 'வ', 'வ', None, None),
+            (KeyCode::B, 'ங', 'ங', None, None),
+            (KeyCode::N, 'ல', 'ல', None, None),
+            (KeyCode::M, 'ர', 'ர', None, None),
+            (KeyCode::Oem2, 'ழ', 'ழ', None, None),
+            (KeyCode::Key1, '1', '!', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&Ta99Key, ROWS);
+    }
+
+    #[test]
+    fn test_levels() {
+        let mut dec = EventDecoder::new(Ta99Key, HandleControl::Ignore);
+        // Caps Lock does nothing
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        for (code, base, shifted) in [
+            (KeyCode::Q, 'ஆ', 'ஸ'),
+            (KeyCode::W, 'ஈ', 'ஷ'),
+            (KeyCode::E, 'ஊ', 'ஜ'),
+            (KeyCode::R, 'ஐ', 'ஹ'),
+            (KeyCode::F, '\u{0BCD}', 'ஃ'),
+            (KeyCode::H, 'க', 'க'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(base))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(shifted))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Tifinagh (Berber) keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A Tifinagh (Berber) keyboard, with the IRCAM letters.
+///
+/// Each letter key types the Tifinagh letter for its sound (e.g. `A` is `ⴰ`,
+/// `C` is `ⵛ`), with Shift giving the emphatic form where there is one (e.g.
+/// Shift + `D` is `ⴹ`) and Shift + `W` giving the labialisation mark (`ⵯ`).
+/// Tifinagh has no upper and lower case, so Caps Lock does nothing. The
+/// other keys are as on a US keyboard.
+pub struct TifinaghKey;
+
+impl KeyboardLayout for TifinaghKey {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        if handle_ctrl == HandleControl::MapLettersToUnicode && modifiers.is_ctrl() {
+            // Ctrl + a letter key still gives the control code on the keycap
+            let key = us.map_keycode(keycode, modifiers, handle_ctrl);
+            if matches!(key, DecodedKey::Unicode(ch) if ch.is_control()) {
+                return key;
+            }
+        }
+        match keycode {
+            KeyCode::Q => DecodedKey::Unicode('ⵇ'),
+            KeyCode::W => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\u{2D6F}')
+                } else {
+                    DecodedKey::Unicode('ⵡ')
+                }
+            }
+            KeyCode::E => DecodedKey::Unicode('ⴻ'),
+            KeyCode::R => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ⵕ')
+                } else {
+                    DecodedKey::Unicode('ⵔ')
+                }
+            }
+            KeyCode::T => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ⵟ')
+                } else {
+                    DecodedKey::Unicode('ⵜ')
+                }
+            }
+            KeyCode::Y => DecodedKey::Unicode('ⵢ'),
+            KeyCode::U => DecodedKey::Unicode('ⵓ'),
+            KeyCode::I => DecodedKey::Unicode('ⵉ'),
+            KeyCode::O => DecodedKey::Unicode('ⵄ'),
+            KeyCode::P => DecodedKey::Unicode('ⵒ'),
+            KeyCode::A => DecodedKey::Unicode('ⴰ'),
+            KeyCode::S => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ⵚ')
+                } else {
+                    DecodedKey::Unicode('ⵙ')
+                }
+            }
+            KeyCode::D => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ⴹ')
+                } else {
+                    DecodedKey::Unicode('ⴷ')
+                }
+            }
+            KeyCode::F => DecodedKey::Unicode('ⴼ'),
+            KeyCode::G => DecodedKey::Unicode('ⴳ'),
+            KeyCode::H => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ⵃ')
+                } else {
+                    DecodedKey::Unicode('ⵀ')
+                }
+            }
+            KeyCode::J => DecodedKey::Unicode('ⵊ'),
+            KeyCode::K => DecodedKey::Unicode('ⴽ'),
+            KeyCode::L => DecodedKey::Unicode('ⵍ'),
+            KeyCode::Z => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('ⵥ')
+                } else {
+                    DecodedKey::Unicode('ⵣ')
+                }
+            }
+            KeyCode::X => DecodedKey::Unicode('ⵅ'),
+            KeyCode::C => DecodedKey::Unicode('ⵛ'),
+            KeyCode::V => DecodedKey::Unicode('ⵖ'),
+            KeyCode::B => DecodedKey::Unicode('ⴱ'),
+            KeyCode::N => DecodedKey::Unicode('ⵏ'),
+            KeyCode::M => DecodedKey::Unicode('ⵎ'),
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // IRCAM letters, placed by sound
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Q, 'ⵇ', 'ⵇ', None, None),
+            (KeyCode::W, 'ⵡ', '\u{2D6F}', None, None),
+            (KeyCode::E, 'ⴻ', 'ⴻ', None, None),
+            (KeyCode::R, 'ⵔ', 'ⵕ', None, None),
+            (KeyCode::T, 'ⵜ', 'ⵟ', None, None),
+            (KeyCode::Y, 'ⵢ', 'ⵢ', None, None),
+            (KeyCode::U, 'ⵓ', 'ⵓ', None, None),
+            (KeyCode::I, 'ⵉ', 'ⵉ', None, None),
+            (KeyCode::O, 'ⵄ', 'ⵄ', None, None),
+            (KeyCode::P, 'ⵒ', 'ⵒ', None, None),
+            (KeyCode::A, 'ⴰ', 'ⴰ', None, None),
+            (KeyCode::S, 'ⵙ', 'ⵚ', None, None),
+            (KeyCode::D, 'ⴷ', 'ⴹ', None, None),
+            (KeyCode::F, 'ⴼ', 'ⴼ', None, None),
+            (KeyCode::G, 'ⴳ', 'ⴳ', None, None),
+            (KeyCode::H, 'ⵀ', 'ⵃ', None, None),
+            (KeyCode::J, 'ⵊ', 'ⵊ', None, None),
+            (KeyCode::K, 'ⴽ', 'ⴽ', None, None),
+            (KeyCode::L, 'ⵍ', 'ⵍ', None, None),
+            (KeyCode::Z, 'ⵣ', 'ⵥ', None, None),
+            (KeyCode::X, 'ⵅ', 'ⵅ', None, None),
+            (KeyCode::C, 'ⵛ', 'ⵛ', None, None),
+            (KeyCode::V, 'ⵖ', 'ⵖ', None, None),
+            (KeyCode::B, 'ⴱ', 'ⴱ', None, None),
+            (KeyCode::N, 'ⵏ', 'ⵏ', None, None),
+            (KeyCode::M, 'ⵎ', 'ⵎ', None, None),
+            (KeyCode::Key1, '1', '!', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&TifinaghKey, ROWS);
+    }
+
+    #[test]
+    fn test_levels() {
+        let mut dec = EventDecoder::new(TifinaghKey, HandleControl::Ignore);
+        for (code, base, shifted) in [
+            (KeyCode::D, 'ⴷ', 'ⴹ'),
+            (KeyCode::S, 'ⵙ', 'ⵚ'),
+            (KeyCode::Z, 'ⵣ', 'ⵥ'),
+            (KeyCode::W, 'ⵡ', '\u{2D6F}'),
+            (KeyCode::A, 'ⴰ', 'ⴰ'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(base))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(shifted))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        }
+    }
+
+    #[test]
+    fn test_caps_lock_ignored() {
+        let mut dec = EventDecoder::new(TifinaghKey, HandleControl::Ignore);
+        dec.process_keyevent(KeyEvent::new(KeyCode::CapsLock, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::T, KeyState::Down)),
+            Some(DecodedKey::Unicode('ⵜ'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::C, KeyState::Down)),
+            Some(DecodedKey::Unicode('ⵛ'))
+        );
+    }
+}
@@ -0,0 +1,299 @@
+//! Turkish Q keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A standard Turkish Q 102-key (or 105-key including Windows keys) keyboard.
+///
+/// Turkish has a dotless `ı` and a dotted `İ`, so the key marked `I` types
+/// `ı`/`I` and the key to the right of `Ş` types `i`/`İ`. The `^`, `¨`, `~`,
+/// `´` and `` ` `` keys are dead keys.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct TrQ105Key;
+
+impl KeyboardLayout for TrQ105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        match keycode {
+            // ========= Row 2 (the numbers) =========
+            KeyCode::Oem8 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('<')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('é')
+                } else {
+                    DecodedKey::Unicode('"')
+                }
+            }
+            KeyCode::Key1 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('>')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('!')
+                } else {
+                    DecodedKey::Unicode('1')
+                }
+            }
+            KeyCode::Key2 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('£')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('\'')
+                } else {
+                    DecodedKey::Unicode('2')
+                }
+            }
+            KeyCode::Key3 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('#')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('^')
+                } else {
+                    DecodedKey::Unicode('3')
+                }
+            }
+            KeyCode::Key4 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('$')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('+')
+                } else {
+                    DecodedKey::Unicode('4')
+                }
+            }
+            KeyCode::Key5 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('½')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('%')
+                } else {
+                    DecodedKey::Unicode('5')
+                }
+            }
+            KeyCode::Key6 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('&')
+                } else {
+                    DecodedKey::Unicode('6')
+                }
+            }
+            KeyCode::Key7 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('{')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('/')
+                } else {
+                    DecodedKey::Unicode('7')
+                }
+            }
+            KeyCode::Key8 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('[')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('(')
+                } else {
+                    DecodedKey::Unicode('8')
+                }
+            }
+            KeyCode::Key9 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode(']')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode(')')
+                } else {
+                    DecodedKey::Unicode('9')
+                }
+            }
+            KeyCode::Key0 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('}')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('=')
+                } else {
+                    DecodedKey::Unicode('0')
+                }
+            }
+            KeyCode::OemMinus => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('\\')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('?')
+                } else {
+                    DecodedKey::Unicode('*')
+                }
+            }
+            KeyCode::OemPlus => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('|')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('_')
+                } else {
+                    DecodedKey::Unicode('-')
+                }
+            }
+            // ========= Row 3 (QWERTY) =========
+            KeyCode::Q => modifiers.handle_ascii_4(ascii_upper!('Q'), '@', '@', handle_ctrl),
+            KeyCode::E => modifiers.handle_ascii_4(ascii_upper!('E'), '€', '€', handle_ctrl),
+            KeyCode::T => modifiers.handle_ascii_4(ascii_upper!('T'), '₺', '₺', handle_ctrl),
+            KeyCode::I => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('i')
+                } else {
+                    modifiers.handle_letter_auto('ı', 'I', handle_ctrl)
+                }
+            }
+            KeyCode::Oem4 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('¨')
+                } else {
+                    modifiers.handle_letter_auto('ğ', 'Ğ', handle_ctrl)
+                }
+            }
+            KeyCode::Oem6 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('~')
+                } else {
+                    modifiers.handle_letter_auto('ü', 'Ü', handle_ctrl)
+                }
+            }
+            // ========= Row 4 (ASDF) =========
+            KeyCode::A => modifiers.handle_ascii_4(ascii_upper!('A'), 'æ', 'Æ', handle_ctrl),
+            KeyCode::S => modifiers.handle_ascii_4(ascii_upper!('S'), 'ß', 'ß', handle_ctrl),
+            KeyCode::Oem1 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('´')
+                } else {
+                    modifiers.handle_letter_auto('ş', 'Ş', handle_ctrl)
+                }
+            }
+            KeyCode::Oem3 => modifiers.handle_letter_auto('i', 'İ', handle_ctrl),
+            KeyCode::Oem7 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::DeadKey('`')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode(';')
+                } else {
+                    DecodedKey::Unicode(',')
+                }
+            }
+            // ========= Row 5 (ZXCV) =========
+            KeyCode::Oem5 => {
+                if modifiers.is_altgr() {
+                    DecodedKey::Unicode('|')
+                } else if modifiers.is_shifted() {
+                    DecodedKey::Unicode('>')
+                } else {
+                    DecodedKey::Unicode('<')
+                }
+            }
+            KeyCode::OemComma => modifiers.handle_letter_auto('ö', 'Ö', handle_ctrl),
+            KeyCode::OemPeriod => modifiers.handle_letter_auto('ç', 'Ç', handle_ctrl),
+            KeyCode::Oem2 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode(':')
+                } else {
+                    DecodedKey::Unicode('.')
+                }
+            }
+            KeyCode::NumpadPeriod => {
+                if modifiers.numlock {
+                    DecodedKey::Unicode(',')
+                } else {
+                    DecodedKey::Unicode(127.into())
+                }
+            }
+            e => {
+                let us = super::Us104Key;
+                us.map_keycode(e, modifiers, handle_ctrl)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdtuq
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '"', 'é', Some('<'), None),
+            (KeyCode::Key1, '1', '!', Some('>'), None),
+            (KeyCode::Key2, '2', '\'', Some('£'), None),
+            (KeyCode::Key3, '3', '^', Some('#'), None),
+            (KeyCode::Key4, '4', '+', Some('$'), None),
+            (KeyCode::Key5, '5', '%', Some('½'), None),
+            (KeyCode::Key6, '6', '&', None, None),
+            (KeyCode::Key7, '7', '/', Some('{'), None),
+            (KeyCode::Key8, '8', '(', Some('['), None),
+            (KeyCode::Key9, '9', ')', Some(']'), None),
+            (KeyCode::Key0, '0', '=', Some('}'), None),
+            (KeyCode::OemMinus, '*', '?', Some('\\'), None),
+            (KeyCode::OemPlus, '-', '_', Some('|'), None),
+            (KeyCode::Q, 'q', 'Q', Some('@'), None),
+            (KeyCode::E, 'e', 'E', Some('€'), None),
+            (KeyCode::T, 't', 'T', Some('₺'), None),
+            (KeyCode::I, 'ı', 'I', Some('i'), None),
+            (KeyCode::Oem4, 'ğ', 'Ğ', Some('¨'), None),
+            (KeyCode::Oem6, 'ü', 'Ü', Some('~'), None),
+            (KeyCode::A, 'a', 'A', Some('æ'), Some('Æ')),
+            (KeyCode::S, 's', 'S', Some('ß'), None),
+            (KeyCode::Oem1, 'ş', 'Ş', Some('´'), None),
+            (KeyCode::Oem3, 'i', 'İ', None, None),
+            (KeyCode::Oem7, ',', ';', Some('`'), None),
+            (KeyCode::Oem5, '<', '>', Some('|'), None),
+            (KeyCode::OemComma, 'ö', 'Ö', None, None),
+            (KeyCode::OemPeriod, 'ç', 'Ç', None, None),
+            (KeyCode::Oem2, '.', ':', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&TrQ105Key, ROWS);
+    }
+
+    #[test]
+    fn test_dotted_and_dotless_i() {
+        // (shift, caps lock, the I key, the İ key)
+        for (lshift, capslock, dotless, dotted) in [
+            (false, false, 'ı', 'i'),
+            (true, false, 'I', 'İ'),
+            (false, true, 'I', 'İ'),
+            (true, true, 'ı', 'i'),
+        ] {
+            let modifiers = Modifiers {
+                lshift,
+                capslock,
+                ..Default::default()
+            };
+            assert_eq!(
+                TrQ105Key.map_keycode(KeyCode::I, &modifiers, HandleControl::Ignore),
+                DecodedKey::Unicode(dotless)
+            );
+            assert_eq!(
+                TrQ105Key.map_keycode(KeyCode::Oem3, &modifiers, HandleControl::Ignore),
+                DecodedKey::Unicode(dotted)
+            );
+        }
+    }
+
+    #[test]
+    fn test_dead_keys() {
+        let mut dec = EventDecoder::new(TrQ105Key, HandleControl::Ignore);
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Key3, KeyState::Down)),
+            Some(DecodedKey::DeadKey('^'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::A, KeyState::Down)),
+            Some(DecodedKey::Unicode('â'))
+        );
+    }
+}
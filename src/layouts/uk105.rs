@@ -4,6 +4,8 @@ use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
 
 /// A standard United Kingdom 102-key (or 105-key including Windows keys) keyboard.
 ///
+/// AltGr + A, E, I, O or U give the vowel with an acute accent.
+///
 /// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
 pub struct Uk105Key;
 
@@ -17,7 +19,7 @@ impl KeyboardLayout for Uk105Key {
         match keycode {
             KeyCode::Oem8 => {
                 if modifiers.is_altgr() {
-                    DecodedKey::Unicode('|')
+                    DecodedKey::Unicode('¦')
                 } else if modifiers.is_shifted() {
                     DecodedKey::Unicode('¬')
                 } else {
@@ -68,6 +70,11 @@ impl KeyboardLayout for Uk105Key {
                     DecodedKey::Unicode('\\')
                 }
             }
+            KeyCode::A => modifiers.handle_ascii_4(ascii_upper!('A'), 'á', 'Á', handle_ctrl),
+            KeyCode::E => modifiers.handle_ascii_4(ascii_upper!('E'), 'é', 'É', handle_ctrl),
+            KeyCode::I => modifiers.handle_ascii_4(ascii_upper!('I'), 'í', 'Í', handle_ctrl),
+            KeyCode::O => modifiers.handle_ascii_4(ascii_upper!('O'), 'ó', 'Ó', handle_ctrl),
+            KeyCode::U => modifiers.handle_ascii_4(ascii_upper!('U'), 'ú', 'Ú', handle_ctrl),
             e => {
                 let us = super::Us104Key;
                 us.map_keycode(e, modifiers, handle_ctrl)
@@ -79,6 +86,7 @@ impl KeyboardLayout for Uk105Key {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::layouts::fixture;
     use crate::{EventDecoder, HandleControl, Keyboard, ScancodeSet, ScancodeSet1, ScancodeSet2};
 
     #[test]
@@ -140,10 +148,67 @@ mod test {
         ];
         for (code, unicode) in data {
             let ev = s.advance_state(code).unwrap().unwrap();
-            assert_eq!(Some(DecodedKey::Unicode(unicode)), dec.process_keyevent(ev));
+            assert_eq!(Some(DecodedKey::Unicode(unicode)), dec.process_item(ev));
         }
     }
 
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbduk
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '`', '¬', Some('¦'), None),
+            (KeyCode::Key1, '1', '!', None, None),
+            (KeyCode::Key2, '2', '"', None, None),
+            (KeyCode::Key3, '3', '£', None, None),
+            (KeyCode::Key4, '4', '$', Some('€'), None),
+            (KeyCode::Key5, '5', '%', None, None),
+            (KeyCode::Key6, '6', '^', None, None),
+            (KeyCode::Key7, '7', '&', None, None),
+            (KeyCode::Key8, '8', '*', None, None),
+            (KeyCode::Key9, '9', '(', None, None),
+            (KeyCode::Key0, '0', ')', None, None),
+            (KeyCode::OemMinus, '-', '_', None, None),
+            (KeyCode::OemPlus, '=', '+', None, None),
+            (KeyCode::Q, 'q', 'Q', None, None),
+            (KeyCode::W, 'w', 'W', None, None),
+            (KeyCode::E, 'e', 'E', Some('é'), Some('É')),
+            (KeyCode::R, 'r', 'R', None, None),
+            (KeyCode::T, 't', 'T', None, None),
+            (KeyCode::Y, 'y', 'Y', None, None),
+            (KeyCode::U, 'u', 'U', Some('ú'), Some('Ú')),
+            (KeyCode::I, 'i', 'I', Some('í'), Some('Í')),
+            (KeyCode::O, 'o', 'O', Some('ó'), Some('Ó')),
+            (KeyCode::P, 'p', 'P', None, None),
+            (KeyCode::Oem4, '[', '{', None, None),
+            (KeyCode::Oem6, ']', '}', None, None),
+            (KeyCode::A, 'a', 'A', Some('á'), Some('Á')),
+            (KeyCode::S, 's', 'S', None, None),
+            (KeyCode::D, 'd', 'D', None, None),
+            (KeyCode::F, 'f', 'F', None, None),
+            (KeyCode::G, 'g', 'G', None, None),
+            (KeyCode::H, 'h', 'H', None, None),
+            (KeyCode::J, 'j', 'J', None, None),
+            (KeyCode::K, 'k', 'K', None, None),
+            (KeyCode::L, 'l', 'L', None, None),
+            (KeyCode::Oem1, ';', ':', None, None),
+            (KeyCode::Oem3, '\'', '@', None, None),
+            (KeyCode::Oem7, '#', '~', None, None),
+            (KeyCode::Oem5, '\\', '|', None, None),
+            (KeyCode::Z, 'z', 'Z', None, None),
+            (KeyCode::X, 'x', 'X', None, None),
+            (KeyCode::C, 'c', 'C', None, None),
+            (KeyCode::V, 'v', 'V', None, None),
+            (KeyCode::B, 'b', 'B', None, None),
+            (KeyCode::N, 'n', 'N', None, None),
+            (KeyCode::M, 'm', 'M', None, None),
+            (KeyCode::OemComma, ',', '<', None, None),
+            (KeyCode::OemPeriod, '.', '>', None, None),
+            (KeyCode::Oem2, '/', '?', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&Uk105Key, ROWS);
+    }
+
     #[test]
     fn test_hash() {
         let mut k = Keyboard::new(
@@ -153,7 +218,7 @@ mod test {
         );
         // As seen on a UK 105 key Dell PS/2 keyboard when pressing `~#`
         let ev = k.add_byte(0x5D).unwrap().unwrap();
-        let decoded_key = k.process_keyevent(ev);
+        let decoded_key = k.process_item(ev);
         assert_eq!(decoded_key, Some(DecodedKey::Unicode('#')));
     }
 
@@ -166,7 +231,7 @@ mod test {
         );
         // As seen on a UK 105 key Dell PS/2 keyboard when pressing `|\`
         let ev = k.add_byte(0x61).unwrap().unwrap();
-        let decoded_key = k.process_keyevent(ev);
+        let decoded_key = k.process_item(ev);
         assert_eq!(decoded_key, Some(DecodedKey::Unicode('\\')));
     }
 
@@ -179,9 +244,9 @@ mod test {
         );
         // As seen on a UK 105 key Dell PS/2 keyboard when pressing Shift and `~#`
         let ev = k.add_byte(0x12).unwrap().unwrap();
-        let _ = k.process_keyevent(ev);
+        let _ = k.process_item(ev);
         let ev = k.add_byte(0x5D).unwrap().unwrap();
-        let decoded_key = k.process_keyevent(ev);
+        let decoded_key = k.process_item(ev);
         assert_eq!(decoded_key, Some(DecodedKey::Unicode('~')));
     }
 
@@ -194,9 +259,9 @@ mod test {
         );
         // As seen on a UK 105 key Dell PS/2 keyboard when pressing Shift and `|\`
         let ev = k.add_byte(0x12).unwrap().unwrap();
-        let _ = k.process_keyevent(ev);
+        let _ = k.process_item(ev);
         let ev = k.add_byte(0x61).unwrap().unwrap();
-        let decoded_key = k.process_keyevent(ev);
+        let decoded_key = k.process_item(ev);
         assert_eq!(decoded_key, Some(DecodedKey::Unicode('|')));
     }
 }
@@ -1,10 +1,13 @@
 //! United States keyboard support
 
+use super::ConfigurableLayout;
 use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
 
 /// A standard United States 101-key (or 104-key including Windows keys) keyboard.
 ///
-/// Has a 1-row high Enter key, with Oem5 above (ANSI layout).
+/// Has a 1-row high Enter key, with Oem7 above (ANSI layout). Some ANSI
+/// keyboards also have an Oem5 key next to the left shift, and it types the
+/// same thing as Oem7.
 pub struct Us104Key;
 
 impl KeyboardLayout for Us104Key {
@@ -214,7 +217,7 @@ impl KeyboardLayout for Us104Key {
                     DecodedKey::Unicode(']')
                 }
             }
-            KeyCode::Oem7 => {
+            KeyCode::Oem5 | KeyCode::Oem7 => {
                 if modifiers.is_shifted() {
                     DecodedKey::Unicode('|')
                 } else {
@@ -485,10 +488,20 @@ impl KeyboardLayout for Us104Key {
     }
 }
 
+impl ConfigurableLayout for Us104Key {
+    fn from_variant(variant: Option<&str>) -> Option<Us104Key> {
+        match variant {
+            None => Some(Us104Key),
+            Some(_) => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{EventDecoder, ScancodeSet, ScancodeSet1};
+    use crate::layouts::fixture;
+    use crate::{DecodedItem, EventDecoder, KeyEvent, KeyState, ScancodeSet, ScancodeSet1};
 
     #[test]
     fn layout() {
@@ -548,7 +561,88 @@ mod test {
         ];
         for (code, unicode) in data {
             let ev = s.advance_state(code).unwrap().unwrap();
-            assert_eq!(Some(DecodedKey::Unicode(unicode)), dec.process_keyevent(ev));
+            assert_eq!(Some(DecodedKey::Unicode(unicode)), dec.process_item(ev));
         }
     }
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdus
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Oem8, '`', '~', None, None),
+            (KeyCode::Key1, '1', '!', None, None),
+            (KeyCode::Key2, '2', '@', None, None),
+            (KeyCode::Key3, '3', '#', None, None),
+            (KeyCode::Key4, '4', '$', None, None),
+            (KeyCode::Key5, '5', '%', None, None),
+            (KeyCode::Key6, '6', '^', None, None),
+            (KeyCode::Key7, '7', '&', None, None),
+            (KeyCode::Key8, '8', '*', None, None),
+            (KeyCode::Key9, '9', '(', None, None),
+            (KeyCode::Key0, '0', ')', None, None),
+            (KeyCode::OemMinus, '-', '_', None, None),
+            (KeyCode::OemPlus, '=', '+', None, None),
+            (KeyCode::Q, 'q', 'Q', None, None),
+            (KeyCode::W, 'w', 'W', None, None),
+            (KeyCode::E, 'e', 'E', None, None),
+            (KeyCode::R, 'r', 'R', None, None),
+            (KeyCode::T, 't', 'T', None, None),
+            (KeyCode::Y, 'y', 'Y', None, None),
+            (KeyCode::U, 'u', 'U', None, None),
+            (KeyCode::I, 'i', 'I', None, None),
+            (KeyCode::O, 'o', 'O', None, None),
+            (KeyCode::P, 'p', 'P', None, None),
+            (KeyCode::Oem4, '[', '{', None, None),
+            (KeyCode::Oem6, ']', '}', None, None),
+            (KeyCode::Oem7, '\\', '|', None, None),
+            (KeyCode::A, 'a', 'A', None, None),
+            (KeyCode::S, 's', 'S', None, None),
+            (KeyCode::D, 'd', 'D', None, None),
+            (KeyCode::F, 'f', 'F', None, None),
+            (KeyCode::G, 'g', 'G', None, None),
+            (KeyCode::H, 'h', 'H', None, None),
+            (KeyCode::J, 'j', 'J', None, None),
+            (KeyCode::K, 'k', 'K', None, None),
+            (KeyCode::L, 'l', 'L', None, None),
+            (KeyCode::Oem1, ';', ':', None, None),
+            (KeyCode::Oem3, '\'', '"', None, None),
+            (KeyCode::Oem5, '\\', '|', None, None),
+            (KeyCode::Z, 'z', 'Z', None, None),
+            (KeyCode::X, 'x', 'X', None, None),
+            (KeyCode::C, 'c', 'C', None, None),
+            (KeyCode::V, 'v', 'V', None, None),
+            (KeyCode::B, 'b', 'B', None, None),
+            (KeyCode::N, 'n', 'N', None, None),
+            (KeyCode::M, 'm', 'M', None, None),
+            (KeyCode::OemComma, ',', '<', None, None),
+            (KeyCode::OemPeriod, '.', '>', None, None),
+            (KeyCode::Oem2, '/', '?', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+            (KeyCode::NumpadDivide, '/', '/', None, None),
+            (KeyCode::NumpadMultiply, '*', '*', None, None),
+            (KeyCode::NumpadSubtract, '-', '-', None, None),
+            (KeyCode::NumpadAdd, '+', '+', None, None),
+        ];
+        fixture::check(&Us104Key, ROWS);
+    }
+
+    #[test]
+    fn oem5() {
+        // Some ANSI keyboards have the ISO key next to Left Shift
+        let mut s = ScancodeSet1::new();
+        let mut dec = EventDecoder::new(Us104Key, HandleControl::Ignore);
+        let ev = s.advance_state(0x56).unwrap().unwrap();
+        assert_eq!(
+            ev,
+            DecodedItem::Key(KeyEvent::new(KeyCode::Oem5, KeyState::Down))
+        );
+        assert_eq!(Some(DecodedKey::Unicode('\\')), dec.process_item(ev));
+        let ev = s.advance_state(0x2A).unwrap().unwrap();
+        assert_eq!(
+            Some(DecodedKey::RawKey(KeyCode::LShift)),
+            dec.process_item(ev)
+        );
+        let ev = s.advance_state(0x56).unwrap().unwrap();
+        assert_eq!(Some(DecodedKey::Unicode('|')), dec.process_item(ev));
+    }
 }
@@ -0,0 +1,321 @@
+//! US International keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A US International 101-key (or 104-key including Windows keys) keyboard.
+///
+/// This is a US layout, with accented letters and extra symbols on AltGr
+/// (and their capitals on Shift + AltGr). The `'`, `"`, `` ` ``, `~` and `^`
+/// keys are dead keys: `'` then `e` gives `é`, `"` then `u` gives `ü`, and
+/// `'` then `c` gives `ç`. Type the dead key then Space to get the character
+/// on its own. A dead key followed by a letter it doesn't go with (e.g. `'`
+/// then `t`) types both.
+///
+/// Has a 1-row high Enter key, with Oem7 above the Enter key (ANSI format).
+pub struct UsInternational104Key;
+
+/// The `'` and `"` dead keys are plain ASCII, so they need their own table.
+/// The other three dead keys use the built-in one.
+const COMPOSITIONS: &[((char, char), char)] = &[
+    (('\'', 'a'), 'á'),
+    (('\'', 'A'), 'Á'),
+    (('\'', 'c'), 'ç'),
+    (('\'', 'C'), 'Ç'),
+    (('\'', 'e'), 'é'),
+    (('\'', 'E'), 'É'),
+    (('\'', 'i'), 'í'),
+    (('\'', 'I'), 'Í'),
+    (('\'', 'o'), 'ó'),
+    (('\'', 'O'), 'Ó'),
+    (('\'', 'u'), 'ú'),
+    (('\'', 'U'), 'Ú'),
+    (('\'', 'y'), 'ý'),
+    (('\'', 'Y'), 'Ý'),
+    (('"', 'a'), 'ä'),
+    (('"', 'A'), 'Ä'),
+    (('"', 'e'), 'ë'),
+    (('"', 'E'), 'Ë'),
+    (('"', 'i'), 'ï'),
+    (('"', 'I'), 'Ï'),
+    (('"', 'o'), 'ö'),
+    (('"', 'O'), 'Ö'),
+    (('"', 'u'), 'ü'),
+    (('"', 'U'), 'Ü'),
+    (('"', 'y'), 'ÿ'),
+];
+
+impl KeyboardLayout for UsInternational104Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let fallback = super::Us104Key;
+        match keycode {
+            KeyCode::Oem8 if !modifiers.is_altgr() => {
+                if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('~')
+                } else {
+                    DecodedKey::DeadKey('`')
+                }
+            }
+            KeyCode::Key1 if modifiers.is_altgr() => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('¹')
+                } else {
+                    DecodedKey::Unicode('¡')
+                }
+            }
+            KeyCode::Key2 if modifiers.is_altgr() => DecodedKey::Unicode('²'),
+            KeyCode::Key3 if modifiers.is_altgr() => DecodedKey::Unicode('³'),
+            KeyCode::Key4 if modifiers.is_altgr() => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('£')
+                } else {
+                    DecodedKey::Unicode('¤')
+                }
+            }
+            KeyCode::Key5 if modifiers.is_altgr() => DecodedKey::Unicode('€'),
+            KeyCode::Key6 if modifiers.is_altgr() => DecodedKey::Unicode('¼'),
+            KeyCode::Key6 if modifiers.is_shifted() => DecodedKey::DeadKey('^'),
+            KeyCode::Key7 if modifiers.is_altgr() => DecodedKey::Unicode('½'),
+            KeyCode::Key8 if modifiers.is_altgr() => DecodedKey::Unicode('¾'),
+            KeyCode::Key9 if modifiers.is_altgr() => DecodedKey::Unicode('‘'),
+            KeyCode::Key0 if modifiers.is_altgr() => DecodedKey::Unicode('’'),
+            KeyCode::OemMinus if modifiers.is_altgr() => DecodedKey::Unicode('¥'),
+            KeyCode::OemPlus if modifiers.is_altgr() => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('÷')
+                } else {
+                    DecodedKey::Unicode('×')
+                }
+            }
+            KeyCode::Q => modifiers.handle_ascii_4(ascii_upper!('Q'), 'ä', 'Ä', handle_ctrl),
+            KeyCode::W => modifiers.handle_ascii_4(ascii_upper!('W'), 'å', 'Å', handle_ctrl),
+            KeyCode::E => modifiers.handle_ascii_4(ascii_upper!('E'), 'é', 'É', handle_ctrl),
+            KeyCode::R => modifiers.handle_ascii_4(ascii_upper!('R'), '®', '®', handle_ctrl),
+            KeyCode::T => modifiers.handle_ascii_4(ascii_upper!('T'), 'þ', 'Þ', handle_ctrl),
+            KeyCode::Y => modifiers.handle_ascii_4(ascii_upper!('Y'), 'ü', 'Ü', handle_ctrl),
+            KeyCode::U => modifiers.handle_ascii_4(ascii_upper!('U'), 'ú', 'Ú', handle_ctrl),
+            KeyCode::I => modifiers.handle_ascii_4(ascii_upper!('I'), 'í', 'Í', handle_ctrl),
+            KeyCode::O => modifiers.handle_ascii_4(ascii_upper!('O'), 'ó', 'Ó', handle_ctrl),
+            KeyCode::P => modifiers.handle_ascii_4(ascii_upper!('P'), 'ö', 'Ö', handle_ctrl),
+            KeyCode::Oem4 if modifiers.is_altgr() => DecodedKey::Unicode('«'),
+            KeyCode::Oem6 if modifiers.is_altgr() => DecodedKey::Unicode('»'),
+            KeyCode::Oem7 if modifiers.is_altgr() => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('¦')
+                } else {
+                    DecodedKey::Unicode('¬')
+                }
+            }
+            KeyCode::A => modifiers.handle_ascii_4(ascii_upper!('A'), 'á', 'Á', handle_ctrl),
+            KeyCode::S => modifiers.handle_ascii_4(ascii_upper!('S'), 'ß', '§', handle_ctrl),
+            KeyCode::D => modifiers.handle_ascii_4(ascii_upper!('D'), 'ð', 'Ð', handle_ctrl),
+            KeyCode::L => modifiers.handle_ascii_4(ascii_upper!('L'), 'ø', 'Ø', handle_ctrl),
+            KeyCode::Oem1 if modifiers.is_altgr() => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('°')
+                } else {
+                    DecodedKey::Unicode('¶')
+                }
+            }
+            KeyCode::Oem3 if modifiers.is_altgr() => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('¨')
+                } else {
+                    DecodedKey::Unicode('´')
+                }
+            }
+            KeyCode::Oem3 => {
+                if modifiers.is_shifted() {
+                    DecodedKey::DeadKey('"')
+                } else {
+                    DecodedKey::DeadKey('\'')
+                }
+            }
+            KeyCode::Z => modifiers.handle_ascii_4(ascii_upper!('Z'), 'æ', 'Æ', handle_ctrl),
+            KeyCode::C => modifiers.handle_ascii_4(ascii_upper!('C'), '©', '¢', handle_ctrl),
+            KeyCode::N => modifiers.handle_ascii_4(ascii_upper!('N'), 'ñ', 'Ñ', handle_ctrl),
+            KeyCode::M => modifiers.handle_ascii_4(ascii_upper!('M'), 'µ', 'µ', handle_ctrl),
+            KeyCode::OemComma if modifiers.is_altgr() => {
+                if modifiers.is_shifted() {
+                    DecodedKey::Unicode('Ç')
+                } else {
+                    DecodedKey::Unicode('ç')
+                }
+            }
+            KeyCode::Oem2 if modifiers.is_altgr() => DecodedKey::Unicode('¿'),
+            e => fallback.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+
+    fn dead_key_combine(&self, dead_key: char, ch: char) -> Option<char> {
+        crate::compose::lookup(COMPOSITIONS, dead_key, ch)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // Taken from https://kbdlayout.info/kbdusx
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Key1, '1', '!', Some('¡'), Some('¹')),
+            (KeyCode::Key2, '2', '@', Some('²'), None),
+            (KeyCode::Key3, '3', '#', Some('³'), None),
+            (KeyCode::Key4, '4', '$', Some('¤'), Some('£')),
+            (KeyCode::Key5, '5', '%', Some('€'), None),
+            (KeyCode::Key6, '6', '^', Some('¼'), None),
+            (KeyCode::Key7, '7', '&', Some('½'), None),
+            (KeyCode::Key8, '8', '*', Some('¾'), None),
+            (KeyCode::Key9, '9', '(', Some('‘'), None),
+            (KeyCode::Key0, '0', ')', Some('’'), None),
+            (KeyCode::OemMinus, '-', '_', Some('¥'), None),
+            (KeyCode::OemPlus, '=', '+', Some('×'), Some('÷')),
+            (KeyCode::Q, 'q', 'Q', Some('ä'), Some('Ä')),
+            (KeyCode::W, 'w', 'W', Some('å'), Some('Å')),
+            (KeyCode::E, 'e', 'E', Some('é'), Some('É')),
+            (KeyCode::R, 'r', 'R', Some('®'), Some('®')),
+            (KeyCode::T, 't', 'T', Some('þ'), Some('Þ')),
+            (KeyCode::Y, 'y', 'Y', Some('ü'), Some('Ü')),
+            (KeyCode::U, 'u', 'U', Some('ú'), Some('Ú')),
+            (KeyCode::I, 'i', 'I', Some('í'), Some('Í')),
+            (KeyCode::O, 'o', 'O', Some('ó'), Some('Ó')),
+            (KeyCode::P, 'p', 'P', Some('ö'), Some('Ö')),
+            (KeyCode::Oem4, '[', '{', Some('«'), None),
+            (KeyCode::Oem6, ']', '}', Some('»'), None),
+            (KeyCode::Oem7, '\\', '|', Some('¬'), Some('¦')),
+            (KeyCode::A, 'a', 'A', Some('á'), Some('Á')),
+            (KeyCode::S, 's', 'S', Some('ß'), Some('§')),
+            (KeyCode::D, 'd', 'D', Some('ð'), Some('Ð')),
+            (KeyCode::L, 'l', 'L', Some('ø'), Some('Ø')),
+            (KeyCode::Oem1, ';', ':', Some('¶'), Some('°')),
+            (KeyCode::Oem3, '\'', '"', Some('´'), Some('¨')),
+            (KeyCode::Z, 'z', 'Z', Some('æ'), Some('Æ')),
+            (KeyCode::C, 'c', 'C', Some('©'), Some('¢')),
+            (KeyCode::N, 'n', 'N', Some('ñ'), Some('Ñ')),
+            (KeyCode::M, 'm', 'M', Some('µ'), Some('µ')),
+            (KeyCode::OemComma, ',', '<', Some('ç'), Some('Ç')),
+            (KeyCode::Oem2, '/', '?', Some('¿'), None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&UsInternational104Key, ROWS);
+    }
+
+    #[test]
+    fn test_altgr_vowels() {
+        let mut dec = EventDecoder::new(UsInternational104Key, HandleControl::MapLettersToUnicode);
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        for (code, lower, upper) in [
+            (KeyCode::A, 'á', 'Á'),
+            (KeyCode::E, 'é', 'É'),
+            (KeyCode::I, 'í', 'Í'),
+            (KeyCode::O, 'ó', 'Ó'),
+            (KeyCode::U, 'ú', 'Ú'),
+            (KeyCode::N, 'ñ', 'Ñ'),
+        ] {
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(lower))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::RShift, KeyState::Down));
+            assert_eq!(
+                dec.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::Unicode(upper))
+            );
+            dec.process_keyevent(KeyEvent::new(KeyCode::RShift, KeyState::Up));
+        }
+        // Without AltGr, it's the US layout
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Up));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Q, KeyState::Down)),
+            Some(DecodedKey::Unicode('q'))
+        );
+    }
+
+    /// Type a dead key (holding Shift if needed), then a key, collecting
+    /// everything typed after the dead key
+    fn dead_then(dead: (KeyCode, bool), code: KeyCode, shifted: bool) -> String {
+        let mut dec = EventDecoder::new(UsInternational104Key, HandleControl::Ignore);
+        let shift = |dec: &mut EventDecoder<UsInternational104Key>, state| {
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, state));
+        };
+        if dead.1 {
+            shift(&mut dec, KeyState::Down);
+        }
+        assert!(matches!(
+            dec.process_keyevent(KeyEvent::new(dead.0, KeyState::Down)),
+            Some(DecodedKey::DeadKey(_))
+        ));
+        if dead.1 {
+            shift(&mut dec, KeyState::Up);
+        }
+        if shifted {
+            shift(&mut dec, KeyState::Down);
+        }
+        let mut output = String::new();
+        if let Some(DecodedKey::Unicode(ch)) =
+            dec.process_keyevent(KeyEvent::new(code, KeyState::Down))
+        {
+            output.push(ch);
+        }
+        while let Some(DecodedKey::Unicode(ch)) = dec.take_queued() {
+            output.push(ch);
+        }
+        output
+    }
+
+    #[test]
+    fn test_dead_keys() {
+        let apostrophe = (KeyCode::Oem3, false);
+        let quote = (KeyCode::Oem3, true);
+        let grave = (KeyCode::Oem8, false);
+        let tilde = (KeyCode::Oem8, true);
+        let circumflex = (KeyCode::Key6, true);
+        let keys = [
+            (KeyCode::A, false),
+            (KeyCode::E, false),
+            (KeyCode::I, false),
+            (KeyCode::O, false),
+            (KeyCode::U, false),
+            (KeyCode::E, true),
+            (KeyCode::N, false),
+            (KeyCode::C, false),
+            (KeyCode::Spacebar, false),
+            (KeyCode::T, false),
+        ];
+        let expected = [
+            (
+                apostrophe,
+                ["á", "é", "í", "ó", "ú", "É", "'n", "ç", "'", "'t"],
+            ),
+            (
+                quote,
+                ["ä", "ë", "ï", "ö", "ü", "Ë", "\"n", "\"c", "\"", "\"t"],
+            ),
+            (grave, ["à", "è", "ì", "ò", "ù", "È", "ǹ", "`c", "`", "`t"]),
+            (tilde, ["ã", "ẽ", "ĩ", "õ", "ũ", "Ẽ", "ñ", "~c", "~", "~t"]),
+            (
+                circumflex,
+                ["â", "ê", "î", "ô", "û", "Ê", "^n", "ĉ", "^", "^t"],
+            ),
+        ];
+        for (dead, results) in expected {
+            for ((code, shifted), result) in keys.iter().zip(results) {
+                assert_eq!(
+                    dead_then(dead, *code, *shifted),
+                    result,
+                    "{:?} then {:?}",
+                    dead,
+                    code
+                );
+            }
+        }
+    }
+}
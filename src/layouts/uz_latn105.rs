@@ -0,0 +1,91 @@
+//! Uzbek (Latin) keyboard support
+
+use crate::{DecodedKey, HandleControl, KeyCode, KeyboardLayout, Modifiers};
+
+/// A standard Uzbek (Latin) 102-key (or 105-key including Windows keys)
+/// keyboard.
+///
+/// This is QWERTY, with the turned comma (`ʻ`, U+02BB) on the `[` and `]`
+/// keys and the modifier letter apostrophe (`ʼ`, U+02BC) on the `'` key, so
+/// `oʻ`, `gʻ` and `ʼ` are typed as proper letters rather than ASCII
+/// apostrophes. These keys type the same with or without Shift (so `Oʻ` can
+/// be typed without letting go of Shift); AltGr gives the US symbols.
+///
+/// Has a 2-row high Enter key, with Oem5 next to the left shift (ISO format).
+pub struct UzLatin105Key;
+
+impl KeyboardLayout for UzLatin105Key {
+    fn map_keycode(
+        &self,
+        keycode: KeyCode,
+        modifiers: &Modifiers,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        let us = super::Us104Key;
+        match keycode {
+            KeyCode::Oem4 | KeyCode::Oem6 if !modifiers.is_altgr() => {
+                DecodedKey::Unicode('\u{02BB}')
+            }
+            KeyCode::Oem3 if !modifiers.is_altgr() => DecodedKey::Unicode('\u{02BC}'),
+            e => us.map_keycode(e, modifiers, handle_ctrl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layouts::fixture;
+    use crate::{EventDecoder, KeyEvent, KeyState};
+
+    #[test]
+    fn test_fixture() {
+        // The 1995 Uzbek Latin alphabet, on a US QWERTY base
+        const ROWS: &[fixture::Row] = &[
+            (KeyCode::Key2, '2', '@', None, None),
+            (KeyCode::Q, 'q', 'Q', None, None),
+            (KeyCode::Oem4, '\u{02BB}', '\u{02BB}', Some('['), Some('{')),
+            (KeyCode::Oem6, '\u{02BB}', '\u{02BB}', Some(']'), Some('}')),
+            (KeyCode::Oem3, '\u{02BC}', '\u{02BC}', Some('\''), Some('"')),
+            (KeyCode::Oem5, '\\', '|', None, None),
+            (KeyCode::Z, 'z', 'Z', None, None),
+            (KeyCode::Spacebar, ' ', ' ', None, None),
+        ];
+        fixture::check(&UzLatin105Key, ROWS);
+    }
+
+    #[test]
+    fn test_code_points() {
+        let mut dec = EventDecoder::new(UzLatin105Key, HandleControl::Ignore);
+        // Oʻzbek
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::O, KeyState::Down)),
+            Some(DecodedKey::Unicode('O'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem4, KeyState::Down)),
+            Some(DecodedKey::Unicode('\u{02BB}'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        // gʻ
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem6, KeyState::Down)),
+            Some(DecodedKey::Unicode('\u{02BB}'))
+        );
+        // maʼno
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem3, KeyState::Down)),
+            Some(DecodedKey::Unicode('\u{02BC}'))
+        );
+        // sh and ch are just two letters
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::S, KeyState::Down)),
+            Some(DecodedKey::Unicode('s'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::H, KeyState::Down)),
+            Some(DecodedKey::Unicode('h'))
+        );
+    }
+}
@@ -10,14 +10,22 @@
 //!   keyboard over GPIO pins and not required if you talk to the i8042 PC keyboard
 //!   controller.
 //! * [`ScancodeSet`] - converts from Scancode Set 1 (i8042 PC keyboard controller) or
-//!   Scancode Set 2 (raw PS/2 keyboard output) into a symbolic [`KeyCode`] and an
-//!   up/down [`KeyState`].
+//!   Scancode Set 2 (raw PS/2 keyboard output) into a [`DecodedItem`] - either
+//!   a symbolic [`KeyCode`] and an up/down [`KeyState`], or a [`ProtocolEvent`]
+//!   like a successful self-test.
 //! * [`EventDecoder`] - converts symbolic [`KeyCode`] and [`KeyState`] into a Unicode
 //!   characters (where possible) according to the currently selected `KeyboardLayout`.
 //!
 //! There is also [`Keyboard`] which combines the above three functions into a single object.
+//!
+//! If you enable the `alloc` feature, [`Keyboard::decode_all`] will run a
+//! whole buffer of bytes through the pipeline in one go.
 
 #![cfg_attr(not(test), no_std)]
+#![forbid(unsafe_code)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 // ****************************************************************************
 //
@@ -25,10 +33,49 @@
 //
 // ****************************************************************************
 
+#[cfg(feature = "alloc")]
+pub mod capture;
+
+mod codepage;
+
+mod debounce;
+pub use crate::debounce::{Debounced, Debouncer};
+
+mod digital;
+pub use crate::digital::DigitalInputMap;
+
+pub mod compose;
+
 pub mod layouts;
 
+#[cfg(feature = "alloc")]
+pub mod loopback;
+
+pub mod prelude;
+
+pub mod protocol;
+
+mod keycode_map;
+pub use crate::keycode_map::KeyCodeMap;
+
+mod names;
+#[cfg(feature = "key-names-de")]
+pub use crate::names::GermanNames;
+pub use crate::names::{EnglishNames, KeyNamer, Named};
+
+mod output;
+pub use crate::output::SmallOutput;
+
+mod pending;
+pub use crate::pending::PendingItems;
+
+mod policy;
+pub use crate::policy::{DefaultPolicy, ModifierPolicy, ShiftLock};
+
 mod scancodes;
-pub use crate::scancodes::{ScancodeSet1, ScancodeSet2};
+pub use crate::scancodes::{
+    ScancodeBytes, ScancodeSet1, ScancodeSet2, ScancodeSetKind, Translator,
+};
 
 // ****************************************************************************
 //
@@ -37,8 +84,11 @@ pub use crate::scancodes::{ScancodeSet1, ScancodeSet2};
 // ****************************************************************************
 
 /// Encapsulates decode/sampling logic, and handles state transitions and key events.
+///
+/// `N` is how many bytes [`Keyboard::push_raw`] can store before they are
+/// decoded by [`Keyboard::process_pending`].
 #[derive(Debug)]
-pub struct Keyboard<L, S>
+pub struct Keyboard<L, S, const N: usize = 16>
 where
     S: ScancodeSet,
     L: KeyboardLayout,
@@ -46,6 +96,9 @@ where
     ps2_decoder: Ps2Decoder,
     scancode_set: S,
     event_decoder: EventDecoder<L>,
+    response: protocol::ResponseTracker,
+    last_device_id: Option<protocol::KeyboardKind>,
+    pending: pending::PendingBytes<N>,
 }
 
 /// Handles decoding of IBM PS/2 Keyboard (and IBM PC/AT Keyboard) bit-streams.
@@ -56,14 +109,36 @@ pub struct Ps2Decoder {
 }
 
 /// Converts KeyEvents into Unicode, according to the current Keyboard Layout
+///
+/// What the modifier keys mean is up to the [`ModifierPolicy`], which is
+/// usually [`DefaultPolicy`].
 #[derive(Debug)]
-pub struct EventDecoder<L>
+pub struct EventDecoder<L, P = DefaultPolicy>
 where
     L: KeyboardLayout,
+    P: ModifierPolicy,
 {
+    policy: P,
     handle_ctrl: HandleControl,
     modifiers: Modifiers,
     layout: L,
+    dead_keys: [char; MAX_DEAD_KEYS],
+    num_dead_keys: usize,
+    max_dead_keys: usize,
+    queued: SmallOutput,
+    compositions: &'static [((char, char), char)],
+    #[cfg(feature = "alloc")]
+    owned_compositions: alloc::vec::Vec<((char, char), char)>,
+    alt_codes: AltCodes,
+    alt_code: Option<AltCode>,
+    unicode_entry: bool,
+    hex_code: Option<HexCode>,
+    compose_key: Option<KeyCode>,
+    compose_sequences: &'static [(char, char, char)],
+    compose: Option<Compose>,
+    scrolllock_numpad_override: bool,
+    chords: [Option<(ModifierMask, KeyCode)>; MAX_SUPPRESSED_CHORDS],
+    suppressed: Option<(ModifierMask, KeyCode)>,
 }
 
 /// Indicates different error conditions.
@@ -74,6 +149,16 @@ pub enum Error {
     BadStopBit,
     ParityError,
     UnknownKeyCode,
+    Incomplete,
+    TrailingBytes,
+    /// A new `E0` or `E1` prefix arrived part-way through a sequence.
+    ///
+    /// The partial sequence is dropped, and decoding carries on from the new
+    /// prefix.
+    InterruptedSequence,
+    /// [`Keyboard::push_raw`] had to overwrite bytes that hadn't been
+    /// decoded yet.
+    PendingOverflow,
 }
 
 /// Keycodes that can be generated by a keyboard.
@@ -81,7 +166,7 @@ pub enum Error {
 /// We use this enum to abstract over Scan Code Set 1 and Scan Code Set 2.
 ///
 /// See <https://kbdlayout.info/kbduk/shiftstates+virtualkeys/base>
-#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord, Hash)]
 #[repr(u8)]
 pub enum KeyCode {
     // ========= Row 1 (the F-keys) =========
@@ -327,10 +412,31 @@ pub enum KeyCode {
     /// Extra JIS key (0x70)
     Oem11,
     /// Extra JIS symbol key (0x73)
+    ///
+    /// This is also the extra `/?` key (ABNT C1) next to Right Shift on
+    /// Brazilian ABNT2 keyboards.
     Oem12,
     /// Extra JIS symbol key (0x7D)
     Oem13,
 
+    // ========= ABNT2 extra keys =========
+    /// The extra numpad key (ABNT C2) on Brazilian ABNT2 keyboards (0x7E).
+    ///
+    /// USB calls this Keypad Comma, but on ABNT2 keyboards it types `.`.
+    NumpadComma,
+
+    // ========= Korean extra keys =========
+    /// The 한/영 key, which switches between Hangul and Latin input (0xF2).
+    ///
+    /// This key has no break code, so it only ever arrives as a
+    /// [`KeyState::SingleShot`] event.
+    Hangul,
+    /// The 한자 key, which converts Hangul to Hanja (0xF1).
+    ///
+    /// This key has no break code, so it only ever arrives as a
+    /// [`KeyState::SingleShot`] event.
+    Hanja,
+
     // ========= Extra Keys =========
     /// Multi-media keys - Previous Track
     PrevTrack,
@@ -350,9 +456,39 @@ pub enum KeyCode {
     VolumeUp,
     /// Multi-media keys - Open Browser
     WWWHome,
+    /// Multi-media keys - Open Browser Search
+    WWWSearch,
+    /// Multi-media keys - Browser Favourites
+    WWWFavourites,
+    /// Multi-media keys - Browser Refresh
+    WWWRefresh,
+    /// Multi-media keys - Browser Stop
+    WWWStop,
+    /// Multi-media keys - Browser Forward
+    WWWForward,
+    /// Multi-media keys - Browser Back
+    WWWBack,
+    /// Multi-media keys - Open My Computer
+    MyComputer,
+    /// Multi-media keys - Open Email
+    Email,
+    /// Multi-media keys - Media Select
+    MediaSelect,
+    /// ACPI keys - Power
+    Power,
+    /// ACPI keys - Sleep
+    Sleep,
+    /// ACPI keys - Wake
+    Wake,
     /// Sent when the keyboard boots
+    ///
+    /// Now reported as [`ProtocolEvent::PowerOnTestOk`]; you only see this
+    /// from [`DecodedItem::into_key_event`].
     PowerOnTestOk,
     /// Sent by the keyboard when too many keys are pressed
+    ///
+    /// Now reported as [`ProtocolEvent::TooManyKeys`]; you only see this from
+    /// [`DecodedItem::into_key_event`].
     TooManyKeys,
     /// Used as a 'hidden' Right Control Key (Pause = RControl2 + Num Lock)
     RControl2,
@@ -361,17 +497,52 @@ pub enum KeyCode {
 }
 
 /// The new state for a key, as part of a key event.
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+///
+/// These sort in the order listed: `Up`, then `Down`, then `SingleShot`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord, Hash)]
 pub enum KeyState {
     /// Key has just been released
     Up,
     /// Key has just been pressed
     Down,
-    /// Key was pressed and then released as an atomic action. Or it's like a
-    /// PowerOnSelfTest event which doesn't have an 'Up' or a 'Down'.
+    /// Key was pressed and then released as an atomic action.
+    ///
+    /// Before [`ProtocolEvent`] was added, this was also used for keyboard
+    /// events like [`KeyCode::PowerOnTestOk`], and you still get that from
+    /// [`DecodedItem::into_key_event`].
     SingleShot,
 }
 
+/// Something the keyboard said which isn't about a key.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[non_exhaustive]
+pub enum ProtocolEvent {
+    /// The keyboard passed its power-on self test
+    PowerOnTestOk,
+    /// Too many keys are held down for the keyboard to tell which ones
+    TooManyKeys,
+    /// The keyboard failed its power-on self test (`0xFC` or `0xFD`)
+    PowerOnTestFailed,
+    /// The keyboard's answer to the Identify (`0xF2`) command, which is
+    /// `None` if it wasn't recognised
+    DeviceId(Option<protocol::KeyboardKind>),
+    /// The keyboard's answer to a command - an ACK, a Resend or an Echo
+    ///
+    /// Only from [`ScancodeSet1`], or from a protocol aware Scancode Set
+    /// (e.g. [`ScancodeSet2::new_protocol_aware`]), which also spots them in
+    /// the middle of a scancode sequence.
+    CommandResponse(protocol::Response),
+}
+
+/// Everything a [`ScancodeSet`] can decode.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DecodedItem {
+    /// A key went up or down
+    Key(KeyEvent),
+    /// The keyboard told us something else
+    Protocol(ProtocolEvent),
+}
+
 /// Options for how we can handle what happens when the Ctrl key is held down
 /// and a letter is pressed.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -385,8 +556,44 @@ pub enum HandleControl {
     Ignore,
 }
 
+/// What [`Keyboard::process_word_with_policy`] should do when a word from the
+/// keyboard has a bad start bit, stop bit or parity bit.
+///
+/// Whatever the policy, you still get the error back.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ErrorPolicy {
+    /// Clear the bit register and forget any partly decoded scancode
+    /// sequence. The next word starts afresh.
+    ClearAll,
+    /// Clear the bit register, but carry on with any partly decoded scancode
+    /// sequence (e.g. if you will ask the keyboard to resend the byte).
+    ClearPs2Only,
+    /// Don't change anything.
+    Propagate,
+}
+
+/// Options for entering characters by holding Left Alt and typing a number
+/// on the numeric keypad (e.g. Alt + 65 for `A`).
+///
+/// In every mode except `Disabled`, a number without a leading zero is a
+/// character from Code Page 437 (the original IBM PC character set). The
+/// mode selects what a number with a leading zero means.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum AltCodes {
+    /// Numpad keys do what they normally do when Alt is held down.
+    Disabled,
+    /// Numbers with a leading zero are Windows-1252 characters (e.g. Alt +
+    /// 0128 for `€`), like on Windows.
+    Windows1252,
+    /// Numbers with a leading zero are Unicode code points (e.g. Alt + 08364
+    /// for `€`).
+    Unicode,
+}
+
 /// A event describing something happen to a key on your keyboard.
-#[derive(Debug, PartialEq, Eq, Clone)]
+///
+/// These sort by [`KeyCode`], and then by [`KeyState`].
+#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord, Hash)]
 pub struct KeyEvent {
     /// Which key this event is for
     pub code: KeyCode,
@@ -408,6 +615,28 @@ pub trait KeyboardLayout {
         modifiers: &Modifiers,
         handle_ctrl: HandleControl,
     ) -> DecodedKey;
+
+    /// Relabel a key which `map_keycode` gave back as a
+    /// `DecodedKey::RawKey`.
+    ///
+    /// This lets a layout change what a non-character key means - for
+    /// example, to treat `Apps` as some other key. The default implementation
+    /// leaves the key alone.
+    fn map_raw(&self, keycode: KeyCode, _modifiers: &Modifiers) -> KeyCode {
+        keycode
+    }
+
+    /// Combine one of this layout's dead keys with the character that
+    /// followed it.
+    ///
+    /// This lets a layout keep the compositions for its dead keys next to
+    /// the keys themselves. [`EventDecoder`] asks the layout after any tables
+    /// given to [`EventDecoder::add_compositions`], and before the built-in
+    /// tables in the [`compose`] module. The default implementation returns
+    /// `None`, leaving it to the built-in tables.
+    fn dead_key_combine(&self, _dead_key: char, _ch: char) -> Option<char> {
+        None
+    }
 }
 
 /// A mechanism to convert bytes from a Keyboard into [`KeyCode`] values.
@@ -415,7 +644,16 @@ pub trait KeyboardLayout {
 /// This conversion is stateful.
 pub trait ScancodeSet {
     /// Handles the state logic for the decoding of scan codes into key events.
-    fn advance_state(&mut self, code: u8) -> Result<Option<KeyEvent>, Error>;
+    fn advance_state(&mut self, code: u8) -> Result<Option<DecodedItem>, Error>;
+
+    /// Which Scancode Set this is.
+    fn kind(&self) -> ScancodeSetKind;
+
+    /// Forget any partly decoded sequence (e.g. after a byte was lost).
+    ///
+    /// The default does nothing, which suits a decoder that holds no state
+    /// between bytes.
+    fn reset(&mut self) {}
 }
 
 /// The set of modifier keys you have on a keyboard.
@@ -433,19 +671,68 @@ pub struct Modifiers {
     pub numlock: bool,
     /// The caps lock toggle is on
     pub capslock: bool,
+    /// The Scroll Lock toggle is on
+    pub scrolllock: bool,
     /// The left alt key is down
     pub lalt: bool,
     /// The right alt key is down
     pub ralt: bool,
     /// Special 'hidden' control key is down (used when you press Pause)
     pub rctrl2: bool,
+    /// The Japanese kana toggle is on (toggled by the Hiragana/Katakana key)
+    pub kana: bool,
+}
+
+/// A combination of held modifier keys, used to describe a chord like
+/// Alt + Tab.
+///
+/// Either Shift key counts as Shift, and so on. Combine masks with `|`.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ModifierMask(u8);
+
+impl ModifierMask {
+    /// No modifiers
+    pub const NONE: ModifierMask = ModifierMask(0);
+    /// Either Shift key
+    pub const SHIFT: ModifierMask = ModifierMask(1 << 0);
+    /// Either Control key
+    pub const CTRL: ModifierMask = ModifierMask(1 << 1);
+    /// Either Alt key (including AltGr)
+    pub const ALT: ModifierMask = ModifierMask(1 << 2);
+
+    /// Does this mask include all of the modifiers in `other`?
+    pub const fn contains(self, other: ModifierMask) -> bool {
+        (self.0 & other.0) == other.0
+    }
+}
+
+impl core::ops::BitOr for ModifierMask {
+    type Output = ModifierMask;
+
+    fn bitor(self, rhs: ModifierMask) -> ModifierMask {
+        ModifierMask(self.0 | rhs.0)
+    }
 }
 
 /// Contains either a Unicode character, or a raw key code.
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+///
+/// These sort with every `Unicode` first (by code point), then every `RawKey`
+/// (by [`KeyCode`]), then every `DeadKey`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, PartialOrd, Ord, Hash)]
 pub enum DecodedKey {
-    RawKey(KeyCode),
     Unicode(char),
+    RawKey(KeyCode),
+    /// A dead key, holding the spacing form of its diacritic (e.g. `^`).
+    ///
+    /// The [`EventDecoder`] will combine this with the next character - see
+    /// the [`compose`] module. After a dead key:
+    ///
+    /// * Space gives the diacritic on its own
+    /// * The same dead key again gives the diacritic on its own, once
+    /// * Escape or Backspace cancels the dead key, and gives nothing
+    /// * A character it can't be combined with gives the diacritic, then the
+    ///   character
+    DeadKey(char),
 }
 
 // ****************************************************************************
@@ -471,6 +758,36 @@ enum DecodeState {
     ExtendedRelease,
     Extended2,
     Extended2Release,
+    /// Got the first byte of a Device ID
+    DeviceId(u8),
+}
+
+/// An ASCII upper case letter, `'A'` to `'Z'`.
+///
+/// Make these in a `const` (e.g. `const A: AsciiUpper = AsciiUpper::new('A');`),
+/// so that a bad letter fails to compile. See [`Modifiers::handle_ascii_4`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct AsciiUpper(char);
+
+/// An Alt + Numpad code that is being typed in
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct AltCode {
+    value: u32,
+    digits: u8,
+    leading_zero: bool,
+}
+
+/// A Ctrl+Shift+U hex code that is being typed in
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct HexCode {
+    value: u32,
+    digits: u8,
+}
+
+/// A Compose key sequence that is being typed in
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Compose {
+    first: Option<char>,
 }
 
 // ****************************************************************************
@@ -479,10 +796,24 @@ enum DecodeState {
 //
 // ****************************************************************************
 
+/// How many dead keys [`EventDecoder`] can hold before the next character
+const MAX_DEAD_KEYS: usize = 2;
+
+/// How many chords [`EventDecoder::suppress_chord`] can hold
+const MAX_SUPPRESSED_CHORDS: usize = 8;
+
 const KEYCODE_BITS: u8 = 11;
 const EXTENDED_KEY_CODE: u8 = 0xE0;
 const EXTENDED2_KEY_CODE: u8 = 0xE1;
 const KEY_RELEASE_CODE: u8 = 0xF0;
+/// Sent by the 한자 key, in both Set 1 and Set 2, with no break code
+const HANJA_KEY_CODE: u8 = 0xF1;
+/// Sent by the 한/영 key, in both Set 1 and Set 2, with no break code
+const HANGUL_KEY_CODE: u8 = 0xF2;
+/// Enough digits for any Unicode code point, with a leading zero
+const MAX_ALT_CODE_DIGITS: u8 = 8;
+/// Enough hex digits for any Unicode code point
+const MAX_HEX_CODE_DIGITS: u8 = 6;
 
 // ****************************************************************************
 //
@@ -497,10 +828,38 @@ where
 {
     /// Make a new Keyboard object with the given layout.
     pub const fn new(scancode_set: S, layout: L, handle_ctrl: HandleControl) -> Keyboard<L, S> {
+        Keyboard::with_pending_depth(scancode_set, layout, handle_ctrl)
+    }
+}
+
+impl<L, S, const N: usize> Keyboard<L, S, N>
+where
+    L: KeyboardLayout,
+    S: ScancodeSet,
+{
+    /// Make a new Keyboard object with the given layout, which can store `N`
+    /// bytes from [`Keyboard::push_raw`].
+    ///
+    /// ```rust
+    /// use pc_keyboard::{layouts, HandleControl, Keyboard, ScancodeSet2};
+    /// let kb: Keyboard<_, _, 4> = Keyboard::with_pending_depth(
+    ///     ScancodeSet2::new(),
+    ///     layouts::Us104Key,
+    ///     HandleControl::Ignore,
+    /// );
+    /// ```
+    pub const fn with_pending_depth(
+        scancode_set: S,
+        layout: L,
+        handle_ctrl: HandleControl,
+    ) -> Keyboard<L, S, N> {
         Keyboard {
             ps2_decoder: Ps2Decoder::new(),
             scancode_set,
             event_decoder: EventDecoder::new(layout, handle_ctrl),
+            response: protocol::ResponseTracker::Idle,
+            last_device_id: None,
+            pending: pending::PendingBytes::new(),
         }
     }
 
@@ -509,21 +868,110 @@ where
         &self.event_decoder.modifiers
     }
 
+    /// Which Scancode Set this keyboard is decoding.
+    pub fn scancode_set_kind(&self) -> ScancodeSetKind {
+        self.scancode_set.kind()
+    }
+
     /// Change the Ctrl key mapping.
     pub fn set_ctrl_handling(&mut self, new_value: HandleControl) {
         self.event_decoder.set_ctrl_handling(new_value);
     }
 
+    /// Change how Alt + Numpad character entry works.
+    pub fn set_alt_codes(&mut self, new_value: AltCodes) {
+        self.event_decoder.set_alt_codes(new_value);
+    }
+
+    /// Get how Alt + Numpad character entry works.
+    pub const fn get_alt_codes(&self) -> AltCodes {
+        self.event_decoder.get_alt_codes()
+    }
+
+    /// Enable or disable Ctrl+Shift+U character entry.
+    ///
+    /// See [`EventDecoder::set_unicode_entry`].
+    pub fn set_unicode_entry(&mut self, enabled: bool) {
+        self.event_decoder.set_unicode_entry(enabled);
+    }
+
+    /// Is Ctrl+Shift+U character entry enabled?
+    pub const fn get_unicode_entry(&self) -> bool {
+        self.event_decoder.get_unicode_entry()
+    }
+
+    /// Choose a key to be the Compose key.
+    ///
+    /// See [`EventDecoder::set_compose_key`].
+    pub fn set_compose_key(&mut self, code: Option<KeyCode>) {
+        self.event_decoder.set_compose_key(code);
+    }
+
+    /// Get the Compose key, if there is one.
+    pub const fn get_compose_key(&self) -> Option<KeyCode> {
+        self.event_decoder.get_compose_key()
+    }
+
+    /// Use your own table of Compose key sequences.
+    ///
+    /// See [`EventDecoder::set_compose_sequences`].
+    pub fn set_compose_sequences(&mut self, sequences: &'static [(char, char, char)]) {
+        self.event_decoder.set_compose_sequences(sequences);
+    }
+
+    /// Enable or disable the Scroll Lock numpad override.
+    ///
+    /// See [`EventDecoder::set_scrolllock_numpad_override`].
+    pub fn set_scrolllock_numpad_override(&mut self, enabled: bool) {
+        self.event_decoder.set_scrolllock_numpad_override(enabled);
+    }
+
+    /// Is the Scroll Lock numpad override enabled?
+    pub const fn get_scrolllock_numpad_override(&self) -> bool {
+        self.event_decoder.get_scrolllock_numpad_override()
+    }
+
     /// Get the current Ctrl key mapping.
     pub const fn get_ctrl_handling(&self) -> HandleControl {
         self.event_decoder.get_ctrl_handling()
     }
 
-    /// Clears the bit register.
+    /// Clears the bit register, and forgets any partly decoded scancode
+    /// sequence.
     ///
     /// Call this when there is a timeout reading data from the keyboard.
     pub fn clear(&mut self) {
         self.ps2_decoder.clear();
+        self.scancode_set.reset();
+    }
+
+    /// Tell the keyboard decoder that you have just sent a command to the
+    /// keyboard, and what kind of response to expect.
+    ///
+    /// The response bytes are then consumed by `add_bit`, `add_byte` or
+    /// `add_word` rather than being decoded as scancodes. Any key presses that
+    /// arrive before the `0xFA` ACK are decoded as normal. The ID bytes (if
+    /// you expect them) give you a [`ProtocolEvent::DeviceId`].
+    ///
+    /// In Scancode Set 1, the first ID byte is also a break code, so you
+    /// must call this to get the ID.
+    ///
+    /// An ancient AT keyboard sends no ID bytes after the ACK. That is only
+    /// spotted when the next byte (which is decoded as a scancode) arrives,
+    /// and then [`Keyboard::last_device_id`] gives
+    /// [`KeyboardKind::AncientAt`](protocol::KeyboardKind::AncientAt). If you
+    /// need to know sooner, time out waiting for the ID yourself.
+    pub fn expect_response(&mut self, expect: protocol::Expect) {
+        self.response = protocol::ResponseTracker::expect(expect);
+    }
+
+    /// Get the keyboard type reported by the last Identify (`0xF2`) command.
+    ///
+    /// Is `None` if no ID has been received yet, or if the last ID received
+    /// was not recognised. See [`Keyboard::expect_response`] for how an
+    /// ancient AT keyboard (which sends no ID) is spotted.
+    pub const fn last_device_id(&self) -> Option<protocol::KeyboardKind> {
+        self.last_device_id
     }
 
     /// Processes a 16-bit word from the keyboard.
@@ -533,31 +981,120 @@ where
     ///   MSB in bit 8.
     /// * The parity bit must be in bit 9.
     /// * The stop bit (1) must be in bit 10.
-    pub fn add_word(&mut self, word: u16) -> Result<Option<KeyEvent>, Error> {
+    pub fn add_word(&mut self, word: u16) -> Result<Option<DecodedItem>, Error> {
         let byte = self.ps2_decoder.add_word(word)?;
         self.add_byte(byte)
     }
 
+    /// Processes a 16-bit word from the keyboard, like `add_word`, but
+    /// cleaning up according to `policy` if the word is corrupt.
+    ///
+    /// If the error [needs a resend](Error::needs_resend), you can ask the
+    /// keyboard to send the byte again by sending it [`protocol::RESEND`].
+    pub fn process_word_with_policy(
+        &mut self,
+        word: u16,
+        policy: ErrorPolicy,
+    ) -> Result<Option<DecodedItem>, Error> {
+        let byte = match self.ps2_decoder.add_word(word) {
+            Ok(byte) => byte,
+            Err(e) => {
+                match policy {
+                    ErrorPolicy::ClearAll => self.clear(),
+                    ErrorPolicy::ClearPs2Only => self.ps2_decoder.clear(),
+                    ErrorPolicy::Propagate => {}
+                }
+                return Err(e);
+            }
+        };
+        self.add_byte(byte)
+    }
+
     /// Processes an 8-bit byte from the keyboard.
     ///
     /// We assume the start, stop and parity bits have been processed and
     /// verified.
-    pub fn add_byte(&mut self, byte: u8) -> Result<Option<KeyEvent>, Error> {
-        self.scancode_set.advance_state(byte)
+    pub fn add_byte(&mut self, byte: u8) -> Result<Option<DecodedItem>, Error> {
+        let tracked = self.response.feed(byte);
+        match tracked {
+            protocol::Tracked::Pass | protocol::Tracked::NoId => {
+                if tracked == protocol::Tracked::NoId {
+                    self.last_device_id = Some(protocol::KeyboardKind::AncientAt);
+                }
+                let item = self.scancode_set.advance_state(byte)?;
+                if let Some(DecodedItem::Protocol(ProtocolEvent::DeviceId(kind))) = item {
+                    self.last_device_id = kind;
+                }
+                Ok(item)
+            }
+            protocol::Tracked::Swallowed => Ok(None),
+            protocol::Tracked::DeviceId(kind) => {
+                self.last_device_id = kind;
+                Ok(Some(DecodedItem::Protocol(ProtocolEvent::DeviceId(kind))))
+            }
+        }
+    }
+
+    /// Store a byte from the keyboard, to be decoded later by
+    /// [`Keyboard::process_pending`].
+    ///
+    /// This does as little as possible, so you can call it from an interrupt
+    /// handler. If `N` bytes are already waiting, the oldest is overwritten
+    /// and counted (see [`Keyboard::pending_dropped`]).
+    pub fn push_raw(&mut self, byte: u8) {
+        self.pending.push(byte);
+    }
+
+    /// Decode the bytes stored by [`Keyboard::push_raw`].
+    ///
+    /// The bytes are decoded in the order they arrived, exactly as if each
+    /// had been given to [`Keyboard::add_byte`] - except that you only get
+    /// the results that aren't `Ok(None)`. Nothing is decoded until you call
+    /// this, so a key press is only seen as quickly as you call it.
+    ///
+    /// If any bytes were overwritten, you get [`Error::PendingOverflow`]
+    /// first, and any partly decoded scancode sequence is dropped. Decoding
+    /// then starts again from the oldest byte left, which may be part-way
+    /// through a sequence.
+    ///
+    /// Any bytes you don't take from the iterator stay stored.
+    pub fn process_pending(&mut self) -> PendingItems<'_, L, S, N> {
+        PendingItems { keyboard: self }
+    }
+
+    /// How many bytes are stored, waiting for [`Keyboard::process_pending`].
+    pub const fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// How many bytes [`Keyboard::push_raw`] has had to overwrite, in total.
+    ///
+    /// This wraps around, rather than overflowing.
+    pub const fn pending_dropped(&self) -> usize {
+        self.pending.dropped()
     }
 
     /// Shift a bit into the register.
     ///
     /// Call this /or/ call `add_word` - don't call both.
     /// Until the last bit is added you get Ok(None) returned.
-    pub fn add_bit(&mut self, bit: bool) -> Result<Option<KeyEvent>, Error> {
+    pub fn add_bit(&mut self, bit: bool) -> Result<Option<DecodedItem>, Error> {
         if let Some(byte) = self.ps2_decoder.add_bit(bit)? {
-            self.scancode_set.advance_state(byte)
+            self.add_byte(byte)
         } else {
             Ok(None)
         }
     }
 
+    /// Processes an 8-bit byte from the keyboard, giving the result as a
+    /// `KeyEvent`, like `add_byte` did before [`DecodedItem`] was added.
+    ///
+    /// See [`DecodedItem::into_key_event`].
+    #[deprecated(note = "use add_byte, which returns a DecodedItem")]
+    pub fn add_byte_legacy(&mut self, byte: u8) -> Result<Option<KeyEvent>, Error> {
+        Ok(self.add_byte(byte)?.and_then(DecodedItem::into_key_event))
+    }
+
     /// Processes a `KeyEvent` returned from `add_bit`, `add_byte` or `add_word`
     /// and produces a decoded key.
     ///
@@ -567,6 +1104,105 @@ where
     pub fn process_keyevent(&mut self, ev: KeyEvent) -> Option<DecodedKey> {
         self.event_decoder.process_keyevent(ev)
     }
+
+    /// Processes a `DecodedItem` returned from `add_bit`, `add_byte` or
+    /// `add_word`.
+    ///
+    /// See [`EventDecoder::process_item`].
+    pub fn process_item(&mut self, item: DecodedItem) -> Option<DecodedKey> {
+        self.event_decoder.process_item(item)
+    }
+
+    /// Add a table of extra dead key compositions.
+    ///
+    /// See [`EventDecoder::add_compositions`].
+    pub fn add_compositions(&mut self, compositions: &'static [((char, char), char)]) {
+        self.event_decoder.add_compositions(compositions);
+    }
+
+    /// Get any extra key that the last call to `process_keyevent` produced.
+    ///
+    /// See [`EventDecoder::take_queued`].
+    pub fn take_queued(&mut self) -> Option<DecodedKey> {
+        self.event_decoder.take_queued()
+    }
+
+    /// Get all the extra characters that the last call to `process_keyevent`
+    /// produced, in one go.
+    ///
+    /// See [`EventDecoder::take_output`].
+    pub fn take_output(&mut self) -> SmallOutput {
+        self.event_decoder.take_output()
+    }
+
+    /// Set how many dead keys can be pressed before the character they
+    /// apply to.
+    ///
+    /// See [`EventDecoder::set_max_dead_keys`].
+    pub fn set_max_dead_keys(&mut self, max: usize) {
+        self.event_decoder.set_max_dead_keys(max);
+    }
+
+    /// Get how many dead keys can be pressed before the character they apply
+    /// to.
+    pub const fn get_max_dead_keys(&self) -> usize {
+        self.event_decoder.get_max_dead_keys()
+    }
+
+    /// Stop a chord (like Alt + Tab) from being decoded.
+    ///
+    /// See [`EventDecoder::suppress_chord`].
+    pub fn suppress_chord(&mut self, mask: ModifierMask, code: KeyCode) -> bool {
+        self.event_decoder.suppress_chord(mask, code)
+    }
+
+    /// Stop suppressing a chord.
+    ///
+    /// See [`EventDecoder::unsuppress_chord`].
+    pub fn unsuppress_chord(&mut self, mask: ModifierMask, code: KeyCode) {
+        self.event_decoder.unsuppress_chord(mask, code);
+    }
+
+    /// Get the chord that the last call to `process_keyevent` suppressed.
+    ///
+    /// See [`EventDecoder::take_suppressed`].
+    pub fn take_suppressed(&mut self) -> Option<(ModifierMask, KeyCode)> {
+        self.event_decoder.take_suppressed()
+    }
+
+    /// Processes a buffer of bytes from the keyboard.
+    ///
+    /// This is the same as calling `add_byte` and then `process_item` for
+    /// each byte in turn. You get back all the Unicode characters that were
+    /// produced, every `DecodedItem` that was produced, and every error that
+    /// was seen.
+    #[cfg(feature = "alloc")]
+    pub fn decode_all(
+        &mut self,
+        bytes: &[u8],
+    ) -> (
+        alloc::string::String,
+        alloc::vec::Vec<DecodedItem>,
+        alloc::vec::Vec<Error>,
+    ) {
+        let mut text = alloc::string::String::new();
+        let mut events = alloc::vec::Vec::new();
+        let mut errors = alloc::vec::Vec::new();
+        for byte in bytes {
+            match self.add_byte(*byte) {
+                Ok(Some(item)) => {
+                    events.push(item.clone());
+                    if let Some(DecodedKey::Unicode(ch)) = self.process_item(item) {
+                        text.push(ch);
+                    }
+                    text.extend(self.take_output().iter());
+                }
+                Ok(None) => {}
+                Err(e) => errors.push(e),
+            }
+        }
+        (text, events, errors)
+    }
 }
 
 impl Ps2Decoder {
@@ -657,7 +1293,24 @@ where
 {
     /// Construct a new event decoder.
     pub const fn new(layout: L, handle_ctrl: HandleControl) -> EventDecoder<L> {
+        EventDecoder::with_policy(layout, handle_ctrl, DefaultPolicy)
+    }
+}
+
+impl<L, P> EventDecoder<L, P>
+where
+    L: KeyboardLayout,
+    P: ModifierPolicy,
+{
+    /// Construct a new event decoder, with your own rules for what the
+    /// modifier keys mean.
+    pub const fn with_policy(
+        layout: L,
+        handle_ctrl: HandleControl,
+        policy: P,
+    ) -> EventDecoder<L, P> {
         EventDecoder {
+            policy,
             handle_ctrl,
             modifiers: Modifiers {
                 lshift: false,
@@ -666,11 +1319,30 @@ where
                 rctrl: false,
                 numlock: true,
                 capslock: false,
+                scrolllock: false,
                 lalt: false,
                 ralt: false,
                 rctrl2: false,
+                kana: false,
             },
             layout,
+            dead_keys: ['\0'; MAX_DEAD_KEYS],
+            num_dead_keys: 0,
+            max_dead_keys: 1,
+            queued: SmallOutput::new(),
+            compositions: &[],
+            #[cfg(feature = "alloc")]
+            owned_compositions: alloc::vec::Vec::new(),
+            alt_codes: AltCodes::Disabled,
+            alt_code: None,
+            unicode_entry: false,
+            hex_code: None,
+            compose_key: None,
+            compose_sequences: compose::COMPOSE_SEQUENCES,
+            compose: None,
+            scrolllock_numpad_override: false,
+            chords: [None; MAX_SUPPRESSED_CHORDS],
+            suppressed: None,
         }
     }
 
@@ -684,14 +1356,106 @@ where
         self.handle_ctrl
     }
 
-    /// Processes a `KeyEvent` returned from `add_bit`, `add_byte` or `add_word`
-    /// and produces a decoded key.
+    /// Change how Alt + Numpad character entry works.
+    pub fn set_alt_codes(&mut self, new_value: AltCodes) {
+        self.alt_codes = new_value;
+        self.alt_code = None;
+    }
+
+    /// Get how Alt + Numpad character entry works.
+    pub const fn get_alt_codes(&self) -> AltCodes {
+        self.alt_codes
+    }
+
+    /// Enable or disable Ctrl+Shift+U character entry.
+    ///
+    /// When enabled, pressing Ctrl+Shift+U and then typing up to six hex
+    /// digits enters the Unicode character with that code point. Finish the
+    /// code with Enter or Space, or abandon it with Escape. Backspace removes
+    /// the last digit.
+    pub fn set_unicode_entry(&mut self, enabled: bool) {
+        self.unicode_entry = enabled;
+        self.hex_code = None;
+    }
+
+    /// Is Ctrl+Shift+U character entry enabled?
+    pub const fn get_unicode_entry(&self) -> bool {
+        self.unicode_entry
+    }
+
+    /// Choose a key to be the Compose key, or `None` to have no Compose key
+    /// (the default).
+    ///
+    /// After pressing the Compose key, the next two characters typed are
+    /// looked up in the table of sequences (by default
+    /// [`compose::COMPOSE_SEQUENCES`]). For example, Compose, `'`, `a` gives
+    /// `á`, and Compose, `s`, `s` gives `ß`. If there is no such sequence,
+    /// you get both characters unchanged (the second from
+    /// [`EventDecoder::take_queued`]). Escape abandons the sequence.
+    pub fn set_compose_key(&mut self, code: Option<KeyCode>) {
+        self.compose_key = code;
+        self.compose = None;
+    }
+
+    /// Get the Compose key, if there is one.
+    pub const fn get_compose_key(&self) -> Option<KeyCode> {
+        self.compose_key
+    }
+
+    /// Use your own table of Compose key sequences, instead of
+    /// [`compose::COMPOSE_SEQUENCES`].
+    ///
+    /// Each entry is `(first, second, result)`.
+    pub fn set_compose_sequences(&mut self, sequences: &'static [(char, char, char)]) {
+        self.compose_sequences = sequences;
+    }
+
+    /// Enable or disable the Scroll Lock numpad override.
+    ///
+    /// When enabled, turning Scroll Lock on makes the numpad keys act as
+    /// navigation keys (arrows, Home, End, etc), whatever the Num Lock state.
+    /// With Scroll Lock off, Num Lock works as normal.
+    pub fn set_scrolllock_numpad_override(&mut self, enabled: bool) {
+        self.scrolllock_numpad_override = enabled;
+    }
+
+    /// Is the Scroll Lock numpad override enabled?
+    pub const fn get_scrolllock_numpad_override(&self) -> bool {
+        self.scrolllock_numpad_override
+    }
+
+    /// Processes a `KeyEvent` and produces a decoded key.
     ///
     /// For example, the KeyEvent for pressing the '5' key on your keyboard
     /// gives a DecodedKey of unicode character '5', unless the shift key is
     /// held in which case you get the unicode character '%'.
+    ///
+    /// A [`KeyState::SingleShot`] event for [`KeyCode::PowerOnTestOk`] or
+    /// [`KeyCode::TooManyKeys`] gives you a [`DecodedKey::RawKey`]. For any
+    /// other key, it is handled as a [`KeyState::Down`] event immediately
+    /// followed by a [`KeyState::Up`] event, and you get the result of the
+    /// `Down` event.
     pub fn process_keyevent(&mut self, ev: KeyEvent) -> Option<DecodedKey> {
-        match ev {
+        if ev.state == KeyState::SingleShot {
+            return self.process_single_shot(ev.code);
+        }
+        if self.alt_codes != AltCodes::Disabled {
+            if let Some(result) = self.process_alt_code(&ev) {
+                return result;
+            }
+        }
+        if self.unicode_entry {
+            if let Some(result) = self.process_hex_code(&ev) {
+                return result;
+            }
+        }
+        if ev.state == KeyState::Down && Some(ev.code) == self.compose_key {
+            // Start a new sequence, giving back anything already typed
+            let first = self.compose.and_then(|compose| compose.first);
+            self.compose = Some(Compose { first: None });
+            return first.map(DecodedKey::Unicode);
+        }
+        let key = match ev {
             KeyEvent {
                 code: KeyCode::LShift,
                 state: KeyState::Down,
@@ -727,6 +1491,21 @@ where
                 self.modifiers.capslock = !self.modifiers.capslock;
                 Some(DecodedKey::RawKey(KeyCode::CapsLock))
             }
+            KeyEvent {
+                code: KeyCode::ScrollLock,
+                state: KeyState::Down,
+            } => {
+                self.modifiers.scrolllock = !self.modifiers.scrolllock;
+                Some(DecodedKey::RawKey(KeyCode::ScrollLock))
+            }
+            KeyEvent {
+                code: KeyCode::Oem11,
+                state: KeyState::Down,
+            } => {
+                // Hiragana/Katakana on a JIS keyboard
+                self.modifiers.kana = !self.modifiers.kana;
+                Some(DecodedKey::RawKey(KeyCode::Oem11))
+            }
             KeyEvent {
                 code: KeyCode::NumpadLock,
                 state: KeyState::Down,
@@ -814,28 +1593,685 @@ where
             KeyEvent {
                 code: c,
                 state: KeyState::Down,
-            } => Some(
-                self.layout
-                    .map_keycode(c, &self.modifiers, self.handle_ctrl),
-            ),
+            } if self.is_suppressed(c) => {
+                self.suppressed = Some((self.modifiers.mask(), c));
+                None
+            }
+            KeyEvent {
+                code: c,
+                state: KeyState::Down,
+            } => {
+                let mut modifiers = self.policy.apply(&self.modifiers);
+                if self.scrolllock_numpad_override && self.modifiers.scrolllock {
+                    // Pretend Num Lock is off, so the numpad navigates
+                    modifiers.numlock = false;
+                }
+                let key = match self.layout.map_keycode(c, &modifiers, self.handle_ctrl) {
+                    DecodedKey::RawKey(k) => DecodedKey::RawKey(self.layout.map_raw(k, &modifiers)),
+                    key => key,
+                };
+                self.apply_dead_key(key)
+            }
             _ => None,
+        };
+        match key {
+            Some(key) if self.compose.is_some() => self.apply_compose(key),
+            key => key,
         }
     }
 
-    /// Change the keyboard layout.
+    /// Processes a `DecodedItem` from a [`ScancodeSet`].
     ///
-    /// Only useful with [`layouts::AnyLayout`], otherwise you can only change a
-    /// layout for exactly the same layout.
-    pub fn change_layout(&mut self, new_layout: L) {
-        self.layout = new_layout;
+    /// A [`DecodedItem::Key`] goes to `process_keyevent`, and a
+    /// [`DecodedItem::Protocol`] gives you a [`DecodedKey::RawKey`] with the
+    /// matching [`KeyCode`] (see [`ProtocolEvent::key_code`]), if it has one.
+    pub fn process_item(&mut self, item: DecodedItem) -> Option<DecodedKey> {
+        match item {
+            DecodedItem::Key(ev) => self.process_keyevent(ev),
+            DecodedItem::Protocol(p) => p.key_code().map(DecodedKey::RawKey),
+        }
     }
-}
 
-impl KeyEvent {
-    pub const fn new(code: KeyCode, state: KeyState) -> KeyEvent {
-        KeyEvent { code, state }
+    /// Get any extra key that the last call to `process_keyevent` produced.
+    ///
+    /// If a dead key is followed by a character it cannot be combined with,
+    /// `process_keyevent` gives you the dead key's diacritic and the
+    /// character is held here until you collect it.
+    pub fn take_queued(&mut self) -> Option<DecodedKey> {
+        self.queued.pop_front().map(DecodedKey::Unicode)
     }
-}
+
+    /// Get all the extra characters that the last call to `process_keyevent`
+    /// produced, in one go.
+    ///
+    /// This empties the queue that [`EventDecoder::take_queued`] reads from.
+    pub fn take_output(&mut self) -> SmallOutput {
+        core::mem::take(&mut self.queued)
+    }
+
+    /// Set how many dead keys can be pressed before the character they
+    /// apply to. The default is one, and the most is two.
+    ///
+    /// With two, you can type (for example) a Greek breathing and an accent
+    /// before the vowel, in either order. When the limit is reached, another
+    /// dead key pushes out the oldest one, which is given back as its
+    /// spacing character.
+    ///
+    /// Any pending dead keys are forgotten.
+    pub fn set_max_dead_keys(&mut self, max: usize) {
+        self.max_dead_keys = max.clamp(1, MAX_DEAD_KEYS);
+        self.num_dead_keys = 0;
+    }
+
+    /// Get how many dead keys can be pressed before the character they apply
+    /// to.
+    pub const fn get_max_dead_keys(&self) -> usize {
+        self.max_dead_keys
+    }
+
+    /// Stop a chord (like Alt + Tab) from being decoded.
+    ///
+    /// When `code` is pressed while exactly the modifiers in `mask` are held,
+    /// `process_keyevent` returns `None` and the chord can be collected with
+    /// [`EventDecoder::take_suppressed`] instead. Modifier keys themselves
+    /// can't be suppressed, so the modifier state is always kept up to date.
+    ///
+    /// Returns `false` if the table of chords is full.
+    pub fn suppress_chord(&mut self, mask: ModifierMask, code: KeyCode) -> bool {
+        if self.chords.contains(&Some((mask, code))) {
+            return true;
+        }
+        for slot in self.chords.iter_mut() {
+            if slot.is_none() {
+                *slot = Some((mask, code));
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Stop suppressing a chord added with [`EventDecoder::suppress_chord`].
+    pub fn unsuppress_chord(&mut self, mask: ModifierMask, code: KeyCode) {
+        for slot in self.chords.iter_mut() {
+            if *slot == Some((mask, code)) {
+                *slot = None;
+            }
+        }
+    }
+
+    /// Get the chord that the last call to `process_keyevent` suppressed.
+    pub fn take_suppressed(&mut self) -> Option<(ModifierMask, KeyCode)> {
+        self.suppressed.take()
+    }
+
+    /// Is this key, with the current modifiers, a suppressed chord?
+    fn is_suppressed(&self, code: KeyCode) -> bool {
+        self.chords.contains(&Some((self.modifiers.mask(), code)))
+    }
+
+    /// Add a table of extra dead key compositions.
+    ///
+    /// Each entry is `((dead_key, base), result)`, as in
+    /// [`compose::DEAD_KEY_COMPOSITIONS`]. These entries are checked before the
+    /// built-in table, so you can use them to add new compositions or to
+    /// override existing ones.
+    ///
+    /// Only one extra table is held - calling this again replaces the table
+    /// given previously.
+    pub fn add_compositions(&mut self, compositions: &'static [((char, char), char)]) {
+        self.compositions = compositions;
+    }
+
+    /// Add some extra dead key compositions, which are checked before both
+    /// the built-in table and any table given to `add_compositions`.
+    #[cfg(feature = "alloc")]
+    pub fn extend_compositions<I>(&mut self, compositions: I)
+    where
+        I: IntoIterator<Item = ((char, char), char)>,
+    {
+        self.owned_compositions.extend(compositions);
+    }
+
+    /// Handle a key which was pressed and released in one event.
+    fn process_single_shot(&mut self, code: KeyCode) -> Option<DecodedKey> {
+        match code {
+            KeyCode::PowerOnTestOk | KeyCode::TooManyKeys => Some(DecodedKey::RawKey(code)),
+            _ => {
+                let result = self.process_keyevent(KeyEvent::new(code, KeyState::Down));
+                self.process_keyevent(KeyEvent::new(code, KeyState::Up));
+                result
+            }
+        }
+    }
+
+    /// Handle Alt + Numpad character entry.
+    ///
+    /// Returns `Some` if the event was used up, and `None` if it should be
+    /// processed as normal.
+    fn process_alt_code(&mut self, ev: &KeyEvent) -> Option<Option<DecodedKey>> {
+        match ev.state {
+            KeyState::Down if self.modifiers.lalt => {
+                if let Some(digit) = Self::numpad_digit(ev.code) {
+                    let alt_code = self.alt_code.get_or_insert(AltCode {
+                        value: 0,
+                        digits: 0,
+                        leading_zero: digit == 0,
+                    });
+                    // Past the limit the code is thrown away, so stop
+                    // adding digits before the value can overflow
+                    if alt_code.digits < MAX_ALT_CODE_DIGITS {
+                        alt_code.value = alt_code.value * 10 + u32::from(digit);
+                    }
+                    alt_code.digits = alt_code.digits.saturating_add(1);
+                    Some(None)
+                } else {
+                    // Any other key press abandons the code
+                    if ev.code != KeyCode::LAlt {
+                        self.alt_code = None;
+                    }
+                    None
+                }
+            }
+            KeyState::Up if ev.code == KeyCode::LAlt => {
+                self.modifiers.lalt = false;
+                let alt_code = self.alt_code.take()?;
+                Some(self.alt_code_to_char(alt_code).map(DecodedKey::Unicode))
+            }
+            _ => None,
+        }
+    }
+
+    /// Convert a completed Alt + Numpad code into a character.
+    fn alt_code_to_char(&self, alt_code: AltCode) -> Option<char> {
+        if alt_code.digits > MAX_ALT_CODE_DIGITS || alt_code.value == 0 {
+            None
+        } else if !alt_code.leading_zero {
+            let byte = u8::try_from(alt_code.value).ok()?;
+            Some(codepage::cp437(byte))
+        } else if self.alt_codes == AltCodes::Windows1252 {
+            let byte = u8::try_from(alt_code.value).ok()?;
+            codepage::cp1252(byte)
+        } else {
+            char::from_u32(alt_code.value)
+        }
+    }
+
+    /// Handle Ctrl+Shift+U character entry.
+    ///
+    /// Returns `Some` if the event was used up, and `None` if it should be
+    /// processed as normal.
+    fn process_hex_code(&mut self, ev: &KeyEvent) -> Option<Option<DecodedKey>> {
+        if ev.state != KeyState::Down {
+            return None;
+        }
+        let mut hex_code = match self.hex_code {
+            Some(hex_code) => hex_code,
+            None => {
+                if ev.code == KeyCode::U && self.modifiers.is_ctrl() && self.modifiers.is_shifted()
+                {
+                    self.hex_code = Some(HexCode {
+                        value: 0,
+                        digits: 0,
+                    });
+                    return Some(None);
+                }
+                return None;
+            }
+        };
+        match ev.code {
+            KeyCode::LShift
+            | KeyCode::RShift
+            | KeyCode::LControl
+            | KeyCode::RControl
+            | KeyCode::RControl2
+            | KeyCode::LAlt
+            | KeyCode::RAltGr
+            | KeyCode::CapsLock
+            | KeyCode::NumpadLock => None,
+            KeyCode::Return | KeyCode::NumpadEnter | KeyCode::Spacebar => {
+                self.hex_code = None;
+                if hex_code.digits == 0 {
+                    Some(None)
+                } else {
+                    Some(char::from_u32(hex_code.value).map(DecodedKey::Unicode))
+                }
+            }
+            KeyCode::Escape => {
+                self.hex_code = None;
+                Some(None)
+            }
+            KeyCode::Backspace => {
+                if hex_code.digits > 0 {
+                    hex_code.value /= 16;
+                    hex_code.digits -= 1;
+                }
+                self.hex_code = Some(hex_code);
+                Some(None)
+            }
+            code => {
+                // Ask the layout what the key is, ignoring Ctrl and Shift
+                // because they are often still held down.
+                let modifiers = Modifiers {
+                    numlock: true,
+                    ..Default::default()
+                };
+                let digit = match self
+                    .layout
+                    .map_keycode(code, &modifiers, HandleControl::Ignore)
+                {
+                    DecodedKey::Unicode(ch) => ch.to_digit(16),
+                    _ => None,
+                };
+                if let Some(digit) = digit {
+                    if hex_code.digits < MAX_HEX_CODE_DIGITS {
+                        hex_code.value = (hex_code.value * 16) + digit;
+                        hex_code.digits += 1;
+                    }
+                    self.hex_code = Some(hex_code);
+                    Some(None)
+                } else {
+                    // Anything else abandons the code
+                    self.hex_code = None;
+                    None
+                }
+            }
+        }
+    }
+
+    /// Get the digit on a numeric keypad key, ignoring Num Lock.
+    const fn numpad_digit(code: KeyCode) -> Option<u8> {
+        match code {
+            KeyCode::Numpad0 => Some(0),
+            KeyCode::Numpad1 => Some(1),
+            KeyCode::Numpad2 => Some(2),
+            KeyCode::Numpad3 => Some(3),
+            KeyCode::Numpad4 => Some(4),
+            KeyCode::Numpad5 => Some(5),
+            KeyCode::Numpad6 => Some(6),
+            KeyCode::Numpad7 => Some(7),
+            KeyCode::Numpad8 => Some(8),
+            KeyCode::Numpad9 => Some(9),
+            _ => None,
+        }
+    }
+
+    /// Combine a dead key with a character, using the user's tables first,
+    /// then the layout, then the built-in tables.
+    fn combine(&self, dead_key: char, ch: char) -> Option<char> {
+        #[cfg(feature = "alloc")]
+        if let Some(combined) = compose::lookup(&self.owned_compositions, dead_key, ch) {
+            return Some(combined);
+        }
+        compose::lookup(self.compositions, dead_key, ch)
+            .or_else(|| self.layout.dead_key_combine(dead_key, ch))
+            .or_else(|| compose::combine(dead_key, ch))
+    }
+
+    /// Combine all the pending dead keys with a character, in either order.
+    fn combine_pending(&self, pending: &[char], ch: char) -> Option<char> {
+        match pending {
+            [dead_key] => self.combine(*dead_key, ch),
+            [first, second] => self
+                .combine(*second, ch)
+                .and_then(|c| self.combine(*first, c))
+                .or_else(|| {
+                    self.combine(*first, ch)
+                        .and_then(|c| self.combine(*second, c))
+                }),
+            _ => None,
+        }
+    }
+
+    /// Hold an extra character for `take_queued`.
+    ///
+    /// If the queue is full, the character is dropped.
+    fn queue(&mut self, ch: char) {
+        let _ = self.queued.push(ch);
+    }
+
+    /// Combine the pending dead keys (if any) with a newly decoded key.
+    ///
+    /// Escape or Backspace cancels any pending dead keys, and produces
+    /// nothing.
+    fn apply_dead_key(&mut self, key: DecodedKey) -> Option<DecodedKey> {
+        let dead_keys = self.dead_keys;
+        let pending = &dead_keys[..self.num_dead_keys];
+        match key {
+            DecodedKey::DeadKey(dead_key) if pending.last() == Some(&dead_key) => {
+                // The same dead key twice types its diacritic (once)
+                self.num_dead_keys = 0;
+                for dead_key in &pending[1..] {
+                    self.queue(*dead_key);
+                }
+                Some(DecodedKey::Unicode(pending[0]))
+            }
+            DecodedKey::DeadKey(dead_key) if pending.len() < self.max_dead_keys => {
+                self.dead_keys[pending.len()] = dead_key;
+                self.num_dead_keys += 1;
+                Some(DecodedKey::DeadKey(dead_key))
+            }
+            DecodedKey::DeadKey(dead_key) => {
+                // Too many dead keys in a row - give up on the oldest one
+                self.dead_keys.rotate_left(1);
+                self.dead_keys[pending.len() - 1] = dead_key;
+                Some(DecodedKey::Unicode(pending[0]))
+            }
+            DecodedKey::Unicode('\u{1b}' | '\u{8}') if !pending.is_empty() => {
+                self.num_dead_keys = 0;
+                None
+            }
+            DecodedKey::Unicode(ch) if !pending.is_empty() => {
+                self.num_dead_keys = 0;
+                if let Some(combined) = self.combine_pending(pending, ch) {
+                    return Some(DecodedKey::Unicode(combined));
+                }
+                // Give back each diacritic on its own, then the character
+                // (unless it was a space, which just 'types' the diacritics)
+                for dead_key in &pending[1..] {
+                    self.queue(*dead_key);
+                }
+                if ch != ' ' {
+                    self.queue(ch);
+                }
+                Some(DecodedKey::Unicode(pending[0]))
+            }
+            // Raw keys (like Shift) don't affect a pending dead key
+            key => Some(key),
+        }
+    }
+
+    /// Add a newly decoded key to the Compose key sequence being typed.
+    ///
+    /// Escape abandons the sequence, and produces nothing.
+    fn apply_compose(&mut self, key: DecodedKey) -> Option<DecodedKey> {
+        let compose = self.compose?;
+        match (key, compose.first) {
+            (DecodedKey::Unicode('\u{1b}'), _) => {
+                self.compose = None;
+                None
+            }
+            (DecodedKey::Unicode(ch), None) => {
+                self.compose = Some(Compose { first: Some(ch) });
+                None
+            }
+            (DecodedKey::Unicode(ch), Some(first)) => {
+                self.compose = None;
+                if let Some(composed) = compose::lookup_sequence(self.compose_sequences, first, ch)
+                {
+                    return Some(DecodedKey::Unicode(composed));
+                }
+                self.queue(ch);
+                Some(DecodedKey::Unicode(first))
+            }
+            // Raw keys (like Shift) and dead keys don't affect the sequence
+            (key, _) => Some(key),
+        }
+    }
+
+    /// Change the keyboard layout.
+    ///
+    /// Only useful with [`layouts::AnyLayout`], otherwise you can only change a
+    /// layout for exactly the same layout.
+    pub fn change_layout(&mut self, new_layout: L) {
+        self.layout = new_layout;
+    }
+}
+
+impl KeyCode {
+    /// How many different key codes there are.
+    ///
+    /// `RAlt2` must stay the last variant.
+    pub const COUNT: usize = KeyCode::RAlt2 as usize + 1;
+
+    /// Every key code, in order, so that `KeyCode::ALL[code.to_index()] ==
+    /// code`.
+    pub const ALL: [KeyCode; KeyCode::COUNT] = [
+        KeyCode::Escape,
+        KeyCode::F1,
+        KeyCode::F2,
+        KeyCode::F3,
+        KeyCode::F4,
+        KeyCode::F5,
+        KeyCode::F6,
+        KeyCode::F7,
+        KeyCode::F8,
+        KeyCode::F9,
+        KeyCode::F10,
+        KeyCode::F11,
+        KeyCode::F12,
+        KeyCode::PrintScreen,
+        KeyCode::SysRq,
+        KeyCode::ScrollLock,
+        KeyCode::PauseBreak,
+        KeyCode::Oem8,
+        KeyCode::Key1,
+        KeyCode::Key2,
+        KeyCode::Key3,
+        KeyCode::Key4,
+        KeyCode::Key5,
+        KeyCode::Key6,
+        KeyCode::Key7,
+        KeyCode::Key8,
+        KeyCode::Key9,
+        KeyCode::Key0,
+        KeyCode::OemMinus,
+        KeyCode::OemPlus,
+        KeyCode::Backspace,
+        KeyCode::Insert,
+        KeyCode::Home,
+        KeyCode::PageUp,
+        KeyCode::NumpadLock,
+        KeyCode::NumpadDivide,
+        KeyCode::NumpadMultiply,
+        KeyCode::NumpadSubtract,
+        KeyCode::Tab,
+        KeyCode::Q,
+        KeyCode::W,
+        KeyCode::E,
+        KeyCode::R,
+        KeyCode::T,
+        KeyCode::Y,
+        KeyCode::U,
+        KeyCode::I,
+        KeyCode::O,
+        KeyCode::P,
+        KeyCode::Oem4,
+        KeyCode::Oem6,
+        KeyCode::Oem5,
+        KeyCode::Oem7,
+        KeyCode::Delete,
+        KeyCode::End,
+        KeyCode::PageDown,
+        KeyCode::Numpad7,
+        KeyCode::Numpad8,
+        KeyCode::Numpad9,
+        KeyCode::NumpadAdd,
+        KeyCode::CapsLock,
+        KeyCode::A,
+        KeyCode::S,
+        KeyCode::D,
+        KeyCode::F,
+        KeyCode::G,
+        KeyCode::H,
+        KeyCode::J,
+        KeyCode::K,
+        KeyCode::L,
+        KeyCode::Oem1,
+        KeyCode::Oem3,
+        KeyCode::Return,
+        KeyCode::Numpad4,
+        KeyCode::Numpad5,
+        KeyCode::Numpad6,
+        KeyCode::LShift,
+        KeyCode::Z,
+        KeyCode::X,
+        KeyCode::C,
+        KeyCode::V,
+        KeyCode::B,
+        KeyCode::N,
+        KeyCode::M,
+        KeyCode::OemComma,
+        KeyCode::OemPeriod,
+        KeyCode::Oem2,
+        KeyCode::RShift,
+        KeyCode::ArrowUp,
+        KeyCode::Numpad1,
+        KeyCode::Numpad2,
+        KeyCode::Numpad3,
+        KeyCode::NumpadEnter,
+        KeyCode::LControl,
+        KeyCode::LWin,
+        KeyCode::LAlt,
+        KeyCode::Spacebar,
+        KeyCode::RAltGr,
+        KeyCode::RWin,
+        KeyCode::Apps,
+        KeyCode::RControl,
+        KeyCode::ArrowLeft,
+        KeyCode::ArrowDown,
+        KeyCode::ArrowRight,
+        KeyCode::Numpad0,
+        KeyCode::NumpadPeriod,
+        KeyCode::Oem9,
+        KeyCode::Oem10,
+        KeyCode::Oem11,
+        KeyCode::Oem12,
+        KeyCode::Oem13,
+        KeyCode::NumpadComma,
+        KeyCode::Hangul,
+        KeyCode::Hanja,
+        KeyCode::PrevTrack,
+        KeyCode::NextTrack,
+        KeyCode::Mute,
+        KeyCode::Calculator,
+        KeyCode::Play,
+        KeyCode::Stop,
+        KeyCode::VolumeDown,
+        KeyCode::VolumeUp,
+        KeyCode::WWWHome,
+        KeyCode::WWWSearch,
+        KeyCode::WWWFavourites,
+        KeyCode::WWWRefresh,
+        KeyCode::WWWStop,
+        KeyCode::WWWForward,
+        KeyCode::WWWBack,
+        KeyCode::MyComputer,
+        KeyCode::Email,
+        KeyCode::MediaSelect,
+        KeyCode::Power,
+        KeyCode::Sleep,
+        KeyCode::Wake,
+        KeyCode::PowerOnTestOk,
+        KeyCode::TooManyKeys,
+        KeyCode::RControl2,
+        KeyCode::RAlt2,
+    ];
+
+    /// Get a unique index for this key code, from `0` to `KeyCode::COUNT - 1`.
+    pub const fn to_index(self) -> usize {
+        self as usize
+    }
+
+    /// Get the key code with the given index, if there is one.
+    pub const fn from_index(index: usize) -> Option<KeyCode> {
+        if index < KeyCode::COUNT {
+            Some(KeyCode::ALL[index])
+        } else {
+            None
+        }
+    }
+
+    /// The W, A, S and D keys, in up, left, down, right order.
+    ///
+    /// These are physical key positions, so on an AZERTY keyboard they are
+    /// labelled Z, Q, S and D.
+    pub const fn wasd() -> [KeyCode; 4] {
+        [KeyCode::W, KeyCode::A, KeyCode::S, KeyCode::D]
+    }
+
+    /// The arrow keys, in up, left, down, right order.
+    pub const fn arrows() -> [KeyCode; 4] {
+        [
+            KeyCode::ArrowUp,
+            KeyCode::ArrowLeft,
+            KeyCode::ArrowDown,
+            KeyCode::ArrowRight,
+        ]
+    }
+}
+
+impl KeyEvent {
+    pub const fn new(code: KeyCode, state: KeyState) -> KeyEvent {
+        KeyEvent { code, state }
+    }
+}
+
+impl Error {
+    /// Was this byte lost on the wire (a bad start, stop or parity bit)?
+    ///
+    /// If so, you can ask the keyboard to send it again, by sending it
+    /// [`protocol::RESEND`].
+    pub const fn needs_resend(self) -> bool {
+        matches!(
+            self,
+            Error::BadStartBit | Error::BadStopBit | Error::ParityError
+        )
+    }
+}
+
+impl ProtocolEvent {
+    /// The `KeyCode` that was used for this event, before `ProtocolEvent` was
+    /// added.
+    ///
+    /// Events that were never reported as a `KeyCode` give you `None`.
+    pub const fn key_code(self) -> Option<KeyCode> {
+        match self {
+            ProtocolEvent::PowerOnTestOk => Some(KeyCode::PowerOnTestOk),
+            ProtocolEvent::TooManyKeys => Some(KeyCode::TooManyKeys),
+            ProtocolEvent::PowerOnTestFailed
+            | ProtocolEvent::DeviceId(_)
+            | ProtocolEvent::CommandResponse(_) => None,
+        }
+    }
+}
+
+impl DecodedItem {
+    /// Get the key event, if this is one.
+    pub const fn key_event(&self) -> Option<&KeyEvent> {
+        match self {
+            DecodedItem::Key(ev) => Some(ev),
+            DecodedItem::Protocol(_) => None,
+        }
+    }
+
+    /// Convert to a `KeyEvent`, the way these events were reported before
+    /// `DecodedItem` was added.
+    ///
+    /// A [`DecodedItem::Protocol`] becomes a [`KeyState::SingleShot`] event
+    /// (see [`ProtocolEvent::key_code`]), or `None` if there was no such
+    /// event before.
+    pub const fn into_key_event(self) -> Option<KeyEvent> {
+        match self {
+            DecodedItem::Key(ev) => Some(ev),
+            DecodedItem::Protocol(p) => match p.key_code() {
+                Some(code) => Some(KeyEvent::new(code, KeyState::SingleShot)),
+                None => None,
+            },
+        }
+    }
+}
+
+impl From<KeyEvent> for DecodedItem {
+    fn from(ev: KeyEvent) -> DecodedItem {
+        DecodedItem::Key(ev)
+    }
+}
+
+impl From<ProtocolEvent> for DecodedItem {
+    fn from(p: ProtocolEvent) -> DecodedItem {
+        DecodedItem::Protocol(p)
+    }
+}
 
 // ****************************************************************************
 //
@@ -844,25 +2280,117 @@ impl KeyEvent {
 // ****************************************************************************
 
 impl Modifiers {
+    /// Is either Shift key held down?
+    ///
+    /// Use this for symbol keys, like `1` and `!`.
     pub const fn is_shifted(&self) -> bool {
         self.lshift | self.rshift
     }
 
+    /// Is either Control key held down?
     pub const fn is_ctrl(&self) -> bool {
         self.lctrl | self.rctrl
     }
 
+    /// Is either Alt key held down?
     pub const fn is_alt(&self) -> bool {
         self.lalt | self.ralt
     }
 
+    /// Is AltGr held down? Control + Left Alt also counts as AltGr.
     pub const fn is_altgr(&self) -> bool {
         self.ralt | (self.lalt & self.is_ctrl())
     }
 
+    /// Should a letter key give an upper case letter?
+    ///
+    /// This is Shift, or Caps Lock, but not both.
     pub const fn is_caps(&self) -> bool {
         self.is_shifted() ^ self.capslock
     }
+
+    /// Which of Shift, Control and Alt are held down.
+    pub const fn mask(&self) -> ModifierMask {
+        let mut mask = 0;
+        if self.is_shifted() {
+            mask |= ModifierMask::SHIFT.0;
+        }
+        if self.is_ctrl() {
+            mask |= ModifierMask::CTRL.0;
+        }
+        if self.is_alt() {
+            mask |= ModifierMask::ALT.0;
+        }
+        ModifierMask(mask)
+    }
+
+    /// Handle a letter key, with any lower and upper case pair of letters.
+    ///
+    /// Caps Lock and Shift both select the upper case letter. If the upper
+    /// case letter is ASCII 'A'..'Z', and Ctrl is held, you get a Control code
+    /// instead (if `handle_ctrl` asks for one). Other letters, like 'Ä' or
+    /// 'Ж', ignore Ctrl.
+    pub fn handle_letter_auto(
+        &self,
+        lower: char,
+        upper: char,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        if upper.is_ascii_uppercase()
+            && handle_ctrl == HandleControl::MapLettersToUnicode
+            && self.is_ctrl()
+        {
+            // Get a Control code, like Ctrl+C => U+0003
+            const ASCII_UPPERCASE_START_OFFSET: u8 = 64;
+            DecodedKey::Unicode((upper as u8 - ASCII_UPPERCASE_START_OFFSET) as char)
+        } else if self.is_caps() {
+            DecodedKey::Unicode(upper)
+        } else {
+            DecodedKey::Unicode(lower)
+        }
+    }
+
+    /// Handle letter keys with standard ASCII 'A'..'Z' keycaps, which also
+    /// have a lower and upper case letter on AltGr.
+    ///
+    /// AltGr wins over Ctrl (because Ctrl + Left Alt is also AltGr), and Caps
+    /// Lock and Shift both select the upper case variant.
+    pub fn handle_ascii_4(
+        &self,
+        letter: AsciiUpper,
+        alt_letter_lower: char,
+        alt_letter_upper: char,
+        handle_ctrl: HandleControl,
+    ) -> DecodedKey {
+        if self.is_altgr() {
+            self.handle_letter_auto(alt_letter_lower, alt_letter_upper, HandleControl::Ignore)
+        } else {
+            self.handle_letter_auto(letter.lower(), letter.upper(), handle_ctrl)
+        }
+    }
+}
+
+impl AsciiUpper {
+    /// Check that `letter` is 'A'..'Z'.
+    ///
+    /// This panics if not, which is a compile error when used in a `const`.
+    pub const fn new(letter: char) -> AsciiUpper {
+        assert!(
+            letter.is_ascii_uppercase(),
+            "not an ASCII upper case letter"
+        );
+        AsciiUpper(letter)
+    }
+
+    /// Get the upper case letter.
+    pub const fn upper(self) -> char {
+        self.0
+    }
+
+    /// Get the lower case letter.
+    pub const fn lower(self) -> char {
+        self.0.to_ascii_lowercase()
+    }
 }
 
 // ****************************************************************************
@@ -884,7 +2412,7 @@ mod test {
             let result = keyboard.add_byte(byte);
             assert_eq!(
                 result,
-                Ok(expected_key.clone()),
+                Ok(expected_key.clone().map(DecodedItem::Key)),
                 "0x{:02x} should have given {:?} not {:?}",
                 byte,
                 expected_key,
@@ -937,7 +2465,10 @@ mod test {
         // stop
         assert_eq!(
             k.add_bit(true),
-            Ok(Some(KeyEvent::new(KeyCode::F9, KeyState::Down)))
+            Ok(Some(DecodedItem::Key(KeyEvent::new(
+                KeyCode::F9,
+                KeyState::Down
+            ))))
         );
     }
 
@@ -950,7 +2481,10 @@ mod test {
         );
         assert_eq!(
             k.add_word(0x0402),
-            Ok(Some(KeyEvent::new(KeyCode::F9, KeyState::Down)))
+            Ok(Some(DecodedItem::Key(KeyEvent::new(
+                KeyCode::F9,
+                KeyState::Down
+            ))))
         );
     }
 
@@ -1005,7 +2539,10 @@ mod test {
         // stop
         assert_eq!(
             k.add_bit(true),
-            Ok(Some(KeyEvent::new(KeyCode::F5, KeyState::Down)))
+            Ok(Some(DecodedItem::Key(KeyEvent::new(
+                KeyCode::F5,
+                KeyState::Down
+            ))))
         );
     }
 
@@ -1052,7 +2589,10 @@ mod test {
         // stop
         assert_eq!(
             k.add_bit(true),
-            Ok(Some(KeyEvent::new(KeyCode::F5, KeyState::Up)))
+            Ok(Some(DecodedItem::Key(KeyEvent::new(
+                KeyCode::F5,
+                KeyState::Up
+            ))))
         );
     }
 
@@ -1204,81 +2744,373 @@ mod test {
     }
 
     #[test]
-    fn test_set_1_down_up_down() {
-        let mut k = Keyboard::new(
-            ScancodeSet1::new(),
-            layouts::Us104Key,
-            HandleControl::MapLettersToUnicode,
-        );
-        let test_sequence = [
-            (0x1e, Some(KeyEvent::new(KeyCode::A, KeyState::Down))),
-            (0x9e, Some(KeyEvent::new(KeyCode::A, KeyState::Up))),
-            (0x1f, Some(KeyEvent::new(KeyCode::S, KeyState::Down))),
+    fn test_sort_events() {
+        let mut events = vec![
+            KeyEvent::new(KeyCode::B, KeyState::Down),
+            KeyEvent::new(KeyCode::A, KeyState::SingleShot),
+            KeyEvent::new(KeyCode::A, KeyState::Down),
+            KeyEvent::new(KeyCode::Escape, KeyState::Up),
+            KeyEvent::new(KeyCode::A, KeyState::Up),
+            KeyEvent::new(KeyCode::B, KeyState::Down),
         ];
-
-        add_bytes(&mut k, &test_sequence);
+        events.sort();
+        assert_eq!(
+            events,
+            [
+                KeyEvent::new(KeyCode::Escape, KeyState::Up),
+                KeyEvent::new(KeyCode::A, KeyState::Up),
+                KeyEvent::new(KeyCode::A, KeyState::Down),
+                KeyEvent::new(KeyCode::A, KeyState::SingleShot),
+                KeyEvent::new(KeyCode::B, KeyState::Down),
+                KeyEvent::new(KeyCode::B, KeyState::Down),
+            ]
+        );
+        events.dedup();
+        assert_eq!(events.len(), 5);
+        let set: std::collections::HashSet<KeyEvent> = events.iter().cloned().collect();
+        assert_eq!(set.len(), 5);
     }
 
     #[test]
-    fn test_set_1_ext_down_up_down() {
+    fn test_sort_decoded_keys() {
+        let mut keys = vec![
+            DecodedKey::DeadKey('^'),
+            DecodedKey::RawKey(KeyCode::F1),
+            DecodedKey::Unicode('b'),
+            DecodedKey::RawKey(KeyCode::Escape),
+            DecodedKey::Unicode('a'),
+            DecodedKey::DeadKey('`'),
+            DecodedKey::Unicode('é'),
+        ];
+        let expected = [
+            DecodedKey::Unicode('a'),
+            DecodedKey::Unicode('b'),
+            DecodedKey::Unicode('é'),
+            DecodedKey::RawKey(KeyCode::Escape),
+            DecodedKey::RawKey(KeyCode::F1),
+            DecodedKey::DeadKey('^'),
+            DecodedKey::DeadKey('`'),
+        ];
+        keys.sort();
+        assert_eq!(keys, expected);
+        // Sorting again, from the reverse order, gives the same answer
+        keys.reverse();
+        keys.sort();
+        assert_eq!(keys, expected);
+        let set: std::collections::BTreeSet<DecodedKey> = keys.iter().copied().collect();
+        assert!(set.iter().eq(expected.iter()));
+    }
+
+    /// Set up a keyboard which is part-way through an Arrow Up (E0 75) and
+    /// part-way through a word in the bit register, then corrupt a word.
+    fn induce_parity_error(policy: ErrorPolicy) -> Keyboard<layouts::Us104Key, ScancodeSet2> {
         let mut k = Keyboard::new(
-            ScancodeSet1::new(),
+            ScancodeSet2::new(),
             layouts::Us104Key,
             HandleControl::MapLettersToUnicode,
         );
-        let test_sequence = [
-            (0xe0, None),
-            (
-                0x1c,
-                Some(KeyEvent::new(KeyCode::NumpadEnter, KeyState::Down)),
-            ),
-            (0xe0, None),
-            (
-                0x9c,
-                Some(KeyEvent::new(KeyCode::NumpadEnter, KeyState::Up)),
-            ),
-        ];
-        add_bytes(&mut k, &test_sequence);
+        assert_eq!(k.process_word_with_policy(0x05C0, policy), Ok(None));
+        for _ in 0..3 {
+            assert_eq!(k.add_bit(false), Ok(None));
+        }
+        // 0x75 with the parity bit flipped
+        let result = k.process_word_with_policy(0x06EA, policy);
+        assert_eq!(result, Err(Error::ParityError));
+        assert!(result.unwrap_err().needs_resend());
+        k
+    }
+
+    /// Shift in a word, one bit at a time
+    fn add_word_bits<L, S>(k: &mut Keyboard<L, S>, word: u16) -> Result<Option<DecodedItem>, Error>
+    where
+        L: KeyboardLayout,
+        S: ScancodeSet,
+    {
+        for bit in 0..10 {
+            assert_eq!(k.add_bit(word & (1 << bit) != 0), Ok(None));
+        }
+        k.add_bit(word & (1 << 10) != 0)
     }
 
     #[test]
-    fn test_set_2_poweron() {
-        let mut k = Keyboard::new(
+    fn test_push_raw() {
+        // Shift, A (with an unknown code in between), Pause, Arrow Up release
+        let bytes = [
+            0x12, 0x1C, 0xF0, 0x1C, 0x60, 0xF0, 0x12, 0xE1, 0x14, 0x77, 0xE1, 0xF0, 0x14, 0xF0,
+            0x77, 0xE0, 0xF0, 0x75,
+        ];
+        let mut direct = Keyboard::new(
             ScancodeSet2::new(),
             layouts::Us104Key,
-            HandleControl::MapLettersToUnicode,
+            HandleControl::Ignore,
         );
-        let test_sequence = [(
-            0xAA,
-            Some(KeyEvent::new(KeyCode::PowerOnTestOk, KeyState::SingleShot)),
-        )];
-        add_bytes(&mut k, &test_sequence);
+        let expected: Vec<_> = bytes
+            .iter()
+            .filter_map(|b| direct.add_byte(*b).transpose())
+            .collect();
+        let mut k: Keyboard<_, _, 32> = Keyboard::with_pending_depth(
+            ScancodeSet2::new(),
+            layouts::Us104Key,
+            HandleControl::Ignore,
+        );
+        assert_eq!(k.process_pending().next(), None);
+        for b in bytes.iter() {
+            k.push_raw(*b);
+        }
+        assert_eq!(k.pending_len(), bytes.len());
+        let results: Vec<_> = k.process_pending().collect();
+        assert_eq!(results, expected);
+        assert!(results.contains(&Err(Error::UnknownKeyCode)));
+        assert_eq!(k.pending_len(), 0);
+        assert_eq!(k.pending_dropped(), 0);
     }
 
     #[test]
-    fn test_set_2_toomanykeys() {
-        let mut k = Keyboard::new(
+    fn test_push_raw_overflow() {
+        let mut k: Keyboard<_, _, 4> = Keyboard::with_pending_depth(
             ScancodeSet2::new(),
             layouts::Us104Key,
-            HandleControl::MapLettersToUnicode,
+            HandleControl::Ignore,
         );
-        let test_sequence = [(
-            0x00,
-            Some(KeyEvent::new(KeyCode::TooManyKeys, KeyState::SingleShot)),
-        )];
-        add_bytes(&mut k, &test_sequence);
+        // A down, then half of an Arrow Up release
+        for b in [0x1C, 0xE0, 0xF0] {
+            k.push_raw(b);
+        }
+        assert_eq!(
+            k.process_pending().next(),
+            Some(Ok(DecodedItem::Key(KeyEvent::new(
+                KeyCode::A,
+                KeyState::Down
+            ))))
+        );
+        // Unused results stay put
+        assert_eq!(k.pending_len(), 2);
+        // The rest of the Arrow Up release, then B down and B up, which
+        // pushes out the E0 and F0
+        for b in [0x75, 0x32, 0xF0, 0x32] {
+            k.push_raw(b);
+        }
+        assert_eq!(k.pending_dropped(), 2);
+        let results: Vec<_> = k.process_pending().collect();
+        assert_eq!(
+            results,
+            [
+                Err(Error::PendingOverflow),
+                // The orphaned byte is Numpad 8
+                Ok(DecodedItem::Key(KeyEvent::new(
+                    KeyCode::Numpad8,
+                    KeyState::Down
+                ))),
+                Ok(DecodedItem::Key(KeyEvent::new(KeyCode::B, KeyState::Down))),
+                Ok(DecodedItem::Key(KeyEvent::new(KeyCode::B, KeyState::Up))),
+            ]
+        );
+        // The overflow is only reported once
+        assert_eq!(k.process_pending().next(), None);
+        assert_eq!(k.pending_dropped(), 2);
     }
 
     #[test]
-    fn test_set_2_down_up() {
+    fn test_clear() {
         let mut k = Keyboard::new(
             ScancodeSet2::new(),
             layouts::Us104Key,
             HandleControl::MapLettersToUnicode,
         );
-        let test_sequence = [
-            (0x29, Some(KeyEvent::new(KeyCode::Spacebar, KeyState::Down))),
-            (0xF0, None),
+        // An E0 arrives, then the rest of the sequence is lost
+        assert_eq!(k.add_byte(0xE0), Ok(None));
+        k.clear();
+        // So this is Left Control, not Right Control
+        assert_eq!(
+            k.add_byte(0x14),
+            Ok(Some(DecodedItem::Key(KeyEvent::new(
+                KeyCode::LControl,
+                KeyState::Down
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_policy_clear_all() {
+        let mut k = induce_parity_error(ErrorPolicy::ClearAll);
+        // The E0 has been forgotten, so this is Numpad 8
+        assert_eq!(
+            add_word_bits(&mut k, 0x04EA),
+            Ok(Some(DecodedItem::Key(KeyEvent::new(
+                KeyCode::Numpad8,
+                KeyState::Down
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_policy_clear_ps2_only() {
+        let mut k = induce_parity_error(ErrorPolicy::ClearPs2Only);
+        // The resent byte finishes the Arrow Up
+        assert_eq!(
+            add_word_bits(&mut k, 0x04EA),
+            Ok(Some(DecodedItem::Key(KeyEvent::new(
+                KeyCode::ArrowUp,
+                KeyState::Down
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_policy_propagate() {
+        let mut k = induce_parity_error(ErrorPolicy::Propagate);
+        // The three stale bits are still in the register, so the word is
+        // misaligned. Words don't use the register, so they still work.
+        assert_eq!(
+            k.process_word_with_policy(0x04EA, ErrorPolicy::Propagate),
+            Ok(Some(DecodedItem::Key(KeyEvent::new(
+                KeyCode::ArrowUp,
+                KeyState::Down
+            ))))
+        );
+        for bit in 0..7 {
+            assert_eq!(k.add_bit(0x0438 & (1 << bit) != 0), Ok(None));
+        }
+        assert_ne!(
+            k.add_bit(0x0438 & (1 << 7) != 0),
+            Ok(Some(DecodedItem::Key(KeyEvent::new(
+                KeyCode::A,
+                KeyState::Down
+            ))))
+        );
+        assert!(!Error::UnknownKeyCode.needs_resend());
+    }
+
+    #[test]
+    fn test_set_1_down_up_down() {
+        let mut k = Keyboard::new(
+            ScancodeSet1::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        let test_sequence = [
+            (0x1e, Some(KeyEvent::new(KeyCode::A, KeyState::Down))),
+            (0x9e, Some(KeyEvent::new(KeyCode::A, KeyState::Up))),
+            (0x1f, Some(KeyEvent::new(KeyCode::S, KeyState::Down))),
+        ];
+
+        add_bytes(&mut k, &test_sequence);
+    }
+
+    #[test]
+    fn test_set_1_ext_down_up_down() {
+        let mut k = Keyboard::new(
+            ScancodeSet1::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        let test_sequence = [
+            (0xe0, None),
+            (
+                0x1c,
+                Some(KeyEvent::new(KeyCode::NumpadEnter, KeyState::Down)),
+            ),
+            (0xe0, None),
+            (
+                0x9c,
+                Some(KeyEvent::new(KeyCode::NumpadEnter, KeyState::Up)),
+            ),
+        ];
+        add_bytes(&mut k, &test_sequence);
+    }
+
+    #[test]
+    fn test_set_2_poweron() {
+        let mut k = Keyboard::new(
+            ScancodeSet2::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        let item = k.add_byte(0xAA).unwrap().unwrap();
+        assert_eq!(item, DecodedItem::Protocol(ProtocolEvent::PowerOnTestOk));
+        assert_eq!(
+            k.process_item(item),
+            Some(DecodedKey::RawKey(KeyCode::PowerOnTestOk))
+        );
+    }
+
+    #[test]
+    fn test_set_2_toomanykeys() {
+        let mut k = Keyboard::new(
+            ScancodeSet2::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        let item = k.add_byte(0x00).unwrap().unwrap();
+        assert_eq!(item, DecodedItem::Protocol(ProtocolEvent::TooManyKeys));
+        assert_eq!(item.key_event(), None);
+        assert_eq!(
+            k.process_item(item),
+            Some(DecodedKey::RawKey(KeyCode::TooManyKeys))
+        );
+    }
+
+    #[test]
+    fn test_command_response_has_no_key_code() {
+        let mut k = Keyboard::new(
+            ScancodeSet2::new_protocol_aware(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        let item = k.add_byte(protocol::ACK).unwrap().unwrap();
+        assert_eq!(
+            item,
+            DecodedItem::Protocol(ProtocolEvent::CommandResponse(protocol::Response::Ack))
+        );
+        assert_eq!(item.clone().into_key_event(), None);
+        assert_eq!(k.process_item(item), None);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_set_2_legacy() {
+        let mut k = Keyboard::new(
+            ScancodeSet2::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        assert_eq!(
+            k.add_byte_legacy(0xAA),
+            Ok(Some(KeyEvent::new(
+                KeyCode::PowerOnTestOk,
+                KeyState::SingleShot
+            )))
+        );
+        assert_eq!(
+            k.add_byte_legacy(0x00),
+            Ok(Some(KeyEvent::new(
+                KeyCode::TooManyKeys,
+                KeyState::SingleShot
+            )))
+        );
+        assert_eq!(
+            k.add_byte_legacy(0x1C),
+            Ok(Some(KeyEvent::new(KeyCode::A, KeyState::Down)))
+        );
+        assert_eq!(k.add_byte_legacy(0xF0), Ok(None));
+        assert_eq!(
+            k.add_byte(0x1C).unwrap().unwrap().into_key_event(),
+            Some(KeyEvent::new(KeyCode::A, KeyState::Up))
+        );
+        // Events that are new since DecodedItem was added are skipped
+        assert_eq!(k.add_byte_legacy(0xFC), Ok(None));
+    }
+
+    #[test]
+    fn test_set_2_down_up() {
+        let mut k = Keyboard::new(
+            ScancodeSet2::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        let test_sequence = [
+            (0x29, Some(KeyEvent::new(KeyCode::Spacebar, KeyState::Down))),
+            (0xF0, None),
             (0x29, Some(KeyEvent::new(KeyCode::Spacebar, KeyState::Up))),
             (0x29, Some(KeyEvent::new(KeyCode::Spacebar, KeyState::Down))),
             (0xF0, None),
@@ -1408,6 +3240,90 @@ mod test {
         add_bytes(&mut k, &test_sequence);
     }
 
+    /// Feed bytes to a scancode set, collecting every non-empty result
+    fn advance_all<S>(set: &mut S, bytes: &[u8]) -> Vec<Result<DecodedItem, Error>>
+    where
+        S: ScancodeSet,
+    {
+        bytes
+            .iter()
+            .filter_map(|b| set.advance_state(*b).transpose())
+            .collect()
+    }
+
+    fn down(code: KeyCode) -> Result<DecodedItem, Error> {
+        Ok(DecodedItem::Key(KeyEvent::new(code, KeyState::Down)))
+    }
+
+    fn up(code: KeyCode) -> Result<DecodedItem, Error> {
+        Ok(DecodedItem::Key(KeyEvent::new(code, KeyState::Up)))
+    }
+
+    #[test]
+    fn test_interrupted_set1() {
+        let mut s = ScancodeSet1::new();
+        // Pause interrupted by Numpad 4 (E0 4B)
+        assert_eq!(
+            advance_all(&mut s, &[0xE1, 0xE0, 0x4B, 0x45]),
+            [
+                Err(Error::InterruptedSequence),
+                down(KeyCode::ArrowLeft),
+                down(KeyCode::NumpadLock)
+            ]
+        );
+        // Arrow release interrupted by Pause
+        assert_eq!(
+            advance_all(&mut s, &[0xE0, 0xE1, 0x1D, 0x45]),
+            [
+                Err(Error::InterruptedSequence),
+                down(KeyCode::RControl2),
+                down(KeyCode::NumpadLock)
+            ]
+        );
+        // Two prefixes in a row
+        assert_eq!(
+            advance_all(&mut s, &[0xE0, 0xE0, 0xC8]),
+            [Err(Error::InterruptedSequence), up(KeyCode::ArrowUp)]
+        );
+        // And we're back to normal
+        assert_eq!(advance_all(&mut s, &[0x1E]), [down(KeyCode::A)]);
+    }
+
+    #[test]
+    fn test_interrupted_set2() {
+        let mut s = ScancodeSet2::new();
+        // Pause interrupted by Arrow Left (E0 6B)
+        assert_eq!(
+            advance_all(&mut s, &[0xE1, 0xE0, 0x6B, 0x77]),
+            [
+                Err(Error::InterruptedSequence),
+                down(KeyCode::ArrowLeft),
+                down(KeyCode::NumpadLock)
+            ]
+        );
+        // Pause release interrupted by an arrow release
+        assert_eq!(
+            advance_all(&mut s, &[0xE1, 0xF0, 0xE0, 0xF0, 0x6B, 0xF0, 0x77]),
+            [
+                Err(Error::InterruptedSequence),
+                up(KeyCode::ArrowLeft),
+                up(KeyCode::NumpadLock)
+            ]
+        );
+        // Arrow release interrupted by Pause
+        assert_eq!(
+            advance_all(&mut s, &[0xE0, 0xF0, 0xE1, 0x14]),
+            [Err(Error::InterruptedSequence), down(KeyCode::RControl2)]
+        );
+        // Plain release interrupted by an arrow
+        assert_eq!(
+            advance_all(&mut s, &[0xF0, 0xE0, 0x6B]),
+            [Err(Error::InterruptedSequence), down(KeyCode::ArrowLeft)]
+        );
+        // And we're back to normal
+        assert_eq!(advance_all(&mut s, &[0x1C]), [down(KeyCode::A)]);
+    }
+
     #[test]
     fn test_pause_events() {
         let mut k = Keyboard::new(
@@ -1558,6 +3474,100 @@ mod test {
         add_bytes(&mut k, &test_sequence);
     }
 
+    #[test]
+    fn test_hidden_pseudo_keys_set1() {
+        let mut k = Keyboard::new(
+            ScancodeSet1::new_hiding_pseudo_keys(),
+            layouts::Uk105Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        let pause = KeyEvent::new(KeyCode::PauseBreak, KeyState::SingleShot);
+        let test_sequence = [
+            // Pause
+            (0xE1, None),
+            (0x1D, None),
+            (0x45, None),
+            (0xE1, None),
+            (0x9D, None),
+            (0xC5, Some(pause.clone())),
+            // Print Screen
+            (0xE0, None),
+            (0x2A, None),
+            (0xE0, None),
+            (
+                0x37,
+                Some(KeyEvent::new(KeyCode::PrintScreen, KeyState::Down)),
+            ),
+            (0xE0, None),
+            (
+                0xB7,
+                Some(KeyEvent::new(KeyCode::PrintScreen, KeyState::Up)),
+            ),
+            (0xE0, None),
+            (0xAA, None),
+            // Num Lock on its own is still Num Lock
+            (
+                0x45,
+                Some(KeyEvent::new(KeyCode::NumpadLock, KeyState::Down)),
+            ),
+            (0xC5, Some(KeyEvent::new(KeyCode::NumpadLock, KeyState::Up))),
+        ];
+        add_bytes(&mut k, &test_sequence);
+        // Which decodes as you would expect
+        assert_eq!(
+            k.process_keyevent(pause),
+            Some(DecodedKey::RawKey(KeyCode::PauseBreak))
+        );
+    }
+
+    #[test]
+    fn test_hidden_pseudo_keys_set2() {
+        let mut k = Keyboard::new(
+            ScancodeSet2::new_hiding_pseudo_keys(),
+            layouts::Uk105Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        let test_sequence = [
+            // Pause
+            (0xE1, None),
+            (0x14, None),
+            (0x77, None),
+            (0xE1, None),
+            (0xF0, None),
+            (0x14, None),
+            (0xF0, None),
+            (
+                0x77,
+                Some(KeyEvent::new(KeyCode::PauseBreak, KeyState::SingleShot)),
+            ),
+            // Print Screen
+            (0xE0, None),
+            (0x12, None),
+            (0xE0, None),
+            (
+                0x7C,
+                Some(KeyEvent::new(KeyCode::PrintScreen, KeyState::Down)),
+            ),
+            (0xE0, None),
+            (0xF0, None),
+            (
+                0x7C,
+                Some(KeyEvent::new(KeyCode::PrintScreen, KeyState::Up)),
+            ),
+            (0xE0, None),
+            (0xF0, None),
+            (0x12, None),
+            // Num Lock on its own is still Num Lock
+            (
+                0x77,
+                Some(KeyEvent::new(KeyCode::NumpadLock, KeyState::Down)),
+            ),
+            (0xF0, None),
+            (0x77, Some(KeyEvent::new(KeyCode::NumpadLock, KeyState::Up))),
+        ];
+        add_bytes(&mut k, &test_sequence);
+    }
+
     #[test]
     fn test_print_screen_events() {
         let mut k = Keyboard::new(
@@ -1605,6 +3615,1319 @@ mod test {
         process_keyevents(&mut k, &test_sequence);
     }
 
+    #[test]
+    fn test_device_id() {
+        let mut k = Keyboard::new(
+            ScancodeSet2::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        assert_eq!(k.last_device_id(), None);
+        k.expect_response(protocol::Expect::DeviceId);
+        let test_sequence = [
+            // A key press that sneaks in before the ACK
+            (0x1C, Some(KeyEvent::new(KeyCode::A, KeyState::Down))),
+            (0xFA, None),
+            (0xAB, None),
+        ];
+        add_bytes(&mut k, &test_sequence);
+        assert_eq!(
+            k.add_byte(0x83),
+            Ok(Some(DecodedItem::Protocol(ProtocolEvent::DeviceId(Some(
+                protocol::KeyboardKind::Mf2
+            )))))
+        );
+        // Back to normal decoding
+        add_bytes(
+            &mut k,
+            &[(0x1C, Some(KeyEvent::new(KeyCode::A, KeyState::Down)))],
+        );
+        assert_eq!(k.last_device_id(), Some(protocol::KeyboardKind::Mf2));
+        // Set 2 can spot an ID even if we weren't expecting one
+        assert_eq!(k.add_byte(0xAB), Ok(None));
+        assert_eq!(
+            k.add_byte(0x84),
+            Ok(Some(DecodedItem::Protocol(ProtocolEvent::DeviceId(Some(
+                protocol::KeyboardKind::Short
+            )))))
+        );
+        assert_eq!(k.last_device_id(), Some(protocol::KeyboardKind::Short));
+        assert_eq!(
+            k.process_item(DecodedItem::Protocol(ProtocolEvent::DeviceId(None))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_device_id_translated() {
+        let mut k = Keyboard::new(
+            ScancodeSet1::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        k.expect_response(protocol::Expect::DeviceId);
+        let test_sequence = [(0xFA, None), (0xAB, None)];
+        add_bytes(&mut k, &test_sequence);
+        assert_eq!(
+            k.add_byte(0x41),
+            Ok(Some(DecodedItem::Protocol(ProtocolEvent::DeviceId(Some(
+                protocol::KeyboardKind::Mf2Translated
+            )))))
+        );
+        assert_eq!(
+            k.last_device_id(),
+            Some(protocol::KeyboardKind::Mf2Translated)
+        );
+    }
+
+    #[test]
+    fn test_device_id_ancient() {
+        let mut k = Keyboard::new(
+            ScancodeSet2::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        k.expect_response(protocol::Expect::DeviceId);
+        // Just an ACK, then a key press rather than an ID
+        let test_sequence = [
+            (0xFA, None),
+            (0x1C, Some(KeyEvent::new(KeyCode::A, KeyState::Down))),
+        ];
+        add_bytes(&mut k, &test_sequence);
+        assert_eq!(k.last_device_id(), Some(protocol::KeyboardKind::AncientAt));
+    }
+
+    #[test]
+    fn test_expect_ack() {
+        let mut k = Keyboard::new(
+            ScancodeSet1::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        k.expect_response(protocol::Expect::Ack);
+        let test_sequence = [
+            // Without the expectation, this would be a break code
+            (0xFA, None),
+            (0x1E, Some(KeyEvent::new(KeyCode::A, KeyState::Down))),
+        ];
+        add_bytes(&mut k, &test_sequence);
+        assert_eq!(k.last_device_id(), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_decode_all() {
+        let bytes = [
+            0x12, 0x1C, 0xF0, 0x1C, 0xF0, 0x12, 0x1C, 0xF0, 0x1C, 0x02, 0xE0, 0x75, 0xE0, 0xF0,
+            0x75,
+        ];
+        let mut k = Keyboard::new(
+            ScancodeSet2::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        let (text, events, errors) = k.decode_all(&bytes);
+
+        // Now do it the long way
+        let mut k = Keyboard::new(
+            ScancodeSet2::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        let mut expected_text = String::new();
+        let mut expected_events = Vec::new();
+        let mut expected_errors = Vec::new();
+        for byte in bytes {
+            match k.add_byte(byte) {
+                Ok(Some(ev)) => {
+                    expected_events.push(ev.clone());
+                    if let Some(DecodedKey::Unicode(ch)) = k.process_item(ev) {
+                        expected_text.push(ch);
+                    }
+                    if let Some(DecodedKey::Unicode(ch)) = k.take_queued() {
+                        expected_text.push(ch);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => expected_errors.push(e),
+            }
+        }
+
+        assert_eq!(text, "Aa");
+        assert_eq!(errors, [Error::UnknownKeyCode]);
+        assert_eq!(events.len(), 8);
+        assert_eq!(
+            events[7],
+            DecodedItem::Key(KeyEvent::new(KeyCode::ArrowUp, KeyState::Up))
+        );
+        assert_eq!(text, expected_text);
+        assert_eq!(events, expected_events);
+        assert_eq!(errors, expected_errors);
+    }
+
+    /// A US layout where Shift + Apps is the Right Windows key
+    struct AppsIsWin;
+
+    impl KeyboardLayout for AppsIsWin {
+        fn map_keycode(
+            &self,
+            keycode: KeyCode,
+            modifiers: &Modifiers,
+            handle_ctrl: HandleControl,
+        ) -> DecodedKey {
+            layouts::Us104Key.map_keycode(keycode, modifiers, handle_ctrl)
+        }
+
+        fn map_raw(&self, keycode: KeyCode, modifiers: &Modifiers) -> KeyCode {
+            match keycode {
+                KeyCode::Apps if modifiers.is_shifted() => KeyCode::RWin,
+                e => e,
+            }
+        }
+    }
+
+    #[test]
+    fn test_map_raw() {
+        let mut k = EventDecoder::new(AppsIsWin, HandleControl::Ignore);
+        let apps = KeyEvent::new(KeyCode::Apps, KeyState::Down);
+        assert_eq!(
+            k.process_keyevent(apps.clone()),
+            Some(DecodedKey::RawKey(KeyCode::Apps))
+        );
+        k.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(
+            k.process_keyevent(apps),
+            Some(DecodedKey::RawKey(KeyCode::RWin))
+        );
+        // Characters don't go through the hook
+        assert_eq!(
+            k.process_keyevent(KeyEvent::new(KeyCode::A, KeyState::Down)),
+            Some(DecodedKey::Unicode('A'))
+        );
+        // Nor do the modifier keys
+        assert_eq!(
+            k.process_keyevent(KeyEvent::new(KeyCode::RShift, KeyState::Down)),
+            Some(DecodedKey::RawKey(KeyCode::RShift))
+        );
+    }
+
+    #[test]
+    fn test_map_raw_default() {
+        let mut k = EventDecoder::new(layouts::Jis109Key, HandleControl::Ignore);
+        k.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        for code in [KeyCode::Apps, KeyCode::Oem9, KeyCode::Oem10, KeyCode::Oem11] {
+            assert_eq!(
+                k.process_keyevent(KeyEvent::new(code, KeyState::Down)),
+                Some(DecodedKey::RawKey(code))
+            );
+        }
+    }
+
+    #[test]
+    fn test_jis_kana_toggle() {
+        let mut k = Keyboard::new(
+            ScancodeSet1::new(),
+            layouts::Jis109Key,
+            HandleControl::Ignore,
+        );
+        let mut type_byte = |byte: u8| {
+            let item = k.add_byte(byte).unwrap()?;
+            k.process_item(item)
+        };
+        // Latin mode
+        assert_eq!(type_byte(0x10), Some(DecodedKey::Unicode('q')));
+        assert_eq!(type_byte(0x90), None);
+        // Hiragana/Katakana switches to kana
+        assert_eq!(type_byte(0x70), Some(DecodedKey::RawKey(KeyCode::Oem11)));
+        assert_eq!(type_byte(0xF0), None);
+        assert_eq!(type_byte(0x10), Some(DecodedKey::Unicode('た')));
+        assert_eq!(type_byte(0x90), None);
+        // Shift gives the small kana
+        assert_eq!(type_byte(0x2A), Some(DecodedKey::RawKey(KeyCode::LShift)));
+        assert_eq!(type_byte(0x2C), Some(DecodedKey::Unicode('っ')));
+        assert_eq!(type_byte(0xAC), None);
+        assert_eq!(type_byte(0xAA), None);
+        // Keys without kana still work
+        assert_eq!(type_byte(0x39), Some(DecodedKey::Unicode(' ')));
+        assert_eq!(type_byte(0xB9), None);
+        // And pressing it again goes back to Latin
+        assert_eq!(type_byte(0x70), Some(DecodedKey::RawKey(KeyCode::Oem11)));
+        assert_eq!(type_byte(0xF0), None);
+        assert_eq!(type_byte(0x10), Some(DecodedKey::Unicode('q')));
+    }
+
+    #[test]
+    fn test_alt_tab() {
+        let mut k = EventDecoder::new(layouts::Us104Key, HandleControl::Ignore);
+        assert!(k.suppress_chord(ModifierMask::ALT, KeyCode::Tab));
+        assert!(k.suppress_chord(ModifierMask::ALT, KeyCode::F4));
+        let tab_down = KeyEvent::new(KeyCode::Tab, KeyState::Down);
+        let tab_up = KeyEvent::new(KeyCode::Tab, KeyState::Up);
+        // Without Alt, Tab is a tab
+        assert_eq!(
+            k.process_keyevent(tab_down.clone()),
+            Some(DecodedKey::Unicode('\t'))
+        );
+        assert_eq!(k.take_suppressed(), None);
+        k.process_keyevent(tab_up.clone());
+        assert_eq!(
+            k.process_keyevent(KeyEvent::new(KeyCode::LAlt, KeyState::Down)),
+            Some(DecodedKey::RawKey(KeyCode::LAlt))
+        );
+        // Cycle through three windows
+        for _ in 0..3 {
+            assert_eq!(k.process_keyevent(tab_down.clone()), None);
+            assert_eq!(k.take_suppressed(), Some((ModifierMask::ALT, KeyCode::Tab)));
+            assert_eq!(k.take_suppressed(), None);
+            assert_eq!(k.process_keyevent(tab_up.clone()), None);
+            assert_eq!(k.take_suppressed(), None);
+            assert!(k.modifiers.lalt);
+        }
+        // Alt + Shift + Tab is a different chord
+        k.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(
+            k.process_keyevent(tab_down.clone()),
+            Some(DecodedKey::Unicode('\t'))
+        );
+        assert_eq!(k.take_suppressed(), None);
+        k.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        assert_eq!(
+            k.process_keyevent(KeyEvent::new(KeyCode::F4, KeyState::Down)),
+            None
+        );
+        assert_eq!(k.take_suppressed(), Some((ModifierMask::ALT, KeyCode::F4)));
+        k.process_keyevent(KeyEvent::new(KeyCode::LAlt, KeyState::Up));
+        assert!(!k.modifiers.lalt);
+        assert_eq!(
+            k.process_keyevent(tab_down.clone()),
+            Some(DecodedKey::Unicode('\t'))
+        );
+        // And we can turn it off again
+        k.unsuppress_chord(ModifierMask::ALT, KeyCode::Tab);
+        k.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        assert_eq!(
+            k.process_keyevent(tab_down),
+            Some(DecodedKey::Unicode('\t'))
+        );
+    }
+
+    #[test]
+    fn test_suppress_chord_full() {
+        let mut k = EventDecoder::new(layouts::Us104Key, HandleControl::Ignore);
+        let keys = [
+            KeyCode::F1,
+            KeyCode::F2,
+            KeyCode::F3,
+            KeyCode::F4,
+            KeyCode::F5,
+            KeyCode::F6,
+            KeyCode::F7,
+            KeyCode::F8,
+        ];
+        for code in keys {
+            assert!(k.suppress_chord(ModifierMask::CTRL | ModifierMask::ALT, code));
+        }
+        // Adding one twice is fine
+        assert!(k.suppress_chord(ModifierMask::CTRL | ModifierMask::ALT, KeyCode::F1));
+        assert!(!k.suppress_chord(ModifierMask::CTRL | ModifierMask::ALT, KeyCode::F9));
+        k.unsuppress_chord(ModifierMask::CTRL | ModifierMask::ALT, KeyCode::F1);
+        assert!(k.suppress_chord(ModifierMask::CTRL | ModifierMask::ALT, KeyCode::F9));
+    }
+
+    #[test]
+    fn test_scancode_set_kind() {
+        let k = Keyboard::new(
+            ScancodeSet1::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        assert_eq!(k.scancode_set_kind(), ScancodeSetKind::Set1);
+        let k = Keyboard::new(
+            ScancodeSet2::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        assert_eq!(k.scancode_set_kind(), ScancodeSetKind::Set2);
+        // Works through a trait object too
+        let sets: [&dyn ScancodeSet; 2] = [&ScancodeSet1::new(), &ScancodeSet2::new()];
+        assert_eq!(sets[0].kind(), ScancodeSetKind::Set1);
+        assert_eq!(sets[1].kind(), ScancodeSetKind::Set2);
+    }
+
+    #[test]
+    fn test_single_shot() {
+        let mut k = Keyboard::new(
+            ScancodeSet2::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        let test_sequence = [
+            // Protocol events come through as raw keys
+            (
+                KeyEvent::new(KeyCode::PowerOnTestOk, KeyState::SingleShot),
+                Some(DecodedKey::RawKey(KeyCode::PowerOnTestOk)),
+            ),
+            (
+                KeyEvent::new(KeyCode::TooManyKeys, KeyState::SingleShot),
+                Some(DecodedKey::RawKey(KeyCode::TooManyKeys)),
+            ),
+            // Anything else is a Down and an Up
+            (
+                KeyEvent::new(KeyCode::A, KeyState::SingleShot),
+                Some(DecodedKey::Unicode('a')),
+            ),
+            (
+                KeyEvent::new(KeyCode::LShift, KeyState::SingleShot),
+                Some(DecodedKey::RawKey(KeyCode::LShift)),
+            ),
+            // So Shift is not left held down
+            (
+                KeyEvent::new(KeyCode::A, KeyState::SingleShot),
+                Some(DecodedKey::Unicode('a')),
+            ),
+            // But Caps Lock is toggled
+            (
+                KeyEvent::new(KeyCode::CapsLock, KeyState::SingleShot),
+                Some(DecodedKey::RawKey(KeyCode::CapsLock)),
+            ),
+            (
+                KeyEvent::new(KeyCode::A, KeyState::SingleShot),
+                Some(DecodedKey::Unicode('A')),
+            ),
+            (
+                KeyEvent::new(KeyCode::PauseBreak, KeyState::SingleShot),
+                Some(DecodedKey::RawKey(KeyCode::PauseBreak)),
+            ),
+        ];
+        process_keyevents(&mut k, &test_sequence);
+        assert_eq!(
+            *k.get_modifiers(),
+            Modifiers {
+                capslock: true,
+                numlock: true,
+                ..Default::default()
+            }
+        );
+    }
+
+    /// Hold Left Alt, type some keys, then let go of Left Alt
+    fn alt_code<L>(decoder: &mut EventDecoder<L>, codes: &[KeyCode]) -> Option<DecodedKey>
+    where
+        L: KeyboardLayout,
+    {
+        assert_eq!(
+            decoder.process_keyevent(KeyEvent::new(KeyCode::LAlt, KeyState::Down)),
+            Some(DecodedKey::RawKey(KeyCode::LAlt))
+        );
+        for code in codes {
+            decoder.process_keyevent(KeyEvent::new(*code, KeyState::Down));
+            decoder.process_keyevent(KeyEvent::new(*code, KeyState::Up));
+        }
+        decoder.process_keyevent(KeyEvent::new(KeyCode::LAlt, KeyState::Up))
+    }
+
+    #[test]
+    fn test_alt_codes() {
+        let mut dec = EventDecoder::new(layouts::Us104Key, HandleControl::Ignore);
+        // Off by default
+        assert_eq!(
+            alt_code(&mut dec, &[KeyCode::Numpad6, KeyCode::Numpad5]),
+            None
+        );
+        dec.set_alt_codes(AltCodes::Windows1252);
+        assert_eq!(
+            alt_code(&mut dec, &[KeyCode::Numpad6, KeyCode::Numpad5]),
+            Some(DecodedKey::Unicode('A'))
+        );
+        // Code Page 437
+        assert_eq!(
+            alt_code(
+                &mut dec,
+                &[KeyCode::Numpad1, KeyCode::Numpad3, KeyCode::Numpad0]
+            ),
+            Some(DecodedKey::Unicode('é'))
+        );
+        // Windows-1252
+        let zero233 = [
+            KeyCode::Numpad0,
+            KeyCode::Numpad2,
+            KeyCode::Numpad3,
+            KeyCode::Numpad3,
+        ];
+        assert_eq!(alt_code(&mut dec, &zero233), Some(DecodedKey::Unicode('é')));
+        let zero128 = [
+            KeyCode::Numpad0,
+            KeyCode::Numpad1,
+            KeyCode::Numpad2,
+            KeyCode::Numpad8,
+        ];
+        assert_eq!(alt_code(&mut dec, &zero128), Some(DecodedKey::Unicode('€')));
+        // Unicode
+        dec.set_alt_codes(AltCodes::Unicode);
+        assert_eq!(alt_code(&mut dec, &zero233), Some(DecodedKey::Unicode('é')));
+        assert_eq!(
+            alt_code(&mut dec, &zero128),
+            Some(DecodedKey::Unicode('\u{0080}'))
+        );
+        // Works with Num Lock off too
+        dec.process_keyevent(KeyEvent::new(KeyCode::NumpadLock, KeyState::Down));
+        assert!(!dec.modifiers.numlock);
+        assert_eq!(alt_code(&mut dec, &zero233), Some(DecodedKey::Unicode('é')));
+    }
+
+    #[test]
+    fn test_alt_codes_invalid() {
+        let mut dec = EventDecoder::new(layouts::Us104Key, HandleControl::Ignore);
+        dec.set_alt_codes(AltCodes::Unicode);
+        // Too big for Code Page 437
+        assert_eq!(
+            alt_code(
+                &mut dec,
+                &[KeyCode::Numpad2, KeyCode::Numpad5, KeyCode::Numpad6]
+            ),
+            None
+        );
+        // Too many digits
+        assert_eq!(
+            alt_code(
+                &mut dec,
+                &[
+                    KeyCode::Numpad0,
+                    KeyCode::Numpad0,
+                    KeyCode::Numpad0,
+                    KeyCode::Numpad0,
+                    KeyCode::Numpad0,
+                    KeyCode::Numpad0,
+                    KeyCode::Numpad0,
+                    KeyCode::Numpad6,
+                    KeyCode::Numpad5,
+                ]
+            ),
+            None
+        );
+        // A surrogate
+        assert_eq!(
+            alt_code(
+                &mut dec,
+                &[
+                    KeyCode::Numpad0,
+                    KeyCode::Numpad5,
+                    KeyCode::Numpad5,
+                    KeyCode::Numpad2,
+                    KeyCode::Numpad9,
+                    KeyCode::Numpad6,
+                ]
+            ),
+            None
+        );
+        // Far too many digits for a u32
+        assert_eq!(alt_code(&mut dec, &[KeyCode::Numpad9; 12]), None);
+        // Aborted by pressing another key
+        assert_eq!(alt_code(&mut dec, &[KeyCode::Numpad6, KeyCode::A]), None);
+        // But the next one is fine
+        assert_eq!(
+            alt_code(&mut dec, &[KeyCode::Numpad6, KeyCode::Numpad5]),
+            Some(DecodedKey::Unicode('A'))
+        );
+    }
+
+    /// Press Ctrl+Shift+U, then type some keys
+    fn hex_code<L>(decoder: &mut EventDecoder<L>, codes: &[KeyCode]) -> Option<DecodedKey>
+    where
+        L: KeyboardLayout,
+    {
+        decoder.process_keyevent(KeyEvent::new(KeyCode::LControl, KeyState::Down));
+        decoder.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(
+            decoder.process_keyevent(KeyEvent::new(KeyCode::U, KeyState::Down)),
+            None
+        );
+        decoder.process_keyevent(KeyEvent::new(KeyCode::U, KeyState::Up));
+        decoder.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        decoder.process_keyevent(KeyEvent::new(KeyCode::LControl, KeyState::Up));
+        let mut result = None;
+        for code in codes {
+            result = decoder.process_keyevent(KeyEvent::new(*code, KeyState::Down));
+            decoder.process_keyevent(KeyEvent::new(*code, KeyState::Up));
+        }
+        result
+    }
+
+    #[test]
+    fn test_unicode_entry() {
+        let mut dec = EventDecoder::new(layouts::Us104Key, HandleControl::Ignore);
+        dec.set_unicode_entry(true);
+        // Basic Multilingual Plane
+        assert_eq!(
+            hex_code(
+                &mut dec,
+                &[
+                    KeyCode::Key2,
+                    KeyCode::Key0,
+                    KeyCode::A,
+                    KeyCode::C,
+                    KeyCode::Return
+                ]
+            ),
+            Some(DecodedKey::Unicode('€'))
+        );
+        // Astral plane, using the numpad, finished with a space
+        assert_eq!(
+            hex_code(
+                &mut dec,
+                &[
+                    KeyCode::Numpad1,
+                    KeyCode::F,
+                    KeyCode::Numpad6,
+                    KeyCode::Numpad0,
+                    KeyCode::Numpad0,
+                    KeyCode::Spacebar
+                ]
+            ),
+            Some(DecodedKey::Unicode('😀'))
+        );
+        // Backspace removes a digit, and the seventh digit is ignored
+        assert_eq!(
+            hex_code(
+                &mut dec,
+                &[
+                    KeyCode::Key0,
+                    KeyCode::Key0,
+                    KeyCode::Key0,
+                    KeyCode::Key0,
+                    KeyCode::Key4,
+                    KeyCode::Key2,
+                    KeyCode::Key2,
+                    KeyCode::Backspace,
+                    KeyCode::Key1,
+                    KeyCode::Return
+                ]
+            ),
+            Some(DecodedKey::Unicode('A'))
+        );
+        // Back to normal
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::A, KeyState::Down)),
+            Some(DecodedKey::Unicode('a'))
+        );
+    }
+
+    #[test]
+    fn test_unicode_entry_invalid() {
+        let mut dec = EventDecoder::new(layouts::Us104Key, HandleControl::Ignore);
+        dec.set_unicode_entry(true);
+        // A surrogate
+        assert_eq!(
+            hex_code(
+                &mut dec,
+                &[
+                    KeyCode::D,
+                    KeyCode::Key8,
+                    KeyCode::Key0,
+                    KeyCode::Key0,
+                    KeyCode::Return
+                ]
+            ),
+            None
+        );
+        // Out of range
+        assert_eq!(
+            hex_code(
+                &mut dec,
+                &[
+                    KeyCode::F,
+                    KeyCode::F,
+                    KeyCode::F,
+                    KeyCode::F,
+                    KeyCode::F,
+                    KeyCode::F,
+                    KeyCode::Return
+                ]
+            ),
+            None
+        );
+        // Cancelled
+        assert_eq!(
+            hex_code(&mut dec, &[KeyCode::Key4, KeyCode::Key1, KeyCode::Escape]),
+            None
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Return, KeyState::Down)),
+            Some(DecodedKey::Unicode('\n'))
+        );
+        // Abandoned
+        assert_eq!(
+            hex_code(&mut dec, &[KeyCode::Key4, KeyCode::Key1, KeyCode::Q]),
+            Some(DecodedKey::Unicode('q'))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Key1, KeyState::Down)),
+            Some(DecodedKey::Unicode('1'))
+        );
+        // Disabled
+        dec.set_unicode_entry(false);
+        dec.process_keyevent(KeyEvent::new(KeyCode::LControl, KeyState::Down));
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::U, KeyState::Down)),
+            Some(DecodedKey::Unicode('U'))
+        );
+    }
+
+    /// A US layout with Greek dead keys on the punctuation keys, and alpha
+    /// on A
+    struct GreekDeadKeys;
+
+    impl KeyboardLayout for GreekDeadKeys {
+        fn map_keycode(
+            &self,
+            keycode: KeyCode,
+            modifiers: &Modifiers,
+            handle_ctrl: HandleControl,
+        ) -> DecodedKey {
+            match keycode {
+                KeyCode::Oem1 => DecodedKey::DeadKey('´'),
+                KeyCode::Oem3 => DecodedKey::DeadKey('᾿'),
+                KeyCode::Oem7 => DecodedKey::DeadKey('῾'),
+                KeyCode::A => DecodedKey::Unicode('α'),
+                e => layouts::Us104Key.map_keycode(e, modifiers, handle_ctrl),
+            }
+        }
+    }
+
+    /// A US layout with a dead `¤` on Oem8, which it combines with `e` to
+    /// give `€`
+    struct CurrencyDeadKey {
+        combines: bool,
+    }
+
+    impl KeyboardLayout for CurrencyDeadKey {
+        fn map_keycode(
+            &self,
+            keycode: KeyCode,
+            modifiers: &Modifiers,
+            handle_ctrl: HandleControl,
+        ) -> DecodedKey {
+            match keycode {
+                KeyCode::Oem8 => DecodedKey::DeadKey('¤'),
+                e => layouts::Us104Key.map_keycode(e, modifiers, handle_ctrl),
+            }
+        }
+
+        fn dead_key_combine(&self, dead_key: char, ch: char) -> Option<char> {
+            match (dead_key, ch) {
+                ('¤', 'e') if self.combines => Some('€'),
+                _ => None,
+            }
+        }
+    }
+
+    /// Tap each key in turn, collecting all the characters
+    fn type_keys<L>(decoder: &mut EventDecoder<L>, keys: &[KeyCode]) -> Vec<DecodedKey>
+    where
+        L: KeyboardLayout,
+    {
+        let mut output = Vec::new();
+        for code in keys {
+            output.extend(decoder.process_keyevent(KeyEvent::new(*code, KeyState::Down)));
+            while let Some(key) = decoder.take_queued() {
+                output.push(key);
+            }
+            decoder.process_keyevent(KeyEvent::new(*code, KeyState::Up));
+        }
+        output
+    }
+
+    #[test]
+    fn test_compose_key() {
+        let mut dec = EventDecoder::new(layouts::Us104Key, HandleControl::Ignore);
+        // Without a Compose key, Apps is just a raw key
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Apps, KeyCode::S, KeyCode::S]),
+            [
+                DecodedKey::RawKey(KeyCode::Apps),
+                DecodedKey::Unicode('s'),
+                DecodedKey::Unicode('s')
+            ]
+        );
+        dec.set_compose_key(Some(KeyCode::Apps));
+        assert_eq!(dec.get_compose_key(), Some(KeyCode::Apps));
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Apps, KeyCode::Oem3, KeyCode::A]),
+            [DecodedKey::Unicode('á')]
+        );
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Apps, KeyCode::S, KeyCode::S]),
+            [DecodedKey::Unicode('ß')]
+        );
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Apps, KeyCode::O, KeyCode::E]),
+            [DecodedKey::Unicode('œ')]
+        );
+        // Shift can be used for the characters in the sequence
+        dec.process_keyevent(KeyEvent::new(KeyCode::Apps, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down)),
+            Some(DecodedKey::RawKey(KeyCode::LShift))
+        );
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::Oem3, KeyState::Down)),
+            None
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::O, KeyState::Down)),
+            Some(DecodedKey::Unicode('ö'))
+        );
+        // Afterwards, typing is back to normal
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::S, KeyCode::S]),
+            [DecodedKey::Unicode('s'), DecodedKey::Unicode('s')]
+        );
+    }
+
+    #[test]
+    fn test_compose_key_unmatched() {
+        let mut dec = EventDecoder::new(layouts::Us104Key, HandleControl::Ignore);
+        dec.set_compose_key(Some(KeyCode::Apps));
+        // Both characters come out unchanged
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Apps, KeyCode::Q, KeyCode::Z]),
+            [DecodedKey::Unicode('q'), DecodedKey::Unicode('z')]
+        );
+        // Pressing Compose again starts over
+        assert_eq!(
+            type_keys(
+                &mut dec,
+                &[
+                    KeyCode::Apps,
+                    KeyCode::Q,
+                    KeyCode::Apps,
+                    KeyCode::S,
+                    KeyCode::S
+                ]
+            ),
+            [DecodedKey::Unicode('q'), DecodedKey::Unicode('ß')]
+        );
+    }
+
+    #[test]
+    fn test_compose_key_escape() {
+        let mut dec = EventDecoder::new(layouts::Us104Key, HandleControl::Ignore);
+        dec.set_compose_key(Some(KeyCode::Apps));
+        assert_eq!(
+            type_keys(
+                &mut dec,
+                &[KeyCode::Apps, KeyCode::Oem3, KeyCode::Escape, KeyCode::A]
+            ),
+            [DecodedKey::Unicode('a')]
+        );
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Apps, KeyCode::Escape, KeyCode::A]),
+            [DecodedKey::Unicode('a')]
+        );
+    }
+
+    #[test]
+    fn test_compose_sequences() {
+        static SEQUENCES: [(char, char, char); 1] = [('-', '>', '→')];
+        let mut dec = EventDecoder::new(layouts::Us104Key, HandleControl::Ignore);
+        dec.set_compose_key(Some(KeyCode::RAltGr));
+        dec.set_compose_sequences(&SEQUENCES);
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        dec.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Up));
+        dec.process_keyevent(KeyEvent::new(KeyCode::OemMinus, KeyState::Down));
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Down));
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::OemPeriod, KeyState::Down)),
+            Some(DecodedKey::Unicode('→'))
+        );
+        dec.process_keyevent(KeyEvent::new(KeyCode::LShift, KeyState::Up));
+        // The default table is no longer used
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::RAltGr, KeyCode::S, KeyCode::S]),
+            [DecodedKey::Unicode('s'), DecodedKey::Unicode('s')]
+        );
+    }
+
+    #[test]
+    fn test_layout_dead_key_combine() {
+        let mut dec = EventDecoder::new(CurrencyDeadKey { combines: true }, HandleControl::Ignore);
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem8, KeyCode::E]),
+            [DecodedKey::DeadKey('¤'), DecodedKey::Unicode('€')]
+        );
+        // The built-in tables are still used for anything else
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem8, KeyCode::A]),
+            [
+                DecodedKey::DeadKey('¤'),
+                DecodedKey::Unicode('¤'),
+                DecodedKey::Unicode('a')
+            ]
+        );
+        // A layout which returns None gives both characters
+        let mut dec = EventDecoder::new(CurrencyDeadKey { combines: false }, HandleControl::Ignore);
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem8, KeyCode::E]),
+            [
+                DecodedKey::DeadKey('¤'),
+                DecodedKey::Unicode('¤'),
+                DecodedKey::Unicode('e')
+            ]
+        );
+        // The user's tables come first
+        static EXTRA: [((char, char), char); 1] = [(('¤', 'e'), 'ɛ')];
+        let mut dec = EventDecoder::new(CurrencyDeadKey { combines: true }, HandleControl::Ignore);
+        dec.add_compositions(&EXTRA);
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem8, KeyCode::E]),
+            [DecodedKey::DeadKey('¤'), DecodedKey::Unicode('ɛ')]
+        );
+    }
+
+    #[test]
+    fn test_dead_key_then_letter() {
+        let mut dec = EventDecoder::new(GreekDeadKeys, HandleControl::Ignore);
+        // A vowel with a precomposed form
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem1, KeyCode::E]),
+            [DecodedKey::DeadKey('´'), DecodedKey::Unicode('é')]
+        );
+        // A letter without one gives the diacritic, then the letter
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem1, KeyCode::B]),
+            [
+                DecodedKey::DeadKey('´'),
+                DecodedKey::Unicode('´'),
+                DecodedKey::Unicode('b')
+            ]
+        );
+        // Nothing is left pending
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::E]),
+            [DecodedKey::Unicode('e')]
+        );
+    }
+
+    #[test]
+    fn test_two_dead_keys() {
+        let mut dec = EventDecoder::new(GreekDeadKeys, HandleControl::Ignore);
+        dec.set_max_dead_keys(2);
+        assert_eq!(dec.get_max_dead_keys(), 2);
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem3, KeyCode::Oem1, KeyCode::A]),
+            [
+                DecodedKey::DeadKey('᾿'),
+                DecodedKey::DeadKey('´'),
+                DecodedKey::Unicode('ἄ')
+            ]
+        );
+        // The other way around
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem1, KeyCode::Oem7, KeyCode::A]),
+            [
+                DecodedKey::DeadKey('´'),
+                DecodedKey::DeadKey('῾'),
+                DecodedKey::Unicode('ἅ')
+            ]
+        );
+        // Just one is fine too
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem7, KeyCode::A]),
+            [DecodedKey::DeadKey('῾'), DecodedKey::Unicode('ἁ')]
+        );
+        // No combination - everything comes out
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem3, KeyCode::Oem1, KeyCode::B]),
+            [
+                DecodedKey::DeadKey('᾿'),
+                DecodedKey::DeadKey('´'),
+                DecodedKey::Unicode('᾿'),
+                DecodedKey::Unicode('´'),
+                DecodedKey::Unicode('b'),
+            ]
+        );
+        // Space gives the diacritics on their own
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem3, KeyCode::Oem1, KeyCode::Spacebar]),
+            [
+                DecodedKey::DeadKey('᾿'),
+                DecodedKey::DeadKey('´'),
+                DecodedKey::Unicode('᾿'),
+                DecodedKey::Unicode('´'),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_handle_letter_auto() {
+        let map = HandleControl::MapLettersToUnicode;
+        let mut modifiers = Modifiers::default();
+        assert_eq!(
+            modifiers.handle_letter_auto('ж', 'Ж', map),
+            DecodedKey::Unicode('ж')
+        );
+        modifiers.lshift = true;
+        assert_eq!(
+            modifiers.handle_letter_auto('ж', 'Ж', map),
+            DecodedKey::Unicode('Ж')
+        );
+        // Non-ASCII letters don't have a Control code
+        modifiers.lshift = false;
+        modifiers.lctrl = true;
+        assert_eq!(
+            modifiers.handle_letter_auto('ä', 'Ä', map),
+            DecodedKey::Unicode('ä')
+        );
+        // Even with a non-ASCII lower case letter (e.g. Turkish dotless i)
+        assert_eq!(
+            modifiers.handle_letter_auto('ı', 'I', map),
+            DecodedKey::Unicode('\u{0009}')
+        );
+        assert_eq!(
+            modifiers.handle_letter_auto('ı', 'I', HandleControl::Ignore),
+            DecodedKey::Unicode('ı')
+        );
+        modifiers.capslock = true;
+        assert_eq!(
+            modifiers.handle_letter_auto('i', 'İ', map),
+            DecodedKey::Unicode('İ')
+        );
+    }
+
+    #[test]
+    fn test_handle_ascii_4() {
+        let map = HandleControl::MapLettersToUnicode;
+        let mut modifiers = Modifiers::default();
+        let w = AsciiUpper::new('W');
+        assert_eq!(w.lower(), 'w');
+        assert_eq!(
+            modifiers.handle_ascii_4(w, 'ŵ', 'Ŵ', map),
+            DecodedKey::Unicode('w')
+        );
+        modifiers.lctrl = true;
+        assert_eq!(
+            modifiers.handle_ascii_4(w, 'ŵ', 'Ŵ', map),
+            DecodedKey::Unicode('\u{0017}')
+        );
+        // Ctrl + Left Alt is AltGr
+        modifiers.lalt = true;
+        modifiers.capslock = true;
+        assert_eq!(
+            modifiers.handle_ascii_4(w, 'ŵ', 'Ŵ', map),
+            DecodedKey::Unicode('Ŵ')
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ascii_upper_checked() {
+        AsciiUpper::new('ŵ');
+    }
+
+    #[test]
+    fn test_scrolllock_numpad_override() {
+        let mut dec = EventDecoder::new(layouts::Us104Key, HandleControl::Ignore);
+        dec.set_scrolllock_numpad_override(true);
+        assert!(dec.get_scrolllock_numpad_override());
+        fn tap(dec: &mut EventDecoder<layouts::Us104Key>, code: KeyCode) -> Option<DecodedKey> {
+            let key = dec.process_keyevent(KeyEvent::new(code, KeyState::Down));
+            dec.process_keyevent(KeyEvent::new(code, KeyState::Up));
+            key
+        }
+        // Num Lock on, Scroll Lock off
+        assert_eq!(
+            tap(&mut dec, KeyCode::Numpad2),
+            Some(DecodedKey::Unicode('2'))
+        );
+        // Num Lock on, Scroll Lock on
+        assert_eq!(
+            tap(&mut dec, KeyCode::ScrollLock),
+            Some(DecodedKey::RawKey(KeyCode::ScrollLock))
+        );
+        assert_eq!(
+            tap(&mut dec, KeyCode::Numpad2),
+            Some(DecodedKey::RawKey(KeyCode::ArrowDown))
+        );
+        // Num Lock off, Scroll Lock on
+        tap(&mut dec, KeyCode::NumpadLock);
+        assert_eq!(
+            tap(&mut dec, KeyCode::Numpad2),
+            Some(DecodedKey::RawKey(KeyCode::ArrowDown))
+        );
+        // Num Lock off, Scroll Lock off
+        tap(&mut dec, KeyCode::ScrollLock);
+        assert_eq!(
+            tap(&mut dec, KeyCode::Numpad2),
+            Some(DecodedKey::RawKey(KeyCode::ArrowDown))
+        );
+        // Other keys are not affected
+        tap(&mut dec, KeyCode::ScrollLock);
+        assert_eq!(tap(&mut dec, KeyCode::Key2), Some(DecodedKey::Unicode('2')));
+        // Without the override, Scroll Lock does nothing to the numpad
+        dec.set_scrolllock_numpad_override(false);
+        tap(&mut dec, KeyCode::NumpadLock);
+        assert_eq!(
+            tap(&mut dec, KeyCode::Numpad2),
+            Some(DecodedKey::Unicode('2'))
+        );
+        assert!(dec.modifiers.scrolllock);
+        assert!(dec.modifiers.numlock);
+    }
+
+    #[test]
+    fn test_shift_lock_policy() {
+        let mut dec =
+            EventDecoder::with_policy(layouts::Uk105Key, HandleControl::Ignore, ShiftLock);
+        let mut tap = |code| {
+            let key = dec.process_keyevent(KeyEvent::new(code, KeyState::Down));
+            dec.process_keyevent(KeyEvent::new(code, KeyState::Up));
+            key
+        };
+        assert_eq!(tap(KeyCode::Key1), Some(DecodedKey::Unicode('1')));
+        assert_eq!(tap(KeyCode::A), Some(DecodedKey::Unicode('a')));
+        tap(KeyCode::CapsLock);
+        assert_eq!(tap(KeyCode::Key1), Some(DecodedKey::Unicode('!')));
+        assert_eq!(tap(KeyCode::Key3), Some(DecodedKey::Unicode('£')));
+        assert_eq!(tap(KeyCode::A), Some(DecodedKey::Unicode('A')));
+        // The numpad doesn't care about Shift
+        assert_eq!(tap(KeyCode::Numpad1), Some(DecodedKey::Unicode('1')));
+    }
+
+    #[test]
+    fn test_custom_policy() {
+        /// Shift doesn't undo Caps Lock
+        struct CapsWins;
+
+        impl ModifierPolicy for CapsWins {
+            fn is_caps(&self, modifiers: &Modifiers) -> bool {
+                modifiers.is_shifted() || modifiers.capslock
+            }
+        }
+
+        let layout = layouts::De105Key;
+        let mut changed = 0;
+        for bits in 0..=255u8 {
+            let modifiers = Modifiers {
+                lshift: (bits & 1) != 0,
+                rctrl: (bits & 2) != 0,
+                numlock: (bits & 4) != 0,
+                capslock: (bits & 8) != 0,
+                lalt: (bits & 16) != 0,
+                ralt: (bits & 32) != 0,
+                ..Default::default()
+            };
+            for code in KeyCode::ALL {
+                let handle_ctrl = if (bits & 64) != 0 {
+                    HandleControl::Ignore
+                } else {
+                    HandleControl::MapLettersToUnicode
+                };
+                let normal =
+                    layout.map_keycode(code, &DefaultPolicy.apply(&modifiers), handle_ctrl);
+                let custom = layout.map_keycode(code, &CapsWins.apply(&modifiers), handle_ctrl);
+                if modifiers.lshift && modifiers.capslock {
+                    // Just like Shift on its own
+                    let shift_only = Modifiers {
+                        capslock: false,
+                        ..modifiers.clone()
+                    };
+                    assert_eq!(
+                        custom,
+                        layout.map_keycode(code, &shift_only, handle_ctrl),
+                        "{:?} with {:?}",
+                        code,
+                        modifiers
+                    );
+                } else {
+                    assert_eq!(normal, custom, "{:?} with {:?}", code, modifiers);
+                }
+                if normal != custom {
+                    changed += 1;
+                }
+            }
+        }
+        assert_ne!(changed, 0);
+    }
+
+    #[test]
+    fn test_take_output() {
+        let mut dec = EventDecoder::new(GreekDeadKeys, HandleControl::Ignore);
+        dec.set_max_dead_keys(2);
+        for code in [KeyCode::Oem3, KeyCode::Oem1] {
+            dec.process_keyevent(KeyEvent::new(code, KeyState::Down));
+            dec.process_keyevent(KeyEvent::new(code, KeyState::Up));
+        }
+        assert_eq!(
+            dec.process_keyevent(KeyEvent::new(KeyCode::B, KeyState::Down)),
+            Some(DecodedKey::Unicode('᾿'))
+        );
+        assert_eq!(dec.take_output(), "´b");
+        assert_eq!(dec.take_output(), "");
+        assert_eq!(dec.take_queued(), None);
+    }
+
+    #[test]
+    fn test_dead_key_cancel() {
+        let mut dec = EventDecoder::new(GreekDeadKeys, HandleControl::Ignore);
+        dec.set_max_dead_keys(2);
+        assert_eq!(
+            type_keys(
+                &mut dec,
+                &[KeyCode::Oem3, KeyCode::Oem1, KeyCode::Escape, KeyCode::A]
+            ),
+            [
+                DecodedKey::DeadKey('᾿'),
+                DecodedKey::DeadKey('´'),
+                DecodedKey::Unicode('α')
+            ]
+        );
+        // Without a dead key, Escape is just Escape
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Escape]),
+            [DecodedKey::Unicode('\u{1b}')]
+        );
+        // Same with just one dead key allowed
+        dec.set_max_dead_keys(1);
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem1, KeyCode::Escape, KeyCode::A]),
+            [DecodedKey::DeadKey('´'), DecodedKey::Unicode('α')]
+        );
+    }
+
+    #[test]
+    fn test_dead_key_edges() {
+        let mut dec = EventDecoder::new(GreekDeadKeys, HandleControl::Ignore);
+        // Space gives the diacritic on its own
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem1, KeyCode::Spacebar]),
+            [DecodedKey::DeadKey('´'), DecodedKey::Unicode('´')]
+        );
+        // So does the same dead key again, and nothing is left pending
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem1, KeyCode::Oem1, KeyCode::A]),
+            [
+                DecodedKey::DeadKey('´'),
+                DecodedKey::Unicode('´'),
+                DecodedKey::Unicode('α')
+            ]
+        );
+        // Backspace cancels, like Escape
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem1, KeyCode::Backspace, KeyCode::A]),
+            [DecodedKey::DeadKey('´'), DecodedKey::Unicode('α')]
+        );
+        // Without a dead key, Backspace is just Backspace
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Backspace]),
+            [DecodedKey::Unicode('\u{8}')]
+        );
+        // With two pending, repeating the last one gives both diacritics
+        dec.set_max_dead_keys(2);
+        assert_eq!(
+            type_keys(
+                &mut dec,
+                &[KeyCode::Oem3, KeyCode::Oem1, KeyCode::Oem1, KeyCode::A]
+            ),
+            [
+                DecodedKey::DeadKey('᾿'),
+                DecodedKey::DeadKey('´'),
+                DecodedKey::Unicode('᾿'),
+                DecodedKey::Unicode('´'),
+                DecodedKey::Unicode('α')
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dead_key_overflow() {
+        let mut dec = EventDecoder::new(GreekDeadKeys, HandleControl::Ignore);
+        dec.set_max_dead_keys(2);
+        // The third dead key pushes out the first
+        assert_eq!(
+            type_keys(
+                &mut dec,
+                &[KeyCode::Oem7, KeyCode::Oem3, KeyCode::Oem1, KeyCode::A]
+            ),
+            [
+                DecodedKey::DeadKey('῾'),
+                DecodedKey::DeadKey('᾿'),
+                DecodedKey::Unicode('῾'),
+                DecodedKey::Unicode('ἄ')
+            ]
+        );
+        // With the default of one, the second pushes out the first
+        dec.set_max_dead_keys(0);
+        assert_eq!(dec.get_max_dead_keys(), 1);
+        assert_eq!(
+            type_keys(&mut dec, &[KeyCode::Oem7, KeyCode::Oem1, KeyCode::A]),
+            [
+                DecodedKey::DeadKey('῾'),
+                DecodedKey::Unicode('῾'),
+                DecodedKey::Unicode('ά')
+            ]
+        );
+    }
+
+    /// Press AltGr+6 (a dead circumflex on the Welsh layout) and then a key
+    fn circumflex_then<L>(decoder: &mut EventDecoder<L>, code: KeyCode) -> Option<DecodedKey>
+    where
+        L: KeyboardLayout,
+    {
+        decoder.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+        assert_eq!(
+            decoder.process_keyevent(KeyEvent::new(KeyCode::Key6, KeyState::Down)),
+            Some(DecodedKey::DeadKey('^'))
+        );
+        decoder.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Up));
+        decoder.process_keyevent(KeyEvent::new(code, KeyState::Down))
+    }
+
+    #[test]
+    fn test_add_compositions() {
+        static EXTRA: [((char, char), char); 2] = [(('^', 'a'), 'ǎ'), (('^', 'q'), 'ꝗ')];
+        let mut dec = EventDecoder::new(layouts::CyUk105Key, HandleControl::Ignore);
+        assert_eq!(
+            circumflex_then(&mut dec, KeyCode::A),
+            Some(DecodedKey::Unicode('â'))
+        );
+        dec.add_compositions(&EXTRA);
+        // Overrides a built-in combination
+        assert_eq!(
+            circumflex_then(&mut dec, KeyCode::A),
+            Some(DecodedKey::Unicode('ǎ'))
+        );
+        // Adds a new one
+        assert_eq!(
+            circumflex_then(&mut dec, KeyCode::Q),
+            Some(DecodedKey::Unicode('ꝗ'))
+        );
+        assert_eq!(dec.take_queued(), None);
+        // The rest of the built-in table still works
+        assert_eq!(
+            circumflex_then(&mut dec, KeyCode::E),
+            Some(DecodedKey::Unicode('ê'))
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_extend_compositions() {
+        static EXTRA: [((char, char), char); 1] = [(('^', 'a'), 'ǎ')];
+        let mut dec = EventDecoder::new(layouts::CyUk105Key, HandleControl::Ignore);
+        dec.add_compositions(&EXTRA);
+        dec.extend_compositions([(('^', 'a'), 'ạ'), (('^', 'z'), 'ẑ')]);
+        assert_eq!(
+            circumflex_then(&mut dec, KeyCode::A),
+            Some(DecodedKey::Unicode('ạ'))
+        );
+        assert_eq!(
+            circumflex_then(&mut dec, KeyCode::Z),
+            Some(DecodedKey::Unicode('ẑ'))
+        );
+    }
+
     #[test]
     fn test_modifier_state_shift() {
         let mut k = Keyboard::new(
@@ -0,0 +1,428 @@
+//! Canned keyboard input, for testing keyboard drivers without a keyboard
+//!
+//! A [`Script`] is a list of things that happen on the PS/2 bus - bytes,
+//! words, individual bits and idle periods - interleaved with the text (and
+//! errors) that a correctly working driver should produce at that point.
+//! [`run`] feeds a script into a [`Keyboard`] and gives you a [`Report`].
+//!
+//! ```rust
+//! use pc_keyboard::{layouts, loopback, HandleControl, Keyboard, ScancodeSet2};
+//!
+//! let mut kb = Keyboard::new(
+//!     ScancodeSet2::new(),
+//!     layouts::Us104Key,
+//!     HandleControl::MapLettersToUnicode,
+//! );
+//! let report = loopback::run(&mut kb, &loopback::US_TYPING);
+//! assert!(report.passed());
+//! assert_eq!(report.text, "Hello, World!");
+//! ```
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{DecodedItem, DecodedKey, Error, Keyboard, KeyboardLayout, ScancodeSet, KEYCODE_BITS};
+
+/// How long the bus can be idle in the middle of a word, in microseconds.
+///
+/// If an [`Item::Idle`] at least this long arrives when only part of a word
+/// has been clocked in, the runner assumes the host timed out and calls
+/// [`Keyboard::clear`].
+pub const BIT_TIMEOUT_US: u32 = 2_000;
+
+/// One step in a [`Script`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Item {
+    /// A byte which has already been checked by the PS/2 controller
+    Byte(u8),
+    /// A whole 11-bit PS/2 word, including start, parity and stop bits
+    Word(u16),
+    /// The lowest `count` bits of `word`, clocked in one at a time (LSB first)
+    Bits { word: u16, count: u8 },
+    /// The bus is idle for this many microseconds
+    Idle(u32),
+    /// The text produced since the last `ExpectText` should be this
+    ExpectText(&'static str),
+    /// The errors produced since the last `ExpectErrors` should be these
+    ExpectErrors(&'static [Error]),
+}
+
+/// A named list of [`Item`]s
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Script {
+    /// What this script is testing
+    pub name: &'static str,
+    /// The steps to run
+    pub items: &'static [Item],
+}
+
+/// Something in a [`Script`] which didn't go to plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// An [`Item::ExpectText`] didn't match
+    Text {
+        /// The index of the item in the script
+        item: usize,
+        /// What the script wanted
+        expected: &'static str,
+        /// What we got
+        got: String,
+    },
+    /// An [`Item::ExpectErrors`] didn't match
+    Errors {
+        /// The index of the item in the script
+        item: usize,
+        /// What the script wanted
+        expected: &'static [Error],
+        /// What we got
+        got: Vec<Error>,
+    },
+}
+
+/// The outcome of running a [`Script`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Report {
+    /// All the text the keyboard produced
+    pub text: String,
+    /// All the key events (and protocol events) the keyboard produced
+    pub events: Vec<DecodedItem>,
+    /// All the errors the keyboard produced
+    pub errors: Vec<Error>,
+    /// How many times a partial word was thrown away after an idle period
+    pub timeouts: usize,
+    /// Every expectation that wasn't met
+    pub failures: Vec<Failure>,
+}
+
+impl Report {
+    /// Did every expectation in the script hold?
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Build the 11-bit PS/2 word for a byte, with a correct start bit, odd
+/// parity bit and stop bit.
+pub const fn encode_word(byte: u8) -> u16 {
+    let parity = (byte.count_ones() & 1) == 0;
+    ((byte as u16) << 1) | ((parity as u16) << 9) | (1 << 10)
+}
+
+/// Run a script through a keyboard.
+///
+/// The keyboard is not reset first, so you can run several scripts in a row.
+pub fn run<L, S>(keyboard: &mut Keyboard<L, S>, script: &Script) -> Report
+where
+    L: KeyboardLayout,
+    S: ScancodeSet,
+{
+    let mut report = Report::default();
+    let mut pending_bits = 0;
+    let mut text_checked = 0;
+    let mut errors_checked = 0;
+    for (idx, item) in script.items.iter().enumerate() {
+        match item {
+            Item::Byte(byte) => {
+                let result = keyboard.add_byte(*byte);
+                record(keyboard, &mut report, result);
+            }
+            Item::Word(word) => {
+                let result = keyboard.add_word(*word);
+                record(keyboard, &mut report, result);
+            }
+            Item::Bits { word, count } => {
+                for bit in 0..*count {
+                    let result = keyboard.add_bit((word >> bit) & 1 == 1);
+                    pending_bits += 1;
+                    if pending_bits == KEYCODE_BITS {
+                        pending_bits = 0;
+                    }
+                    record(keyboard, &mut report, result);
+                }
+            }
+            Item::Idle(us) => {
+                if *us >= BIT_TIMEOUT_US && pending_bits != 0 {
+                    keyboard.clear();
+                    pending_bits = 0;
+                    report.timeouts += 1;
+                }
+            }
+            Item::ExpectText(expected) => {
+                let got = &report.text[text_checked..];
+                if got != *expected {
+                    report.failures.push(Failure::Text {
+                        item: idx,
+                        expected,
+                        got: String::from(got),
+                    });
+                }
+                text_checked = report.text.len();
+            }
+            Item::ExpectErrors(expected) => {
+                let got = &report.errors[errors_checked..];
+                if got != *expected {
+                    report.failures.push(Failure::Errors {
+                        item: idx,
+                        expected,
+                        got: Vec::from(got),
+                    });
+                }
+                errors_checked = report.errors.len();
+            }
+        }
+    }
+    report
+}
+
+/// Decode one result from the keyboard into the report
+fn record<L, S>(
+    keyboard: &mut Keyboard<L, S>,
+    report: &mut Report,
+    result: Result<Option<DecodedItem>, Error>,
+) where
+    L: KeyboardLayout,
+    S: ScancodeSet,
+{
+    match result {
+        Ok(Some(item)) => {
+            report.events.push(item.clone());
+            let mut decoded = keyboard.process_item(item);
+            while let Some(key) = decoded {
+                if let DecodedKey::Unicode(c) = key {
+                    report.text.push(c);
+                }
+                decoded = keyboard.take_queued();
+            }
+        }
+        Ok(None) => {}
+        Err(e) => report.errors.push(e),
+    }
+}
+
+/// A whole, valid, PS/2 word
+const fn word(byte: u8) -> Item {
+    Item::Word(encode_word(byte))
+}
+
+/// Types "Hello, World!" using whole words.
+///
+/// Use Scan Code Set 2 and a [`Us104Key`](crate::layouts::Us104Key) layout.
+pub const US_TYPING: Script = Script {
+    name: "US typing",
+    items: &[
+        // Shift + H
+        word(0x12),
+        word(0x33),
+        word(0xF0),
+        word(0x33),
+        word(0xF0),
+        word(0x12),
+        // e, l, l, o
+        word(0x24),
+        word(0xF0),
+        word(0x24),
+        word(0x4B),
+        word(0xF0),
+        word(0x4B),
+        word(0x4B),
+        word(0xF0),
+        word(0x4B),
+        word(0x44),
+        word(0xF0),
+        word(0x44),
+        // comma, space
+        word(0x41),
+        word(0xF0),
+        word(0x41),
+        word(0x29),
+        word(0xF0),
+        word(0x29),
+        Item::ExpectText("Hello, "),
+        // Shift + W
+        word(0x12),
+        word(0x1D),
+        word(0xF0),
+        word(0x1D),
+        word(0xF0),
+        word(0x12),
+        // o, r, l, d
+        word(0x44),
+        word(0xF0),
+        word(0x44),
+        word(0x2D),
+        word(0xF0),
+        word(0x2D),
+        word(0x4B),
+        word(0xF0),
+        word(0x4B),
+        word(0x23),
+        word(0xF0),
+        word(0x23),
+        // Shift + 1
+        word(0x12),
+        word(0x16),
+        word(0xF0),
+        word(0x16),
+        word(0xF0),
+        word(0x12),
+        Item::ExpectText("World!"),
+        Item::ExpectErrors(&[]),
+    ],
+};
+
+/// Types the symbols which differ between UK and US keyboards, using bytes.
+///
+/// Use Scan Code Set 2 and a [`Uk105Key`](crate::layouts::Uk105Key) layout.
+pub const UK_SYMBOLS: Script = Script {
+    name: "UK symbols",
+    items: &[
+        // Shift + 3, Shift + ', #, Shift + #, \, Shift + \
+        Item::Byte(0x12),
+        Item::Byte(0x26),
+        Item::Byte(0xF0),
+        Item::Byte(0x26),
+        Item::Byte(0x52),
+        Item::Byte(0xF0),
+        Item::Byte(0x52),
+        Item::Byte(0xF0),
+        Item::Byte(0x12),
+        Item::Byte(0x5D),
+        Item::Byte(0xF0),
+        Item::Byte(0x5D),
+        Item::Byte(0x12),
+        Item::Byte(0x5D),
+        Item::Byte(0xF0),
+        Item::Byte(0x5D),
+        Item::Byte(0xF0),
+        Item::Byte(0x12),
+        Item::Byte(0x61),
+        Item::Byte(0xF0),
+        Item::Byte(0x61),
+        Item::Byte(0x12),
+        Item::Byte(0x61),
+        Item::Byte(0xF0),
+        Item::Byte(0x61),
+        Item::Byte(0xF0),
+        Item::Byte(0x12),
+        // AltGr + 4
+        Item::Byte(0xE0),
+        Item::Byte(0x11),
+        Item::Byte(0x25),
+        Item::Byte(0xF0),
+        Item::Byte(0x25),
+        Item::Byte(0xE0),
+        Item::Byte(0xF0),
+        Item::Byte(0x11),
+        Item::ExpectText("£@#~\\|€"),
+        Item::ExpectErrors(&[]),
+    ],
+};
+
+/// Sends broken words, timed-out partial words and unknown scancodes, with
+/// good key presses in between.
+///
+/// Use Scan Code Set 2 and a [`Us104Key`](crate::layouts::Us104Key) layout.
+pub const PATHOLOGICAL: Script = Script {
+    name: "Pathological errors",
+    items: &[
+        // 'a' with a bad parity bit, start bit and stop bit
+        Item::Word(encode_word(0x1C) ^ (1 << 9)),
+        Item::Word(encode_word(0x1C) | 1),
+        Item::Word(encode_word(0x1C) & !(1 << 10)),
+        Item::ExpectErrors(&[Error::ParityError, Error::BadStartBit, Error::BadStopBit]),
+        // Half an 'x', then the host times out
+        Item::Bits {
+            word: encode_word(0x22),
+            count: 5,
+        },
+        Item::Idle(10_000),
+        // A 'b', bit by bit
+        Item::Bits {
+            word: encode_word(0x32),
+            count: 11,
+        },
+        Item::Bits {
+            word: encode_word(0xF0),
+            count: 11,
+        },
+        Item::Bits {
+            word: encode_word(0x32),
+            count: 11,
+        },
+        // A short pause between words is fine
+        Item::Idle(500),
+        Item::ExpectText("b"),
+        // Nothing is mapped to 0x02
+        Item::Byte(0x02),
+        Item::ExpectErrors(&[Error::UnknownKeyCode]),
+        // A 'c', to show we recovered
+        word(0x21),
+        word(0xF0),
+        word(0x21),
+        Item::ExpectText("c"),
+        Item::ExpectErrors(&[]),
+    ],
+};
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{layouts, HandleControl, ScancodeSet2};
+
+    fn us_keyboard() -> Keyboard<layouts::Us104Key, ScancodeSet2> {
+        Keyboard::new(
+            ScancodeSet2::new(),
+            layouts::Us104Key,
+            HandleControl::MapLettersToUnicode,
+        )
+    }
+
+    #[test]
+    fn test_encode_word() {
+        let dec = crate::Ps2Decoder::new();
+        for byte in 0..=255u8 {
+            assert_eq!(dec.add_word(encode_word(byte)), Ok(byte));
+        }
+    }
+
+    #[test]
+    fn test_us_typing() {
+        let report = run(&mut us_keyboard(), &US_TYPING);
+        assert_eq!(report.failures, []);
+        assert_eq!(report.text, "Hello, World!");
+        assert_eq!(report.events.len(), 32);
+    }
+
+    #[test]
+    fn test_uk_symbols() {
+        let mut kb = Keyboard::new(
+            ScancodeSet2::new(),
+            layouts::Uk105Key,
+            HandleControl::MapLettersToUnicode,
+        );
+        let report = run(&mut kb, &UK_SYMBOLS);
+        assert!(report.passed(), "{:?}", report.failures);
+    }
+
+    #[test]
+    fn test_pathological() {
+        let report = run(&mut us_keyboard(), &PATHOLOGICAL);
+        assert!(report.passed(), "{:?}", report.failures);
+        assert_eq!(report.timeouts, 1);
+        assert_eq!(report.text, "bc");
+    }
+
+    #[test]
+    fn test_failures() {
+        // The US layout doesn't have a pound sign
+        let report = run(&mut us_keyboard(), &UK_SYMBOLS);
+        assert!(!report.passed());
+        assert_eq!(
+            report.failures,
+            [Failure::Text {
+                item: 35,
+                expected: "£@#~\\|€",
+                got: String::from("#\"\\|\\|4"),
+            }]
+        );
+    }
+}
@@ -0,0 +1,464 @@
+//! Human readable names for keys
+//!
+//! Every [`KeyCode`] has a short English name (see [`KeyCode::name`]), which
+//! won't change between releases. If you need the names in another language
+//! (e.g. to speak them aloud), implement [`KeyNamer`] and use
+//! [`KeyCode::display_with`] or [`DecodedKey::display_with`].
+
+use core::fmt;
+
+use crate::{DecodedKey, KeyCode};
+
+/// Gives a name for each [`KeyCode`].
+pub trait KeyNamer {
+    /// The name of this key.
+    fn name(&self, code: KeyCode) -> &'static str;
+}
+
+/// The English names, from [`KeyCode::name`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct EnglishNames;
+
+impl KeyNamer for EnglishNames {
+    fn name(&self, code: KeyCode) -> &'static str {
+        code.name()
+    }
+}
+
+/// German names, like the legends on a German keyboard.
+///
+/// This is mostly here as a template for your own [`KeyNamer`]. The symbol
+/// keys are named after where they are, because what is printed on them
+/// depends on the layout.
+#[cfg(feature = "key-names-de")]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct GermanNames;
+
+#[cfg(feature = "key-names-de")]
+impl KeyNamer for GermanNames {
+    fn name(&self, code: KeyCode) -> &'static str {
+        match code {
+            KeyCode::Escape => "Esc",
+            KeyCode::F1 => "F1",
+            KeyCode::F2 => "F2",
+            KeyCode::F3 => "F3",
+            KeyCode::F4 => "F4",
+            KeyCode::F5 => "F5",
+            KeyCode::F6 => "F6",
+            KeyCode::F7 => "F7",
+            KeyCode::F8 => "F8",
+            KeyCode::F9 => "F9",
+            KeyCode::F10 => "F10",
+            KeyCode::F11 => "F11",
+            KeyCode::F12 => "F12",
+            KeyCode::PrintScreen => "Druck",
+            KeyCode::SysRq => "S-Abf",
+            KeyCode::ScrollLock => "Rollen",
+            KeyCode::PauseBreak => "Pause/Untbr",
+            KeyCode::Oem8 => "Zirkumflex-Taste",
+            KeyCode::Key1 => "1",
+            KeyCode::Key2 => "2",
+            KeyCode::Key3 => "3",
+            KeyCode::Key4 => "4",
+            KeyCode::Key5 => "5",
+            KeyCode::Key6 => "6",
+            KeyCode::Key7 => "7",
+            KeyCode::Key8 => "8",
+            KeyCode::Key9 => "9",
+            KeyCode::Key0 => "0",
+            KeyCode::OemMinus => "Eszett-Taste",
+            KeyCode::OemPlus => "Akut-Taste",
+            KeyCode::Backspace => "Rücktaste",
+            KeyCode::Insert => "Einfügen",
+            KeyCode::Home => "Pos1",
+            KeyCode::PageUp => "Bild auf",
+            KeyCode::NumpadLock => "Num",
+            KeyCode::NumpadDivide => "Ziffernblock Geteilt",
+            KeyCode::NumpadMultiply => "Ziffernblock Mal",
+            KeyCode::NumpadSubtract => "Ziffernblock Minus",
+            KeyCode::Tab => "Tabulator",
+            KeyCode::Q => "Q",
+            KeyCode::W => "W",
+            KeyCode::E => "E",
+            KeyCode::R => "R",
+            KeyCode::T => "T",
+            KeyCode::Y => "Y",
+            KeyCode::U => "U",
+            KeyCode::I => "I",
+            KeyCode::O => "O",
+            KeyCode::P => "P",
+            KeyCode::Oem4 => "Ü-Taste",
+            KeyCode::Oem6 => "Plus-Taste",
+            KeyCode::Oem5 => "Backslash-Taste",
+            KeyCode::Oem7 => "Raute-Taste",
+            KeyCode::Delete => "Entfernen",
+            KeyCode::End => "Ende",
+            KeyCode::PageDown => "Bild ab",
+            KeyCode::Numpad7 => "Ziffernblock 7",
+            KeyCode::Numpad8 => "Ziffernblock 8",
+            KeyCode::Numpad9 => "Ziffernblock 9",
+            KeyCode::NumpadAdd => "Ziffernblock Plus",
+            KeyCode::CapsLock => "Feststelltaste",
+            KeyCode::A => "A",
+            KeyCode::S => "S",
+            KeyCode::D => "D",
+            KeyCode::F => "F",
+            KeyCode::G => "G",
+            KeyCode::H => "H",
+            KeyCode::J => "J",
+            KeyCode::K => "K",
+            KeyCode::L => "L",
+            KeyCode::Oem1 => "Ö-Taste",
+            KeyCode::Oem3 => "Ä-Taste",
+            KeyCode::Return => "Eingabe",
+            KeyCode::Numpad4 => "Ziffernblock 4",
+            KeyCode::Numpad5 => "Ziffernblock 5",
+            KeyCode::Numpad6 => "Ziffernblock 6",
+            KeyCode::LShift => "Umschalt links",
+            KeyCode::Z => "Z",
+            KeyCode::X => "X",
+            KeyCode::C => "C",
+            KeyCode::V => "V",
+            KeyCode::B => "B",
+            KeyCode::N => "N",
+            KeyCode::M => "M",
+            KeyCode::OemComma => "Komma",
+            KeyCode::OemPeriod => "Punkt",
+            KeyCode::Oem2 => "Minus",
+            KeyCode::RShift => "Umschalt rechts",
+            KeyCode::ArrowUp => "Pfeil nach oben",
+            KeyCode::Numpad1 => "Ziffernblock 1",
+            KeyCode::Numpad2 => "Ziffernblock 2",
+            KeyCode::Numpad3 => "Ziffernblock 3",
+            KeyCode::NumpadEnter => "Ziffernblock Eingabe",
+            KeyCode::LControl => "Strg links",
+            KeyCode::LWin => "Windows links",
+            KeyCode::LAlt => "Alt",
+            KeyCode::Spacebar => "Leertaste",
+            KeyCode::RAltGr => "Alt Gr",
+            KeyCode::RWin => "Windows rechts",
+            KeyCode::Apps => "Menü",
+            KeyCode::RControl => "Strg rechts",
+            KeyCode::ArrowLeft => "Pfeil nach links",
+            KeyCode::ArrowDown => "Pfeil nach unten",
+            KeyCode::ArrowRight => "Pfeil nach rechts",
+            KeyCode::Numpad0 => "Ziffernblock 0",
+            KeyCode::NumpadPeriod => "Ziffernblock Komma",
+            KeyCode::Oem9 => "Muhenkan-Taste",
+            KeyCode::Oem10 => "Henkan-Taste",
+            KeyCode::Oem11 => "Katakana/Hiragana-Taste",
+            KeyCode::Oem12 => "Ro-Taste",
+            KeyCode::Oem13 => "Yen-Taste",
+            KeyCode::NumpadComma => "Ziffernblock Trennzeichen",
+            KeyCode::Hangul => "Hangul-Taste",
+            KeyCode::Hanja => "Hanja-Taste",
+            KeyCode::PrevTrack => "Vorheriger Titel",
+            KeyCode::NextTrack => "Nächster Titel",
+            KeyCode::Mute => "Stumm",
+            KeyCode::Calculator => "Rechner",
+            KeyCode::Play => "Wiedergabe",
+            KeyCode::Stop => "Stopp",
+            KeyCode::VolumeDown => "Leiser",
+            KeyCode::VolumeUp => "Lauter",
+            KeyCode::WWWHome => "Browser",
+            KeyCode::WWWSearch => "Suchen",
+            KeyCode::WWWFavourites => "Favoriten",
+            KeyCode::WWWRefresh => "Aktualisieren",
+            KeyCode::WWWStop => "Abbrechen",
+            KeyCode::WWWForward => "Vorwärts",
+            KeyCode::WWWBack => "Zurück",
+            KeyCode::MyComputer => "Arbeitsplatz",
+            KeyCode::Email => "E-Mail",
+            KeyCode::MediaSelect => "Medienauswahl",
+            KeyCode::Power => "Ein/Aus",
+            KeyCode::Sleep => "Standby",
+            KeyCode::Wake => "Aufwachen",
+            KeyCode::PowerOnTestOk => "Selbsttest bestanden",
+            KeyCode::TooManyKeys => "Zu viele Tasten",
+            KeyCode::RControl2 => "Strg rechts (versteckt)",
+            KeyCode::RAlt2 => "Alt rechts (versteckt)",
+        }
+    }
+}
+
+impl KeyCode {
+    /// A short English name for this key, like `"Left Shift"` or `"Q"`.
+    ///
+    /// The symbol keys are named after their US legends, because what is
+    /// printed on them depends on the layout. These names won't change, so
+    /// you can store them (e.g. in a config file).
+    pub const fn name(self) -> &'static str {
+        match self {
+            KeyCode::Escape => "Escape",
+            KeyCode::F1 => "F1",
+            KeyCode::F2 => "F2",
+            KeyCode::F3 => "F3",
+            KeyCode::F4 => "F4",
+            KeyCode::F5 => "F5",
+            KeyCode::F6 => "F6",
+            KeyCode::F7 => "F7",
+            KeyCode::F8 => "F8",
+            KeyCode::F9 => "F9",
+            KeyCode::F10 => "F10",
+            KeyCode::F11 => "F11",
+            KeyCode::F12 => "F12",
+            KeyCode::PrintScreen => "Print Screen",
+            KeyCode::SysRq => "SysRq",
+            KeyCode::ScrollLock => "Scroll Lock",
+            KeyCode::PauseBreak => "Pause",
+            KeyCode::Oem8 => "Backtick",
+            KeyCode::Key1 => "1",
+            KeyCode::Key2 => "2",
+            KeyCode::Key3 => "3",
+            KeyCode::Key4 => "4",
+            KeyCode::Key5 => "5",
+            KeyCode::Key6 => "6",
+            KeyCode::Key7 => "7",
+            KeyCode::Key8 => "8",
+            KeyCode::Key9 => "9",
+            KeyCode::Key0 => "0",
+            KeyCode::OemMinus => "Minus",
+            KeyCode::OemPlus => "Equals",
+            KeyCode::Backspace => "Backspace",
+            KeyCode::Insert => "Insert",
+            KeyCode::Home => "Home",
+            KeyCode::PageUp => "Page Up",
+            KeyCode::NumpadLock => "Num Lock",
+            KeyCode::NumpadDivide => "Numpad Divide",
+            KeyCode::NumpadMultiply => "Numpad Multiply",
+            KeyCode::NumpadSubtract => "Numpad Subtract",
+            KeyCode::Tab => "Tab",
+            KeyCode::Q => "Q",
+            KeyCode::W => "W",
+            KeyCode::E => "E",
+            KeyCode::R => "R",
+            KeyCode::T => "T",
+            KeyCode::Y => "Y",
+            KeyCode::U => "U",
+            KeyCode::I => "I",
+            KeyCode::O => "O",
+            KeyCode::P => "P",
+            KeyCode::Oem4 => "Left Bracket",
+            KeyCode::Oem6 => "Right Bracket",
+            KeyCode::Oem5 => "Backslash",
+            KeyCode::Oem7 => "Hash",
+            KeyCode::Delete => "Delete",
+            KeyCode::End => "End",
+            KeyCode::PageDown => "Page Down",
+            KeyCode::Numpad7 => "Numpad 7",
+            KeyCode::Numpad8 => "Numpad 8",
+            KeyCode::Numpad9 => "Numpad 9",
+            KeyCode::NumpadAdd => "Numpad Add",
+            KeyCode::CapsLock => "Caps Lock",
+            KeyCode::A => "A",
+            KeyCode::S => "S",
+            KeyCode::D => "D",
+            KeyCode::F => "F",
+            KeyCode::G => "G",
+            KeyCode::H => "H",
+            KeyCode::J => "J",
+            KeyCode::K => "K",
+            KeyCode::L => "L",
+            KeyCode::Oem1 => "Semicolon",
+            KeyCode::Oem3 => "Quote",
+            KeyCode::Return => "Return",
+            KeyCode::Numpad4 => "Numpad 4",
+            KeyCode::Numpad5 => "Numpad 5",
+            KeyCode::Numpad6 => "Numpad 6",
+            KeyCode::LShift => "Left Shift",
+            KeyCode::Z => "Z",
+            KeyCode::X => "X",
+            KeyCode::C => "C",
+            KeyCode::V => "V",
+            KeyCode::B => "B",
+            KeyCode::N => "N",
+            KeyCode::M => "M",
+            KeyCode::OemComma => "Comma",
+            KeyCode::OemPeriod => "Period",
+            KeyCode::Oem2 => "Slash",
+            KeyCode::RShift => "Right Shift",
+            KeyCode::ArrowUp => "Up",
+            KeyCode::Numpad1 => "Numpad 1",
+            KeyCode::Numpad2 => "Numpad 2",
+            KeyCode::Numpad3 => "Numpad 3",
+            KeyCode::NumpadEnter => "Numpad Enter",
+            KeyCode::LControl => "Left Control",
+            KeyCode::LWin => "Left Windows",
+            KeyCode::LAlt => "Left Alt",
+            KeyCode::Spacebar => "Space",
+            KeyCode::RAltGr => "AltGr",
+            KeyCode::RWin => "Right Windows",
+            KeyCode::Apps => "Menu",
+            KeyCode::RControl => "Right Control",
+            KeyCode::ArrowLeft => "Left",
+            KeyCode::ArrowDown => "Down",
+            KeyCode::ArrowRight => "Right",
+            KeyCode::Numpad0 => "Numpad 0",
+            KeyCode::NumpadPeriod => "Numpad Period",
+            KeyCode::Oem9 => "Muhenkan",
+            KeyCode::Oem10 => "Henkan",
+            KeyCode::Oem11 => "Katakana/Hiragana",
+            KeyCode::Oem12 => "Ro",
+            KeyCode::Oem13 => "Yen",
+            KeyCode::NumpadComma => "Numpad Comma",
+            KeyCode::Hangul => "Hangul",
+            KeyCode::Hanja => "Hanja",
+            KeyCode::PrevTrack => "Previous Track",
+            KeyCode::NextTrack => "Next Track",
+            KeyCode::Mute => "Mute",
+            KeyCode::Calculator => "Calculator",
+            KeyCode::Play => "Play",
+            KeyCode::Stop => "Stop",
+            KeyCode::VolumeDown => "Volume Down",
+            KeyCode::VolumeUp => "Volume Up",
+            KeyCode::WWWHome => "Browser Home",
+            KeyCode::WWWSearch => "Browser Search",
+            KeyCode::WWWFavourites => "Browser Favourites",
+            KeyCode::WWWRefresh => "Browser Refresh",
+            KeyCode::WWWStop => "Browser Stop",
+            KeyCode::WWWForward => "Browser Forward",
+            KeyCode::WWWBack => "Browser Back",
+            KeyCode::MyComputer => "My Computer",
+            KeyCode::Email => "Email",
+            KeyCode::MediaSelect => "Media Select",
+            KeyCode::Power => "Power",
+            KeyCode::Sleep => "Sleep",
+            KeyCode::Wake => "Wake",
+            KeyCode::PowerOnTestOk => "Self Test Passed",
+            KeyCode::TooManyKeys => "Too Many Keys",
+            KeyCode::RControl2 => "Hidden Right Control",
+            KeyCode::RAlt2 => "Hidden Right Alt",
+        }
+    }
+
+    /// Display this key's name, from the given [`KeyNamer`].
+    pub fn display_with<N>(self, namer: &N) -> Named<'_, KeyCode, N>
+    where
+        N: KeyNamer + ?Sized,
+    {
+        Named { item: self, namer }
+    }
+}
+
+impl DecodedKey {
+    /// Display this key, using the given [`KeyNamer`] for a
+    /// [`DecodedKey::RawKey`].
+    ///
+    /// Control characters are escaped (e.g. `\n`, or `\u{8}` for Backspace),
+    /// so the output is always printable.
+    pub fn display_with<N>(self, namer: &N) -> Named<'_, DecodedKey, N>
+    where
+        N: KeyNamer + ?Sized,
+    {
+        Named { item: self, namer }
+    }
+}
+
+/// Displays a [`KeyCode`] or [`DecodedKey`] using a [`KeyNamer`].
+///
+/// See [`KeyCode::display_with`] and [`DecodedKey::display_with`].
+pub struct Named<'a, T, N: ?Sized> {
+    item: T,
+    namer: &'a N,
+}
+
+impl<'a, N> fmt::Display for Named<'a, KeyCode, N>
+where
+    N: KeyNamer + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.namer.name(self.item))
+    }
+}
+
+impl<'a, N> fmt::Display for Named<'a, DecodedKey, N>
+where
+    N: KeyNamer + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.item {
+            DecodedKey::Unicode(ch) | DecodedKey::DeadKey(ch) if ch.is_control() => {
+                write!(f, "{}", ch.escape_debug())
+            }
+            DecodedKey::Unicode(ch) | DecodedKey::DeadKey(ch) => write!(f, "{}", ch),
+            DecodedKey::RawKey(code) => f.write_str(self.namer.name(code)),
+        }
+    }
+}
+
+impl fmt::Display for KeyCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl fmt::Display for DecodedKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display_with(&EnglishNames).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check_names(namer: &dyn KeyNamer) {
+        for code in KeyCode::ALL {
+            let name = namer.name(code);
+            assert!(!name.is_empty(), "{:?}", code);
+            assert_eq!(name.trim(), name, "{:?}", code);
+        }
+    }
+
+    #[test]
+    fn test_english_names() {
+        check_names(&EnglishNames);
+        let mut names: Vec<&str> = KeyCode::ALL.iter().map(|code| code.name()).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), KeyCode::COUNT, "names must be unique");
+    }
+
+    #[cfg(feature = "key-names-de")]
+    #[test]
+    fn test_german_names() {
+        check_names(&GermanNames);
+        assert_eq!(
+            format!("{}", KeyCode::Return.display_with(&GermanNames)),
+            "Eingabe"
+        );
+        // Only the digits, letters and function keys keep their English names
+        for code in KeyCode::ALL {
+            let name = GermanNames.name(code);
+            let untranslated =
+                name.len() == 1 || (name.starts_with('F') && name[1..].parse::<u8>().is_ok());
+            assert!(untranslated || name != code.name(), "{:?}", code);
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", KeyCode::LShift), "Left Shift");
+        assert_eq!(format!("{}", DecodedKey::RawKey(KeyCode::Return)), "Return");
+        assert_eq!(format!("{}", DecodedKey::Unicode('ß')), "ß");
+        assert_eq!(format!("{}", DecodedKey::Unicode('\u{8}')), "\\u{8}");
+        assert_eq!(format!("{}", DecodedKey::Unicode('\n')), "\\n");
+        assert_eq!(format!("{}", DecodedKey::DeadKey('^')), "^");
+
+        struct Shouty;
+        impl KeyNamer for Shouty {
+            fn name(&self, _code: KeyCode) -> &'static str {
+                "KEY"
+            }
+        }
+        let namer: &dyn KeyNamer = &Shouty;
+        assert_eq!(
+            format!("{}", DecodedKey::RawKey(KeyCode::A).display_with(namer)),
+            "KEY"
+        );
+        assert_eq!(
+            format!("{}", DecodedKey::Unicode('a').display_with(namer)),
+            "a"
+        );
+    }
+}
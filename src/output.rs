@@ -0,0 +1,179 @@
+//! Short runs of characters, without needing `alloc`
+
+use core::fmt;
+
+/// A short run of characters, produced by a single key press.
+///
+/// Most key presses give you one character, but some give you several - for
+/// example, a dead key followed by a letter it doesn't combine with. This is
+/// a fixed-size list, so it works without `alloc`. It dereferences to a slice
+/// of `char`, and can be compared against a `&str`.
+#[derive(Copy, Clone, Default)]
+pub struct SmallOutput {
+    chars: [char; SmallOutput::CAPACITY],
+    len: usize,
+}
+
+impl SmallOutput {
+    /// The most characters a [`SmallOutput`] can hold.
+    pub const CAPACITY: usize = 8;
+
+    /// Make a new, empty, [`SmallOutput`].
+    pub const fn new() -> SmallOutput {
+        SmallOutput {
+            chars: ['\0'; SmallOutput::CAPACITY],
+            len: 0,
+        }
+    }
+
+    /// Make a [`SmallOutput`] holding a single character.
+    pub const fn from_char(ch: char) -> SmallOutput {
+        let mut output = SmallOutput::new();
+        output.chars[0] = ch;
+        output.len = 1;
+        output
+    }
+
+    /// Add a character to the end.
+    ///
+    /// If there is no room, you get the character back.
+    pub fn push(&mut self, ch: char) -> Result<(), char> {
+        if self.is_full() {
+            return Err(ch);
+        }
+        self.chars[self.len] = ch;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Remove the first character, if there is one.
+    pub fn pop_front(&mut self) -> Option<char> {
+        if self.len == 0 {
+            return None;
+        }
+        let ch = self.chars[0];
+        self.chars.copy_within(1..self.len, 0);
+        self.len -= 1;
+        Some(ch)
+    }
+
+    /// Remove all the characters.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Is there no room for any more characters?
+    pub const fn is_full(&self) -> bool {
+        self.len == SmallOutput::CAPACITY
+    }
+}
+
+impl core::ops::Deref for SmallOutput {
+    type Target = [char];
+
+    fn deref(&self) -> &[char] {
+        &self.chars[..self.len]
+    }
+}
+
+impl<'a> IntoIterator for &'a SmallOutput {
+    type Item = &'a char;
+    type IntoIter = core::slice::Iter<'a, char>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Only the characters in use count, not whatever is left over in the array
+impl PartialEq for SmallOutput {
+    fn eq(&self, other: &SmallOutput) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for SmallOutput {}
+
+impl PartialEq<str> for SmallOutput {
+    fn eq(&self, other: &str) -> bool {
+        self.iter().copied().eq(other.chars())
+    }
+}
+
+impl PartialEq<&str> for SmallOutput {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl fmt::Debug for SmallOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SmallOutput").field(&&**self).finish()
+    }
+}
+
+impl fmt::Display for SmallOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for ch in self.iter() {
+            fmt::Write::write_char(f, *ch)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_push() {
+        let mut output = SmallOutput::new();
+        assert!(output.is_empty());
+        assert_eq!(output, "");
+        assert_eq!(output.push('a'), Ok(()));
+        assert_eq!(output.push('é'), Ok(()));
+        assert_eq!(output.len(), 2);
+        assert_eq!(output, "aé");
+        assert_ne!(output, "a");
+        assert_eq!(SmallOutput::from_char('x'), "x");
+        output.clear();
+        assert_eq!(output, SmallOutput::default());
+        // Left-over characters don't count
+        output.push('a').unwrap();
+        assert_eq!(output, SmallOutput::from_char('a'));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut output = SmallOutput::new();
+        for ch in "12345678".chars() {
+            assert_eq!(output.push(ch), Ok(()));
+        }
+        assert!(output.is_full());
+        assert_eq!(output.push('9'), Err('9'));
+        assert_eq!(output, "12345678");
+        // Making room
+        assert_eq!(output.pop_front(), Some('1'));
+        assert_eq!(output.push('9'), Ok(()));
+        assert_eq!(output, "23456789");
+    }
+
+    #[test]
+    fn test_iteration() {
+        let mut output = SmallOutput::new();
+        for ch in "abc".chars() {
+            output.push(ch).unwrap();
+        }
+        let mut s = String::new();
+        for ch in &output {
+            s.push(*ch);
+        }
+        assert_eq!(s, "abc");
+        assert_eq!(format!("{}", output), "abc");
+        assert_eq!(format!("{:?}", output), "SmallOutput(['a', 'b', 'c'])");
+        assert_eq!(output.pop_front(), Some('a'));
+        assert_eq!(output.pop_front(), Some('b'));
+        assert_eq!(output.pop_front(), Some('c'));
+        assert_eq!(output.pop_front(), None);
+    }
+}
@@ -0,0 +1,137 @@
+//! Bytes stored by [`Keyboard::push_raw`], waiting to be decoded
+
+use crate::{DecodedItem, Error, Keyboard, KeyboardLayout, ScancodeSet};
+
+/// A ring buffer of bytes from the keyboard, which overwrites the oldest byte
+/// when it's full.
+#[derive(Debug)]
+pub(crate) struct PendingBytes<const N: usize> {
+    bytes: [u8; N],
+    start: usize,
+    len: usize,
+    dropped: usize,
+    overflowed: bool,
+}
+
+impl<const N: usize> PendingBytes<N> {
+    pub(crate) const fn new() -> PendingBytes<N> {
+        PendingBytes {
+            bytes: [0; N],
+            start: 0,
+            len: 0,
+            dropped: 0,
+            overflowed: false,
+        }
+    }
+
+    pub(crate) fn push(&mut self, byte: u8) {
+        if N == 0 {
+            self.dropped = self.dropped.wrapping_add(1);
+            self.overflowed = true;
+        } else if self.len == N {
+            // Overwrite the oldest byte
+            self.bytes[self.start] = byte;
+            self.start = (self.start + 1) % N;
+            self.dropped = self.dropped.wrapping_add(1);
+            self.overflowed = true;
+        } else {
+            self.bytes[(self.start + self.len) % N] = byte;
+            self.len += 1;
+        }
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+        let byte = self.bytes[self.start];
+        self.start = (self.start + 1) % N;
+        self.len -= 1;
+        Some(byte)
+    }
+
+    /// Has the buffer overflowed since we last asked?
+    pub(crate) fn take_overflow(&mut self) -> bool {
+        core::mem::replace(&mut self.overflowed, false)
+    }
+
+    pub(crate) const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) const fn dropped(&self) -> usize {
+        self.dropped
+    }
+}
+
+/// Decodes the bytes stored by [`Keyboard::push_raw`].
+///
+/// See [`Keyboard::process_pending`].
+#[derive(Debug)]
+pub struct PendingItems<'a, L, S, const N: usize>
+where
+    L: KeyboardLayout,
+    S: ScancodeSet,
+{
+    pub(crate) keyboard: &'a mut Keyboard<L, S, N>,
+}
+
+impl<'a, L, S, const N: usize> Iterator for PendingItems<'a, L, S, N>
+where
+    L: KeyboardLayout,
+    S: ScancodeSet,
+{
+    type Item = Result<DecodedItem, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.keyboard.pending.take_overflow() {
+            // Whatever sequence we were part-way through has lost bytes
+            self.keyboard.scancode_set.reset();
+            return Some(Err(Error::PendingOverflow));
+        }
+        while let Some(byte) = self.keyboard.pending.pop() {
+            match self.keyboard.add_byte(byte) {
+                Ok(None) => {}
+                Ok(Some(item)) => return Some(Ok(item)),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ring() {
+        let mut pending = PendingBytes::<3>::new();
+        assert_eq!(pending.pop(), None);
+        pending.push(1);
+        pending.push(2);
+        assert_eq!(pending.pop(), Some(1));
+        pending.push(3);
+        pending.push(4);
+        assert_eq!(pending.len(), 3);
+        assert!(!pending.take_overflow());
+        // Full, so 2 goes
+        pending.push(5);
+        assert!(pending.take_overflow());
+        assert!(!pending.take_overflow());
+        assert_eq!(pending.dropped(), 1);
+        assert_eq!(pending.pop(), Some(3));
+        assert_eq!(pending.pop(), Some(4));
+        assert_eq!(pending.pop(), Some(5));
+        assert_eq!(pending.pop(), None);
+    }
+
+    #[test]
+    fn test_zero_depth() {
+        let mut pending = PendingBytes::<0>::new();
+        pending.push(1);
+        assert_eq!(pending.pop(), None);
+        assert_eq!(pending.dropped(), 1);
+        assert!(pending.take_overflow());
+    }
+}
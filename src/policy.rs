@@ -0,0 +1,143 @@
+//! Rules for what the modifier keys mean
+
+use crate::Modifiers;
+
+/// Decides what the modifier keys mean, for an
+/// [`EventDecoder`](crate::EventDecoder).
+///
+/// Each method answers one question about the modifier keys that are held
+/// (or locked). The default for each method gives the usual answer, so you
+/// only need to write the ones you want to change.
+///
+/// Before the [`KeyboardLayout`](crate::KeyboardLayout) sees the modifiers,
+/// the [`EventDecoder`](crate::EventDecoder) adjusts them (see
+/// [`ModifierPolicy::apply`]) so that [`Modifiers::is_shifted`],
+/// [`Modifiers::is_caps`], [`Modifiers::is_altgr`], [`Modifiers::is_ctrl`]
+/// and `Modifiers::numlock` give the policy's answers. This means every
+/// layout follows the policy, without any changes.
+pub trait ModifierPolicy {
+    /// Should symbol keys (like `1` or `[`) give their shifted symbol?
+    fn is_shifted(&self, modifiers: &Modifiers) -> bool {
+        modifiers.is_shifted()
+    }
+
+    /// Should letter keys give an upper case letter?
+    fn is_caps(&self, modifiers: &Modifiers) -> bool {
+        modifiers.is_caps()
+    }
+
+    /// Should keys give their AltGr character?
+    fn is_altgr(&self, modifiers: &Modifiers) -> bool {
+        modifiers.is_altgr()
+    }
+
+    /// Should letter keys give a Control code (if the
+    /// [`HandleControl`](crate::HandleControl) setting allows)?
+    fn is_ctrl(&self, modifiers: &Modifiers) -> bool {
+        modifiers.is_ctrl()
+    }
+
+    /// Should the numpad give digits (rather than navigation keys)?
+    fn is_numlock(&self, modifiers: &Modifiers) -> bool {
+        modifiers.numlock
+    }
+
+    /// Build the modifiers that a layout should see.
+    ///
+    /// You shouldn't need to change this.
+    fn apply(&self, modifiers: &Modifiers) -> Modifiers {
+        let shifted = self.is_shifted(modifiers);
+        let ctrl = self.is_ctrl(modifiers);
+        let altgr = self.is_altgr(modifiers);
+        Modifiers {
+            lshift: shifted,
+            rshift: false,
+            lctrl: ctrl,
+            rctrl: false,
+            numlock: self.is_numlock(modifiers),
+            // So that `is_caps` is `shifted ^ capslock`
+            capslock: self.is_caps(modifiers) ^ shifted,
+            scrolllock: modifiers.scrolllock,
+            // Ctrl + Left Alt counts as AltGr, so hide Left Alt if the policy
+            // says this isn't AltGr
+            lalt: modifiers.lalt && (altgr || !ctrl),
+            ralt: altgr,
+            rctrl2: modifiers.rctrl2,
+            kana: modifiers.kana,
+        }
+    }
+}
+
+/// The usual meaning of the modifier keys.
+///
+/// Caps Lock only affects letters, and AltGr is Right Alt or Ctrl + Left Alt.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct DefaultPolicy;
+
+impl ModifierPolicy for DefaultPolicy {
+    // Nothing needs adjusting
+    fn apply(&self, modifiers: &Modifiers) -> Modifiers {
+        modifiers.clone()
+    }
+}
+
+/// Caps Lock works like Shift Lock on a typewriter - it shifts every key,
+/// not just the letters.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ShiftLock;
+
+impl ModifierPolicy for ShiftLock {
+    fn is_shifted(&self, modifiers: &Modifiers) -> bool {
+        modifiers.is_shifted() ^ modifiers.capslock
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_apply_identity() {
+        // Applying the standard rules by hand changes nothing that matters
+        struct Standard;
+        impl ModifierPolicy for Standard {}
+        for bits in 0..=255u8 {
+            let modifiers = Modifiers {
+                lshift: (bits & 1) != 0,
+                rshift: (bits & 2) != 0,
+                lctrl: (bits & 4) != 0,
+                rctrl: (bits & 8) != 0,
+                numlock: (bits & 16) != 0,
+                capslock: (bits & 32) != 0,
+                lalt: (bits & 64) != 0,
+                ralt: (bits & 128) != 0,
+                ..Default::default()
+            };
+            let applied = Standard.apply(&modifiers);
+            assert_eq!(applied.is_shifted(), modifiers.is_shifted());
+            assert_eq!(applied.is_caps(), modifiers.is_caps());
+            assert_eq!(applied.is_altgr(), modifiers.is_altgr());
+            assert_eq!(applied.is_ctrl(), modifiers.is_ctrl());
+            assert_eq!(applied.numlock, modifiers.numlock);
+        }
+    }
+
+    #[test]
+    fn test_shift_lock() {
+        let modifiers = Modifiers {
+            capslock: true,
+            ..Default::default()
+        };
+        let applied = ShiftLock.apply(&modifiers);
+        assert!(applied.is_shifted());
+        assert!(applied.is_caps());
+        let modifiers = Modifiers {
+            capslock: true,
+            rshift: true,
+            ..Default::default()
+        };
+        let applied = ShiftLock.apply(&modifiers);
+        assert!(!applied.is_shifted());
+        assert!(!applied.is_caps());
+    }
+}
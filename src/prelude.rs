@@ -0,0 +1,53 @@
+//! The types and traits you almost always need
+//!
+//! ```rust
+//! use pc_keyboard::prelude::*;
+//!
+//! let mut kb = Keyboard::new(
+//!     ScancodeSet1::new(),
+//!     layouts::Us104Key,
+//!     HandleControl::Ignore,
+//! );
+//! if let Ok(Some(item)) = kb.add_byte(0x1E) {
+//!     assert_eq!(kb.process_item(item), Some(DecodedKey::Unicode('a')));
+//! }
+//! ```
+//!
+//! It also has everything you need to write your own [`KeyboardLayout`]:
+//!
+//! ```rust
+//! use pc_keyboard::prelude::*;
+//!
+//! /// A US layout, where AltGr + E gives a Euro sign
+//! struct UsEuro;
+//!
+//! impl KeyboardLayout for UsEuro {
+//!     fn map_keycode(
+//!         &self,
+//!         keycode: KeyCode,
+//!         modifiers: &Modifiers,
+//!         handle_ctrl: HandleControl,
+//!     ) -> DecodedKey {
+//!         const E: AsciiUpper = AsciiUpper::new('E');
+//!         match keycode {
+//!             KeyCode::E => modifiers.handle_ascii_4(E, '€', '€', handle_ctrl),
+//!             KeyCode::Oem8 if modifiers.is_shifted() => DecodedKey::DeadKey('~'),
+//!             _ => layouts::Us104Key.map_keycode(keycode, modifiers, handle_ctrl),
+//!         }
+//!     }
+//! }
+//!
+//! let mut decoder = EventDecoder::new(UsEuro, HandleControl::Ignore);
+//! decoder.process_keyevent(KeyEvent::new(KeyCode::RAltGr, KeyState::Down));
+//! assert_eq!(
+//!     decoder.process_keyevent(KeyEvent::new(KeyCode::E, KeyState::Down)),
+//!     Some(DecodedKey::Unicode('€'))
+//! );
+//! ```
+
+pub use crate::layouts;
+pub use crate::{
+    AsciiUpper, DecodedItem, DecodedKey, Error, EventDecoder, HandleControl, KeyCode, KeyEvent,
+    KeyState, Keyboard, KeyboardLayout, Modifiers, ProtocolEvent, Ps2Decoder, ScancodeSet,
+    ScancodeSet1, ScancodeSet2,
+};
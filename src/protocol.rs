@@ -0,0 +1,254 @@
+//! Decoding of the non-scancode responses a PS/2 keyboard sends
+//!
+//! After the host sends a command (e.g. `0xED` Set LEDs, or `0xF2` Identify),
+//! the keyboard answers on the same data line that carries the scan codes.
+//! This module turns those answers into typed values.
+//!
+//! See the OS dev wiki: <https://wiki.osdev.org/PS/2_Keyboard#Commands>
+//! and <https://wiki.osdev.org/PS/2_Keyboard#Special_Bytes>
+
+/// The kinds of keyboard that can be reported in response to the Identify
+/// (`0xF2`) command.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[non_exhaustive]
+pub enum KeyboardKind {
+    /// No ID bytes at all - an ancient AT keyboard.
+    AncientAt,
+    /// `AB 83` - a standard MF2 keyboard.
+    Mf2,
+    /// `AB 41` or `AB C1` - an MF2 keyboard, with the ID byte mangled by
+    /// translation in the PS/2 controller.
+    Mf2Translated,
+    /// `AB 84` - a 'short' keyboard, as found on many laptops.
+    Short,
+    /// `AB 85` - an NCD N-97 keyboard, or a 122-key host-connect keyboard.
+    Ncd97,
+    /// `AB 86` - a 122-key keyboard.
+    Key122,
+    /// `AB 90` - a Japanese "G" keyboard.
+    JapaneseG,
+    /// `AB 91` - a Japanese "P" keyboard.
+    JapaneseP,
+    /// `AB 92` - a Japanese "A" keyboard.
+    JapaneseA,
+    /// `AC A1` - an NCD Sun layout keyboard.
+    NcdSun,
+}
+
+/// The single-byte responses a keyboard can send back to the host.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[non_exhaustive]
+pub enum Response {
+    /// `0xFA` - Command acknowledged
+    Ack,
+    /// `0xFE` - Please send the last command again
+    Resend,
+    /// `0xEE` - Response to the Echo command
+    Echo,
+    /// `0xAA` - Self test passed
+    SelfTestPassed,
+    /// `0xFC` or `0xFD` - Self test failed
+    SelfTestFailed,
+}
+
+/// A response that the [`Keyboard`](crate::Keyboard) has been told to wait
+/// for, because the application has just sent a command.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Expect {
+    /// The command is just acknowledged with an `0xFA` (e.g. Set LEDs).
+    Ack,
+    /// The command is acknowledged and then followed by two ID bytes (i.e.
+    /// Identify).
+    DeviceId,
+}
+
+/// Command acknowledged
+pub const ACK: u8 = 0xFA;
+/// Resend the last command
+pub const RESEND: u8 = 0xFE;
+/// Response to the Echo command
+pub const ECHO: u8 = 0xEE;
+/// Self test passed
+pub const SELF_TEST_PASSED: u8 = 0xAA;
+/// Self test failed
+pub const SELF_TEST_FAILED: u8 = 0xFC;
+/// Self test failed (alternative value)
+pub const SELF_TEST_FAILED2: u8 = 0xFD;
+
+/// Tracks a command response we are waiting for.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ResponseTracker {
+    Idle,
+    WaitAck,
+    WaitAckThenId,
+    WaitIdFirst,
+    WaitIdSecond(u8),
+}
+
+/// What the [`ResponseTracker`] did with a byte.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Tracked {
+    /// Not part of a response - decode it as a scancode
+    Pass,
+    /// Part of a response - drop it
+    Swallowed,
+    /// The final byte of an ID response
+    DeviceId(Option<KeyboardKind>),
+    /// The ID response ended with no ID bytes (so this is an ancient AT
+    /// keyboard), and this byte is a scancode
+    NoId,
+}
+
+/// Convert the bytes received after the `0xF2` Identify command (not
+/// including the `0xFA` ACK) into a [`KeyboardKind`].
+///
+/// Returns `None` if the ID is not recognised.
+pub const fn parse_id(bytes: &[u8]) -> Option<KeyboardKind> {
+    match bytes {
+        [] => Some(KeyboardKind::AncientAt),
+        [0xAB, 0x83] => Some(KeyboardKind::Mf2),
+        [0xAB, 0x41] | [0xAB, 0xC1] => Some(KeyboardKind::Mf2Translated),
+        [0xAB, 0x84] => Some(KeyboardKind::Short),
+        [0xAB, 0x85] => Some(KeyboardKind::Ncd97),
+        [0xAB, 0x86] => Some(KeyboardKind::Key122),
+        [0xAB, 0x90] => Some(KeyboardKind::JapaneseG),
+        [0xAB, 0x91] => Some(KeyboardKind::JapaneseP),
+        [0xAB, 0x92] => Some(KeyboardKind::JapaneseA),
+        [0xAC, 0xA1] => Some(KeyboardKind::NcdSun),
+        _ => None,
+    }
+}
+
+/// Convert a single byte from the keyboard into a [`Response`], if it is one.
+///
+/// Note that some of these bytes may also be valid scancodes, depending on
+/// the Scancode Set in use, so only call this when you are expecting a
+/// response.
+pub const fn parse_response(byte: u8) -> Option<Response> {
+    match byte {
+        ACK => Some(Response::Ack),
+        RESEND => Some(Response::Resend),
+        ECHO => Some(Response::Echo),
+        SELF_TEST_PASSED => Some(Response::SelfTestPassed),
+        SELF_TEST_FAILED | SELF_TEST_FAILED2 => Some(Response::SelfTestFailed),
+        _ => None,
+    }
+}
+
+/// Convert a single byte into a [`Response`], if it is one of the responses
+/// to a command (ACK, Resend or Echo).
+///
+/// Unlike [`parse_response`], this ignores the self test results.
+pub(crate) const fn parse_command_response(byte: u8) -> Option<Response> {
+    match byte {
+        ACK | RESEND | ECHO => parse_response(byte),
+        _ => None,
+    }
+}
+
+impl ResponseTracker {
+    /// Start waiting for the given response.
+    pub(crate) const fn expect(expect: Expect) -> ResponseTracker {
+        match expect {
+            Expect::Ack => ResponseTracker::WaitAck,
+            Expect::DeviceId => ResponseTracker::WaitAckThenId,
+        }
+    }
+
+    /// Feed a byte from the keyboard through the tracker.
+    pub(crate) fn feed(&mut self, byte: u8) -> Tracked {
+        match *self {
+            ResponseTracker::Idle => Tracked::Pass,
+            ResponseTracker::WaitAck if byte == ACK || byte == RESEND => {
+                *self = ResponseTracker::Idle;
+                Tracked::Swallowed
+            }
+            ResponseTracker::WaitAckThenId if byte == ACK => {
+                *self = ResponseTracker::WaitIdFirst;
+                Tracked::Swallowed
+            }
+            ResponseTracker::WaitAckThenId if byte == RESEND => {
+                *self = ResponseTracker::Idle;
+                Tracked::Swallowed
+            }
+            ResponseTracker::WaitAck | ResponseTracker::WaitAckThenId => {
+                // Key presses can arrive before the ACK - keep waiting
+                Tracked::Pass
+            }
+            ResponseTracker::WaitIdFirst if byte == 0xAB || byte == 0xAC => {
+                *self = ResponseTracker::WaitIdSecond(byte);
+                Tracked::Swallowed
+            }
+            ResponseTracker::WaitIdFirst => {
+                // No ID bytes, so it's an old AT keyboard and this is a
+                // scancode
+                *self = ResponseTracker::Idle;
+                Tracked::NoId
+            }
+            ResponseTracker::WaitIdSecond(first) => {
+                *self = ResponseTracker::Idle;
+                Tracked::DeviceId(parse_id(&[first, byte]))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_id() {
+        assert_eq!(parse_id(&[]), Some(KeyboardKind::AncientAt));
+        assert_eq!(parse_id(&[0xAB, 0x83]), Some(KeyboardKind::Mf2));
+        assert_eq!(parse_id(&[0xAB, 0x41]), Some(KeyboardKind::Mf2Translated));
+        assert_eq!(parse_id(&[0xAB, 0xC1]), Some(KeyboardKind::Mf2Translated));
+        assert_eq!(parse_id(&[0xAB, 0x84]), Some(KeyboardKind::Short));
+        assert_eq!(parse_id(&[0xAC, 0xA1]), Some(KeyboardKind::NcdSun));
+        assert_eq!(parse_id(&[0xAB]), None);
+        assert_eq!(parse_id(&[0xAB, 0x00]), None);
+        assert_eq!(parse_id(&[0xAB, 0x83, 0x00]), None);
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(parse_response(0xFA), Some(Response::Ack));
+        assert_eq!(parse_response(0xFE), Some(Response::Resend));
+        assert_eq!(parse_response(0xEE), Some(Response::Echo));
+        assert_eq!(parse_response(0xAA), Some(Response::SelfTestPassed));
+        assert_eq!(parse_response(0xFC), Some(Response::SelfTestFailed));
+        assert_eq!(parse_response(0xFD), Some(Response::SelfTestFailed));
+        assert_eq!(parse_response(0x1C), None);
+    }
+
+    #[test]
+    fn test_tracker_id() {
+        let mut t = ResponseTracker::expect(Expect::DeviceId);
+        assert_eq!(t.feed(0x1C), Tracked::Pass);
+        assert_eq!(t.feed(ACK), Tracked::Swallowed);
+        assert_eq!(t.feed(0xAB), Tracked::Swallowed);
+        assert_eq!(
+            t.feed(0xC1),
+            Tracked::DeviceId(Some(KeyboardKind::Mf2Translated))
+        );
+        assert_eq!(t, ResponseTracker::Idle);
+        assert_eq!(t.feed(ACK), Tracked::Pass);
+    }
+
+    #[test]
+    fn test_tracker_no_id() {
+        let mut t = ResponseTracker::expect(Expect::DeviceId);
+        assert_eq!(t.feed(ACK), Tracked::Swallowed);
+        assert_eq!(t.feed(0x1C), Tracked::NoId);
+        assert_eq!(t, ResponseTracker::Idle);
+        assert_eq!(t.feed(0x1C), Tracked::Pass);
+    }
+
+    #[test]
+    fn test_tracker_ack() {
+        let mut t = ResponseTracker::expect(Expect::Ack);
+        assert_eq!(t.feed(ACK), Tracked::Swallowed);
+        assert_eq!(t, ResponseTracker::Idle);
+        assert_eq!(t.feed(0x1C), Tracked::Pass);
+    }
+}
@@ -0,0 +1,105 @@
+//! Short runs of scancode bytes, without needing `alloc`
+
+use core::fmt;
+
+/// The bytes a keyboard sends for a single key event.
+///
+/// Most key events are one, two or three bytes, but Pause is eight in
+/// Scancode Set 2. This is a fixed-size list, so it works without `alloc`.
+/// It dereferences to a slice of `u8`.
+#[derive(Copy, Clone, Default)]
+pub struct ScancodeBytes {
+    bytes: [u8; ScancodeBytes::CAPACITY],
+    len: usize,
+}
+
+impl ScancodeBytes {
+    /// The most bytes a [`ScancodeBytes`] can hold.
+    pub const CAPACITY: usize = 8;
+
+    /// Make a new, empty, [`ScancodeBytes`].
+    pub const fn new() -> ScancodeBytes {
+        ScancodeBytes {
+            bytes: [0; ScancodeBytes::CAPACITY],
+            len: 0,
+        }
+    }
+
+    /// Make a [`ScancodeBytes`] holding a copy of the given bytes.
+    ///
+    /// Panics if there are more than [`ScancodeBytes::CAPACITY`] of them.
+    pub(crate) fn from_slice(bytes: &[u8]) -> ScancodeBytes {
+        let mut result = ScancodeBytes::new();
+        result.bytes[..bytes.len()].copy_from_slice(bytes);
+        result.len = bytes.len();
+        result
+    }
+}
+
+impl core::ops::Deref for ScancodeBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl<'a> IntoIterator for &'a ScancodeBytes {
+    type Item = &'a u8;
+    type IntoIter = core::slice::Iter<'a, u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Only the bytes in use count, not whatever is left over in the array
+impl PartialEq for ScancodeBytes {
+    fn eq(&self, other: &ScancodeBytes) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for ScancodeBytes {}
+
+impl PartialEq<[u8]> for ScancodeBytes {
+    fn eq(&self, other: &[u8]) -> bool {
+        **self == *other
+    }
+}
+
+impl<const N: usize> PartialEq<[u8; N]> for ScancodeBytes {
+    fn eq(&self, other: &[u8; N]) -> bool {
+        **self == other[..]
+    }
+}
+
+impl fmt::Debug for ScancodeBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ScancodeBytes").field(&&**self).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bytes() {
+        let bytes = ScancodeBytes::from_slice(&[0xE0, 0xF0, 0x75]);
+        assert_eq!(bytes.len(), 3);
+        assert_eq!(bytes, [0xE0, 0xF0, 0x75]);
+        assert_eq!(bytes, *[0xE0, 0xF0, 0x75].as_slice());
+        assert_ne!(bytes, [0xE0, 0xF0]);
+        assert_eq!(ScancodeBytes::new(), ScancodeBytes::default());
+        assert_eq!(
+            format!("{:?}", ScancodeBytes::from_slice(&[0x1C])),
+            "ScancodeBytes([28])"
+        );
+        let mut total = 0u32;
+        for byte in &bytes {
+            total += u32::from(*byte);
+        }
+        assert_eq!(total, 0xE0 + 0xF0 + 0x75);
+    }
+}
@@ -1,7 +1,99 @@
 //! A collection of Scancode implementations
 
+mod bytes;
 mod set1;
 mod set2;
+mod translate;
 
+pub use self::bytes::ScancodeBytes;
 pub use self::set1::ScancodeSet1;
 pub use self::set2::ScancodeSet2;
+pub use self::translate::Translator;
+
+/// Identifies a Scancode Set.
+///
+/// See [`ScancodeSet::kind`](crate::ScancodeSet::kind).
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[non_exhaustive]
+pub enum ScancodeSetKind {
+    /// Scancode Set 1, as used by the IBM PC/XT (and the i8042 controller,
+    /// when it is translating)
+    Set1,
+    /// Scancode Set 2, as used by the IBM PC/AT and PS/2 keyboards
+    Set2,
+}
+
+/// Hides the 'hidden' keys which Pause and Print Screen are made from.
+///
+/// [`KeyCode::RAlt2`](crate::KeyCode::RAlt2) events are dropped. A
+/// [`KeyCode::RControl2`](crate::KeyCode::RControl2) press marks the start
+/// of a Pause sequence, which comes out as a single
+/// [`KeyCode::PauseBreak`](crate::KeyCode::PauseBreak) event once the Num
+/// Lock release at the end of it arrives.
+#[derive(Debug, Default)]
+pub(crate) struct PseudoKeyFilter {
+    in_pause: bool,
+}
+
+impl PseudoKeyFilter {
+    pub(crate) const fn new() -> PseudoKeyFilter {
+        PseudoKeyFilter { in_pause: false }
+    }
+
+    /// Filter the result of one byte
+    pub(crate) fn filter(
+        &mut self,
+        item: Option<crate::DecodedItem>,
+    ) -> Option<crate::DecodedItem> {
+        use crate::{DecodedItem, KeyCode, KeyEvent, KeyState};
+        let ev = match item {
+            Some(DecodedItem::Key(ev)) => ev,
+            other => return other,
+        };
+        match ev.code {
+            KeyCode::RAlt2 => None,
+            KeyCode::RControl2 => {
+                if ev.state == KeyState::Down {
+                    self.in_pause = true;
+                }
+                None
+            }
+            KeyCode::NumpadLock if self.in_pause => {
+                if ev.state == KeyState::Up {
+                    self.in_pause = false;
+                    Some(DecodedItem::Key(KeyEvent::new(
+                        KeyCode::PauseBreak,
+                        KeyState::SingleShot,
+                    )))
+                } else {
+                    None
+                }
+            }
+            _ => Some(DecodedItem::Key(ev)),
+        }
+    }
+
+    /// Forget any Pause sequence we were part-way through
+    pub(crate) fn reset(&mut self) {
+        self.in_pause = false;
+    }
+}
+
+/// Run a fresh decoder over a complete scancode sequence.
+///
+/// Exactly one event must come out, on the very last byte.
+pub(crate) fn decode_sequence<S: crate::ScancodeSet>(
+    mut set: S,
+    bytes: &[u8],
+) -> Result<crate::DecodedItem, crate::Error> {
+    let mut iter = bytes.iter();
+    while let Some(byte) = iter.next() {
+        if let Some(event) = set.advance_state(*byte)? {
+            if iter.len() != 0 {
+                return Err(crate::Error::TrailingBytes);
+            }
+            return Ok(event);
+        }
+    }
+    Err(crate::Error::Incomplete)
+}
@@ -1,15 +1,33 @@
 //! Scan Code Set 1 support
 
+use super::{PseudoKeyFilter, ScancodeBytes};
 use crate::{
-    DecodeState, Error, KeyCode, KeyEvent, KeyState, ScancodeSet, EXTENDED2_KEY_CODE,
-    EXTENDED_KEY_CODE,
+    protocol, DecodeState, DecodedItem, Error, KeyCode, KeyEvent, KeyState, ProtocolEvent,
+    ScancodeSet, ScancodeSetKind, EXTENDED2_KEY_CODE, EXTENDED_KEY_CODE, HANGUL_KEY_CODE,
+    HANJA_KEY_CODE,
 };
 
+/// Sent by the keyboard when its buffer overflows, or it can't tell which
+/// keys are held down
+const OVERRUN_CODE: u8 = 0xFF;
+
+/// Sent by the keyboard when it has an internal error (e.g. it can't tell
+/// which keys are held down)
+const ERROR_CODE: u8 = 0x00;
+
+/// The extended codes for the fake Left Shift and Right Shift (make and
+/// break) which an i8042 sends around the navigation keys
+const FAKE_SHIFT_CODES: [u8; 4] = [0x2A, 0xAA, 0x36, 0xB6];
+
 /// Contains the implementation of Scancode Set 1.
 ///
 /// See the OS dev wiki: <https://wiki.osdev.org/PS/2_Keyboard#Scan_Code_Set_1>
 pub struct ScancodeSet1 {
     state: DecodeState,
+    pseudo_keys: Option<PseudoKeyFilter>,
+    protocol_aware: bool,
+    filter_fake_shifts: bool,
+    jis_releases: bool,
 }
 
 impl ScancodeSet1 {
@@ -17,6 +35,121 @@ impl ScancodeSet1 {
     pub const fn new() -> ScancodeSet1 {
         ScancodeSet1 {
             state: DecodeState::Start,
+            pseudo_keys: None,
+            protocol_aware: false,
+            filter_fake_shifts: false,
+            jis_releases: false,
+        }
+    }
+
+    /// Construct a new [`ScancodeSet1`] decoder which drops fake shifts.
+    ///
+    /// When Num Lock is on, or Shift is held down, a keyboard sends a fake
+    /// Shift press or release (`E0 2A`, `E0 AA`, `E0 36` or `E0 B6`) around
+    /// the keys in the navigation cluster, so that they look like the
+    /// keypad keys they used to share. This decoder swallows those
+    /// sequences, and only gives you the navigation key itself.
+    pub const fn new_filtering_fake_shifts() -> ScancodeSet1 {
+        ScancodeSet1 {
+            state: DecodeState::Start,
+            pseudo_keys: None,
+            protocol_aware: false,
+            filter_fake_shifts: true,
+            jis_releases: false,
+        }
+    }
+
+    /// Construct a new [`ScancodeSet1`] decoder which hides the 'hidden'
+    /// keys, [`KeyCode::RControl2`] and [`KeyCode::RAlt2`].
+    ///
+    /// The Pause sequence (`E1 1D 45 E1 9D C5`) comes out as a single
+    /// [`KeyCode::PauseBreak`] [`KeyState::SingleShot`] event, on its last
+    /// byte, and the `E0 2A` before Print Screen (and the `E0 AA` after it,
+    /// on release) is dropped, so you only get Print Screen Down and Up.
+    pub const fn new_hiding_pseudo_keys() -> ScancodeSet1 {
+        ScancodeSet1 {
+            state: DecodeState::Start,
+            pseudo_keys: Some(PseudoKeyFilter::new()),
+            protocol_aware: false,
+            filter_fake_shifts: false,
+            jis_releases: false,
+        }
+    }
+
+    /// Construct a new [`ScancodeSet1`] decoder which understands command
+    /// responses.
+    ///
+    /// `FA` (ACK), `FE` (Resend) and `EE` (Echo) give you a
+    /// [`ProtocolEvent::CommandResponse`], whenever they arrive - even in
+    /// the middle of a multi-byte scancode sequence, which then carries on
+    /// as if they weren't there. `FE` is also the break code for [`KeyCode::NumpadComma`], so you
+    /// can't get that key release in this mode.
+    pub const fn new_protocol_aware() -> ScancodeSet1 {
+        let mut set = ScancodeSet1::new();
+        set.protocol_aware = true;
+        set
+    }
+
+    /// Construct a new [`ScancodeSet1`] decoder which reads `FD` and `FE` as
+    /// key releases.
+    ///
+    /// `FD` and `FE` are the break codes for [`KeyCode::Oem13`] (the JIS Yen
+    /// key) and [`KeyCode::NumpadComma`] (on JIS and Brazilian ABNT2
+    /// keyboards), but a keyboard also sends them to say its self test failed
+    /// or to ask for a command again. Normally they give you a
+    /// [`ProtocolEvent`]. Use this decoder if you have one of those keys, and
+    /// don't need the protocol events.
+    pub const fn new_decoding_jis_releases() -> ScancodeSet1 {
+        let mut set = ScancodeSet1::new();
+        set.jis_releases = true;
+        set
+    }
+
+    /// Decode a single, complete, scancode sequence.
+    ///
+    /// Returns [`Error::Incomplete`] if the bytes run out before a key event
+    /// is produced, and [`Error::TrailingBytes`] if there are bytes left over
+    /// after it.
+    pub fn decode_sequence(bytes: &[u8]) -> Result<DecodedItem, Error> {
+        super::decode_sequence(ScancodeSet1::new(), bytes)
+    }
+
+    /// Encode a key event as the bytes a keyboard would send for it.
+    ///
+    /// This is the reverse of decoding, and uses the same tables. Print
+    /// Screen and Pause give you their whole sequences (Pause has no break
+    /// code, so releasing it gives you no bytes at all). Hangul and Hanja
+    /// only have a [`KeyState::SingleShot`] event, and no other key does.
+    ///
+    /// Returns `None` if there is no such event in Scancode Set 1 (which
+    /// includes the codes for [`ProtocolEvent`]s).
+    pub fn encode(code: KeyCode, state: KeyState) -> Option<ScancodeBytes> {
+        let bytes = |bytes: &[u8]| Some(ScancodeBytes::from_slice(bytes));
+        match (code, state) {
+            (KeyCode::PrintScreen, KeyState::Down) => bytes(&[0xE0, 0x2A, 0xE0, 0x37]),
+            (KeyCode::PrintScreen, KeyState::Up) => bytes(&[0xE0, 0xB7, 0xE0, 0xAA]),
+            (KeyCode::PauseBreak, KeyState::Down | KeyState::SingleShot) => {
+                bytes(&[0xE1, 0x1D, 0x45, 0xE1, 0x9D, 0xC5])
+            }
+            (KeyCode::PauseBreak, KeyState::Up) => bytes(&[]),
+            (KeyCode::Hangul, KeyState::SingleShot) => bytes(&[HANGUL_KEY_CODE]),
+            (KeyCode::Hanja, KeyState::SingleShot) => bytes(&[HANJA_KEY_CODE]),
+            (KeyCode::Hangul | KeyCode::Hanja, _) | (_, KeyState::SingleShot) => None,
+            (code, state) => {
+                let release = if state == KeyState::Up { 0x80 } else { 0x00 };
+                let find = |map: fn(u8) -> Result<KeyCode, Error>| {
+                    (0x00..0x80).find(|byte| map(*byte) == Ok(code))
+                };
+                if let Some(byte) = find(Self::map_scancode) {
+                    bytes(&[byte | release])
+                } else if let Some(byte) = find(Self::map_extended_scancode) {
+                    bytes(&[EXTENDED_KEY_CODE, byte | release])
+                } else if let Some(byte) = find(Self::map_extended2_scancode) {
+                    bytes(&[EXTENDED2_KEY_CODE, byte | release])
+                } else {
+                    None
+                }
+            }
         }
     }
 
@@ -111,6 +244,12 @@ impl ScancodeSet1 {
             0x56 => Ok(KeyCode::Oem5),
             0x57 => Ok(KeyCode::F11),
             0x58 => Ok(KeyCode::F12),
+            0x70 => Ok(KeyCode::Oem11),
+            0x73 => Ok(KeyCode::Oem12),
+            0x79 => Ok(KeyCode::Oem10),
+            0x7B => Ok(KeyCode::Oem9),
+            0x7D => Ok(KeyCode::Oem13),
+            0x7E => Ok(KeyCode::NumpadComma),
             _ => Err(Error::UnknownKeyCode),
         }
     }
@@ -172,7 +311,8 @@ impl ScancodeSet1 {
             //0x43
             //0x44
             //0x45
-            //0x46
+            // Break (Ctrl + Pause)
+            0x46 => Ok(KeyCode::PauseBreak),
             0x47 => Ok(KeyCode::Home),
             0x48 => Ok(KeyCode::ArrowUp),
             0x49 => Ok(KeyCode::PageUp),
@@ -189,27 +329,22 @@ impl ScancodeSet1 {
             0x5B => Ok(KeyCode::LWin),
             0x5C => Ok(KeyCode::RWin),
             0x5D => Ok(KeyCode::Apps),
-            // 0x5E ACPI Power
-            // 0x5F ACPI Sleep
+            0x5E => Ok(KeyCode::Power),
+            0x5F => Ok(KeyCode::Sleep),
             // 0x60
             // 0x61
             // 0x62
-            // 0x63 ACPI Wake
+            0x63 => Ok(KeyCode::Wake),
             // 0x64
-            // 0x65 WWW Search
-            // 0x66 WWW Favourites
-            // 0x67 WWW Refresh
-            // 0x68 WWW Stop
-            // 0x69 WWW Forward
-            // 0x6A WWW Back
-            // 0x6B My Computer
-            // 0x6C Email
-            // 0x6D Media Select
-            0x70 => Ok(KeyCode::Oem11),
-            0x73 => Ok(KeyCode::Oem12),
-            0x79 => Ok(KeyCode::Oem10),
-            0x7B => Ok(KeyCode::Oem9),
-            0x7D => Ok(KeyCode::Oem13),
+            0x65 => Ok(KeyCode::WWWSearch),
+            0x66 => Ok(KeyCode::WWWFavourites),
+            0x67 => Ok(KeyCode::WWWRefresh),
+            0x68 => Ok(KeyCode::WWWStop),
+            0x69 => Ok(KeyCode::WWWForward),
+            0x6A => Ok(KeyCode::WWWBack),
+            0x6B => Ok(KeyCode::MyComputer),
+            0x6C => Ok(KeyCode::Email),
+            0x6D => Ok(KeyCode::MediaSelect),
             _ => Err(Error::UnknownKeyCode),
         }
     }
@@ -229,17 +364,82 @@ impl ScancodeSet for ScancodeSet1 {
     /// ## Start:
     /// * `E0` => Goto Extended
     /// * `E1` => Goto Extended 2
+    /// * `FF`, `00` => Too Many Keys
+    /// * `F1`, `F2` => Hanja and Hangul (Single Shot, as these keys have no
+    ///   break code)
+    /// * `FC`, `FD` => Power On Test Failed
+    /// * `EE`, `FA`, `FE` => Command Response (these are not break codes)
     /// * `< 0x80` => Key Down
     /// * `>= 0x80` => Key Up
     ///
+    /// Note that `AA` (self test passed) and `AB` (the first byte of the
+    /// keyboard ID) are also the break codes for [`KeyCode::LShift`] and [`KeyCode::Oem7`], so
+    /// they are decoded as Key Up. Use
+    /// [`Keyboard::expect_response`](crate::Keyboard::expect_response) if you
+    /// have asked for them. In particular, the keyboard's answer to the
+    /// Identify command (e.g. `AB 41`) only gives you a
+    /// [`ProtocolEvent::DeviceId`] that way.
+    ///
+    /// `FD` and `FE` are also the break codes for [`KeyCode::Oem13`] and
+    /// [`KeyCode::NumpadComma`], but they are only decoded as Key Up by
+    /// [`ScancodeSet1::new_decoding_jis_releases`].
+    ///
     /// ## Extended:
+    /// * `2A`, `AA`, `36`, `B6` => Nothing, if filtering fake shifts (see
+    ///   [`ScancodeSet1::new_filtering_fake_shifts`])
     /// * `< 0x80` => Extended Key Down
     /// * `>= 0x80` => Extended Key Up
     ///
     /// ## Extended 2:
     /// * `< 0x80` => Extended 2 Key Down
     /// * `>= 0x80` => Extended 2 Key Up
-    fn advance_state(&mut self, code: u8) -> Result<Option<KeyEvent>, Error> {
+    fn advance_state(&mut self, code: u8) -> Result<Option<DecodedItem>, Error> {
+        let item = self.decode_byte(code)?;
+        match &mut self.pseudo_keys {
+            Some(filter) => Ok(filter.filter(item)),
+            None => Ok(item),
+        }
+    }
+
+    fn kind(&self) -> ScancodeSetKind {
+        ScancodeSetKind::Set1
+    }
+
+    fn reset(&mut self) {
+        self.state = DecodeState::Start;
+        if let Some(filter) = &mut self.pseudo_keys {
+            filter.reset();
+        }
+    }
+}
+
+impl ScancodeSet1 {
+    /// Decode one byte, before any pseudo keys are hidden.
+    fn decode_byte(&mut self, code: u8) -> Result<Option<DecodedItem>, Error> {
+        if self.protocol_aware {
+            if let Some(response) = protocol::parse_command_response(code) {
+                // Leave any sequence we're part-way through alone
+                return Ok(Some(DecodedItem::Protocol(ProtocolEvent::CommandResponse(
+                    response,
+                ))));
+            }
+        }
+        if self.state != DecodeState::Start {
+            // A prefix can never appear inside a sequence, so the keyboard
+            // (or controller) must have dropped or interleaved some bytes.
+            // Start again from the new prefix.
+            match code {
+                EXTENDED_KEY_CODE => {
+                    self.state = DecodeState::Extended;
+                    return Err(Error::InterruptedSequence);
+                }
+                EXTENDED2_KEY_CODE => {
+                    self.state = DecodeState::Extended2;
+                    return Err(Error::InterruptedSequence);
+                }
+                _ => {}
+            }
+        }
         match self.state {
             DecodeState::Start => {
                 match code {
@@ -251,38 +451,67 @@ impl ScancodeSet for ScancodeSet1 {
                         self.state = DecodeState::Extended2;
                         Ok(None)
                     }
+                    OVERRUN_CODE | ERROR_CODE => {
+                        Ok(Some(DecodedItem::Protocol(ProtocolEvent::TooManyKeys)))
+                    }
+                    HANGUL_KEY_CODE => Ok(Some(DecodedItem::Key(KeyEvent::new(
+                        KeyCode::Hangul,
+                        KeyState::SingleShot,
+                    )))),
+                    HANJA_KEY_CODE => Ok(Some(DecodedItem::Key(KeyEvent::new(
+                        KeyCode::Hanja,
+                        KeyState::SingleShot,
+                    )))),
+                    protocol::SELF_TEST_FAILED => Ok(Some(DecodedItem::Protocol(
+                        ProtocolEvent::PowerOnTestFailed,
+                    ))),
+                    protocol::SELF_TEST_FAILED2 if !self.jis_releases => Ok(Some(
+                        DecodedItem::Protocol(ProtocolEvent::PowerOnTestFailed),
+                    )),
+                    protocol::RESEND if !self.jis_releases => Ok(Some(DecodedItem::Protocol(
+                        ProtocolEvent::CommandResponse(protocol::Response::Resend),
+                    ))),
+                    // Command responses. There is no key with the matching
+                    // make code, so these can't be break codes.
+                    protocol::ACK => Ok(Some(DecodedItem::Protocol(
+                        ProtocolEvent::CommandResponse(protocol::Response::Ack),
+                    ))),
+                    protocol::ECHO => Ok(Some(DecodedItem::Protocol(
+                        ProtocolEvent::CommandResponse(protocol::Response::Echo),
+                    ))),
                     0x80..=0xFF => {
                         // Break codes
-                        Ok(Some(KeyEvent::new(
+                        Ok(Some(DecodedItem::Key(KeyEvent::new(
                             Self::map_scancode(code - 0x80)?,
                             KeyState::Up,
-                        )))
+                        ))))
                     }
                     _ => {
                         // Make codes
-                        Ok(Some(KeyEvent::new(
+                        Ok(Some(DecodedItem::Key(KeyEvent::new(
                             Self::map_scancode(code)?,
                             KeyState::Down,
-                        )))
+                        ))))
                     }
                 }
             }
             DecodeState::Extended => {
                 self.state = DecodeState::Start;
                 match code {
+                    _ if self.filter_fake_shifts && FAKE_SHIFT_CODES.contains(&code) => Ok(None),
                     0x80..=0xFF => {
                         // Extended break codes
-                        Ok(Some(KeyEvent::new(
+                        Ok(Some(DecodedItem::Key(KeyEvent::new(
                             Self::map_extended_scancode(code - 0x80)?,
                             KeyState::Up,
-                        )))
+                        ))))
                     }
                     _ => {
                         // Extended make codes
-                        Ok(Some(KeyEvent::new(
+                        Ok(Some(DecodedItem::Key(KeyEvent::new(
                             Self::map_extended_scancode(code)?,
                             KeyState::Down,
-                        )))
+                        ))))
                     }
                 }
             }
@@ -291,17 +520,17 @@ impl ScancodeSet for ScancodeSet1 {
                 match code {
                     0x80..=0xFF => {
                         // Extended 2 break codes
-                        Ok(Some(KeyEvent::new(
+                        Ok(Some(DecodedItem::Key(KeyEvent::new(
                             Self::map_extended2_scancode(code - 0x80)?,
                             KeyState::Up,
-                        )))
+                        ))))
                     }
                     _ => {
                         // Extended 2 make codes
-                        Ok(Some(KeyEvent::new(
+                        Ok(Some(DecodedItem::Key(KeyEvent::new(
                             Self::map_extended2_scancode(code)?,
                             KeyState::Down,
-                        )))
+                        ))))
                     }
                 }
             }
@@ -336,7 +565,409 @@ mod test {
         }
         codes.sort();
         println!("{:?}", codes);
-        assert_eq!(codes.len(), 87);
-        assert_eq!(errs.len(), 41);
+        assert_eq!(codes.len(), 93);
+        assert_eq!(errs.len(), 35);
+    }
+
+    #[test]
+    fn validate_extended_scancodes() {
+        let mut codes = Vec::new();
+        for code in 0x00..=0x7F {
+            if let Ok(c) = ScancodeSet1::map_extended_scancode(code) {
+                codes.push(c);
+            }
+        }
+        codes.sort();
+        codes.dedup();
+        // The same extended keys as the other Scan Code Set
+        assert_eq!(codes.len(), 41);
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let mut count = 0;
+        for code in KeyCode::ALL {
+            if matches!(code, KeyCode::PrintScreen | KeyCode::PauseBreak) {
+                // These are several events in one
+                continue;
+            }
+            for state in [KeyState::Down, KeyState::Up, KeyState::SingleShot] {
+                if let Some(bytes) = ScancodeSet1::encode(code, state) {
+                    // Oem13 and NumpadComma releases clash with protocol bytes
+                    let decoder = ScancodeSet1::new_decoding_jis_releases();
+                    assert_eq!(
+                        super::super::decode_sequence(decoder, &bytes),
+                        Ok(DecodedItem::Key(KeyEvent::new(code, state))),
+                        "{:?} {:?}",
+                        code,
+                        state
+                    );
+                    count += 1;
+                }
+            }
+        }
+        // Every key in the tables (twice), and Hangul and Hanja (once)
+        assert_eq!(count, 268);
+        assert_eq!(ScancodeSet1::encode(KeyCode::A, KeyState::SingleShot), None);
+        assert_eq!(ScancodeSet1::encode(KeyCode::Hangul, KeyState::Down), None);
+        assert_eq!(
+            ScancodeSet1::encode(KeyCode::TooManyKeys, KeyState::Down),
+            None
+        );
+    }
+
+    #[test]
+    fn test_encode_sequences() {
+        assert_eq!(
+            ScancodeSet1::encode(KeyCode::A, KeyState::Down).unwrap(),
+            [0x1E]
+        );
+        assert_eq!(
+            ScancodeSet1::encode(KeyCode::A, KeyState::Up).unwrap(),
+            [0x9E]
+        );
+        assert_eq!(
+            ScancodeSet1::encode(KeyCode::ArrowUp, KeyState::Up).unwrap(),
+            [0xE0, 0xC8]
+        );
+        assert_eq!(
+            ScancodeSet1::encode(KeyCode::RControl2, KeyState::Down).unwrap(),
+            [0xE1, 0x1D]
+        );
+        assert_eq!(
+            ScancodeSet1::encode(KeyCode::PauseBreak, KeyState::Up).unwrap(),
+            []
+        );
+        // The whole sequences decode as one event, if you hide the pseudo keys
+        let mut set = ScancodeSet1::new_hiding_pseudo_keys();
+        for (code, state, expected) in [
+            (KeyCode::PrintScreen, KeyState::Down, KeyState::Down),
+            (KeyCode::PrintScreen, KeyState::Up, KeyState::Up),
+            (KeyCode::PauseBreak, KeyState::Down, KeyState::SingleShot),
+        ] {
+            let bytes = ScancodeSet1::encode(code, state).unwrap();
+            let events: Vec<_> = bytes
+                .iter()
+                .filter_map(|b| set.advance_state(*b).unwrap())
+                .collect();
+            assert_eq!(events, [DecodedItem::Key(KeyEvent::new(code, expected))]);
+        }
+    }
+
+    #[test]
+    fn test_decode_sequence() {
+        assert_eq!(
+            ScancodeSet1::decode_sequence(&[0x1E]),
+            Ok(DecodedItem::Key(KeyEvent::new(KeyCode::A, KeyState::Down)))
+        );
+        assert_eq!(
+            ScancodeSet1::decode_sequence(&[0x9E]),
+            Ok(DecodedItem::Key(KeyEvent::new(KeyCode::A, KeyState::Up)))
+        );
+        assert_eq!(
+            ScancodeSet1::decode_sequence(&[0xE0, 0x48]),
+            Ok(DecodedItem::Key(KeyEvent::new(
+                KeyCode::ArrowUp,
+                KeyState::Down
+            )))
+        );
+        assert_eq!(
+            ScancodeSet1::decode_sequence(&[0xE0, 0xC8]),
+            Ok(DecodedItem::Key(KeyEvent::new(
+                KeyCode::ArrowUp,
+                KeyState::Up
+            )))
+        );
+        assert_eq!(
+            ScancodeSet1::decode_sequence(&[0xE1, 0x1D]),
+            Ok(DecodedItem::Key(KeyEvent::new(
+                KeyCode::RControl2,
+                KeyState::Down
+            )))
+        );
+        // Pause is several key events in one
+        assert_eq!(
+            ScancodeSet1::decode_sequence(&[0xE1, 0x1D, 0x45, 0xE1, 0x9D, 0xC5]),
+            Err(Error::TrailingBytes)
+        );
+    }
+
+    #[test]
+    fn test_acpi_keys() {
+        for (code, make, brk) in [
+            (KeyCode::Power, 0x5E, 0xDE),
+            (KeyCode::Sleep, 0x5F, 0xDF),
+            (KeyCode::Wake, 0x63, 0xE3),
+        ] {
+            assert_eq!(
+                ScancodeSet1::decode_sequence(&[0xE0, make]),
+                Ok(DecodedItem::Key(KeyEvent::new(code, KeyState::Down)))
+            );
+            assert_eq!(
+                ScancodeSet1::decode_sequence(&[0xE0, brk]),
+                Ok(DecodedItem::Key(KeyEvent::new(code, KeyState::Up)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_browser_keys() {
+        for (code, make, brk) in [(KeyCode::WWWBack, 0x6A, 0xEA), (KeyCode::Email, 0x6C, 0xEC)] {
+            assert_eq!(
+                ScancodeSet1::decode_sequence(&[0xE0, make]),
+                Ok(DecodedItem::Key(KeyEvent::new(code, KeyState::Down)))
+            );
+            assert_eq!(
+                ScancodeSet1::decode_sequence(&[0xE0, brk]),
+                Ok(DecodedItem::Key(KeyEvent::new(code, KeyState::Up)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_ctrl_break() {
+        // Ctrl + Pause sends Break, which has a make and a break code
+        let mut set = ScancodeSet1::new();
+        let results: Vec<_> = [0xE0, 0x46, 0xE0, 0xC6]
+            .iter()
+            .map(|b| set.advance_state(*b))
+            .collect();
+        assert_eq!(
+            results,
+            [
+                Ok(None),
+                Ok(Some(DecodedItem::Key(KeyEvent::new(
+                    KeyCode::PauseBreak,
+                    KeyState::Down
+                )))),
+                Ok(None),
+                Ok(Some(DecodedItem::Key(KeyEvent::new(
+                    KeyCode::PauseBreak,
+                    KeyState::Up
+                )))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_break_codes() {
+        for code in 0x80..=0xFF {
+            let result = ScancodeSet1::decode_sequence(&[code]);
+            match code {
+                0xE0 | 0xE1 => assert_eq!(result, Err(Error::Incomplete)),
+                0xFF => assert_eq!(
+                    result,
+                    Ok(DecodedItem::Protocol(ProtocolEvent::TooManyKeys))
+                ),
+                0xEE | 0xFA | 0xFE => assert_eq!(
+                    result,
+                    Ok(DecodedItem::Protocol(ProtocolEvent::CommandResponse(
+                        protocol::parse_response(code).unwrap()
+                    ))),
+                    "{:02x}",
+                    code
+                ),
+                0xFC | 0xFD => assert_eq!(
+                    result,
+                    Ok(DecodedItem::Protocol(ProtocolEvent::PowerOnTestFailed))
+                ),
+                0xF1 => assert_eq!(
+                    result,
+                    Ok(DecodedItem::Key(KeyEvent::new(
+                        KeyCode::Hanja,
+                        KeyState::SingleShot
+                    )))
+                ),
+                0xF2 => assert_eq!(
+                    result,
+                    Ok(DecodedItem::Key(KeyEvent::new(
+                        KeyCode::Hangul,
+                        KeyState::SingleShot
+                    )))
+                ),
+                _ => match ScancodeSet1::map_scancode(code - 0x80) {
+                    Ok(keycode) => assert_eq!(
+                        result,
+                        Ok(DecodedItem::Key(KeyEvent::new(keycode, KeyState::Up))),
+                        "{:02x}",
+                        code
+                    ),
+                    Err(_) => assert_eq!(result, Err(Error::UnknownKeyCode), "{:02x}", code),
+                },
+            }
+        }
+        // The highest break code for a real key
+        assert_eq!(
+            ScancodeSet1::decode_sequence(&[0xD8]),
+            Ok(DecodedItem::Key(KeyEvent::new(KeyCode::F12, KeyState::Up)))
+        );
+        // These double as protocol bytes, but are real break codes too
+        assert_eq!(
+            ScancodeSet1::decode_sequence(&[0xAA]),
+            Ok(DecodedItem::Key(KeyEvent::new(
+                KeyCode::LShift,
+                KeyState::Up
+            )))
+        );
+        assert_eq!(
+            ScancodeSet1::decode_sequence(&[0xAB]),
+            Ok(DecodedItem::Key(KeyEvent::new(KeyCode::Oem7, KeyState::Up)))
+        );
+    }
+
+    #[test]
+    fn test_protocol_bytes() {
+        assert_eq!(
+            ScancodeSet1::decode_sequence(&[0xFC]),
+            Ok(DecodedItem::Protocol(ProtocolEvent::PowerOnTestFailed))
+        );
+        // A Device ID can't be told apart from two break codes, unless you
+        // use Keyboard::expect_response
+        let mut set = ScancodeSet1::new();
+        assert_eq!(
+            set.advance_state(0xAB),
+            Ok(Some(DecodedItem::Key(KeyEvent::new(
+                KeyCode::Oem7,
+                KeyState::Up
+            ))))
+        );
+        assert_eq!(
+            set.advance_state(0x83),
+            Ok(Some(DecodedItem::Key(KeyEvent::new(
+                KeyCode::Key2,
+                KeyState::Up
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_protocol_aware() {
+        let ack = Ok(Some(DecodedItem::Protocol(ProtocolEvent::CommandResponse(
+            protocol::Response::Ack,
+        ))));
+        let mut set = ScancodeSet1::new_protocol_aware();
+        // An ACK in the middle of Arrow Up
+        assert_eq!(set.advance_state(0xE0), Ok(None));
+        assert_eq!(set.advance_state(0xFA), ack);
+        assert_eq!(
+            set.advance_state(0x48),
+            Ok(Some(DecodedItem::Key(KeyEvent::new(
+                KeyCode::ArrowUp,
+                KeyState::Down
+            ))))
+        );
+        assert_eq!(
+            set.advance_state(0xFE),
+            Ok(Some(DecodedItem::Protocol(ProtocolEvent::CommandResponse(
+                protocol::Response::Resend
+            ))))
+        );
+        assert_eq!(
+            set.advance_state(0xEE),
+            Ok(Some(DecodedItem::Protocol(ProtocolEvent::CommandResponse(
+                protocol::Response::Echo
+            ))))
+        );
+        // Otherwise, FA is only an ACK between sequences
+        let mut set = ScancodeSet1::new();
+        assert_eq!(set.advance_state(0xFA), ack);
+        assert_eq!(set.advance_state(0xE0), Ok(None));
+        assert_eq!(set.advance_state(0xFA), Err(Error::UnknownKeyCode));
+    }
+
+    #[test]
+    fn test_overrun() {
+        for code in [0xFF, 0x00] {
+            let mut set = ScancodeSet1::new();
+            assert_eq!(
+                set.advance_state(code),
+                Ok(Some(DecodedItem::Protocol(ProtocolEvent::TooManyKeys))),
+                "{:02x}",
+                code
+            );
+            assert!(set.state == DecodeState::Start);
+            // Nothing is left pending
+            assert_eq!(
+                set.advance_state(0x1E),
+                Ok(Some(DecodedItem::Key(KeyEvent::new(
+                    KeyCode::A,
+                    KeyState::Down
+                ))))
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_sequence_malformed() {
+        assert_eq!(ScancodeSet1::decode_sequence(&[]), Err(Error::Incomplete));
+        assert_eq!(
+            ScancodeSet1::decode_sequence(&[0xE0]),
+            Err(Error::Incomplete)
+        );
+        assert_eq!(
+            ScancodeSet1::decode_sequence(&[0xE1]),
+            Err(Error::Incomplete)
+        );
+        assert_eq!(
+            ScancodeSet1::decode_sequence(&[0xE0, 0x00]),
+            Err(Error::UnknownKeyCode)
+        );
+        assert_eq!(
+            ScancodeSet1::decode_sequence(&[0x1E, 0x1E]),
+            Err(Error::TrailingBytes)
+        );
+    }
+
+    /// Run every byte through the decoder, collecting the key events
+    fn replay(set: &mut ScancodeSet1, bytes: &[u8]) -> Vec<KeyEvent> {
+        let mut events = Vec::new();
+        for byte in bytes {
+            if let Some(DecodedItem::Key(ev)) = set.advance_state(*byte).unwrap() {
+                events.push(ev);
+            }
+        }
+        events
+    }
+
+    #[test]
+    fn test_fake_shifts() {
+        // Insert, then Left Arrow, with Num Lock on
+        let bytes = [
+            0xE0, 0x2A, 0xE0, 0x52, 0xE0, 0xD2, 0xE0, 0xAA, // Insert
+            0xE0, 0x2A, 0xE0, 0x4B, 0xE0, 0xCB, 0xE0, 0xAA, // Left Arrow
+        ];
+        let mut set = ScancodeSet1::new_filtering_fake_shifts();
+        assert_eq!(
+            replay(&mut set, &bytes),
+            [
+                KeyEvent::new(KeyCode::Insert, KeyState::Down),
+                KeyEvent::new(KeyCode::Insert, KeyState::Up),
+                KeyEvent::new(KeyCode::ArrowLeft, KeyState::Down),
+                KeyEvent::new(KeyCode::ArrowLeft, KeyState::Up),
+            ]
+        );
+        // Left Arrow with Right Shift held down, which releases the shift
+        // first and presses it again afterwards
+        let bytes = [0x36, 0xE0, 0xB6, 0xE0, 0x4B, 0xE0, 0xCB, 0xE0, 0x36, 0xB6];
+        assert_eq!(
+            replay(&mut set, &bytes),
+            [
+                KeyEvent::new(KeyCode::RShift, KeyState::Down),
+                KeyEvent::new(KeyCode::ArrowLeft, KeyState::Down),
+                KeyEvent::new(KeyCode::ArrowLeft, KeyState::Up),
+                KeyEvent::new(KeyCode::RShift, KeyState::Up),
+            ]
+        );
+        // Without the filter, they come through
+        let mut set = ScancodeSet1::new();
+        assert_eq!(
+            replay(&mut set, &[0xE0, 0x2A, 0xE0, 0x52]),
+            [
+                KeyEvent::new(KeyCode::RAlt2, KeyState::Down),
+                KeyEvent::new(KeyCode::Insert, KeyState::Down),
+            ]
+        );
+        assert_eq!(set.advance_state(0xE0), Ok(None));
+        assert_eq!(set.advance_state(0x36), Err(Error::UnknownKeyCode));
     }
 }
@@ -1,16 +1,25 @@
 //! Scan Code Set 2 support
 
+use super::{PseudoKeyFilter, ScancodeBytes};
 use crate::{
-    DecodeState, Error, KeyCode, KeyEvent, KeyState, ScancodeSet, EXTENDED2_KEY_CODE,
-    EXTENDED_KEY_CODE, KEY_RELEASE_CODE,
+    protocol, DecodeState, DecodedItem, Error, KeyCode, KeyEvent, KeyState, ProtocolEvent,
+    ScancodeSet, ScancodeSetKind, EXTENDED2_KEY_CODE, EXTENDED_KEY_CODE, HANGUL_KEY_CODE,
+    HANJA_KEY_CODE, KEY_RELEASE_CODE,
 };
 
+/// The first byte of most keyboards' answer to the Identify command
+const DEVICE_ID_CODE: u8 = 0xAB;
+/// The first byte of the NCD Sun keyboard's answer to the Identify command
+const DEVICE_ID_CODE2: u8 = 0xAC;
+
 /// Contains the implementation of Scancode Set 2.
 ///
 /// See the OS dev wiki: <https://wiki.osdev.org/PS/2_Keyboard#Scan_Code_Set_2>
 /// Additional reference: <https://www.win.tue.nl/~aeb/linux/kbd/scancodes-10.html>
 pub struct ScancodeSet2 {
     state: DecodeState,
+    pseudo_keys: Option<PseudoKeyFilter>,
+    protocol_aware: bool,
 }
 
 impl ScancodeSet2 {
@@ -18,6 +27,101 @@ impl ScancodeSet2 {
     pub const fn new() -> ScancodeSet2 {
         ScancodeSet2 {
             state: DecodeState::Start,
+            pseudo_keys: None,
+            protocol_aware: false,
+        }
+    }
+
+    /// Construct a new [`ScancodeSet2`] decoder which hides the 'hidden'
+    /// keys, [`KeyCode::RControl2`] and [`KeyCode::RAlt2`].
+    ///
+    /// The Pause sequence (`E1 14 77 E1 F0 14 F0 77`) comes out as a single
+    /// [`KeyCode::PauseBreak`] [`KeyState::SingleShot`] event, on its last
+    /// byte, and the `E0 12` before Print Screen (and the `E0 F0 12` after
+    /// it, on release) is dropped, so you only get Print Screen Down and Up.
+    pub const fn new_hiding_pseudo_keys() -> ScancodeSet2 {
+        ScancodeSet2 {
+            state: DecodeState::Start,
+            pseudo_keys: Some(PseudoKeyFilter::new()),
+            protocol_aware: false,
+        }
+    }
+
+    /// Construct a new [`ScancodeSet2`] decoder which understands command
+    /// responses.
+    ///
+    /// `FA` (ACK), `FE` (Resend) and `EE` (Echo) give you a
+    /// [`ProtocolEvent::CommandResponse`], whenever they arrive - even in
+    /// the middle of a multi-byte scancode sequence, which then carries on
+    /// as if they weren't there. None of these are scancodes in Set 2.
+    pub const fn new_protocol_aware() -> ScancodeSet2 {
+        let mut set = ScancodeSet2::new();
+        set.protocol_aware = true;
+        set
+    }
+
+    /// Decode a single, complete, scancode sequence.
+    ///
+    /// Returns [`Error::Incomplete`] if the bytes run out before a key event
+    /// is produced, and [`Error::TrailingBytes`] if there are bytes left over
+    /// after it.
+    pub fn decode_sequence(bytes: &[u8]) -> Result<DecodedItem, Error> {
+        super::decode_sequence(ScancodeSet2::new(), bytes)
+    }
+
+    /// Encode a key event as the bytes a keyboard would send for it.
+    ///
+    /// This is the reverse of decoding, and uses the same tables. Print
+    /// Screen and Pause give you their whole sequences (Pause has no break
+    /// code, so releasing it gives you no bytes at all). Hangul and Hanja
+    /// only have a [`KeyState::SingleShot`] event, and no other key does.
+    ///
+    /// Returns `None` if there is no such event in Scancode Set 2 (which
+    /// includes the codes for [`ProtocolEvent`]s).
+    pub fn encode(code: KeyCode, state: KeyState) -> Option<ScancodeBytes> {
+        let bytes = |bytes: &[u8]| Some(ScancodeBytes::from_slice(bytes));
+        match (code, state) {
+            (KeyCode::PrintScreen, KeyState::Down) => bytes(&[0xE0, 0x12, 0xE0, 0x7C]),
+            (KeyCode::PrintScreen, KeyState::Up) => {
+                bytes(&[0xE0, KEY_RELEASE_CODE, 0x7C, 0xE0, KEY_RELEASE_CODE, 0x12])
+            }
+            (KeyCode::PauseBreak, KeyState::Down | KeyState::SingleShot) => bytes(&[
+                0xE1,
+                0x14,
+                0x77,
+                0xE1,
+                KEY_RELEASE_CODE,
+                0x14,
+                KEY_RELEASE_CODE,
+                0x77,
+            ]),
+            (KeyCode::PauseBreak, KeyState::Up) => bytes(&[]),
+            (KeyCode::Hangul, KeyState::SingleShot) => bytes(&[HANGUL_KEY_CODE]),
+            (KeyCode::Hanja, KeyState::SingleShot) => bytes(&[HANJA_KEY_CODE]),
+            (KeyCode::Hangul | KeyCode::Hanja, _) | (_, KeyState::SingleShot) => None,
+            // These are in the table, but they aren't keys
+            (KeyCode::TooManyKeys | KeyCode::PowerOnTestOk, _) => None,
+            (code, state) => {
+                let find = |map: fn(u8) -> Result<KeyCode, Error>| {
+                    (0x00..=0xFF).find(|byte| map(*byte) == Ok(code))
+                };
+                let (prefix, byte) = if let Some(byte) = find(Self::map_scancode) {
+                    (None, byte)
+                } else if let Some(byte) = find(Self::map_extended_scancode) {
+                    (Some(EXTENDED_KEY_CODE), byte)
+                } else {
+                    (
+                        Some(EXTENDED2_KEY_CODE),
+                        find(Self::map_extended2_scancode)?,
+                    )
+                };
+                match (prefix, state) {
+                    (None, KeyState::Up) => bytes(&[KEY_RELEASE_CODE, byte]),
+                    (None, _) => bytes(&[byte]),
+                    (Some(prefix), KeyState::Up) => bytes(&[prefix, KEY_RELEASE_CODE, byte]),
+                    (Some(prefix), _) => bytes(&[prefix, byte]),
+                }
+            }
         }
     }
 
@@ -101,6 +205,7 @@ impl ScancodeSet2 {
             0x6A => Ok(KeyCode::Oem13),
             0x6B => Ok(KeyCode::Numpad4),
             0x6C => Ok(KeyCode::Numpad7),
+            0x6D => Ok(KeyCode::NumpadComma),
             0x70 => Ok(KeyCode::Numpad0),
             0x71 => Ok(KeyCode::NumpadPeriod),
             0x72 => Ok(KeyCode::Numpad2),
@@ -118,6 +223,8 @@ impl ScancodeSet2 {
             0x7E => Ok(KeyCode::ScrollLock),
             0x7F => Ok(KeyCode::SysRq),
             0x83 => Ok(KeyCode::F7),
+            // Alt + Print Screen
+            0x84 => Ok(KeyCode::SysRq),
             0xAA => Ok(KeyCode::PowerOnTestOk),
             _ => Err(Error::UnknownKeyCode),
         }
@@ -126,23 +233,35 @@ impl ScancodeSet2 {
     /// Implements the extended byte codes for set 2 (prefixed with E0)
     fn map_extended_scancode(code: u8) -> Result<KeyCode, Error> {
         match code {
+            0x10 => Ok(KeyCode::WWWSearch),
             0x11 => Ok(KeyCode::RAltGr),
             0x12 => Ok(KeyCode::RAlt2),
             0x14 => Ok(KeyCode::RControl),
             0x15 => Ok(KeyCode::PrevTrack),
+            0x18 => Ok(KeyCode::WWWFavourites),
             0x1F => Ok(KeyCode::LWin),
+            0x20 => Ok(KeyCode::WWWRefresh),
             0x21 => Ok(KeyCode::VolumeDown),
             0x23 => Ok(KeyCode::Mute),
             0x27 => Ok(KeyCode::RWin),
+            0x28 => Ok(KeyCode::WWWStop),
             0x2B => Ok(KeyCode::Calculator),
             0x2F => Ok(KeyCode::Apps),
+            0x30 => Ok(KeyCode::WWWForward),
             0x32 => Ok(KeyCode::VolumeUp),
             0x34 => Ok(KeyCode::Play),
+            0x37 => Ok(KeyCode::Power),
+            0x38 => Ok(KeyCode::WWWBack),
             0x3A => Ok(KeyCode::WWWHome),
             0x3B => Ok(KeyCode::Stop),
+            0x3F => Ok(KeyCode::Sleep),
+            0x40 => Ok(KeyCode::MyComputer),
+            0x48 => Ok(KeyCode::Email),
             0x4A => Ok(KeyCode::NumpadDivide),
             0x4D => Ok(KeyCode::NextTrack),
+            0x50 => Ok(KeyCode::MediaSelect),
             0x5A => Ok(KeyCode::NumpadEnter),
+            0x5E => Ok(KeyCode::Wake),
             0x69 => Ok(KeyCode::End),
             0x6B => Ok(KeyCode::ArrowLeft),
             0x6C => Ok(KeyCode::Home),
@@ -154,10 +273,21 @@ impl ScancodeSet2 {
             0x7A => Ok(KeyCode::PageDown),
             0x7C => Ok(KeyCode::PrintScreen),
             0x7D => Ok(KeyCode::PageUp),
+            // Break (Ctrl + Pause), not Scroll Lock
+            0x7E => Ok(KeyCode::PauseBreak),
             _ => Err(Error::UnknownKeyCode),
         }
     }
 
+    /// Implements the single byte codes which aren't keys.
+    fn map_protocol_code(code: u8) -> Option<ProtocolEvent> {
+        match Self::map_scancode(code) {
+            Ok(KeyCode::TooManyKeys) => Some(ProtocolEvent::TooManyKeys),
+            Ok(KeyCode::PowerOnTestOk) => Some(ProtocolEvent::PowerOnTestOk),
+            _ => None,
+        }
+    }
+
     /// Implements the alternate extended byte codes for set 2 (prefixed with E1)
     fn map_extended2_scancode(code: u8) -> Result<KeyCode, Error> {
         match code {
@@ -174,6 +304,10 @@ impl ScancodeSet for ScancodeSet2 {
     /// * F0 => Goto Release
     /// * E0 => Goto Extended
     /// * E1 => Goto Extended2
+    /// * F1, F2 => Hanja and Hangul Single Shot Event (these keys have no
+    ///   break code)
+    /// * FC, FD => Power On Test Failed
+    /// * AB, AC => Goto Device ID
     /// * xx => Key Down Event
     ///
     /// ## Release:
@@ -192,7 +326,68 @@ impl ScancodeSet for ScancodeSet2 {
     ///
     /// ## Release-Extended2:
     /// * xxx => Extended2 Key Up Event
-    fn advance_state(&mut self, code: u8) -> Result<Option<KeyEvent>, Error> {
+    ///
+    /// ## Device ID:
+    /// * xx => Device ID (the answer to the Identify command)
+    ///
+    /// In any state but Start and Device ID, an `AA` or `00` abandons the
+    /// sequence, and gives Power On Test OK or Too Many Keys.
+    fn advance_state(&mut self, code: u8) -> Result<Option<DecodedItem>, Error> {
+        let item = self.decode_byte(code)?;
+        match &mut self.pseudo_keys {
+            Some(filter) => Ok(filter.filter(item)),
+            None => Ok(item),
+        }
+    }
+
+    fn kind(&self) -> ScancodeSetKind {
+        ScancodeSetKind::Set2
+    }
+
+    fn reset(&mut self) {
+        self.state = DecodeState::Start;
+        if let Some(filter) = &mut self.pseudo_keys {
+            filter.reset();
+        }
+    }
+}
+
+impl ScancodeSet2 {
+    /// Decode one byte, before any pseudo keys are hidden.
+    fn decode_byte(&mut self, code: u8) -> Result<Option<DecodedItem>, Error> {
+        if self.protocol_aware {
+            if let Some(response) = protocol::parse_command_response(code) {
+                // Leave any sequence we're part-way through alone
+                return Ok(Some(DecodedItem::Protocol(ProtocolEvent::CommandResponse(
+                    response,
+                ))));
+            }
+        }
+        if self.state != DecodeState::Start {
+            // A prefix can never appear inside a sequence, so the keyboard
+            // (or controller) must have dropped or interleaved some bytes.
+            // Start again from the new prefix.
+            match code {
+                EXTENDED_KEY_CODE => {
+                    self.state = DecodeState::Extended;
+                    return Err(Error::InterruptedSequence);
+                }
+                EXTENDED2_KEY_CODE => {
+                    self.state = DecodeState::Extended2;
+                    return Err(Error::InterruptedSequence);
+                }
+                _ => {}
+            }
+            // Nor can the self test result or an overrun, which mean the
+            // keyboard has been reset (e.g. hot-plugged) or has lost track.
+            // They aren't keys, so never give a key event for them.
+            if !matches!(self.state, DecodeState::DeviceId(_)) {
+                if let Some(event) = Self::map_protocol_code(code) {
+                    self.state = DecodeState::Start;
+                    return Ok(Some(DecodedItem::Protocol(event)));
+                }
+            }
+        }
         match self.state {
             DecodeState::Start => match code {
                 EXTENDED_KEY_CODE => {
@@ -207,21 +402,35 @@ impl ScancodeSet for ScancodeSet2 {
                     self.state = DecodeState::Release;
                     Ok(None)
                 }
-                _ => {
-                    let keycode = Self::map_scancode(code)?;
-                    if keycode == KeyCode::TooManyKeys || keycode == KeyCode::PowerOnTestOk {
-                        Ok(Some(KeyEvent::new(keycode, KeyState::SingleShot)))
-                    } else {
-                        Ok(Some(KeyEvent::new(
-                            Self::map_scancode(code)?,
-                            KeyState::Down,
-                        )))
-                    }
+                HANGUL_KEY_CODE => Ok(Some(DecodedItem::Key(KeyEvent::new(
+                    KeyCode::Hangul,
+                    KeyState::SingleShot,
+                )))),
+                HANJA_KEY_CODE => Ok(Some(DecodedItem::Key(KeyEvent::new(
+                    KeyCode::Hanja,
+                    KeyState::SingleShot,
+                )))),
+                protocol::SELF_TEST_FAILED | protocol::SELF_TEST_FAILED2 => Ok(Some(
+                    DecodedItem::Protocol(ProtocolEvent::PowerOnTestFailed),
+                )),
+                DEVICE_ID_CODE | DEVICE_ID_CODE2 => {
+                    self.state = DecodeState::DeviceId(code);
+                    Ok(None)
                 }
+                _ => match Self::map_protocol_code(code) {
+                    Some(event) => Ok(Some(DecodedItem::Protocol(event))),
+                    None => Ok(Some(DecodedItem::Key(KeyEvent::new(
+                        Self::map_scancode(code)?,
+                        KeyState::Down,
+                    )))),
+                },
             },
             DecodeState::Release => {
                 self.state = DecodeState::Start;
-                Ok(Some(KeyEvent::new(Self::map_scancode(code)?, KeyState::Up)))
+                Ok(Some(DecodedItem::Key(KeyEvent::new(
+                    Self::map_scancode(code)?,
+                    KeyState::Up,
+                ))))
             }
             DecodeState::Extended => match code {
                 KEY_RELEASE_CODE => {
@@ -232,15 +441,18 @@ impl ScancodeSet for ScancodeSet2 {
                     self.state = DecodeState::Start;
 
                     let keycode = Self::map_extended_scancode(code)?;
-                    Ok(Some(KeyEvent::new(keycode, KeyState::Down)))
+                    Ok(Some(DecodedItem::Key(KeyEvent::new(
+                        keycode,
+                        KeyState::Down,
+                    ))))
                 }
             },
             DecodeState::ExtendedRelease => {
                 self.state = DecodeState::Start;
-                Ok(Some(KeyEvent::new(
+                Ok(Some(DecodedItem::Key(KeyEvent::new(
                     Self::map_extended_scancode(code)?,
                     KeyState::Up,
-                )))
+                ))))
             }
             DecodeState::Extended2 => match code {
                 KEY_RELEASE_CODE => {
@@ -249,18 +461,24 @@ impl ScancodeSet for ScancodeSet2 {
                 }
                 _ => {
                     self.state = DecodeState::Start;
-                    Ok(Some(KeyEvent::new(
+                    Ok(Some(DecodedItem::Key(KeyEvent::new(
                         Self::map_extended2_scancode(code)?,
                         KeyState::Down,
-                    )))
+                    ))))
                 }
             },
+            DecodeState::DeviceId(first) => {
+                self.state = DecodeState::Start;
+                Ok(Some(DecodedItem::Protocol(ProtocolEvent::DeviceId(
+                    protocol::parse_id(&[first, code]),
+                ))))
+            }
             DecodeState::Extended2Release => {
                 self.state = DecodeState::Start;
-                Ok(Some(KeyEvent::new(
+                Ok(Some(DecodedItem::Key(KeyEvent::new(
                     Self::map_extended2_scancode(code)?,
                     KeyState::Up,
-                )))
+                ))))
             }
         }
     }
@@ -289,7 +507,362 @@ mod test {
         }
         codes.sort();
         println!("{:?}", codes);
-        assert_eq!(codes.len(), 94);
-        assert_eq!(errs.len(), 162);
+        assert_eq!(codes.len(), 96);
+        assert_eq!(errs.len(), 160);
+    }
+
+    #[test]
+    fn validate_extended_scancodes() {
+        let mut codes = Vec::new();
+        for code in 0x00..=0xFF {
+            if let Ok(c) = ScancodeSet2::map_extended_scancode(code) {
+                codes.push(c);
+            }
+        }
+        codes.sort();
+        codes.dedup();
+        // The same extended keys as the other Scan Code Set
+        assert_eq!(codes.len(), 41);
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let mut count = 0;
+        for code in KeyCode::ALL {
+            if matches!(code, KeyCode::PrintScreen | KeyCode::PauseBreak) {
+                // These are several events in one
+                continue;
+            }
+            for state in [KeyState::Down, KeyState::Up, KeyState::SingleShot] {
+                if let Some(bytes) = ScancodeSet2::encode(code, state) {
+                    assert_eq!(
+                        ScancodeSet2::decode_sequence(&bytes),
+                        Ok(DecodedItem::Key(KeyEvent::new(code, state))),
+                        "{:?} {:?}",
+                        code,
+                        state
+                    );
+                    count += 1;
+                }
+            }
+        }
+        // Every key in the tables (twice), and Hangul and Hanja (once)
+        assert_eq!(count, 268);
+        assert_eq!(ScancodeSet2::encode(KeyCode::A, KeyState::SingleShot), None);
+        assert_eq!(ScancodeSet2::encode(KeyCode::Hangul, KeyState::Down), None);
+        assert_eq!(
+            ScancodeSet2::encode(KeyCode::TooManyKeys, KeyState::Down),
+            None
+        );
+    }
+
+    #[test]
+    fn test_encode_sequences() {
+        assert_eq!(
+            ScancodeSet2::encode(KeyCode::A, KeyState::Down).unwrap(),
+            [0x1C]
+        );
+        assert_eq!(
+            ScancodeSet2::encode(KeyCode::A, KeyState::Up).unwrap(),
+            [0xF0, 0x1C]
+        );
+        assert_eq!(
+            ScancodeSet2::encode(KeyCode::ArrowUp, KeyState::Up).unwrap(),
+            [0xE0, 0xF0, 0x75]
+        );
+        assert_eq!(
+            ScancodeSet2::encode(KeyCode::RControl2, KeyState::Down).unwrap(),
+            [0xE1, 0x14]
+        );
+        assert_eq!(
+            ScancodeSet2::encode(KeyCode::PauseBreak, KeyState::Up).unwrap(),
+            []
+        );
+        // The whole sequences decode as one event, if you hide the pseudo keys
+        let mut set = ScancodeSet2::new_hiding_pseudo_keys();
+        for (code, state, expected) in [
+            (KeyCode::PrintScreen, KeyState::Down, KeyState::Down),
+            (KeyCode::PrintScreen, KeyState::Up, KeyState::Up),
+            (KeyCode::PauseBreak, KeyState::Down, KeyState::SingleShot),
+        ] {
+            let bytes = ScancodeSet2::encode(code, state).unwrap();
+            let events: Vec<_> = bytes
+                .iter()
+                .filter_map(|b| set.advance_state(*b).unwrap())
+                .collect();
+            assert_eq!(events, [DecodedItem::Key(KeyEvent::new(code, expected))]);
+        }
+    }
+
+    #[test]
+    fn test_decode_sequence() {
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0x1C]),
+            Ok(DecodedItem::Key(KeyEvent::new(KeyCode::A, KeyState::Down)))
+        );
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xF0, 0x1C]),
+            Ok(DecodedItem::Key(KeyEvent::new(KeyCode::A, KeyState::Up)))
+        );
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xE0, 0x75]),
+            Ok(DecodedItem::Key(KeyEvent::new(
+                KeyCode::ArrowUp,
+                KeyState::Down
+            )))
+        );
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xE0, 0xF0, 0x75]),
+            Ok(DecodedItem::Key(KeyEvent::new(
+                KeyCode::ArrowUp,
+                KeyState::Up
+            )))
+        );
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xE1, 0x14]),
+            Ok(DecodedItem::Key(KeyEvent::new(
+                KeyCode::RControl2,
+                KeyState::Down
+            )))
+        );
+        // Pause is several key events in one
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xE1, 0x14, 0x77, 0xE1, 0xF0, 0x14, 0xF0, 0x77]),
+            Err(Error::TrailingBytes)
+        );
+    }
+
+    #[test]
+    fn test_acpi_keys() {
+        for (code, byte) in [
+            (KeyCode::Power, 0x37),
+            (KeyCode::Sleep, 0x3F),
+            (KeyCode::Wake, 0x5E),
+        ] {
+            assert_eq!(
+                ScancodeSet2::decode_sequence(&[0xE0, byte]),
+                Ok(DecodedItem::Key(KeyEvent::new(code, KeyState::Down)))
+            );
+            assert_eq!(
+                ScancodeSet2::decode_sequence(&[0xE0, 0xF0, byte]),
+                Ok(DecodedItem::Key(KeyEvent::new(code, KeyState::Up)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_browser_keys() {
+        for (code, byte) in [(KeyCode::WWWBack, 0x38), (KeyCode::Email, 0x48)] {
+            assert_eq!(
+                ScancodeSet2::decode_sequence(&[0xE0, byte]),
+                Ok(DecodedItem::Key(KeyEvent::new(code, KeyState::Down)))
+            );
+            assert_eq!(
+                ScancodeSet2::decode_sequence(&[0xE0, 0xF0, byte]),
+                Ok(DecodedItem::Key(KeyEvent::new(code, KeyState::Up)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_ctrl_break() {
+        // Ctrl + Pause sends Break, which has a make and a break code
+        let mut set = ScancodeSet2::new();
+        let results: Vec<_> = [0xE0, 0x7E, 0xE0, 0xF0, 0x7E]
+            .iter()
+            .map(|b| set.advance_state(*b))
+            .collect();
+        assert_eq!(
+            results,
+            [
+                Ok(None),
+                Ok(Some(DecodedItem::Key(KeyEvent::new(
+                    KeyCode::PauseBreak,
+                    KeyState::Down
+                )))),
+                Ok(None),
+                Ok(None),
+                Ok(Some(DecodedItem::Key(KeyEvent::new(
+                    KeyCode::PauseBreak,
+                    KeyState::Up
+                )))),
+            ]
+        );
+        // Without the E0, it's still Scroll Lock
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0x7E]),
+            Ok(DecodedItem::Key(KeyEvent::new(
+                KeyCode::ScrollLock,
+                KeyState::Down
+            )))
+        );
+    }
+
+    #[test]
+    fn test_alt_print_screen() {
+        // Print Screen sends SysRq instead, while Alt is held
+        let mut set = ScancodeSet2::new();
+        let results: Vec<_> = [0x11, 0x84, 0xF0, 0x84, 0xF0, 0x11]
+            .iter()
+            .filter_map(|b| set.advance_state(*b).transpose())
+            .collect();
+        assert_eq!(
+            results,
+            [
+                Ok(DecodedItem::Key(KeyEvent::new(
+                    KeyCode::LAlt,
+                    KeyState::Down
+                ))),
+                Ok(DecodedItem::Key(KeyEvent::new(
+                    KeyCode::SysRq,
+                    KeyState::Down
+                ))),
+                Ok(DecodedItem::Key(KeyEvent::new(
+                    KeyCode::SysRq,
+                    KeyState::Up
+                ))),
+                Ok(DecodedItem::Key(KeyEvent::new(KeyCode::LAlt, KeyState::Up))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_protocol_bytes() {
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xFC]),
+            Ok(DecodedItem::Protocol(ProtocolEvent::PowerOnTestFailed))
+        );
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xFD]),
+            Ok(DecodedItem::Protocol(ProtocolEvent::PowerOnTestFailed))
+        );
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xAB, 0x83]),
+            Ok(DecodedItem::Protocol(ProtocolEvent::DeviceId(Some(
+                protocol::KeyboardKind::Mf2
+            ))))
+        );
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xAC, 0xA1]),
+            Ok(DecodedItem::Protocol(ProtocolEvent::DeviceId(Some(
+                protocol::KeyboardKind::NcdSun
+            ))))
+        );
+        // An ID we don't know
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xAB, 0x00]),
+            Ok(DecodedItem::Protocol(ProtocolEvent::DeviceId(None)))
+        );
+    }
+
+    #[test]
+    fn test_protocol_aware() {
+        let ack = Ok(Some(DecodedItem::Protocol(ProtocolEvent::CommandResponse(
+            protocol::Response::Ack,
+        ))));
+        let mut set = ScancodeSet2::new_protocol_aware();
+        // An ACK in the middle of Arrow Up, then in the middle of its release
+        let results: Vec<_> = [0xE0, 0xFA, 0x75, 0xE0, 0xF0, 0xFA, 0x75]
+            .iter()
+            .map(|b| set.advance_state(*b))
+            .collect();
+        assert_eq!(
+            results,
+            [
+                Ok(None),
+                ack.clone(),
+                Ok(Some(DecodedItem::Key(KeyEvent::new(
+                    KeyCode::ArrowUp,
+                    KeyState::Down
+                )))),
+                Ok(None),
+                Ok(None),
+                ack.clone(),
+                Ok(Some(DecodedItem::Key(KeyEvent::new(
+                    KeyCode::ArrowUp,
+                    KeyState::Up
+                )))),
+            ]
+        );
+        assert_eq!(
+            set.advance_state(0xEE),
+            Ok(Some(DecodedItem::Protocol(ProtocolEvent::CommandResponse(
+                protocol::Response::Echo
+            ))))
+        );
+        // Otherwise, FA isn't anything
+        let mut set = ScancodeSet2::new();
+        assert_eq!(set.advance_state(0xFA), Err(Error::UnknownKeyCode));
+    }
+
+    #[test]
+    fn test_protocol_codes_mid_sequence() {
+        let mut set = ScancodeSet2::new();
+        // The self test result, in place of a release
+        assert_eq!(set.advance_state(0xF0), Ok(None));
+        assert_eq!(
+            set.advance_state(0xAA),
+            Ok(Some(DecodedItem::Protocol(ProtocolEvent::PowerOnTestOk)))
+        );
+        // And in place of an extended key
+        assert_eq!(set.advance_state(0xE0), Ok(None));
+        assert_eq!(
+            set.advance_state(0xAA),
+            Ok(Some(DecodedItem::Protocol(ProtocolEvent::PowerOnTestOk)))
+        );
+        // An overrun, in place of an extended release
+        assert_eq!(set.advance_state(0xE0), Ok(None));
+        assert_eq!(set.advance_state(0xF0), Ok(None));
+        assert_eq!(
+            set.advance_state(0x00),
+            Ok(Some(DecodedItem::Protocol(ProtocolEvent::TooManyKeys)))
+        );
+        // Nothing is left pending
+        assert_eq!(
+            set.advance_state(0x1C),
+            Ok(Some(DecodedItem::Key(KeyEvent::new(
+                KeyCode::A,
+                KeyState::Down
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_korean_keys() {
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xF2]),
+            Ok(DecodedItem::Key(KeyEvent::new(
+                KeyCode::Hangul,
+                KeyState::SingleShot
+            )))
+        );
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xF1]),
+            Ok(DecodedItem::Key(KeyEvent::new(
+                KeyCode::Hanja,
+                KeyState::SingleShot
+            )))
+        );
+    }
+
+    #[test]
+    fn test_decode_sequence_malformed() {
+        assert_eq!(ScancodeSet2::decode_sequence(&[]), Err(Error::Incomplete));
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xE0]),
+            Err(Error::Incomplete)
+        );
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xE1]),
+            Err(Error::Incomplete)
+        );
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0xE0, 0x01]),
+            Err(Error::UnknownKeyCode)
+        );
+        assert_eq!(
+            ScancodeSet2::decode_sequence(&[0x1C, 0x1C]),
+            Err(Error::TrailingBytes)
+        );
     }
 }
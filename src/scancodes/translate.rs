@@ -0,0 +1,198 @@
+//! Translation between Scancode Set 2 and Scancode Set 1, byte by byte
+
+use super::ScancodeBytes;
+use crate::{HANGUL_KEY_CODE, HANJA_KEY_CODE, KEY_RELEASE_CODE};
+
+/// The i8042 translation table, from Scancode Set 2 to Scancode Set 1.
+///
+/// Bytes from `0x80` upwards go through unchanged, apart from `0x83` (F7)
+/// and `0x84` (Alt + Print Screen).
+const SET2_TO_SET1: [u8; 0x85] = [
+    0xFF, 0x43, 0x41, 0x3F, 0x3D, 0x3B, 0x3C, 0x58, // 0x00
+    0x64, 0x44, 0x42, 0x40, 0x3E, 0x0F, 0x29, 0x59, // 0x08
+    0x65, 0x38, 0x2A, 0x70, 0x1D, 0x10, 0x02, 0x5A, // 0x10
+    0x66, 0x71, 0x2C, 0x1F, 0x1E, 0x11, 0x03, 0x5B, // 0x18
+    0x67, 0x2E, 0x2D, 0x20, 0x12, 0x05, 0x04, 0x5C, // 0x20
+    0x68, 0x39, 0x2F, 0x21, 0x14, 0x13, 0x06, 0x5D, // 0x28
+    0x69, 0x31, 0x30, 0x23, 0x22, 0x15, 0x07, 0x5E, // 0x30
+    0x6A, 0x72, 0x32, 0x24, 0x16, 0x08, 0x09, 0x5F, // 0x38
+    0x6B, 0x33, 0x25, 0x17, 0x18, 0x0B, 0x0A, 0x60, // 0x40
+    0x6C, 0x34, 0x35, 0x26, 0x27, 0x19, 0x0C, 0x61, // 0x48
+    0x6D, 0x73, 0x28, 0x74, 0x1A, 0x0D, 0x62, 0x6E, // 0x50
+    0x3A, 0x36, 0x1C, 0x1B, 0x75, 0x2B, 0x63, 0x76, // 0x58
+    0x55, 0x56, 0x77, 0x78, 0x79, 0x7A, 0x0E, 0x7B, // 0x60
+    0x7C, 0x4F, 0x7D, 0x4B, 0x47, 0x7E, 0x7F, 0x6F, // 0x68
+    0x52, 0x53, 0x50, 0x4C, 0x4D, 0x48, 0x01, 0x45, // 0x70
+    0x57, 0x4E, 0x51, 0x4A, 0x37, 0x49, 0x46, 0x54, // 0x78
+    0x80, 0x81, 0x82, 0x41, 0x54, // 0x80
+];
+
+/// Translate a single Scancode Set 2 byte, ignoring any `F0` before it.
+const fn set2_to_set1(byte: u8) -> u8 {
+    if (byte as usize) < SET2_TO_SET1.len() {
+        SET2_TO_SET1[byte as usize]
+    } else {
+        byte
+    }
+}
+
+/// Find the Scancode Set 2 byte which translates to a Scancode Set 1 make
+/// code.
+///
+/// Where two Set 2 bytes give the same Set 1 byte, the later one wins, as
+/// that is the one a keyboard actually sends (e.g. `0x83` for F7).
+const fn set1_to_set2(byte: u8) -> u8 {
+    let mut i = SET2_TO_SET1.len();
+    while i > 0 {
+        i -= 1;
+        if SET2_TO_SET1[i] == byte {
+            return i as u8;
+        }
+    }
+    byte
+}
+
+/// Translates Scancode Set 2 into Scancode Set 1, the way an i8042 keyboard
+/// controller does.
+///
+/// Feed it the bytes from the keyboard, one at a time. A Set 2 `F0` (break)
+/// prefix gives you no byte, and the byte after it comes out with the top
+/// bit set. Prefixes like `E0` and `E1`, and bytes like `FA` (ACK), go
+/// through unchanged.
+///
+/// For example, Set 2 `E0 F0 75` (Up Arrow released) becomes Set 1 `E0 C8`.
+#[derive(Debug, Default)]
+pub struct Translator {
+    release: bool,
+}
+
+impl Translator {
+    /// Make a new [`Translator`].
+    pub const fn new() -> Translator {
+        Translator { release: false }
+    }
+
+    /// Translate the next byte from a Scancode Set 2 keyboard.
+    ///
+    /// Returns `None` if there is no Scancode Set 1 byte to send yet.
+    pub fn translate(&mut self, byte: u8) -> Option<u8> {
+        if byte == KEY_RELEASE_CODE {
+            self.release = true;
+            return None;
+        }
+        let translated = set2_to_set1(byte);
+        if self.release {
+            self.release = false;
+            Some(translated | 0x80)
+        } else {
+            Some(translated)
+        }
+    }
+
+    /// Forget any `F0` we have seen, ready for a new sequence.
+    pub fn reset(&mut self) {
+        self.release = false;
+    }
+
+    /// Translate a Scancode Set 1 byte back into Scancode Set 2.
+    ///
+    /// This is the reverse of [`Translator::translate`]. A break code gives
+    /// you two bytes (`F0` and then the make code), `FF` (overrun) gives you
+    /// `00`, and prefixes like `E0` and `E1` (and the Hangul and Hanja
+    /// codes) go through unchanged. Every other byte is treated as a make or
+    /// break code, so don't use this on command responses.
+    pub fn untranslate(byte: u8) -> ScancodeBytes {
+        match byte {
+            0xE0 | 0xE1 | HANGUL_KEY_CODE | HANJA_KEY_CODE => ScancodeBytes::from_slice(&[byte]),
+            0xFF => ScancodeBytes::from_slice(&[0x00]),
+            0x80..=0xFF => {
+                ScancodeBytes::from_slice(&[KEY_RELEASE_CODE, set1_to_set2(byte & 0x7F)])
+            }
+            _ => ScancodeBytes::from_slice(&[set1_to_set2(byte)]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{KeyCode, KeyState, ScancodeSet1, ScancodeSet2};
+
+    fn translate_all(bytes: &[u8]) -> Vec<u8> {
+        let mut translator = Translator::new();
+        bytes
+            .iter()
+            .filter_map(|b| translator.translate(*b))
+            .collect()
+    }
+
+    fn untranslate_all(bytes: &[u8]) -> Vec<u8> {
+        bytes
+            .iter()
+            .flat_map(|b| Translator::untranslate(*b).to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn test_sequences() {
+        let sequences: &[(&[u8], &[u8])] = &[
+            // A, pressed and released
+            (&[0x1C, 0xF0, 0x1C], &[0x1E, 0x9E]),
+            // Up Arrow, pressed and released
+            (&[0xE0, 0x75, 0xE0, 0xF0, 0x75], &[0xE0, 0x48, 0xE0, 0xC8]),
+            // Print Screen, pressed and released
+            (
+                &[0xE0, 0x12, 0xE0, 0x7C, 0xE0, 0xF0, 0x7C, 0xE0, 0xF0, 0x12],
+                &[0xE0, 0x2A, 0xE0, 0x37, 0xE0, 0xB7, 0xE0, 0xAA],
+            ),
+            // Pause
+            (
+                &[0xE1, 0x14, 0x77, 0xE1, 0xF0, 0x14, 0xF0, 0x77],
+                &[0xE1, 0x1D, 0x45, 0xE1, 0x9D, 0xC5],
+            ),
+            // F7, and Alt + Print Screen
+            (&[0x83, 0xF0, 0x83], &[0x41, 0xC1]),
+            (&[0x84, 0xF0, 0x84], &[0x54, 0xD4]),
+            // Overrun, and ACK
+            (&[0x00, 0xFA], &[0xFF, 0xFA]),
+        ];
+        for (set2, set1) in sequences {
+            assert_eq!(translate_all(set2), *set1, "{:02x?}", set2);
+        }
+        for (set2, set1) in &sequences[..6] {
+            assert_eq!(untranslate_all(set1), *set2, "{:02x?}", set1);
+        }
+    }
+
+    #[test]
+    fn test_every_key() {
+        let mut count = 0;
+        for code in KeyCode::ALL {
+            for state in [KeyState::Down, KeyState::Up, KeyState::SingleShot] {
+                let set2 = ScancodeSet2::encode(code, state);
+                let set1 = ScancodeSet1::encode(code, state);
+                if let (Some(set2), Some(set1)) = (set2, set1) {
+                    assert_eq!(translate_all(&set2), *set1, "{:?} {:?}", code, state);
+                    // SysRq has two codes in Set 2, so compare what they decode to
+                    assert_eq!(
+                        ScancodeSet2::decode_sequence(&untranslate_all(&set1)),
+                        ScancodeSet2::decode_sequence(&set2),
+                        "{:?} {:?}",
+                        code,
+                        state
+                    );
+                    count += 1;
+                }
+            }
+        }
+        assert_eq!(count, 273);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut translator = Translator::new();
+        assert_eq!(translator.translate(0xF0), None);
+        translator.reset();
+        assert_eq!(translator.translate(0x1C), Some(0x1E));
+    }
+}